@@ -0,0 +1,170 @@
+//! Proc macros for `async-std`'s generator-based stream authoring model.
+//!
+//! This crate is not meant to be used directly; it is re-exported through
+//! `async_std::stream`.
+
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::visit_mut::{self, VisitMut};
+use syn::{parse_macro_input, Block, Expr, ExprForLoop, ExprYield, ItemFn, Result, Token, Type};
+
+/// The `item = Type` argument accepted by `#[stream]`.
+struct ItemArg {
+    ty: Type,
+}
+
+impl Parse for ItemArg {
+    fn parse(input: ParseStream<'_>) -> Result<Self> {
+        let ident: syn::Ident = input.parse()?;
+        if ident != "item" {
+            return Err(syn::Error::new(ident.span(), "expected `item = Type`"));
+        }
+        input.parse::<Token![=]>()?;
+        Ok(ItemArg {
+            ty: input.parse()?,
+        })
+    }
+}
+
+/// Rewrites every `yield expr` in a block into a send through the
+/// generator's [`Yielder`], turning the block into a regular `async` body.
+///
+/// [`Yielder`]: ../async_std/stream/generator/struct.Yielder.html
+struct YieldReplacer;
+
+impl VisitMut for YieldReplacer {
+    fn visit_expr_mut(&mut self, expr: &mut Expr) {
+        match expr {
+            Expr::Yield(ExprYield { expr: value, .. }) => {
+                let value = value
+                    .take()
+                    .map(|boxed| *boxed)
+                    .unwrap_or_else(|| syn::parse_quote!(()));
+                *expr = syn::parse_quote! { __yield_sender.send(#value).await };
+            }
+            // Don't recurse into nested closures: a `yield` there belongs to
+            // that closure's own generator, not this one, and closures can't
+            // capture our `__yield_sender` binding across an await point.
+            Expr::Closure(_) => {}
+            _ => visit_mut::visit_expr_mut(self, expr),
+        }
+    }
+}
+
+fn desugar_body(block: &Block) -> proc_macro2::TokenStream {
+    let mut block = block.clone();
+    YieldReplacer.visit_block_mut(&mut block);
+    quote! { #block }
+}
+
+/// Turns an `async fn` whose body contains `yield` expressions into a
+/// function returning `impl Stream<Item = T>`.
+///
+/// ```ignore
+/// #[stream(item = i32)]
+/// async fn count_to(n: i32) {
+///     let mut i = 0;
+///     while i < n {
+///         yield i;
+///         i += 1;
+///     }
+/// }
+/// ```
+///
+/// Each `yield expr` is desugared into `__yield_sender.send(expr).await`,
+/// and the function becomes one that builds a hand-written [`GenStream`]
+/// state machine wrapping the (now ordinary) `async` body: nothing runs
+/// before the first poll, and each `yield` suspends the body for exactly
+/// one `poll_next` call -- no executor task is spawned, so the body may
+/// borrow across a `yield` and is not required to be `Send`.
+///
+/// [`GenStream`]: ../async_std/stream/generator/struct.GenStream.html
+#[proc_macro_attribute]
+pub fn stream(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let item_ty = parse_macro_input!(attr as ItemArg).ty;
+    let input = parse_macro_input!(item as ItemFn);
+
+    let ItemFn {
+        attrs,
+        vis,
+        sig,
+        block,
+    } = input;
+
+    let fn_name = &sig.ident;
+    let inputs = &sig.inputs;
+    let generics = &sig.generics;
+    let where_clause = &sig.generics.where_clause;
+    let body = desugar_body(&block);
+
+    let expanded = quote! {
+        #(#attrs)*
+        #vis fn #fn_name #generics (#inputs) -> impl ::async_std::stream::Stream<Item = #item_ty> #where_clause {
+            ::async_std::stream::generator::new_generator(move |__yield_sender| async move #body)
+        }
+    };
+
+    expanded.into()
+}
+
+/// Expands a `#[for_await] for x in stream { .. }` loop into a loop that
+/// pins `stream` in place and polls it directly, for use inside `async
+/// fn`s.
+///
+/// This polls the stream through [`Stream::poll_next`] rather than going
+/// through [`StreamExt::next`], so it works for any `Stream` without
+/// requiring it (or a `Pin` of it) to be `Unpin`.
+///
+/// [`Stream::poll_next`]: ../async_std/stream/trait.Stream.html#tymethod.poll_next
+/// [`StreamExt::next`]: ../async_std/stream/trait.StreamExt.html#method.next
+#[proc_macro_attribute]
+pub fn for_await(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(item as ExprForLoop);
+    let ExprForLoop {
+        pat, expr, body, ..
+    } = input;
+
+    let expanded = quote! {
+        {
+            let mut __for_await_stream = #expr;
+            let mut __for_await_stream =
+                unsafe { ::core::pin::Pin::new_unchecked(&mut __for_await_stream) };
+            loop {
+                let __for_await_item = ::core::future::poll_fn(|cx| {
+                    ::async_std::stream::Stream::poll_next(__for_await_stream.as_mut(), cx)
+                })
+                .await;
+                match __for_await_item {
+                    ::core::option::Option::Some(#pat) => #body
+                    ::core::option::Option::None => break,
+                }
+            }
+        }
+    };
+
+    expanded.into()
+}
+
+/// Builds an ad-hoc [`Stream`] from a block of code containing `yield`
+/// expressions, without declaring a named function.
+///
+/// This is the expression-level counterpart to `#[stream]`; reach for the
+/// attribute when the generator deserves a name, and for `stream_block!`
+/// for one-off generators built inline. It shares the same lazy,
+/// non-`Send` semantics -- see the `#[stream]` docs.
+///
+/// [`Stream`]: ../async_std/stream/trait.Stream.html
+#[proc_macro]
+pub fn stream_block(item: TokenStream) -> TokenStream {
+    let block = parse_macro_input!(item as Block);
+    let body = desugar_body(&block);
+
+    let expanded = quote! {
+        ::async_std::stream::generator::new_generator(move |__yield_sender| async move #body)
+    };
+
+    expanded.into()
+}
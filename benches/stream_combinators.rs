@@ -0,0 +1,85 @@
+//! Criterion benchmarks for the stream combinators on the hot path of a
+//! high-throughput pipeline.
+//!
+//! This can't actually be run in this checkout: there's no workspace
+//! `Cargo.toml` here to add a `criterion` dev-dependency or a `[[bench]]`
+//! entry to, the same gap documented in `task/wasm.rs` and `io/io_uring.rs`
+//! for their own missing-manifest dependencies. Wiring it up needs, at the
+//! crate root:
+//!
+//! ```toml
+//! [dev-dependencies]
+//! criterion = "0.5"
+//!
+//! [[bench]]
+//! name = "stream_combinators"
+//! harness = false
+//! ```
+//!
+//! and then `cargo bench --bench stream_combinators` to produce the
+//! before/after numbers the request asked to track in the repo; there's
+//! nowhere to commit them to until that's in place.
+//!
+//! Of the combinators actually named in the request -- map, filter, fold,
+//! merge, throttle, collect -- only two exist anywhere in this snapshot:
+//!
+//! - `fold` ([`StreamExt::fold`], `stream/stream/mod.rs`): already returns
+//!   the concrete, unboxed [`FoldFuture`] rather than a
+//!   `Pin<Box<dyn Future>>`, so there's no allocation here to find.
+//! - `throttle` corresponds to [`StreamExt::rate_limit`]
+//!   (`stream/stream/rate_limit.rs`), which does box one thing: the
+//!   `sleep` future backing its token-bucket timer. That's not reworkable
+//!   here without inventing a concrete name for `crate::task::sleep`'s
+//!   return type, which isn't part of this snapshot (the same ambient gap
+//!   `future::timeout::Timeout` and `stream::Debounce`/`ChunksTimeout`/
+//!   `Sample` box their own timers for) -- naming it in just this one file
+//!   would make it the odd one out among four otherwise-consistent boxed
+//!   timers rather than removing a real inconsistency.
+//!
+//! `map`, `filter`, `merge`, and `collect` have no combinator file in this
+//! tree at all (no `map.rs`, `filter.rs`, or `merge.rs`, and no
+//! `StreamExt::collect` method -- only the lower-level
+//! [`FromStream::from_stream`] from the `Sum`/`Product`/`FromStream`
+//! rework). There's nothing to benchmark or rework for them here; whoever
+//! adds those combinators should fold their allocation profile into this
+//! suite at the same time, before picking a boxed-vs-concrete internal
+//! representation for either.
+//!
+//! [`FoldFuture`]: async_std::stream::stream::FoldFuture
+//! [`StreamExt::fold`]: async_std::stream::StreamExt::fold
+//! [`StreamExt::rate_limit`]: async_std::stream::StreamExt::rate_limit
+//! [`FromStream::from_stream`]: async_std::stream::from_stream::FromStream::from_stream
+
+use core::time::Duration;
+
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+
+use async_std::stream::{self, StreamExt};
+use async_std::task;
+
+const ITEMS: u64 = 1_000_000;
+
+fn fold_throughput(c: &mut Criterion) {
+    c.bench_function("fold/sum_u64", |b| {
+        b.iter(|| task::block_on(stream::from_iter(0..ITEMS).fold(0u64, |acc, x| acc + x)))
+    });
+}
+
+fn rate_limit_throughput(c: &mut Criterion) {
+    // Small enough not to make the benchmark itself take ages, large
+    // enough that the token bucket's own bookkeeping -- not the 1ms
+    // timer tick -- dominates per-item cost.
+    let capacity = 64;
+    let refill_every = Duration::from_millis(1);
+
+    c.bench_function("rate_limit/drain_within_capacity", |b| {
+        b.iter_batched(
+            || stream::from_iter(0..capacity as u64).rate_limit(capacity, refill_every),
+            |limited| task::block_on(limited.fold(0u64, |acc, x| acc + x)),
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+criterion_group!(combinators, fold_throughput, rate_limit_throughput);
+criterion_main!(combinators);
@@ -0,0 +1,66 @@
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use crate::stream::Stream;
+
+/// A stream that is one of two possible stream types.
+///
+/// This lets functions that branch and return structurally different stream
+/// types (for example a filtered vs. unfiltered pipeline) return a single
+/// concrete `impl Stream` without boxing. Use [`left`]/[`right`] to build
+/// one directly, or [`StreamExt::either`] to pick an arm with a `bool`.
+///
+/// [`left`]: enum.Either.html#method.left
+/// [`right`]: enum.Either.html#method.right
+/// [`StreamExt::either`]: trait.StreamExt.html#method.either
+#[derive(Debug, Clone)]
+pub enum Either<A, B> {
+    /// The first stream variant.
+    Left(A),
+    /// The second stream variant.
+    Right(B),
+}
+
+impl<A, B> Either<A, B> {
+    /// Wraps a stream as the `Left` variant of an `Either`.
+    pub fn left(stream: A) -> Either<A, B>
+    where
+        A: Stream,
+        B: Stream<Item = A::Item>,
+    {
+        Either::Left(stream)
+    }
+
+    /// Wraps a stream as the `Right` variant of an `Either`.
+    pub fn right(stream: B) -> Either<A, B>
+    where
+        A: Stream,
+        B: Stream<Item = A::Item>,
+    {
+        Either::Right(stream)
+    }
+}
+
+impl<A, B> Stream for Either<A, B>
+where
+    A: Stream,
+    B: Stream<Item = A::Item>,
+{
+    type Item = A::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        unsafe {
+            match self.get_unchecked_mut() {
+                Either::Left(stream) => Pin::new_unchecked(stream).poll_next(cx),
+                Either::Right(stream) => Pin::new_unchecked(stream).poll_next(cx),
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self {
+            Either::Left(stream) => stream.size_hint(),
+            Either::Right(stream) => stream.size_hint(),
+        }
+    }
+}
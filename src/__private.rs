@@ -0,0 +1,7 @@
+//! Re-exports used by this crate's macros (e.g. [`crate::select!`]) so
+//! their expansions don't require callers to have `alloc` types in
+//! scope themselves. Not part of the public API.
+
+pub use alloc::boxed::Box;
+pub use alloc::vec;
+pub use alloc::vec::Vec;
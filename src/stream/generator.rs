@@ -0,0 +1,126 @@
+use core::cell::RefCell;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use alloc::rc::Rc;
+
+use crate::stream::Stream;
+
+/// The hand-written [`Stream`] state machine behind `#[stream]` and
+/// `stream_block!`.
+///
+/// `future` is the generator body, an ordinary `async` block. Polling a
+/// `GenStream` drives that body forward exactly one step: if the body
+/// yields through its [`Yielder`], the yielded value is lifted out of
+/// `slot` and returned as `Some`; if the body is genuinely waiting on some
+/// other future (real I/O, a timer, ...), the `Pending` is propagated
+/// as-is; if the body runs to completion, the stream ends.
+///
+/// Nothing runs before the first call to `poll_next`, and each `yield`
+/// suspends the body for exactly one `poll_next` call -- there is no
+/// executor task involved, so the body may borrow across a `yield` and
+/// need not be `Send`.
+///
+/// [`Stream`]: ../trait.Stream.html
+pub struct GenStream<Fut, T> {
+    future: Fut,
+    slot: Rc<RefCell<Option<T>>>,
+}
+
+impl<Fut, T> GenStream<Fut, T> {
+    /// Builds a `GenStream` from a generator body and the slot its
+    /// [`Yielder`] writes into.
+    pub fn new(future: Fut, slot: Rc<RefCell<Option<T>>>) -> Self {
+        Self { future, slot }
+    }
+}
+
+impl<Fut, T> Stream for GenStream<Fut, T>
+where
+    Fut: Future<Output = ()>,
+{
+    type Item = T;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        // Safety: `future` is never moved out of `self` for as long as the
+        // `GenStream` exists, so projecting a pinned reference to it is
+        // sound.
+        let this = unsafe { self.get_unchecked_mut() };
+        let future = unsafe { Pin::new_unchecked(&mut this.future) };
+
+        match future.poll(cx) {
+            Poll::Ready(()) => Poll::Ready(None),
+            Poll::Pending => match this.slot.borrow_mut().take() {
+                Some(value) => Poll::Ready(Some(value)),
+                None => Poll::Pending,
+            },
+        }
+    }
+}
+
+/// Handle passed into a generator body; `yield expr` desugars to
+/// `__yield_sender.send(expr).await`.
+pub struct Yielder<T> {
+    slot: Rc<RefCell<Option<T>>>,
+}
+
+impl<T> Yielder<T> {
+    /// Builds a `Yielder` writing into the given slot.
+    fn new(slot: Rc<RefCell<Option<T>>>) -> Self {
+        Self { slot }
+    }
+
+    /// Suspends the generator body for one `poll_next` call, handing
+    /// `value` to the stream's consumer.
+    pub fn send(&self, value: T) -> YieldFuture<'_, T> {
+        YieldFuture {
+            slot: &self.slot,
+            value: Some(value),
+            done: false,
+        }
+    }
+}
+
+/// Builds a [`GenStream`] from a generator body, handing it the [`Yielder`]
+/// it sends its `yield`ed values through.
+///
+/// This is the entry point the `#[stream]` and `stream_block!` macros
+/// expand into; `make_future` is the `async move { .. }` block produced by
+/// rewriting `yield expr` into `__yield_sender.send(expr).await`.
+///
+/// [`GenStream`]: struct.GenStream.html
+/// [`Yielder`]: struct.Yielder.html
+pub fn new_generator<Fut, T>(make_future: impl FnOnce(Yielder<T>) -> Fut) -> GenStream<Fut, T>
+where
+    Fut: Future<Output = ()>,
+{
+    let slot = Rc::new(RefCell::new(None));
+    let yielder = Yielder::new(slot.clone());
+    GenStream::new(make_future(yielder), slot)
+}
+
+/// Future returned by [`Yielder::send`].
+///
+/// Polled once, it stores its value in the slot and returns `Pending`,
+/// suspending the generator; [`GenStream::poll_next`] takes the value back
+/// out on that same poll. Polled again (once the generator is resumed on
+/// the next `poll_next` call), it returns `Ready`.
+pub struct YieldFuture<'a, T> {
+    slot: &'a RefCell<Option<T>>,
+    value: Option<T>,
+    done: bool,
+}
+
+impl<'a, T> Future for YieldFuture<'a, T> {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<()> {
+        if self.done {
+            return Poll::Ready(());
+        }
+        *self.slot.borrow_mut() = self.value.take();
+        self.done = true;
+        Poll::Pending
+    }
+}
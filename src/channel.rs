@@ -0,0 +1,362 @@
+//! A multi-producer, multi-consumer queue, bounded or unbounded, used
+//! internally to stream blocking-pool work (directory walks, glob
+//! matches, filesystem watch events) back onto the async side, and
+//! exposed for the same purpose in application code.
+
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use core::time::Duration;
+
+use alloc::collections::VecDeque;
+use alloc::sync::Arc;
+use std::sync::Mutex;
+
+use crate::mpmc::WakerSet;
+use crate::stream::Stream;
+
+struct State<T> {
+    queue: VecDeque<T>,
+    /// `None` for an unbounded channel.
+    capacity: Option<usize>,
+    sender_count: usize,
+    receiver_count: usize,
+    send_wakers: WakerSet,
+    recv_wakers: WakerSet,
+}
+
+impl<T> State<T> {
+    fn is_disconnected(&self) -> bool {
+        self.receiver_count == 0
+    }
+
+    fn wake_one_receiver(&mut self) {
+        self.recv_wakers.wake_one();
+    }
+
+    fn wake_one_sender(&mut self) {
+        self.send_wakers.wake_one();
+    }
+}
+
+struct Shared<T> {
+    state: Mutex<State<T>>,
+}
+
+/// Creates a channel that holds at most `capacity` items; a sender
+/// waits when the channel is full.
+pub fn bounded<T>(capacity: usize) -> (Sender<T>, Receiver<T>) {
+    assert!(capacity > 0, "bounded channel capacity must be greater than zero");
+    new(Some(capacity))
+}
+
+/// Creates a channel with no capacity limit; `send` never waits.
+pub fn unbounded<T>() -> (Sender<T>, Receiver<T>) {
+    new(None)
+}
+
+fn new<T>(capacity: Option<usize>) -> (Sender<T>, Receiver<T>) {
+    let shared = Arc::new(Shared {
+        state: Mutex::new(State {
+            queue: VecDeque::new(),
+            capacity,
+            sender_count: 1,
+            receiver_count: 1,
+            send_wakers: WakerSet::new(),
+            recv_wakers: WakerSet::new(),
+        }),
+    });
+    (Sender { shared: shared.clone() }, Receiver { shared })
+}
+
+/// The sending half of a channel.
+pub struct Sender<T> {
+    shared: Arc<Shared<T>>,
+}
+
+/// The error returned by [`Sender::try_send`].
+#[derive(Debug, PartialEq, Eq)]
+pub enum TrySendError<T> {
+    /// The channel is at capacity.
+    Full(T),
+    /// Every receiver has been dropped.
+    Disconnected(T),
+}
+
+/// The error returned by [`Sender::send`] and [`Sender::send_timeout`].
+#[derive(Debug, PartialEq, Eq)]
+pub enum SendError<T> {
+    /// Every receiver has been dropped.
+    Disconnected(T),
+    /// [`Sender::send_timeout`] only: the channel stayed full for the
+    /// whole timeout.
+    Timeout(T),
+}
+
+impl<T> Sender<T> {
+    /// Sends `value` without waiting, failing if the channel is full
+    /// or disconnected.
+    pub fn try_send(&self, value: T) -> Result<(), TrySendError<T>> {
+        let mut state = self.shared.state.lock().unwrap();
+        if state.is_disconnected() {
+            return Err(TrySendError::Disconnected(value));
+        }
+        if let Some(capacity) = state.capacity {
+            if state.queue.len() >= capacity {
+                return Err(TrySendError::Full(value));
+            }
+        }
+        state.queue.push_back(value);
+        state.wake_one_receiver();
+        Ok(())
+    }
+
+    /// Sends `value`, waiting if the channel is full.
+    pub async fn send(&self, value: T) -> Result<(), SendError<T>> {
+        let mut value = value;
+        loop {
+            match self.try_send(value) {
+                Ok(()) => {
+                    crate::task::consume_budget().await;
+                    return Ok(());
+                }
+                Err(TrySendError::Disconnected(v)) => return Err(SendError::Disconnected(v)),
+                Err(TrySendError::Full(v)) => value = v,
+            }
+            let registered = Send { shared: &self.shared };
+            registered.await;
+        }
+    }
+
+    /// Sends `value`, waiting at most `duration` for room in the
+    /// channel.
+    pub async fn send_timeout(&self, value: T, duration: Duration) -> Result<(), SendError<T>> {
+        let deadline = std::time::Instant::now() + duration;
+        let mut value = value;
+        loop {
+            match self.try_send(value) {
+                Ok(()) => return Ok(()),
+                Err(TrySendError::Disconnected(v)) => return Err(SendError::Disconnected(v)),
+                Err(TrySendError::Full(v)) => value = v,
+            }
+            let remaining = deadline.saturating_duration_since(std::time::Instant::now());
+            if remaining.is_zero() {
+                return Err(SendError::Timeout(value));
+            }
+            if crate::future::timeout(remaining, Send { shared: &self.shared }).await.is_err() {
+                return Err(SendError::Timeout(value));
+            }
+        }
+    }
+
+    /// The number of items currently queued.
+    pub fn len(&self) -> usize {
+        self.shared.state.lock().unwrap().queue.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The channel's capacity, or `None` if unbounded.
+    pub fn capacity(&self) -> Option<usize> {
+        self.shared.state.lock().unwrap().capacity
+    }
+
+    pub fn is_full(&self) -> bool {
+        let state = self.shared.state.lock().unwrap();
+        matches!(state.capacity, Some(capacity) if state.queue.len() >= capacity)
+    }
+
+    /// The number of live `Sender` handles, including this one.
+    pub fn sender_count(&self) -> usize {
+        self.shared.state.lock().unwrap().sender_count
+    }
+
+    /// The number of live `Receiver` handles.
+    pub fn receiver_count(&self) -> usize {
+        self.shared.state.lock().unwrap().receiver_count
+    }
+}
+
+struct Send<'a, T> {
+    shared: &'a Arc<Shared<T>>,
+}
+
+impl<'a, T> Future for Send<'a, T> {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let mut state = self.shared.state.lock().unwrap();
+        let has_room = match state.capacity {
+            Some(capacity) => state.queue.len() < capacity,
+            None => true,
+        };
+        if has_room || state.is_disconnected() {
+            return Poll::Ready(());
+        }
+        state.send_wakers.register(cx.waker());
+        Poll::Pending
+    }
+}
+
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Self {
+        self.shared.state.lock().unwrap().sender_count += 1;
+        Self { shared: self.shared.clone() }
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        let mut state = self.shared.state.lock().unwrap();
+        state.sender_count -= 1;
+        if state.sender_count == 0 {
+            state.recv_wakers.wake_all();
+        }
+    }
+}
+
+/// The receiving half of a channel.
+pub struct Receiver<T> {
+    shared: Arc<Shared<T>>,
+}
+
+/// The error returned by [`Receiver::try_recv`].
+#[derive(Debug, PartialEq, Eq)]
+pub enum TryRecvError {
+    /// No item is queued right now.
+    Empty,
+    /// Every sender has been dropped and the queue is empty.
+    Disconnected,
+}
+
+/// The error returned by [`Receiver::recv_timeout`].
+#[derive(Debug, PartialEq, Eq)]
+pub enum RecvTimeoutError {
+    /// Every sender has been dropped and the queue is empty.
+    Disconnected,
+    /// No item arrived within the timeout.
+    Timeout,
+}
+
+impl<T> Receiver<T> {
+    /// Receives an item without waiting, failing if none is queued.
+    pub fn try_recv(&self) -> Result<T, TryRecvError> {
+        let mut state = self.shared.state.lock().unwrap();
+        if let Some(value) = state.queue.pop_front() {
+            state.wake_one_sender();
+            Ok(value)
+        } else if state.sender_count == 0 {
+            Err(TryRecvError::Disconnected)
+        } else {
+            Err(TryRecvError::Empty)
+        }
+    }
+
+    /// Receives an item, waiting if the channel is empty.
+    pub async fn recv(&self) -> Option<T> {
+        loop {
+            match self.try_recv() {
+                Ok(value) => {
+                    crate::task::consume_budget().await;
+                    return Some(value);
+                }
+                Err(TryRecvError::Disconnected) => return None,
+                Err(TryRecvError::Empty) => Recv { shared: &self.shared }.await,
+            }
+        }
+    }
+
+    /// Receives an item, waiting at most `duration`.
+    pub async fn recv_timeout(&self, duration: Duration) -> Result<T, RecvTimeoutError> {
+        match crate::future::timeout(duration, self.recv()).await {
+            Ok(Some(value)) => Ok(value),
+            Ok(None) => Err(RecvTimeoutError::Disconnected),
+            Err(_) => Err(RecvTimeoutError::Timeout),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.shared.state.lock().unwrap().queue.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn capacity(&self) -> Option<usize> {
+        self.shared.state.lock().unwrap().capacity
+    }
+
+    pub fn is_full(&self) -> bool {
+        let state = self.shared.state.lock().unwrap();
+        matches!(state.capacity, Some(capacity) if state.queue.len() >= capacity)
+    }
+
+    pub fn sender_count(&self) -> usize {
+        self.shared.state.lock().unwrap().sender_count
+    }
+
+    pub fn receiver_count(&self) -> usize {
+        self.shared.state.lock().unwrap().receiver_count
+    }
+}
+
+struct Recv<'a, T> {
+    shared: &'a Arc<Shared<T>>,
+}
+
+impl<'a, T> Future for Recv<'a, T> {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let mut state = self.shared.state.lock().unwrap();
+        if !state.queue.is_empty() || state.sender_count == 0 {
+            return Poll::Ready(());
+        }
+        state.recv_wakers.register(cx.waker());
+        Poll::Pending
+    }
+}
+
+impl<T> Clone for Receiver<T> {
+    fn clone(&self) -> Self {
+        self.shared.state.lock().unwrap().receiver_count += 1;
+        Self { shared: self.shared.clone() }
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        let mut state = self.shared.state.lock().unwrap();
+        state.receiver_count -= 1;
+        if state.receiver_count == 0 {
+            state.send_wakers.wake_all();
+        }
+    }
+}
+
+impl<T> Stream for Receiver<T> {
+    type Item = T;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        match self.try_recv() {
+            Ok(value) => Poll::Ready(Some(value)),
+            Err(TryRecvError::Disconnected) => Poll::Ready(None),
+            Err(TryRecvError::Empty) => {
+                let mut state = self.shared.state.lock().unwrap();
+                if !state.queue.is_empty() || state.sender_count == 0 {
+                    drop(state);
+                    // A sender landed an item or disconnected between
+                    // the `try_recv` above and taking the lock again;
+                    // retry instead of registering a waker we'd have
+                    // to immediately wake ourselves.
+                    return Pin::new(self.get_mut()).poll_next(cx);
+                }
+                state.recv_wakers.register(cx.waker());
+                Poll::Pending
+            }
+        }
+    }
+}
@@ -0,0 +1,102 @@
+use core::future::Future;
+use core::pin::Pin;
+
+use crate::stream::Stream;
+
+/// Trait to represent types that can be created by multiplying the elements
+/// of a stream.
+///
+/// This trait is used to implement the [`product`] method on streams. Types
+/// which implement the trait can be generated by the [`product`] method.
+/// Like [`FromStream`] this trait should rarely be called directly and
+/// instead interacted with through [`StreamExt::product`].
+///
+/// [`product`]: trait.Product.html#tymethod.product
+/// [`FromStream`]: trait.FromStream.html
+/// [`StreamExt::product`]: trait.StreamExt.html#method.product
+pub trait Product<A = Self>: Sized {
+    /// Method which takes a stream and generates `Self` from the elements by
+    /// multiplying the items.
+    fn product<'a, S>(stream: S) -> Pin<Box<dyn Future<Output = Self> + 'a>>
+    where
+        S: Stream<Item = A> + 'a;
+}
+
+use crate::stream::stream::StreamExt;
+use core::num::Wrapping;
+use core::ops::Mul;
+
+macro_rules! integer_product {
+    (@impls $one: expr, $($a:ty)*) => ($(
+        #[cfg(not(feature = "num-traits"))]
+        impl Product for $a {
+            fn product<'a, S>(stream: S) -> Pin<Box<dyn Future<Output = Self> + 'a>>
+            where
+                S: Stream<Item = $a> + 'a,
+            {
+                Box::pin(async move { stream.fold($one, Mul::mul).await } )
+            }
+        }
+        impl<'a> Product<&'a $a> for $a {
+            fn product<'b, S>(stream: S) -> Pin<Box<dyn Future<Output = Self> + 'b>>
+            where
+                S: Stream<Item = &'a $a> + 'b,
+            {
+                Box::pin(async move { stream.fold($one, Mul::mul).await } )
+            }
+        }
+    )*);
+    ($($a:ty)*) => (
+        integer_product!(@impls 1, $($a)*);
+        integer_product!(@impls Wrapping(1), $(Wrapping<$a>)*);
+    );
+}
+
+macro_rules! float_product {
+    ($($a:ty)*) => ($(
+        #[cfg(not(feature = "num-traits"))]
+        impl Product for $a {
+            fn product<'a, S>(stream: S) -> Pin<Box<dyn Future<Output = Self> + 'a>>
+                where S: Stream<Item = $a> + 'a,
+            {
+                Box::pin(async move { stream.fold(1.0, |a, b| a * b).await } )
+            }
+        }
+        impl<'a> Product<&'a $a> for $a {
+            fn product<'b, S>(stream: S) -> Pin<Box<dyn Future<Output = Self> + 'b>>
+                where S: Stream<Item = &'a $a> + 'b,
+            {
+                Box::pin(async move { stream.fold(1.0, |a, b| a * b).await } )
+            }
+        }
+    )*);
+    ($($a:ty)*) => (
+        float_product!(@impls 1.0, $($a)*);
+        float_product!(@impls Wrapping(1.0), $(Wrapping<$a>)*);
+    );
+}
+
+integer_product! { i8 i16 i32 i64 i128 isize u8 u16 u32 u64 u128 usize }
+float_product! { f32 f64 }
+
+/// Blanket impl of [`Product`] for any type with a multiplicative identity,
+/// gated behind the `num-traits` feature.
+///
+/// This lets [`StreamExt::product`] work for arbitrary-precision integers,
+/// decimals, and other domain-specific numeric types, not just the
+/// primitives above.
+///
+/// [`Product`]: trait.Product.html
+/// [`StreamExt::product`]: trait.StreamExt.html#method.product
+#[cfg(feature = "num-traits")]
+impl<T> Product for T
+where
+    T: Mul<Output = T> + num_traits::One,
+{
+    fn product<'a, S>(stream: S) -> Pin<Box<dyn Future<Output = Self> + 'a>>
+    where
+        S: Stream<Item = T> + 'a,
+    {
+        Box::pin(async move { stream.fold(T::one(), Mul::mul).await })
+    }
+}
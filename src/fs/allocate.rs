@@ -0,0 +1,74 @@
+//! Reserving space ahead of time and punching holes in it, so databases
+//! and download managers can avoid the fragmentation and ENOSPC
+//! surprises that come from growing a file one write at a time.
+
+use crate::fs::File;
+use crate::io;
+use crate::task::spawn_blocking;
+
+#[cfg(unix)]
+use std::os::unix::io::AsRawFd;
+
+/// Extension trait adding space reservation to [`File`].
+pub trait FileAllocateExt {
+    /// Reserves `len` bytes of space for the file, extending it if
+    /// necessary, without actually writing data (`posix_fallocate` on
+    /// Unix, `SetFileValidData` on Windows).
+    async fn allocate(&self, len: u64) -> io::Result<()>;
+
+    /// Deallocates the byte range `[offset, offset + len)`, turning it
+    /// into a sparse hole that reads back as zeroes without using disk
+    /// space (`fallocate(FALLOC_FL_PUNCH_HOLE)` on Linux).
+    async fn punch_hole(&self, offset: u64, len: u64) -> io::Result<()>;
+}
+
+impl FileAllocateExt for File {
+    #[cfg(target_os = "linux")]
+    async fn allocate(&self, len: u64) -> io::Result<()> {
+        let fd = self.as_raw_fd();
+        spawn_blocking(move || {
+            let ret = unsafe { libc::posix_fallocate(fd, 0, len as libc::off_t) };
+            if ret == 0 {
+                Ok(())
+            } else {
+                Err(io::Error::from_raw_os_error(ret))
+            }
+        })
+        .await
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    async fn allocate(&self, len: u64) -> io::Result<()> {
+        let file = self.clone();
+        spawn_blocking(move || file.as_std().set_len(len)).await
+    }
+
+    #[cfg(target_os = "linux")]
+    async fn punch_hole(&self, offset: u64, len: u64) -> io::Result<()> {
+        let fd = self.as_raw_fd();
+        spawn_blocking(move || {
+            let ret = unsafe {
+                libc::fallocate(
+                    fd,
+                    libc::FALLOC_FL_PUNCH_HOLE | libc::FALLOC_FL_KEEP_SIZE,
+                    offset as libc::off_t,
+                    len as libc::off_t,
+                )
+            };
+            if ret == 0 {
+                Ok(())
+            } else {
+                Err(io::Error::last_os_error())
+            }
+        })
+        .await
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    async fn punch_hole(&self, _offset: u64, _len: u64) -> io::Result<()> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "punch_hole requires FALLOC_FL_PUNCH_HOLE, which is Linux-only",
+        ))
+    }
+}
@@ -0,0 +1,164 @@
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use crate::stream::either::Either;
+use crate::stream::product::Product;
+use crate::stream::sum::Sum;
+use crate::stream::try_product::TryProduct;
+use crate::stream::try_sum::{SumError, TrySum};
+use crate::stream::Stream;
+
+/// An extension trait for the [`Stream`] trait, providing a collection of
+/// useful combinators.
+///
+/// [`Stream`]: trait.Stream.html
+pub trait StreamExt: Stream {
+    /// Advances the stream and returns the next value.
+    ///
+    /// Returns `None` when iteration is finished.
+    fn next(&mut self) -> NextFuture<'_, Self>
+    where
+        Self: Unpin,
+    {
+        NextFuture { stream: self }
+    }
+
+    /// Folds every element into an accumulator by applying an operation,
+    /// returning the final result.
+    fn fold<B, F>(self, init: B, f: F) -> FoldFuture<Self, F, B>
+    where
+        Self: Sized,
+        F: FnMut(B, Self::Item) -> B,
+    {
+        FoldFuture {
+            stream: self,
+            f,
+            acc: Some(init),
+        }
+    }
+
+    /// Sums the elements of this stream.
+    ///
+    /// See [`Sum::sum`] for details.
+    ///
+    /// [`Sum::sum`]: trait.Sum.html#tymethod.sum
+    fn sum<'a, S>(self) -> Pin<Box<dyn Future<Output = S> + 'a>>
+    where
+        Self: Sized + Stream + 'a,
+        S: Sum<Self::Item> + 'a,
+    {
+        S::sum(self)
+    }
+
+    /// Multiplies the elements of this stream together.
+    ///
+    /// See [`Product::product`] for details.
+    ///
+    /// [`Product::product`]: trait.Product.html#tymethod.product
+    fn product<'a, P>(self) -> Pin<Box<dyn Future<Output = P> + 'a>>
+    where
+        Self: Sized + Stream + 'a,
+        P: Product<Self::Item> + 'a,
+    {
+        P::product(self)
+    }
+
+    /// Sums the elements of this stream, short-circuiting on overflow.
+    ///
+    /// See [`TrySum::try_sum`] for details.
+    ///
+    /// [`TrySum::try_sum`]: trait.TrySum.html#tymethod.try_sum
+    fn try_sum<'a, S>(self) -> Pin<Box<dyn Future<Output = Result<S, SumError>> + 'a>>
+    where
+        Self: Sized + Stream + 'a,
+        S: TrySum<Self::Item> + 'a,
+    {
+        S::try_sum(self)
+    }
+
+    /// Multiplies the elements of this stream together, short-circuiting on
+    /// overflow.
+    ///
+    /// See [`TryProduct::try_product`] for details.
+    ///
+    /// [`TryProduct::try_product`]: trait.TryProduct.html#tymethod.try_product
+    fn try_product<'a, P>(self) -> Pin<Box<dyn Future<Output = Result<P, SumError>> + 'a>>
+    where
+        Self: Sized + Stream + 'a,
+        P: TryProduct<Self::Item> + 'a,
+    {
+        P::try_product(self)
+    }
+
+    /// Combines this stream with another, structurally different stream
+    /// into a single concrete [`Either`] stream, picking this stream (the
+    /// `Left` arm) when `cond` is `true`, and `other` (the `Right` arm)
+    /// otherwise.
+    ///
+    /// The choice is made once, up front, before either arm is polled, so
+    /// `cond` is a plain `bool` rather than a predicate over the (opaque,
+    /// not-yet-polled) stream -- there is nothing meaningful to inspect on
+    /// `self` at this point.
+    ///
+    /// [`Either`]: enum.Either.html
+    fn either<B>(self, cond: bool, other: B) -> Either<Self, B>
+    where
+        Self: Sized,
+        B: Stream<Item = Self::Item>,
+    {
+        if cond {
+            Either::Left(self)
+        } else {
+            Either::Right(other)
+        }
+    }
+}
+
+impl<S: Stream + ?Sized> StreamExt for S {}
+
+/// Future for the [`next`](StreamExt::next) method.
+#[derive(Debug)]
+pub struct NextFuture<'a, S: ?Sized> {
+    stream: &'a mut S,
+}
+
+impl<'a, S: Stream + Unpin + ?Sized> Future for NextFuture<'a, S> {
+    type Output = Option<S::Item>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut *self.stream).poll_next(cx)
+    }
+}
+
+/// Future for the [`fold`](StreamExt::fold) method.
+#[derive(Debug)]
+pub struct FoldFuture<S, F, B> {
+    stream: S,
+    f: F,
+    acc: Option<B>,
+}
+
+impl<S, F, B> Future for FoldFuture<S, F, B>
+where
+    S: Stream,
+    F: FnMut(B, S::Item) -> B,
+{
+    type Output = B;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = unsafe { self.get_unchecked_mut() };
+        let mut stream = unsafe { Pin::new_unchecked(&mut this.stream) };
+
+        loop {
+            match stream.as_mut().poll_next(cx) {
+                Poll::Ready(Some(item)) => {
+                    let acc = this.acc.take().unwrap();
+                    this.acc = Some((this.f)(acc, item));
+                }
+                Poll::Ready(None) => return Poll::Ready(this.acc.take().unwrap()),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
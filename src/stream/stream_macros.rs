@@ -0,0 +1,42 @@
+//! Ergonomic stream construction and consumption.
+//!
+//! The [`Sum`]/[`Product`] machinery shows how much of the combinator
+//! surface in this crate is built on top of manual `Stream`/`fold`
+//! plumbing. The `#[stream]` and `#[for_await]` macros re-exported here let
+//! callers write generators and consume them without hand-writing
+//! `poll_next`:
+//!
+//! ```ignore
+//! use async_std::stream;
+//!
+//! #[stream::stream(item = i32)]
+//! async fn count_to(n: i32) {
+//!     let mut i = 0;
+//!     while i < n {
+//!         yield i;
+//!         i += 1;
+//!     }
+//! }
+//!
+//! # async fn run() {
+//! #[stream::for_await]
+//! for n in count_to(3) {
+//!     println!("{}", n);
+//! }
+//! # }
+//! ```
+//!
+//! Unlike the [`sync::channel`]-backed approach this started from, a
+//! `#[stream]` fn's body is not spawned onto the executor: it is an
+//! ordinary `async` block wrapped by [`generator::GenStream`], which drives
+//! it forward one step per `poll_next` call. Nothing runs before the first
+//! poll, each `yield` suspends the body for exactly that one call, and the
+//! body may freely borrow across a `yield` and need not be `Send`.
+//!
+//! [`sync::channel`]: ../sync/fn.channel.html
+//! [`generator::GenStream`]: generator/struct.GenStream.html
+//! [`Sum`]: trait.Sum.html
+//! [`Product`]: trait.Product.html
+
+#[doc(inline)]
+pub use async_std_macros::{for_await, stream, stream_block};
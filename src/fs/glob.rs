@@ -0,0 +1,134 @@
+//! Glob pattern matching over the filesystem, so CLI tools don't each
+//! reach for a blocking `glob` crate or spawn ad-hoc tasks to avoid
+//! stalling the executor.
+
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use std::path::{Path, PathBuf};
+
+use crate::io;
+use crate::stream::Stream;
+use crate::task::spawn_blocking;
+
+/// Returns a stream of paths matching `pattern`, e.g.
+/// `"logs/**/*.json"`. Supports `*` (any characters except `/`), `?`
+/// (any single character except `/`), and `**` (any number of
+/// directory levels, including zero).
+///
+/// Directory reads run on the blocking pool; matching each entry
+/// against the pattern happens inline as results come back.
+pub fn glob(pattern: impl AsRef<str>) -> Glob {
+    let pattern = pattern.as_ref();
+    let (root, segments) = split_pattern(pattern);
+    let (sender, receiver) = crate::channel::unbounded();
+
+    spawn_blocking(move || walk(&root, &segments, &sender));
+
+    Glob { receiver }
+}
+
+/// Stream of matching paths returned by [`glob`].
+pub struct Glob {
+    receiver: crate::channel::Receiver<io::Result<PathBuf>>,
+}
+
+impl Stream for Glob {
+    type Item = io::Result<PathBuf>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.get_mut().receiver).poll_next(cx)
+    }
+}
+
+/// Splits a pattern like `"logs/**/*.json"` into its non-wildcard root
+/// directory (`"logs"`) and the remaining path segments to match
+/// against (`["**", "*.json"]`), so the walk doesn't have to scan
+/// directories that can't possibly contain a match.
+fn split_pattern(pattern: &str) -> (PathBuf, Vec<String>) {
+    let mut root = PathBuf::new();
+    let mut rest: Vec<&str> = pattern.split('/').collect();
+    while let Some(first) = rest.first() {
+        if is_literal(first) {
+            root.push(first);
+            rest.remove(0);
+        } else {
+            break;
+        }
+    }
+    if root.as_os_str().is_empty() {
+        root.push(".");
+    }
+    (root, rest.into_iter().map(String::from).collect())
+}
+
+fn is_literal(segment: &str) -> bool {
+    !segment.contains(['*', '?'])
+}
+
+fn walk(root: &Path, segments: &[String], sender: &crate::channel::Sender<io::Result<PathBuf>>) {
+    match_segments(root, segments, sender);
+}
+
+fn match_segments(dir: &Path, segments: &[String], sender: &crate::channel::Sender<io::Result<PathBuf>>) {
+    let Some((segment, rest)) = segments.split_first() else {
+        let _ = sender.try_send(Ok(dir.to_path_buf()));
+        return;
+    };
+
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) => {
+            let _ = sender.try_send(Err(e));
+            return;
+        }
+    };
+
+    for entry in entries {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(e) => {
+                let _ = sender.try_send(Err(e));
+                continue;
+            }
+        };
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+
+        if segment == "**" {
+            // `**` matches zero directory levels (try the rest of the
+            // pattern against this entry directly) and any number more
+            // (recurse into subdirectories with `**` still in play).
+            match_segments(dir, rest, sender);
+            if entry.file_type().map(|t| t.is_dir()).unwrap_or(false) {
+                match_segments(&entry.path(), segments, sender);
+            }
+            return;
+        }
+
+        if matches_glob(segment, &name) {
+            if rest.is_empty() {
+                let _ = sender.try_send(Ok(entry.path()));
+            } else if entry.file_type().map(|t| t.is_dir()).unwrap_or(false) {
+                match_segments(&entry.path(), rest, sender);
+            }
+        }
+    }
+}
+
+/// Matches a single non-`**` path segment pattern (`*`/`?` wildcards)
+/// against a file name.
+fn matches_glob(pattern: &str, name: &str) -> bool {
+    fn inner(pattern: &[char], name: &[char]) -> bool {
+        match (pattern.first(), name.first()) {
+            (None, None) => true,
+            (Some('*'), _) => inner(&pattern[1..], name) || (!name.is_empty() && inner(pattern, &name[1..])),
+            (Some('?'), Some(_)) => inner(&pattern[1..], &name[1..]),
+            (Some(p), Some(n)) if p == n => inner(&pattern[1..], &name[1..]),
+            _ => false,
+        }
+    }
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    inner(&pattern, &name)
+}
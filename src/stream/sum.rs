@@ -8,11 +8,11 @@ use crate::stream::Stream;
 /// This trait is used to implement the [`sum`] method on streams. Types which
 /// implement the trait can be generated by the [`sum`] method. Like
 /// [`FromStream`] this trait should rarely be called directly and instead
-/// interacted with through [`Stream::sum`].
+/// interacted with through [`StreamExt::sum`].
 ///
 /// [`sum`]: trait.Sum.html#tymethod.sum
 /// [`FromStream`]: trait.FromStream.html
-/// [`Stream::sum`]: trait.Stream.html#method.sum
+/// [`StreamExt::sum`]: trait.StreamExt.html#method.sum
 pub trait Sum<A = Self>: Sized {
     /// Method which takes a stream and generates `Self` from the elements by
     /// "summing up" the items.
@@ -27,6 +27,7 @@ use core::ops::Add;
 
 macro_rules! integer_sum {
     (@impls $zero: expr, $($a:ty)*) => ($(
+        #[cfg(not(feature = "num-traits"))]
         impl Sum for $a {
             fn sum<'a, S>(stream: S) -> Pin<Box<dyn Future<Output = Self>+ 'a>>
             where
@@ -52,6 +53,7 @@ macro_rules! integer_sum {
 
 macro_rules! float_sum {
     ($($a:ty)*) => ($(
+        #[cfg(not(feature = "num-traits"))]
         impl Sum for $a {
             fn sum<'a, S>(stream: S) -> Pin<Box<dyn Future<Output = Self> + 'a>>
                 where S: Stream<Item = $a> + 'a,
@@ -75,3 +77,24 @@ macro_rules! float_sum {
 
 integer_sum! { i8 i16 i32 i64 i128 isize u8 u16 u32 u64 u128 usize }
 float_sum! { f32 f64 }
+
+/// Blanket impl of [`Sum`] for any type with an additive identity, gated
+/// behind the `num-traits` feature.
+///
+/// This lets [`StreamExt::sum`] work for arbitrary-precision integers, decimals,
+/// and other domain-specific numeric types, not just the primitives above.
+///
+/// [`Sum`]: trait.Sum.html
+/// [`StreamExt::sum`]: trait.StreamExt.html#method.sum
+#[cfg(feature = "num-traits")]
+impl<T> Sum for T
+where
+    T: Add<Output = T> + num_traits::Zero,
+{
+    fn sum<'a, S>(stream: S) -> Pin<Box<dyn Future<Output = Self> + 'a>>
+    where
+        S: Stream<Item = T> + 'a,
+    {
+        Box::pin(async move { stream.fold(T::zero(), Add::add).await })
+    }
+}
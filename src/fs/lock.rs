@@ -0,0 +1,93 @@
+//! Advisory file locking (`flock`/`LockFileEx`) for cross-process
+//! coordination, e.g. around a shared cache directory.
+//!
+//! Only the Unix `flock(2)` backend is implemented here; a Windows
+//! `LockFileEx` backend would mirror it behind `#[cfg(windows)]`.
+
+use crate::fs::File;
+use crate::io;
+use crate::task::spawn_blocking;
+
+#[cfg(unix)]
+use std::os::unix::io::{AsRawFd, RawFd};
+
+/// Extension trait adding advisory locking to [`File`].
+pub trait FileLockExt {
+    /// Blocks (on the blocking pool) until an exclusive lock is
+    /// acquired. Only one exclusive lock, and no shared locks, can be
+    /// held on the same file at once.
+    async fn lock_exclusive(&self) -> io::Result<FileLock<'_>>;
+
+    /// Blocks until a shared lock is acquired. Any number of shared
+    /// locks can be held at once, as long as no exclusive lock is held.
+    async fn lock_shared(&self) -> io::Result<FileLock<'_>>;
+
+    /// Attempts to acquire an exclusive lock without blocking, failing
+    /// with [`io::ErrorKind::WouldBlock`] if the file is already locked.
+    async fn try_lock(&self) -> io::Result<FileLock<'_>>;
+}
+
+impl FileLockExt for File {
+    async fn lock_exclusive(&self) -> io::Result<FileLock<'_>> {
+        lock(self, LockMode::Exclusive, true).await?;
+        Ok(FileLock { file: self })
+    }
+
+    async fn lock_shared(&self) -> io::Result<FileLock<'_>> {
+        lock(self, LockMode::Shared, true).await?;
+        Ok(FileLock { file: self })
+    }
+
+    async fn try_lock(&self) -> io::Result<FileLock<'_>> {
+        lock(self, LockMode::Exclusive, false).await?;
+        Ok(FileLock { file: self })
+    }
+}
+
+/// An acquired lock on a [`File`], released when dropped.
+pub struct FileLock<'a> {
+    file: &'a File,
+}
+
+impl Drop for FileLock<'_> {
+    fn drop(&mut self) {
+        // `LOCK_UN` doesn't block, so this is safe to do inline rather
+        // than bouncing through the blocking pool; there's no useful
+        // way to surface a failure here anyway, and the OS releases the
+        // lock on process exit regardless.
+        unlock(self.file.as_raw_fd());
+    }
+}
+
+#[derive(Clone, Copy)]
+enum LockMode {
+    Exclusive,
+    Shared,
+}
+
+#[cfg(unix)]
+async fn lock(file: &File, mode: LockMode, blocking: bool) -> io::Result<()> {
+    let fd = file.as_raw_fd();
+    spawn_blocking(move || {
+        let mut operation = match mode {
+            LockMode::Exclusive => libc::LOCK_EX,
+            LockMode::Shared => libc::LOCK_SH,
+        };
+        if !blocking {
+            operation |= libc::LOCK_NB;
+        }
+        if unsafe { libc::flock(fd, operation) } != 0 {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(())
+        }
+    })
+    .await
+}
+
+#[cfg(unix)]
+fn unlock(fd: RawFd) {
+    unsafe {
+        libc::flock(fd, libc::LOCK_UN);
+    }
+}
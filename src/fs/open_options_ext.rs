@@ -0,0 +1,109 @@
+//! Platform-specific [`OpenOptions`](super::OpenOptions) flags, for
+//! callers (storage engines, mainly) that need `O_DIRECT` or other bits
+//! std's cross-platform `OpenOptions` API doesn't expose.
+
+use crate::fs::OpenOptions;
+
+/// Extension trait mirroring `std::os::unix::fs::OpenOptionsExt`.
+#[cfg(unix)]
+pub trait OpenOptionsExt {
+    /// Sets the file mode bits used if the file is created (before
+    /// applying the process umask).
+    fn mode(&mut self, mode: u32) -> &mut Self;
+
+    /// OR's `flags` into the flags passed to the underlying `open(2)`
+    /// call, in addition to the ones `OpenOptions` sets itself (e.g.
+    /// `O_DIRECT`, `O_NOATIME`, `O_TMPFILE`).
+    fn custom_flags(&mut self, flags: i32) -> &mut Self;
+}
+
+#[cfg(unix)]
+impl OpenOptionsExt for OpenOptions {
+    fn mode(&mut self, mode: u32) -> &mut Self {
+        std::os::unix::fs::OpenOptionsExt::mode(self.as_std_mut(), mode);
+        self
+    }
+
+    fn custom_flags(&mut self, flags: i32) -> &mut Self {
+        std::os::unix::fs::OpenOptionsExt::custom_flags(self.as_std_mut(), flags);
+        self
+    }
+}
+
+/// Extension trait mirroring `std::os::windows::fs::OpenOptionsExt`.
+#[cfg(windows)]
+pub trait OpenOptionsExt {
+    fn access_mode(&mut self, access: u32) -> &mut Self;
+    fn share_mode(&mut self, share: u32) -> &mut Self;
+    fn custom_flags(&mut self, flags: u32) -> &mut Self;
+    fn attributes(&mut self, attributes: u32) -> &mut Self;
+}
+
+#[cfg(windows)]
+impl OpenOptionsExt for OpenOptions {
+    fn access_mode(&mut self, access: u32) -> &mut Self {
+        std::os::windows::fs::OpenOptionsExt::access_mode(self.as_std_mut(), access);
+        self
+    }
+
+    fn share_mode(&mut self, share: u32) -> &mut Self {
+        std::os::windows::fs::OpenOptionsExt::share_mode(self.as_std_mut(), share);
+        self
+    }
+
+    fn custom_flags(&mut self, flags: u32) -> &mut Self {
+        std::os::windows::fs::OpenOptionsExt::custom_flags(self.as_std_mut(), flags);
+        self
+    }
+
+    fn attributes(&mut self, attributes: u32) -> &mut Self {
+        std::os::windows::fs::OpenOptionsExt::attributes(self.as_std_mut(), attributes);
+        self
+    }
+}
+
+/// A buffer suitably aligned for `O_DIRECT` I/O, which on Linux
+/// requires the buffer address, length, and file offset to all be
+/// multiples of the filesystem's logical block size.
+///
+/// Allocates `len` bytes aligned to `align` (use the filesystem's block
+/// size, typically 512 or 4096; when unsure, 4096 is safe on every
+/// common filesystem).
+pub struct AlignedBuffer {
+    ptr: *mut u8,
+    len: usize,
+    layout: alloc::alloc::Layout,
+}
+
+impl AlignedBuffer {
+    /// Allocates a zeroed, `align`-byte-aligned buffer of `len` bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `len` is zero or `align` isn't a power of two.
+    pub fn new(len: usize, align: usize) -> Self {
+        let layout = alloc::alloc::Layout::from_size_align(len, align).expect("invalid O_DIRECT buffer size/alignment");
+        let ptr = unsafe { alloc::alloc::alloc_zeroed(layout) };
+        if ptr.is_null() {
+            alloc::alloc::handle_alloc_error(layout);
+        }
+        Self { ptr, len, layout }
+    }
+
+    pub fn as_slice(&self) -> &[u8] {
+        unsafe { core::slice::from_raw_parts(self.ptr, self.len) }
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [u8] {
+        unsafe { core::slice::from_raw_parts_mut(self.ptr, self.len) }
+    }
+}
+
+impl Drop for AlignedBuffer {
+    fn drop(&mut self) {
+        unsafe { alloc::alloc::dealloc(self.ptr, self.layout) };
+    }
+}
+
+unsafe impl Send for AlignedBuffer {}
+unsafe impl Sync for AlignedBuffer {}
@@ -0,0 +1,132 @@
+//! Coalescing `sync_data` calls and a configurable flush policy, for
+//! write-ahead logs that would otherwise burn a blocking-pool thread on
+//! every record.
+
+use core::time::Duration;
+use std::sync::Mutex;
+use std::time::Instant;
+
+use crate::fs::File;
+use crate::io;
+use crate::sync::Notify;
+use crate::task::spawn_blocking;
+
+/// When a [`SyncedFile`] should fsync automatically.
+pub enum SyncPolicy {
+    /// Sync after every write.
+    Always,
+    /// Sync once at least `bytes` have been written since the last
+    /// sync.
+    EveryBytes(u64),
+    /// Sync no more often than once per `interval`, regardless of how
+    /// many writes land in between.
+    EveryInterval(Duration),
+}
+
+struct SyncState {
+    /// Bumped every time a sync finishes; callers that arrive while a
+    /// sync is already running wait for this to move past the epoch
+    /// they observed, rather than starting a second one.
+    epoch: u64,
+    in_flight: bool,
+    /// The outcome of the most recently finished sync. `io::Error`
+    /// isn't `Clone`, so failures are kept as a raw OS error code.
+    outcome: Option<Result<(), i32>>,
+}
+
+/// Wraps a [`File`] so that concurrent callers requesting a data sync
+/// share a single underlying `fsync`/`fdatasync` call (a "group
+/// commit") instead of each paying for their own blocking-pool round
+/// trip.
+pub struct SyncedFile {
+    policy: SyncPolicy,
+    file: File,
+    bytes_since_sync: Mutex<u64>,
+    last_sync: Mutex<Instant>,
+    state: Mutex<SyncState>,
+    notify: Notify,
+}
+
+impl SyncedFile {
+    pub fn new(file: File, policy: SyncPolicy) -> Self {
+        Self {
+            policy,
+            file,
+            bytes_since_sync: Mutex::new(0),
+            last_sync: Mutex::new(Instant::now()),
+            state: Mutex::new(SyncState {
+                epoch: 0,
+                in_flight: false,
+                outcome: None,
+            }),
+            notify: Notify::new(),
+        }
+    }
+
+    /// Records that `bytes` were just written, and syncs if the policy
+    /// says it's time to.
+    pub async fn record_write(&self, bytes: u64) -> io::Result<()> {
+        let should_sync = match self.policy {
+            SyncPolicy::Always => true,
+            SyncPolicy::EveryBytes(threshold) => {
+                let mut total = self.bytes_since_sync.lock().unwrap();
+                *total += bytes;
+                *total >= threshold
+            }
+            SyncPolicy::EveryInterval(interval) => self.last_sync.lock().unwrap().elapsed() >= interval,
+        };
+
+        if should_sync {
+            self.sync_data().await?;
+        }
+        Ok(())
+    }
+
+    /// Ensures the file's data is durable on disk. If a sync is already
+    /// in flight when this is called, waits for that one to finish
+    /// (which covers this call's writes too, since they were issued
+    /// before it returned) instead of starting a second.
+    pub async fn sync_data(&self) -> io::Result<()> {
+        let observed_epoch = {
+            let mut state = self.state.lock().unwrap();
+            if !state.in_flight {
+                state.in_flight = true;
+                None
+            } else {
+                Some(state.epoch)
+            }
+        };
+
+        let result = match observed_epoch {
+            None => {
+                let file = self.file.clone();
+                let result = spawn_blocking(move || file.as_std().sync_data()).await;
+
+                let mut state = self.state.lock().unwrap();
+                state.in_flight = false;
+                state.epoch += 1;
+                state.outcome = Some(result.as_ref().map(|_| ()).map_err(|e| e.raw_os_error().unwrap_or(-1)));
+                drop(state);
+                self.notify.notify_all();
+
+                result
+            }
+            Some(observed_epoch) => loop {
+                self.notify.notified().await;
+                let state = self.state.lock().unwrap();
+                if state.epoch > observed_epoch {
+                    break match state.outcome {
+                        Some(Ok(())) => Ok(()),
+                        Some(Err(code)) => Err(io::Error::from_raw_os_error(code)),
+                        None => Ok(()),
+                    };
+                }
+            },
+        };
+
+        *self.bytes_since_sync.lock().unwrap() = 0;
+        *self.last_sync.lock().unwrap() = Instant::now();
+
+        result
+    }
+}
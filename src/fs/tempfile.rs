@@ -0,0 +1,162 @@
+//! Temporary files and directories, so test suites and atomic-write
+//! patterns (write to a temp file, then rename into place) don't need
+//! to mix a separate blocking `tempfile`-style crate into async code.
+
+use std::path::{Path, PathBuf};
+
+use crate::fs::File;
+use crate::io;
+use crate::task::spawn_blocking;
+
+/// A temporary directory that's removed (recursively) when dropped.
+pub struct TempDir {
+    path: PathBuf,
+    persisted: bool,
+}
+
+impl TempDir {
+    /// Creates a new temporary directory under the system temp dir.
+    pub async fn new() -> io::Result<Self> {
+        spawn_blocking(|| {
+            let path = unique_path(&std::env::temp_dir());
+            std::fs::create_dir(&path)?;
+            Ok(Self { path, persisted: false })
+        })
+        .await
+    }
+
+    /// The path of the directory.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Consumes the guard without removing the directory, returning its
+    /// path.
+    pub fn persist(mut self) -> PathBuf {
+        self.persisted = true;
+        self.path.clone()
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        if !self.persisted {
+            let _ = std::fs::remove_dir_all(&self.path);
+        }
+    }
+}
+
+/// A temporary file that's removed when dropped, unless [`persist`]ed.
+///
+/// [`persist`]: TempFile::persist
+pub struct TempFile {
+    file: Option<File>,
+    path: PathBuf,
+    persisted: bool,
+}
+
+impl TempFile {
+    /// The path of the file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Takes the underlying [`File`] for reading/writing, leaving the
+    /// cleanup-on-drop path tracking in place.
+    pub fn as_file_mut(&mut self) -> &mut File {
+        self.file.as_mut().expect("TempFile already persisted")
+    }
+
+    /// Renames the file to `dst`, consuming the guard without removing
+    /// it.
+    ///
+    /// Handy for the "write to a temp file, then atomically rename into
+    /// place" pattern: the temp file lives alongside `dst` for the
+    /// rename to stay on the same filesystem.
+    pub async fn persist(mut self, dst: impl AsRef<Path>) -> io::Result<()> {
+        self.persisted = true;
+        let src = self.path.clone();
+        let dst = dst.as_ref().to_path_buf();
+        spawn_blocking(move || std::fs::rename(src, dst)).await
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        if !self.persisted {
+            let _ = std::fs::remove_file(&self.path);
+        }
+    }
+}
+
+/// Creates a new temporary file under the system temp dir.
+pub async fn tempfile() -> io::Result<TempFile> {
+    let path = unique_path(&std::env::temp_dir());
+    let file = File::create(&path).await?;
+    Ok(TempFile {
+        file: Some(file),
+        path,
+        persisted: false,
+    })
+}
+
+fn unique_path(dir: &Path) -> PathBuf {
+    use std::sync::atomic::{AtomicU64, Ordering};
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    let pid = std::process::id();
+    let n = COUNTER.fetch_add(1, Ordering::Relaxed);
+    dir.join(format!(".tmp-{pid}-{n}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn temp_dir_exists_while_held_and_is_removed_on_drop() {
+        crate::task::block_on(async {
+            let dir = TempDir::new().await.unwrap();
+            let path = dir.path().to_path_buf();
+            assert!(path.is_dir());
+            drop(dir);
+            assert!(!path.exists(), "TempDir should remove its directory on drop");
+        });
+    }
+
+    #[test]
+    fn temp_dir_persist_leaves_the_directory_behind() {
+        crate::task::block_on(async {
+            let dir = TempDir::new().await.unwrap();
+            let path = dir.persist();
+            assert!(path.is_dir());
+            std::fs::remove_dir_all(&path).unwrap();
+        });
+    }
+
+    #[test]
+    fn temp_file_exists_while_held_and_is_removed_on_drop() {
+        crate::task::block_on(async {
+            let file = tempfile().await.unwrap();
+            let path = file.path().to_path_buf();
+            assert!(path.is_file());
+            drop(file);
+            assert!(!path.exists(), "TempFile should remove its file on drop");
+        });
+    }
+
+    #[test]
+    fn temp_file_persist_renames_into_place() {
+        crate::task::block_on(async {
+            let file = tempfile().await.unwrap();
+            let src = file.path().to_path_buf();
+            let dst = unique_path(&std::env::temp_dir());
+
+            file.persist(&dst).await.unwrap();
+
+            assert!(!src.exists(), "the original temp path should be gone after persist");
+            assert!(dst.is_file());
+            std::fs::remove_file(&dst).unwrap();
+        });
+    }
+}
@@ -0,0 +1,85 @@
+//! Setting file modification/access times, and the extra metadata
+//! needed for backup-style tools to round-trip them faithfully.
+
+use std::path::Path;
+use std::time::SystemTime;
+
+use crate::fs::{File, Metadata};
+use crate::io;
+use crate::task::spawn_blocking;
+
+/// Sets the modified and accessed times of the file at `path`, run on
+/// the blocking pool.
+pub async fn set_times(path: impl AsRef<Path>, modified: SystemTime, accessed: SystemTime) -> io::Result<()> {
+    let path = path.as_ref().to_path_buf();
+    spawn_blocking(move || {
+        let times = std::fs::FileTimes::new().set_modified(modified).set_accessed(accessed);
+        std::fs::File::options().write(true).open(&path)?.set_times(times)
+    })
+    .await
+}
+
+/// Extension trait adding time-setting to [`File`].
+pub trait FileTimesExt {
+    /// Sets this file's modified time, run on the blocking pool.
+    async fn set_modified(&self, time: SystemTime) -> io::Result<()>;
+}
+
+impl FileTimesExt for File {
+    async fn set_modified(&self, time: SystemTime) -> io::Result<()> {
+        let file = self.clone();
+        spawn_blocking(move || {
+            let times = std::fs::FileTimes::new().set_modified(time);
+            file.as_std().set_times(times)
+        })
+        .await
+    }
+}
+
+/// Extension trait adding time and Unix-specific accessors to
+/// [`Metadata`] that aren't exposed by the inherent API.
+pub trait MetadataExt {
+    /// The file's creation time, if the platform and filesystem record
+    /// one.
+    fn created_at(&self) -> io::Result<SystemTime>;
+
+    /// The file's last-accessed time.
+    fn accessed_at(&self) -> io::Result<SystemTime>;
+
+    /// The file's owning user ID, on Unix.
+    #[cfg(unix)]
+    fn uid(&self) -> u32;
+
+    /// The file's owning group ID, on Unix.
+    #[cfg(unix)]
+    fn gid(&self) -> u32;
+
+    /// The file's permission mode bits, on Unix.
+    #[cfg(unix)]
+    fn mode(&self) -> u32;
+}
+
+impl MetadataExt for Metadata {
+    fn created_at(&self) -> io::Result<SystemTime> {
+        self.as_std().created()
+    }
+
+    fn accessed_at(&self) -> io::Result<SystemTime> {
+        self.as_std().accessed()
+    }
+
+    #[cfg(unix)]
+    fn uid(&self) -> u32 {
+        std::os::unix::fs::MetadataExt::uid(self.as_std())
+    }
+
+    #[cfg(unix)]
+    fn gid(&self) -> u32 {
+        std::os::unix::fs::MetadataExt::gid(self.as_std())
+    }
+
+    #[cfg(unix)]
+    fn mode(&self) -> u32 {
+        std::os::unix::fs::MetadataExt::mode(self.as_std())
+    }
+}
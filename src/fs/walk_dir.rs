@@ -0,0 +1,124 @@
+//! Recursive directory traversal, so callers don't each hand-roll a
+//! stack of `read_dir` futures.
+
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use std::path::PathBuf;
+
+use crate::fs::DirEntry;
+use crate::io;
+use crate::stream::Stream;
+use crate::task::spawn_blocking;
+
+/// Builder for a recursive directory walk, started with [`walk_dir`].
+pub struct WalkDir {
+    root: PathBuf,
+    max_depth: Option<usize>,
+    follow_links: bool,
+    skip_hidden: bool,
+}
+
+/// Starts building a recursive walk of `root`.
+pub fn walk_dir(root: impl Into<PathBuf>) -> WalkDir {
+    WalkDir {
+        root: root.into(),
+        max_depth: None,
+        follow_links: false,
+        skip_hidden: true,
+    }
+}
+
+impl WalkDir {
+    /// Limits how many directory levels below `root` are descended
+    /// into. `0` yields only `root`'s direct children.
+    pub fn max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = Some(depth);
+        self
+    }
+
+    /// Descends into symlinked directories instead of yielding them as
+    /// leaf entries. Off by default to avoid infinite loops on cyclic
+    /// symlinks.
+    pub fn follow_links(mut self, yes: bool) -> Self {
+        self.follow_links = yes;
+        self
+    }
+
+    /// Skips entries whose file name starts with `.`. On by default.
+    pub fn skip_hidden(mut self, yes: bool) -> Self {
+        self.skip_hidden = yes;
+        self
+    }
+
+    /// Starts the walk, returning a stream of entries as they're
+    /// discovered.
+    ///
+    /// The walk itself -- including every `stat` call needed to decide
+    /// whether to descend into a symlink -- runs on a single blocking
+    /// pool task, with entries forwarded to the stream as they're
+    /// found; it doesn't yet fan individual directories' `stat` calls
+    /// out across multiple blocking pool tasks the way a fully
+    /// parallel walker would.
+    pub fn into_stream(self) -> WalkDirStream {
+        let (sender, receiver) = crate::channel::unbounded();
+
+        spawn_blocking(move || {
+            let mut stack = alloc::vec![(self.root.clone(), 0usize)];
+            while let Some((dir, depth)) = stack.pop() {
+                let entries = match std::fs::read_dir(&dir) {
+                    Ok(entries) => entries,
+                    Err(e) => {
+                        let _ = sender.try_send(Err(e));
+                        continue;
+                    }
+                };
+
+                for entry in entries {
+                    let entry = match entry {
+                        Ok(entry) => entry,
+                        Err(e) => {
+                            let _ = sender.try_send(Err(e));
+                            continue;
+                        }
+                    };
+
+                    if self.skip_hidden && entry.file_name().to_string_lossy().starts_with('.') {
+                        continue;
+                    }
+
+                    let file_type = match entry.file_type() {
+                        Ok(ft) => ft,
+                        Err(e) => {
+                            let _ = sender.try_send(Err(e));
+                            continue;
+                        }
+                    };
+
+                    let should_descend = file_type.is_dir() || (file_type.is_symlink() && self.follow_links);
+
+                    if should_descend && self.max_depth.map_or(true, |max| depth < max) {
+                        stack.push((entry.path(), depth + 1));
+                    }
+
+                    let _ = sender.try_send(Ok(DirEntry::from_std(entry)));
+                }
+            }
+        });
+
+        WalkDirStream { receiver }
+    }
+}
+
+/// Stream of [`DirEntry`] produced by [`WalkDir::into_stream`].
+pub struct WalkDirStream {
+    receiver: crate::channel::Receiver<io::Result<DirEntry>>,
+}
+
+impl Stream for WalkDirStream {
+    type Item = io::Result<DirEntry>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.get_mut().receiver).poll_next(cx)
+    }
+}
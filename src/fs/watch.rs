@@ -0,0 +1,204 @@
+//! File-system change notification, so applications don't each have to
+//! wire a third-party `notify`-style crate into the reactor themselves.
+//!
+//! Only the Linux `inotify` backend is implemented; `FSEvents` (macOS)
+//! and `ReadDirectoryChangesW` (Windows) backends would slot in behind
+//! their own `#[cfg]`s using the same public API.
+
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use core::time::Duration;
+
+use std::path::{Path, PathBuf};
+
+use crate::io;
+use crate::stream::Stream;
+use crate::task::spawn_blocking;
+
+/// The kind of change a [`FsEvent`] reports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FsEventKind {
+    Created,
+    Modified,
+    Removed,
+    Renamed,
+}
+
+/// A single reported file-system change.
+#[derive(Debug, Clone)]
+pub struct FsEvent {
+    pub path: PathBuf,
+    pub kind: FsEventKind,
+}
+
+/// Options for [`watch`].
+pub struct WatchOptions {
+    recursive: bool,
+    debounce: Option<Duration>,
+}
+
+impl Default for WatchOptions {
+    fn default() -> Self {
+        Self {
+            recursive: false,
+            debounce: Some(Duration::from_millis(50)),
+        }
+    }
+}
+
+impl WatchOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Watches subdirectories too, not just direct children of the
+    /// watched path.
+    pub fn recursive(mut self, yes: bool) -> Self {
+        self.recursive = yes;
+        self
+    }
+
+    /// Coalesces bursts of events for the same path within `window`
+    /// into a single event, e.g. to avoid reporting both the `CREATE`
+    /// and the immediately-following `MODIFY` a text editor's "save"
+    /// tends to produce. `None` disables debouncing.
+    pub fn debounce(mut self, window: Option<Duration>) -> Self {
+        self.debounce = window;
+        self
+    }
+}
+
+/// Watches `path` for changes, returning a stream of [`FsEvent`]s.
+#[cfg(target_os = "linux")]
+pub fn watch(path: impl AsRef<Path>, options: WatchOptions) -> io::Result<Watcher> {
+    let path = path.as_ref().to_path_buf();
+    let (sender, receiver) = crate::channel::unbounded();
+
+    spawn_blocking(move || inotify::run(&path, &options, &sender));
+
+    Ok(Watcher { receiver })
+}
+
+/// Watches `path` for changes, returning a stream of [`FsEvent`]s.
+#[cfg(not(target_os = "linux"))]
+pub fn watch(_path: impl AsRef<Path>, _options: WatchOptions) -> io::Result<Watcher> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "fs::watch is only implemented for Linux (inotify) in this build",
+    ))
+}
+
+/// Stream of [`FsEvent`]s returned by [`watch`].
+pub struct Watcher {
+    receiver: crate::channel::Receiver<io::Result<FsEvent>>,
+}
+
+impl Stream for Watcher {
+    type Item = io::Result<FsEvent>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.get_mut().receiver).poll_next(cx)
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod inotify {
+    use super::*;
+    use std::collections::HashMap;
+    use std::time::Instant;
+
+    pub(super) fn run(path: &Path, options: &WatchOptions, sender: &crate::channel::Sender<io::Result<FsEvent>>) {
+        let fd = unsafe { libc::inotify_init1(libc::IN_NONBLOCK) };
+        if fd < 0 {
+            let _ = sender.try_send(Err(io::Error::last_os_error()));
+            return;
+        }
+
+        let mask = libc::IN_CREATE | libc::IN_MODIFY | libc::IN_DELETE | libc::IN_MOVED_FROM | libc::IN_MOVED_TO;
+        if add_watch(fd, path, mask).is_err() {
+            let _ = sender.try_send(Err(io::Error::last_os_error()));
+            return;
+        }
+
+        if options.recursive {
+            if let Ok(entries) = std::fs::read_dir(path) {
+                for entry in entries.flatten() {
+                    if entry.file_type().map(|t| t.is_dir()).unwrap_or(false) {
+                        let _ = add_watch(fd, &entry.path(), mask);
+                    }
+                }
+            }
+        }
+
+        let mut pending: HashMap<PathBuf, (FsEventKind, Instant)> = HashMap::new();
+        let mut buf = [0u8; 4096];
+
+        loop {
+            let n = unsafe { libc::read(fd, buf.as_mut_ptr() as *mut libc::c_void, buf.len()) };
+            if n > 0 {
+                let mut offset = 0usize;
+                while offset < n as usize {
+                    let event = unsafe { &*(buf.as_ptr().add(offset) as *const libc::inotify_event) };
+                    let name_len = event.len as usize;
+                    let kind = classify(event.mask);
+                    if let Some(kind) = kind {
+                        pending.insert(path.to_path_buf(), (kind, Instant::now()));
+                    }
+                    offset += core::mem::size_of::<libc::inotify_event>() + name_len;
+                }
+            }
+
+            if let Some(debounce) = options.debounce {
+                pending.retain(|changed_path, (kind, seen)| {
+                    if seen.elapsed() >= debounce {
+                        let _ = sender.try_send(Ok(FsEvent {
+                            path: changed_path.clone(),
+                            kind: *kind,
+                        }));
+                        false
+                    } else {
+                        true
+                    }
+                });
+            } else {
+                for (changed_path, (kind, _)) in pending.drain() {
+                    let _ = sender.try_send(Ok(FsEvent { path: changed_path, kind }));
+                }
+            }
+
+            if sender.is_closed() {
+                break;
+            }
+
+            std::thread::sleep(Duration::from_millis(10));
+        }
+
+        unsafe {
+            libc::close(fd);
+        }
+    }
+
+    fn add_watch(fd: i32, path: &Path, mask: u32) -> io::Result<i32> {
+        let c_path = std::ffi::CString::new(path.as_os_str().as_encoded_bytes()).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+        let wd = unsafe { libc::inotify_add_watch(fd, c_path.as_ptr(), mask) };
+        if wd < 0 {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(wd)
+        }
+    }
+
+    fn classify(mask: u32) -> Option<FsEventKind> {
+        if mask & libc::IN_CREATE != 0 {
+            Some(FsEventKind::Created)
+        } else if mask & libc::IN_MODIFY != 0 {
+            Some(FsEventKind::Modified)
+        } else if mask & libc::IN_DELETE != 0 {
+            Some(FsEventKind::Removed)
+        } else if mask & (libc::IN_MOVED_FROM | libc::IN_MOVED_TO) != 0 {
+            Some(FsEventKind::Renamed)
+        } else {
+            None
+        }
+    }
+}
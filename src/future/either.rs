@@ -0,0 +1,7 @@
+//! Re-export of [`Either`](crate::stream::either::Either) under
+//! `future` as well as `stream`, since the same type implements both
+//! [`Future`](core::future::Future) and
+//! [`Stream`](crate::stream::Stream) (along with [`Read`](crate::io::Read)
+//! and [`Write`](crate::io::Write) when both arms do).
+
+pub use crate::stream::either::Either;
@@ -0,0 +1,104 @@
+//! Guaranteeing an async cleanup step runs, even past cancellation.
+
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+/// Future for the [`finally`] function and [`FutureExt::finally`]
+/// method.
+///
+/// [`FutureExt::finally`]: crate::future::FutureExt::finally
+pub struct Finally<F, C, MkC>
+where
+    F: Future,
+    C: Future<Output = ()> + Send + 'static,
+{
+    future: Option<F>,
+    make_cleanup: Option<MkC>,
+    cleanup: Option<C>,
+    output: Option<F::Output>,
+}
+
+/// Runs `future` to completion, then runs the cleanup future produced
+/// by `make_cleanup` before resolving with `future`'s output.
+///
+/// The cleanup also runs if the returned future is dropped before
+/// `future` finishes (e.g. its task is cancelled, or it loses a
+/// `select!` race): it's detached onto its own task via
+/// [`task::spawn`](crate::task::spawn) and left to run to completion
+/// in the background, since a synchronous `Drop` can't `.await`
+/// anything itself.
+pub fn finally<F, C, MkC>(future: F, make_cleanup: MkC) -> Finally<F, C, MkC>
+where
+    F: Future,
+    MkC: FnOnce() -> C,
+    C: Future<Output = ()> + Send + 'static,
+{
+    Finally {
+        future: Some(future),
+        make_cleanup: Some(make_cleanup),
+        cleanup: None,
+        output: None,
+    }
+}
+
+impl<F, C, MkC> Future for Finally<F, C, MkC>
+where
+    F: Future,
+    MkC: FnOnce() -> C,
+    C: Future<Output = ()> + Send + 'static,
+{
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<F::Output> {
+        // SAFETY: every field is only ever polled through a re-pinned
+        // reference and taken by value once it's done producing a
+        // value, matching this crate's structural-pinning convention.
+        let this = unsafe { self.get_unchecked_mut() };
+
+        if let Some(future) = &mut this.future {
+            let pinned = unsafe { Pin::new_unchecked(future) };
+            match pinned.poll(cx) {
+                Poll::Ready(output) => {
+                    this.future = None;
+                    this.output = Some(output);
+                    let make_cleanup = this.make_cleanup.take().expect("cleanup already started");
+                    this.cleanup = Some(make_cleanup());
+                }
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+
+        let cleanup = this.cleanup.as_mut().expect("cleanup always set once `future` resolves");
+        let pinned = unsafe { Pin::new_unchecked(cleanup) };
+        match pinned.poll(cx) {
+            Poll::Ready(()) => {
+                this.cleanup = None;
+                Poll::Ready(this.output.take().expect("output set before cleanup starts"))
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+impl<F, C, MkC> Drop for Finally<F, C, MkC>
+where
+    F: Future,
+    C: Future<Output = ()> + Send + 'static,
+{
+    fn drop(&mut self) {
+        if let Some(make_cleanup) = self.make_cleanup.take() {
+            // `future` never resolved, so its cleanup was never
+            // created. We still owe the caller a cleanup run, so
+            // build it now and detach it onto its own task -- a
+            // synchronous `Drop` has no way to `.await` it directly.
+            self.future = None;
+            crate::task::spawn(make_cleanup());
+        } else if let Some(cleanup) = self.cleanup.take() {
+            // `future` resolved but the cleanup it kicked off hadn't
+            // finished yet; let it keep running in the background
+            // instead of abandoning it partway through.
+            crate::task::spawn(cleanup);
+        }
+    }
+}
@@ -0,0 +1,160 @@
+//! Extension methods for [`Future`], mirroring
+//! [`StreamExt`](crate::stream::StreamExt) on the stream side.
+
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use core::time::Duration;
+
+use std::time::Instant;
+
+use crate::future::finally::{finally, Finally};
+use crate::future::select_macro::noop_waker;
+use crate::future::timeout::{timeout, timeout_at, Timeout};
+
+/// A future that remembers whether it has already resolved, so it's
+/// safe to poll again after it has -- required for writing correct
+/// manual select loops where one branch finishes before the others
+/// and would otherwise need `Option`-wrapping gymnastics to avoid
+/// being polled a second time.
+pub trait FusedFuture: Future {
+    /// Returns `true` once this future has resolved to `Poll::Ready`.
+    /// Once `true`, it stays `true` for the life of the value.
+    fn is_terminated(&self) -> bool;
+}
+
+/// Extension methods available on every [`Future`].
+pub trait FutureExt: Future {
+    /// Wraps this future so that it can be safely polled again after
+    /// it resolves: every poll after the first `Ready` also returns
+    /// `Pending`, forever, instead of re-polling the underlying future
+    /// (which most futures don't support doing after completion).
+    fn fuse(self) -> Fuse<Self>
+    where
+        Self: Sized,
+    {
+        Fuse { inner: Some(self) }
+    }
+
+    /// Fails with [`Elapsed`](crate::future::Elapsed) if this future
+    /// doesn't resolve within `duration`.
+    fn timeout(self, duration: Duration) -> Timeout<Self>
+    where
+        Self: Sized,
+    {
+        timeout(duration, self)
+    }
+
+    /// Fails with [`Elapsed`](crate::future::Elapsed) if this future
+    /// doesn't resolve before `deadline`.
+    fn timeout_at(self, deadline: Instant) -> Timeout<Self>
+    where
+        Self: Sized,
+    {
+        timeout_at(deadline, self)
+    }
+
+    /// Polls this future exactly once and returns its output if it
+    /// was already ready, discarding the future (and any wakeup it
+    /// might otherwise have scheduled) if it wasn't.
+    ///
+    /// There's no executor behind this poll, so a future that relies
+    /// on a real waker to ever make progress will always come back
+    /// `None` here -- this is for opportunistically observing a value
+    /// that might already be sitting there (e.g. a channel `recv`
+    /// with a message already buffered), not for driving a future
+    /// that still has work to do.
+    fn now_or_never(self) -> Option<Self::Output>
+    where
+        Self: Sized,
+    {
+        pin_utils::pin_mut!(self);
+        let waker = noop_waker();
+        let mut cx = Context::from_waker(&waker);
+        match self.poll(&mut cx) {
+            Poll::Ready(value) => Some(value),
+            Poll::Pending => None,
+        }
+    }
+
+    /// Runs `make_cleanup`'s future after this one resolves, or -- if
+    /// this future is instead dropped before resolving, e.g. because
+    /// its task was cancelled -- on a detached task in the
+    /// background, so the cleanup always runs to completion either
+    /// way.
+    fn finally<C, MkC>(self, make_cleanup: MkC) -> Finally<Self, C, MkC>
+    where
+        Self: Sized,
+        MkC: FnOnce() -> C,
+        C: Future<Output = ()> + Send + 'static,
+    {
+        finally(self, make_cleanup)
+    }
+}
+
+impl<F: Future + ?Sized> FutureExt for F {}
+
+/// Future returned by [`FutureExt::fuse`].
+pub struct Fuse<F> {
+    inner: Option<F>,
+}
+
+impl<F: Future> Future for Fuse<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<F::Output> {
+        // SAFETY: `inner` is never moved out of its `Option` slot by
+        // value while pinned; taking it happens only after it's
+        // finished producing a value, matching the structural-pinning
+        // convention used by this crate's other hand-written futures.
+        let this = unsafe { self.get_unchecked_mut() };
+        match &mut this.inner {
+            Some(inner) => {
+                let inner_pin = unsafe { Pin::new_unchecked(inner) };
+                match inner_pin.poll(cx) {
+                    Poll::Ready(output) => {
+                        this.inner = None;
+                        Poll::Ready(output)
+                    }
+                    Poll::Pending => Poll::Pending,
+                }
+            }
+            None => Poll::Pending,
+        }
+    }
+}
+
+impl<F: Future> FusedFuture for Fuse<F> {
+    fn is_terminated(&self) -> bool {
+        self.inner.is_none()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::future::pending;
+
+    #[test]
+    fn now_or_never_returns_ready_value() {
+        assert_eq!(core::future::ready(42).now_or_never(), Some(42));
+    }
+
+    #[test]
+    fn now_or_never_discards_a_pending_future() {
+        assert_eq!(pending::<()>().now_or_never(), None);
+    }
+
+    #[test]
+    fn fuse_keeps_returning_pending_after_the_first_ready() {
+        crate::task::block_on(async {
+            let mut fused = core::future::ready(1).fuse();
+            assert!(!fused.is_terminated());
+            assert_eq!(Pin::new(&mut fused).await, 1);
+            assert!(fused.is_terminated());
+            // Polling again must not re-poll the (already consumed)
+            // inner future; it should just stay `Pending` forever.
+            assert_eq!(crate::future::poll_immediate(&mut fused).await, Poll::Pending);
+        });
+    }
+}
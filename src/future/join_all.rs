@@ -0,0 +1,95 @@
+//! Awaiting a dynamically-sized collection of futures, for the "fan
+//! out N parallel requests" case the fixed-arity `join` helpers don't
+//! cover.
+
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
+/// Awaits every future in `futures` concurrently, returning their
+/// outputs in the same order once all of them have resolved.
+pub fn join_all<I>(futures: I) -> JoinAll<<I::Item as Future>::Output>
+where
+    I: IntoIterator,
+    I::Item: Future,
+{
+    let futures: Vec<_> = futures
+        .into_iter()
+        .map(|future| Some(Box::pin(future) as Pin<Box<dyn Future<Output = _>>>))
+        .collect();
+    let len = futures.len();
+    let mut results = Vec::with_capacity(len);
+    results.resize_with(len, || None);
+    JoinAll { futures, results, remaining: len }
+}
+
+/// Future returned by [`join_all`].
+pub struct JoinAll<T> {
+    futures: Vec<Option<Pin<Box<dyn Future<Output = T>>>>>,
+    results: Vec<Option<T>>,
+    remaining: usize,
+}
+
+impl<T> Future for JoinAll<T> {
+    type Output = Vec<T>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Vec<T>> {
+        let this = self.get_mut();
+        for (slot, result) in this.futures.iter_mut().zip(this.results.iter_mut()) {
+            if let Some(future) = slot {
+                if let Poll::Ready(value) = future.as_mut().poll(cx) {
+                    *result = Some(value);
+                    *slot = None;
+                    this.remaining -= 1;
+                }
+            }
+        }
+        if this.remaining == 0 {
+            Poll::Ready(this.results.iter_mut().map(|result| result.take().unwrap()).collect())
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+/// Awaits every future in `futures` concurrently, short-circuiting
+/// with the first `Err` and cancelling the rest (by dropping them) as
+/// soon as it's observed.
+pub async fn try_join_all<I, T, E>(futures: I) -> Result<Vec<T>, E>
+where
+    I: IntoIterator,
+    I::Item: Future<Output = Result<T, E>>,
+{
+    let mut futures: Vec<_> = futures.into_iter().map(Box::pin).collect();
+    let mut results = Vec::with_capacity(futures.len());
+    results.resize_with(futures.len(), || None);
+    let mut remaining = futures.len();
+
+    core::future::poll_fn(|cx| {
+        for (slot, result) in futures.iter_mut().zip(results.iter_mut()) {
+            if result.is_some() {
+                continue;
+            }
+            if let Poll::Ready(value) = slot.as_mut().poll(cx) {
+                match value {
+                    Ok(value) => {
+                        *result = Some(Ok(value));
+                        remaining -= 1;
+                    }
+                    Err(err) => return Poll::Ready(Err(err)),
+                }
+            }
+        }
+        if remaining == 0 {
+            Poll::Ready(Ok(()))
+        } else {
+            Poll::Pending
+        }
+    })
+    .await?;
+
+    Ok(results.into_iter().map(|result| result.unwrap().unwrap()).collect())
+}
@@ -0,0 +1,167 @@
+//! `join!`/`try_join!`: awaiting several differently-typed futures
+//! concurrently on the current task, without spawning or boxing.
+//!
+//! The method-chaining pairwise `join` composes two futures at a
+//! time, which gets unreadable past three or four. These macros take
+//! any number up to six futures directly; beyond that, collect
+//! same-typed futures into a `Vec` and use
+//! [`future::join_all`](crate::future::join_all) instead.
+//!
+//! Each macro is built by pairwise-composing a hidden two-future
+//! combinator (`$crate::__join2!`/`$crate::__try_join2!`), the same
+//! way the public pairwise `join`/`try_join` would be chained by hand
+//! -- just with the nesting (and re-flattening of the resulting nested
+//! tuple) done once, here, instead of at every call site.
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __join2 {
+    ($a:expr, $b:expr) => {
+        async {
+            let mut a = $a;
+            let mut b = $b;
+            // SAFETY: `a`/`b` are local to this async block and never
+            // moved again after being pinned, matching the
+            // structural-pinning convention used by this crate's
+            // other hand-written futures.
+            let mut a = unsafe { core::pin::Pin::new_unchecked(&mut a) };
+            let mut b = unsafe { core::pin::Pin::new_unchecked(&mut b) };
+            let mut a_out = None;
+            let mut b_out = None;
+            core::future::poll_fn(|cx| {
+                if a_out.is_none() {
+                    if let core::task::Poll::Ready(v) = a.as_mut().poll(cx) {
+                        a_out = Some(v);
+                    }
+                }
+                if b_out.is_none() {
+                    if let core::task::Poll::Ready(v) = b.as_mut().poll(cx) {
+                        b_out = Some(v);
+                    }
+                }
+                if a_out.is_some() && b_out.is_some() {
+                    core::task::Poll::Ready(())
+                } else {
+                    core::task::Poll::Pending
+                }
+            })
+            .await;
+            (a_out.unwrap(), b_out.unwrap())
+        }
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __try_join2 {
+    ($a:expr, $b:expr) => {
+        async {
+            let mut a = $a;
+            let mut b = $b;
+            // SAFETY: see `__join2!`.
+            let mut a = unsafe { core::pin::Pin::new_unchecked(&mut a) };
+            let mut b = unsafe { core::pin::Pin::new_unchecked(&mut b) };
+            let mut a_out = None;
+            let mut b_out = None;
+            core::future::poll_fn(|cx| {
+                if a_out.is_none() {
+                    if let core::task::Poll::Ready(v) = a.as_mut().poll(cx) {
+                        match v {
+                            Ok(v) => a_out = Some(v),
+                            Err(e) => return core::task::Poll::Ready(Err(e)),
+                        }
+                    }
+                }
+                if b_out.is_none() {
+                    if let core::task::Poll::Ready(v) = b.as_mut().poll(cx) {
+                        match v {
+                            Ok(v) => b_out = Some(v),
+                            Err(e) => return core::task::Poll::Ready(Err(e)),
+                        }
+                    }
+                }
+                if a_out.is_some() && b_out.is_some() {
+                    core::task::Poll::Ready(Ok(()))
+                } else {
+                    core::task::Poll::Pending
+                }
+            })
+            .await
+            .map(|()| (a_out.unwrap(), b_out.unwrap()))
+        }
+    };
+}
+
+/// Awaits every given future concurrently on the current task,
+/// returning a tuple of their outputs once all have resolved.
+///
+/// Supports two to six branches.
+#[macro_export]
+macro_rules! join {
+    ($a:expr, $b:expr $(,)?) => {
+        $crate::__join2!($a, $b).await
+    };
+    ($a:expr, $b:expr, $c:expr $(,)?) => {{
+        let ((a, b), c) = $crate::__join2!($crate::__join2!($a, $b), $c).await;
+        (a, b, c)
+    }};
+    ($a:expr, $b:expr, $c:expr, $d:expr $(,)?) => {{
+        let ((a, b, c), d) = $crate::__join2!($crate::__join2!($crate::__join2!($a, $b), $c), $d).await;
+        (a, b, c, d)
+    }};
+    ($a:expr, $b:expr, $c:expr, $d:expr, $e:expr $(,)?) => {{
+        let ((a, b, c, d), e) =
+            $crate::__join2!($crate::__join2!($crate::__join2!($crate::__join2!($a, $b), $c), $d), $e).await;
+        (a, b, c, d, e)
+    }};
+    ($a:expr, $b:expr, $c:expr, $d:expr, $e:expr, $f:expr $(,)?) => {{
+        let ((a, b, c, d, e), f) = $crate::__join2!(
+            $crate::__join2!($crate::__join2!($crate::__join2!($crate::__join2!($a, $b), $c), $d), $e),
+            $f
+        )
+        .await;
+        (a, b, c, d, e, f)
+    }};
+}
+
+/// Like [`join!`], but for futures each resolving to a `Result`: as
+/// soon as any of them resolves to `Err`, `try_join!` returns that
+/// error immediately, dropping the rest of the still-pending futures
+/// (aborting whatever work they were in the middle of).
+///
+/// Supports two to six branches.
+#[macro_export]
+macro_rules! try_join {
+    ($a:expr, $b:expr $(,)?) => {
+        $crate::__try_join2!($a, $b).await
+    };
+    ($a:expr, $b:expr, $c:expr $(,)?) => {
+        $crate::__try_join2!($crate::__try_join2!($a, $b), $c)
+            .await
+            .map(|((a, b), c)| (a, b, c))
+    };
+    ($a:expr, $b:expr, $c:expr, $d:expr $(,)?) => {
+        $crate::__try_join2!($crate::__try_join2!($crate::__try_join2!($a, $b), $c), $d)
+            .await
+            .map(|((a, b, c), d)| (a, b, c, d))
+    };
+    ($a:expr, $b:expr, $c:expr, $d:expr, $e:expr $(,)?) => {
+        $crate::__try_join2!(
+            $crate::__try_join2!($crate::__try_join2!($crate::__try_join2!($a, $b), $c), $d),
+            $e
+        )
+        .await
+        .map(|((a, b, c, d), e)| (a, b, c, d, e))
+    };
+    ($a:expr, $b:expr, $c:expr, $d:expr, $e:expr, $f:expr $(,)?) => {
+        $crate::__try_join2!(
+            $crate::__try_join2!(
+                $crate::__try_join2!($crate::__try_join2!($crate::__try_join2!($a, $b), $c), $d),
+                $e
+            ),
+            $f
+        )
+        .await
+        .map(|((a, b, c, d, e), f)| (a, b, c, d, e, f))
+    };
+}
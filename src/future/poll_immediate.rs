@@ -0,0 +1,50 @@
+//! Polling a future exactly once from within another async context,
+//! without committing to waiting for it.
+
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+/// Future for the [`poll_immediate`] function.
+pub struct PollImmediate<F> {
+    future: Option<F>,
+}
+
+/// Polls `future` once and resolves with the result, whether or not
+/// `future` itself was ready -- useful for "take whatever is already
+/// ready" draining loops where falling back to `Pending` and waiting
+/// isn't what's wanted.
+///
+/// ```ignore
+/// match future::poll_immediate(some_future).await {
+///     Poll::Ready(value) => handle(value),
+///     Poll::Pending => try_again_later(),
+/// }
+/// ```
+pub fn poll_immediate<F: Future>(future: F) -> PollImmediate<F> {
+    PollImmediate { future: Some(future) }
+}
+
+impl<F: Future> Future for PollImmediate<F> {
+    type Output = Poll<F::Output>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // SAFETY: `future` is only ever polled through a re-pinned
+        // reference and taken by value after it has already resolved,
+        // never moved while still pending.
+        let this = unsafe { self.get_unchecked_mut() };
+        match &mut this.future {
+            Some(future) => {
+                let pinned = unsafe { Pin::new_unchecked(future) };
+                match pinned.poll(cx) {
+                    Poll::Ready(value) => {
+                        this.future = None;
+                        Poll::Ready(Poll::Ready(value))
+                    }
+                    Poll::Pending => Poll::Ready(Poll::Pending),
+                }
+            }
+            None => panic!("PollImmediate polled after it already resolved"),
+        }
+    }
+}
@@ -0,0 +1,60 @@
+//! Racing a dynamically-sized collection of futures, for "whichever
+//! replica answers first" without building an unbalanced tree of
+//! nested binary [`race`](crate::future::race) calls.
+
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use alloc::vec::Vec;
+
+/// Races a collection of same-typed futures, resolving with the
+/// output of whichever one finishes first, alongside its index in
+/// `futures` and the remaining, still-unresolved futures.
+pub fn race_all<I>(futures: I) -> RaceAll<I::Item>
+where
+    I: IntoIterator,
+    I::Item: Future + Unpin,
+{
+    RaceAll { futures: futures.into_iter().collect(), next: 0 }
+}
+
+/// Future returned by [`race_all`].
+pub struct RaceAll<F> {
+    futures: Vec<F>,
+    // Round-robin cursor, so no single future can starve the others by
+    // always being polled first.
+    next: usize,
+}
+
+/// The output of [`race_all`]: the winning future's output and index,
+/// plus every other future that hadn't resolved yet.
+pub struct RaceAllOutput<F: Future> {
+    pub output: F::Output,
+    pub index: usize,
+    pub remaining: Vec<F>,
+}
+
+impl<F: Future + Unpin> Future for RaceAll<F> {
+    type Output = RaceAllOutput<F>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let len = this.futures.len();
+        assert!(len > 0, "race_all requires at least one future");
+
+        let start = this.next % len;
+        for offset in 0..len {
+            let i = (start + offset) % len;
+            if let Poll::Ready(output) = Pin::new(&mut this.futures[i]).poll(cx) {
+                this.next = i + 1;
+                let winner = this.futures.swap_remove(i);
+                let _ = winner;
+                let remaining = core::mem::take(&mut this.futures);
+                return Poll::Ready(RaceAllOutput { output, index: i, remaining });
+            }
+        }
+
+        Poll::Pending
+    }
+}
@@ -0,0 +1,92 @@
+//! A retry loop with configurable backoff, driven by the crate's own
+//! timer, so every network client doesn't reimplement this slightly
+//! differently.
+
+use core::future::Future;
+use core::time::Duration;
+
+/// How long to wait between retry attempts.
+#[derive(Debug, Clone, Copy)]
+pub enum BackoffStrategy {
+    /// Always wait the same amount of time.
+    Fixed(Duration),
+    /// Double the wait on every attempt, starting at `base` and
+    /// capped at `max`.
+    Exponential { base: Duration, max: Duration },
+    /// Like `Exponential`, but with up to 50% of the computed delay
+    /// subtracted at random, to avoid many retrying clients
+    /// resynchronizing on the same schedule (the "thundering herd").
+    ExponentialJitter { base: Duration, max: Duration },
+}
+
+impl BackoffStrategy {
+    /// The delay to wait before retry attempt number `attempt` (1 for
+    /// the first retry, after the first failed try).
+    pub fn delay(&self, attempt: u32) -> Duration {
+        match *self {
+            BackoffStrategy::Fixed(delay) => delay,
+            BackoffStrategy::Exponential { base, max } => exponential(base, max, attempt),
+            BackoffStrategy::ExponentialJitter { base, max } => {
+                let delay = exponential(base, max, attempt);
+                let jitter = (splitmix64(attempt as u64) % 1_000_000) as f64 / 1_000_000.0;
+                delay.mul_f64(1.0 - jitter * 0.5)
+            }
+        }
+    }
+}
+
+fn exponential(base: Duration, max: Duration, attempt: u32) -> Duration {
+    match base.checked_mul(1u32.checked_shl(attempt.saturating_sub(1)).unwrap_or(u32::MAX)) {
+        Some(delay) => delay.min(max),
+        None => max,
+    }
+}
+
+/// A fast, deterministic, non-cryptographic mix function used only to
+/// spread jitter across retry attempts -- not a general-purpose RNG.
+fn splitmix64(mut x: u64) -> u64 {
+    x = x.wrapping_add(0x9E3779B97F4A7C15);
+    let mut z = x;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+    z ^ (z >> 31)
+}
+
+/// Calls `f` until it succeeds, retrying up to `max_attempts` times
+/// (including the first) with `strategy` governing the delay between
+/// attempts.
+pub async fn retry<F, Fut, T, E>(strategy: BackoffStrategy, max_attempts: u32, f: F) -> Result<T, E>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    retry_if(strategy, max_attempts, f, |_| true).await
+}
+
+/// Like [`retry`], but only retries an error if `retryable` returns
+/// `true` for it; otherwise returns it immediately.
+pub async fn retry_if<F, Fut, T, E, P>(
+    strategy: BackoffStrategy,
+    max_attempts: u32,
+    mut f: F,
+    mut retryable: P,
+) -> Result<T, E>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+    P: FnMut(&E) -> bool,
+{
+    let mut attempt = 0;
+    loop {
+        attempt += 1;
+        match f().await {
+            Ok(value) => return Ok(value),
+            Err(err) => {
+                if attempt >= max_attempts || !retryable(&err) {
+                    return Err(err);
+                }
+                crate::task::sleep(strategy.delay(attempt)).await;
+            }
+        }
+    }
+}
@@ -0,0 +1,122 @@
+//! `select!` waits on several, possibly differently-typed futures or
+//! channel operations at once and runs whichever resolves first,
+//! dropping (and so cancelling) every other branch's future.
+//!
+//! Unlike [`crate::future::race`], branches don't need a shared output
+//! type -- each branch's pattern match and body run immediately after
+//! *that* branch's future resolves, and the branches are polled in a
+//! rotating order so no branch is starved by always being checked
+//! last.
+//!
+//! ```ignore
+//! select! {
+//!     msg = rx1.recv() => handle(msg),
+//!     msg = rx2.recv() => handle(msg),
+//!     () = timeout_fut => bail(),
+//! }
+//! ```
+//!
+//! An optional `default => body` arm, if present, makes the whole
+//! expression non-blocking: if no branch is immediately ready, `body`
+//! runs instead of waiting.
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::future::Future;
+use core::pin::Pin;
+use core::sync::atomic::{AtomicUsize, Ordering};
+use core::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+
+static NEXT_START: AtomicUsize = AtomicUsize::new(0);
+
+/// Polls `branches` in a rotating order until one resolves, cancelling
+/// the rest by dropping them once it does. Used by the [`select!`]
+/// macro; not meant to be called directly.
+#[doc(hidden)]
+pub async fn select_boxed<'a, R>(mut branches: Vec<Pin<Box<dyn Future<Output = R> + 'a>>>) -> R {
+    assert!(!branches.is_empty(), "select! requires at least one branch");
+    let start = NEXT_START.fetch_add(1, Ordering::Relaxed) % branches.len();
+    core::future::poll_fn(move |cx| poll_branches(&mut branches, start, cx)).await
+}
+
+/// Polls every branch exactly once, in rotating order, and returns the
+/// first that's ready -- or `None` if none are. Used by `select!`'s
+/// `default` arm, which must not wait.
+#[doc(hidden)]
+pub fn try_select_boxed<R>(branches: &mut [Pin<Box<dyn Future<Output = R> + '_>>]) -> Option<R> {
+    if branches.is_empty() {
+        return None;
+    }
+    let start = NEXT_START.fetch_add(1, Ordering::Relaxed) % branches.len();
+    let waker = noop_waker();
+    let mut cx = Context::from_waker(&waker);
+    match poll_branches(branches, start, &mut cx) {
+        Poll::Ready(value) => Some(value),
+        Poll::Pending => None,
+    }
+}
+
+fn poll_branches<R>(branches: &mut [Pin<Box<dyn Future<Output = R> + '_>>], start: usize, cx: &mut Context<'_>) -> Poll<R> {
+    let len = branches.len();
+    for offset in 0..len {
+        let index = (start + offset) % len;
+        if let Poll::Ready(value) = branches[index].as_mut().poll(cx) {
+            return Poll::Ready(value);
+        }
+    }
+    Poll::Pending
+}
+
+/// A waker that does nothing when woken. Used anywhere a future needs
+/// to be polled without an executor backing it -- `select!`'s
+/// `default` arm, and [`FutureExt::now_or_never`](crate::future::FutureExt::now_or_never).
+pub(crate) fn noop_waker() -> Waker {
+    fn clone(_: *const ()) -> RawWaker {
+        raw()
+    }
+    fn no_op(_: *const ()) {}
+    fn raw() -> RawWaker {
+        const VTABLE: RawWakerVTable = RawWakerVTable::new(clone, no_op, no_op, no_op);
+        RawWaker::new(core::ptr::null(), &VTABLE)
+    }
+    unsafe { Waker::from_raw(raw()) }
+}
+
+/// Waits on multiple heterogeneous futures (or channel operations),
+/// running the body of whichever resolves first. See the [module-level
+/// docs](self) for syntax and semantics.
+#[macro_export]
+macro_rules! select {
+    (
+        $($pat:pat = $fut:expr => $body:expr),+ $(,)?
+    ) => {{
+        let branches: $crate::__private::Vec<::core::pin::Pin<$crate::__private::Box<dyn ::core::future::Future<Output = _> + '_>>> =
+            $crate::__private::vec![
+                $(
+                    $crate::__private::Box::pin(async {
+                        let $pat = $fut.await;
+                        $body
+                    })
+                ),+
+            ];
+        $crate::future::select_macro::select_boxed(branches).await
+    }};
+
+    (
+        $($pat:pat = $fut:expr => $body:expr),+ , default => $default:expr $(,)?
+    ) => {{
+        let mut branches: $crate::__private::Vec<::core::pin::Pin<$crate::__private::Box<dyn ::core::future::Future<Output = _> + '_>>> =
+            $crate::__private::vec![
+                $(
+                    $crate::__private::Box::pin(async {
+                        let $pat = $fut.await;
+                        $body
+                    })
+                ),+
+            ];
+        match $crate::future::select_macro::try_select_boxed(&mut branches) {
+            ::core::option::Option::Some(value) => value,
+            ::core::option::Option::None => $default,
+        }
+    }};
+}
@@ -0,0 +1,79 @@
+//! Bounding how long a future is allowed to take, either by a
+//! duration from now ([`timeout`]) or an absolute deadline
+//! ([`timeout_at`]) -- the latter so a single deadline can be threaded
+//! through layered calls without recomputing (and risking going
+//! negative on) a remaining duration at each layer.
+
+use core::fmt;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use core::time::Duration;
+
+use alloc::boxed::Box;
+
+use std::time::Instant;
+
+use crate::task::sleep;
+
+/// The error returned when a future didn't resolve before its
+/// deadline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Elapsed;
+
+impl fmt::Display for Elapsed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("future timed out")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Elapsed {}
+
+/// Future for the [`timeout`] function and [`FutureExt::timeout`]
+/// method.
+///
+/// [`FutureExt::timeout`]: crate::future::FutureExt::timeout
+pub struct Timeout<F> {
+    future: F,
+    timer: Pin<Box<dyn Future<Output = ()> + Send>>,
+}
+
+/// Waits for `future` to resolve, failing with [`Elapsed`] if
+/// `duration` passes first.
+pub fn timeout<F: Future>(duration: Duration, future: F) -> Timeout<F> {
+    Timeout {
+        future,
+        timer: Box::pin(sleep(duration)),
+    }
+}
+
+/// Waits for `future` to resolve, failing with [`Elapsed`] if
+/// `deadline` passes first.
+pub fn timeout_at<F: Future>(deadline: Instant, future: F) -> Timeout<F> {
+    let remaining = deadline.saturating_duration_since(crate::time::now());
+    Timeout {
+        future,
+        timer: Box::pin(sleep(remaining)),
+    }
+}
+
+impl<F: Future> Future for Timeout<F> {
+    type Output = Result<F::Output, Elapsed>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // SAFETY: `future` is never moved out from behind the `Pin`;
+        // only polled through a re-pinned reference, matching the
+        // structural-pinning convention used by this crate's other
+        // hand-written futures (e.g. `stream::Debounce`).
+        let this = unsafe { self.get_unchecked_mut() };
+        let future = unsafe { Pin::new_unchecked(&mut this.future) };
+        if let Poll::Ready(value) = future.poll(cx) {
+            return Poll::Ready(Ok(value));
+        }
+        match this.timer.as_mut().poll(cx) {
+            Poll::Ready(()) => Poll::Ready(Err(Elapsed)),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
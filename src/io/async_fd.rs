@@ -0,0 +1,147 @@
+//! Driving arbitrary file descriptors through the reactor, for types
+//! this crate has no dedicated wrapper for -- serial ports, netlink
+//! sockets, FUSE handles, anything that's `AsRawFd` and can be put in
+//! non-blocking mode.
+//!
+//! [`Async<T>`] is the same reactor-registration idiom as
+//! [`process::Pty`](crate::process::pty::Pty)'s `PtyMaster` and
+//! [`io::stdin_raw::RawStdin`](crate::io::stdin_raw::RawStdin), pulled
+//! out into something generic and public instead of being duplicated
+//! per type.
+//!
+//! [`readable`](Async::readable) and [`writable`](Async::writable)
+//! poll the same `poll_readable`/`poll_writable` entry points
+//! [`Read`](crate::io::Read)/[`Write`](crate::io::Write) impls use
+//! elsewhere in this crate, just with an empty buffer: [`Reactor`](crate::rt::Reactor)
+//! resolves readiness before attempting the (here zero-byte) I/O
+//! itself, rather than needing a distinct "are you ready" entry point
+//! of its own.
+
+use core::future::poll_fn;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use std::os::unix::io::{AsRawFd, RawFd};
+
+use crate::io::{self, Read, Write};
+
+/// A non-blocking, reactor-driven handle to any `T: AsRawFd`.
+pub struct Async<T> {
+    io: T,
+    fd: RawFd,
+    reactor: crate::rt::Reactor,
+}
+
+impl<T: AsRawFd> Async<T> {
+    /// Puts `io`'s file descriptor in non-blocking mode and registers
+    /// it with the reactor.
+    pub fn new(io: T) -> io::Result<Async<T>> {
+        let fd = io.as_raw_fd();
+        set_nonblocking(fd)?;
+        Ok(Async {
+            io,
+            fd,
+            reactor: crate::rt::Reactor::register(fd)?,
+        })
+    }
+
+    /// The wrapped value.
+    pub fn get_ref(&self) -> &T {
+        &self.io
+    }
+
+    /// The wrapped value, mutably.
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.io
+    }
+
+    /// Unwraps this, returning the inner value (left in non-blocking
+    /// mode -- callers that need blocking semantics back should
+    /// clear `O_NONBLOCK` themselves).
+    pub fn into_inner(self) -> T {
+        self.io
+    }
+
+    /// Resolves once the descriptor is readable.
+    pub async fn readable(&self) -> io::Result<()> {
+        poll_fn(|cx| match self.reactor.poll_readable(cx, self.fd, &mut []) {
+            Poll::Ready(Ok(_)) => Poll::Ready(Ok(())),
+            Poll::Ready(Err(err)) => Poll::Ready(Err(err)),
+            Poll::Pending => Poll::Pending,
+        })
+        .await
+    }
+
+    /// Resolves once the descriptor is writable.
+    pub async fn writable(&self) -> io::Result<()> {
+        poll_fn(|cx| match self.reactor.poll_writable(cx, self.fd, &[]) {
+            Poll::Ready(Ok(_)) => Poll::Ready(Ok(())),
+            Poll::Ready(Err(err)) => Poll::Ready(Err(err)),
+            Poll::Pending => Poll::Pending,
+        })
+        .await
+    }
+
+    /// Runs `op` against the wrapped value, retrying it against
+    /// [`readable`](Async::readable) each time it reports
+    /// [`WouldBlock`](io::ErrorKind::WouldBlock).
+    ///
+    /// For operations that aren't a plain [`Read`](crate::io::Read)
+    /// call -- `recvfrom`, an ioctl-gated read, anything with its own
+    /// signature -- where the blanket `Read`/`Write` impls below
+    /// don't apply.
+    pub async fn read_with<R>(&self, mut op: impl FnMut(&T) -> io::Result<R>) -> io::Result<R> {
+        loop {
+            match op(&self.io) {
+                Err(err) if err.kind() == io::ErrorKind::WouldBlock => self.readable().await?,
+                result => return result,
+            }
+        }
+    }
+
+    /// Like [`read_with`](Async::read_with), but waits on
+    /// [`writable`](Async::writable) instead.
+    pub async fn write_with<R>(&self, mut op: impl FnMut(&T) -> io::Result<R>) -> io::Result<R> {
+        loop {
+            match op(&self.io) {
+                Err(err) if err.kind() == io::ErrorKind::WouldBlock => self.writable().await?,
+                result => return result,
+            }
+        }
+    }
+}
+
+impl<T> Read for Async<T> {
+    fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        this.reactor.poll_readable(cx, this.fd, buf)
+    }
+}
+
+impl<T> Write for Async<T> {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        this.reactor.poll_writable(cx, this.fd, buf)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+fn set_nonblocking(fd: RawFd) -> io::Result<()> {
+    unsafe {
+        let flags = libc::fcntl(fd, libc::F_GETFL, 0);
+        if flags < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        if libc::fcntl(fd, libc::F_SETFL, flags | libc::O_NONBLOCK) < 0 {
+            return Err(io::Error::last_os_error());
+        }
+    }
+    Ok(())
+}
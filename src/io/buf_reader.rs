@@ -0,0 +1,157 @@
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use alloc::boxed::Box;
+use alloc::vec;
+
+use crate::io::{self, BufRead, Read, Seek, SeekFrom};
+
+const DEFAULT_BUF_SIZE: usize = 8 * 1024;
+
+/// Wraps a reader, buffering its input so small reads don't each issue a
+/// separate call into the underlying reader.
+pub struct BufReader<R> {
+    inner: R,
+    buf: Box<[u8]>,
+    pos: usize,
+    cap: usize,
+}
+
+impl<R: Read> BufReader<R> {
+    /// Wraps `inner` with a default-sized buffer.
+    pub fn new(inner: R) -> Self {
+        Self::with_capacity(DEFAULT_BUF_SIZE, inner)
+    }
+
+    /// Wraps `inner` with a buffer of the given size.
+    pub fn with_capacity(capacity: usize, inner: R) -> Self {
+        Self {
+            inner,
+            buf: vec![0; capacity].into_boxed_slice(),
+            pos: 0,
+            cap: 0,
+        }
+    }
+
+    /// Borrows the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Mutably borrows the underlying reader.
+    ///
+    /// Reading directly from it bypasses the buffer, which can desync
+    /// it from what `BufReader` thinks has already been consumed.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Unwraps this `BufReader`, discarding any buffered (but not yet
+    /// consumed) data.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    /// Returns the contents of the internal buffer, ignoring whatever
+    /// has already been consumed from it.
+    pub fn buffer(&self) -> &[u8] {
+        &self.buf[self.pos..self.cap]
+    }
+}
+
+impl<R: Read + Unpin> Read for BufReader<R> {
+    fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+
+        // Bypass the buffer for reads at least as large as it, the same
+        // way std's `BufReader` does.
+        if this.pos == this.cap && buf.len() >= this.buf.len() {
+            return Pin::new(&mut this.inner).poll_read(cx, buf);
+        }
+
+        let available = match Pin::new(&mut *this).poll_fill_buf(cx) {
+            Poll::Ready(Ok(buf)) => buf,
+            Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+            Poll::Pending => return Poll::Pending,
+        };
+
+        let n = core::cmp::min(available.len(), buf.len());
+        buf[..n].copy_from_slice(&available[..n]);
+        Pin::new(&mut *this).consume(n);
+        Poll::Ready(Ok(n))
+    }
+}
+
+impl<R: Read + Unpin> BufRead for BufReader<R> {
+    fn poll_fill_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<&[u8]>> {
+        let this = self.get_mut();
+
+        if this.pos >= this.cap {
+            match Pin::new(&mut this.inner).poll_read(cx, &mut this.buf) {
+                Poll::Ready(Ok(n)) => {
+                    this.pos = 0;
+                    this.cap = n;
+                }
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+
+        Poll::Ready(Ok(&this.buf[this.pos..this.cap]))
+    }
+
+    fn consume(self: Pin<&mut Self>, amt: usize) {
+        let this = self.get_mut();
+        this.pos = core::cmp::min(this.pos + amt, this.cap);
+    }
+}
+
+impl<R: Read + Seek + Unpin> BufReader<R> {
+    /// Seeks relative to the current position, adjusting inside the
+    /// existing buffer instead of discarding it and issuing a syscall
+    /// when the target position is still covered by buffered data.
+    pub fn seek_relative(&mut self, offset: i64) -> SeekRelative<'_, R> {
+        SeekRelative { reader: self, offset }
+    }
+}
+
+/// Future for the [`BufReader::seek_relative`] method.
+pub struct SeekRelative<'a, R> {
+    reader: &'a mut BufReader<R>,
+    offset: i64,
+}
+
+impl<'a, R> Future for SeekRelative<'a, R>
+where
+    R: Read + Seek + Unpin,
+{
+    type Output = io::Result<()>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let reader = &mut *this.reader;
+
+        let pos = reader.pos as i64;
+        let cap = reader.cap as i64;
+
+        if this.offset >= -pos && this.offset <= cap - pos {
+            reader.pos = (pos + this.offset) as usize;
+            return Poll::Ready(Ok(()));
+        }
+
+        // The target position isn't covered by what's buffered; drop the
+        // buffer and seek the underlying reader, compensating for the
+        // buffered-but-unconsumed bytes we're about to discard.
+        let buffered = cap - pos;
+        match Pin::new(&mut reader.inner).poll_seek(cx, SeekFrom::Current(this.offset - buffered)) {
+            Poll::Ready(Ok(_)) => {
+                reader.pos = 0;
+                reader.cap = 0;
+                Poll::Ready(Ok(()))
+            }
+            Poll::Ready(Err(e)) => Poll::Ready(Err(e)),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
@@ -0,0 +1,201 @@
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use alloc::vec::Vec;
+
+use crate::io::{self, Write};
+
+const DEFAULT_BUF_SIZE: usize = 8 * 1024;
+
+/// Wraps a writer, buffering its output so small writes don't each
+/// issue a separate call into the underlying writer.
+///
+/// Buffered data is lost if a `BufWriter` is dropped without being
+/// flushed; use [`into_inner`](Self::into_inner) (or
+/// [`flush_on_drop`](Self::flush_on_drop)) rather than relying on drop
+/// to save it.
+pub struct BufWriter<W> {
+    inner: Option<W>,
+    buf: Vec<u8>,
+    flush_on_drop: bool,
+}
+
+impl<W: Write> BufWriter<W> {
+    /// Wraps `inner` with a default-sized buffer.
+    pub fn new(inner: W) -> Self {
+        Self::with_capacity(DEFAULT_BUF_SIZE, inner)
+    }
+
+    /// Wraps `inner` with a buffer of the given size.
+    pub fn with_capacity(capacity: usize, inner: W) -> Self {
+        Self {
+            inner: Some(inner),
+            buf: Vec::with_capacity(capacity),
+            flush_on_drop: false,
+        }
+    }
+
+    /// Opts into spawning a best-effort flush of any buffered data if
+    /// this `BufWriter` is dropped without having been flushed.
+    ///
+    /// This is a safety net, not a substitute for calling
+    /// [`into_inner`](Self::into_inner): the spawned flush races the
+    /// rest of the program shutting down and its result is discarded.
+    pub fn flush_on_drop(mut self, flush_on_drop: bool) -> Self {
+        self.flush_on_drop = flush_on_drop;
+        self
+    }
+
+    /// Borrows the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        self.inner.as_ref().expect("BufWriter used after into_inner")
+    }
+
+    /// Returns the contents of the internal buffer that haven't been
+    /// written out yet.
+    pub fn buffer(&self) -> &[u8] {
+        &self.buf
+    }
+
+    /// Flushes the buffer and returns the underlying writer.
+    pub fn into_inner(self) -> IntoInner<W> {
+        IntoInner { writer: Some(self) }
+    }
+
+    /// Attempts to flush the buffer and return the underlying writer,
+    /// returning both the error and a `BufWriter` holding onto whatever
+    /// couldn't be written if the flush fails.
+    pub fn try_into_inner(self) -> TryIntoInner<W> {
+        TryIntoInner { writer: Some(self) }
+    }
+
+    fn poll_flush_buf(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>>
+    where
+        W: Unpin,
+    {
+        let this = self.get_mut();
+        let inner = this.inner.as_mut().expect("BufWriter used after into_inner");
+
+        let mut written = 0;
+        let result = loop {
+            if written == this.buf.len() {
+                break Ok(());
+            }
+            match Pin::new(&mut *inner).poll_write(cx, &this.buf[written..]) {
+                Poll::Ready(Ok(0)) => {
+                    break Err(io::Error::new(io::ErrorKind::WriteZero, "failed to write buffered data"))
+                }
+                Poll::Ready(Ok(n)) => written += n,
+                Poll::Ready(Err(e)) => break Err(e),
+                Poll::Pending => {
+                    this.buf.drain(..written);
+                    return Poll::Pending;
+                }
+            }
+        };
+        this.buf.drain(..written);
+        Poll::Ready(result)
+    }
+}
+
+impl<W: Write + Unpin> Write for BufWriter<W> {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+
+        if this.buf.len() + buf.len() > this.buf.capacity() {
+            match Pin::new(&mut *this).poll_flush_buf(cx) {
+                Poll::Ready(Ok(())) => {}
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+
+        if buf.len() >= this.buf.capacity() {
+            let inner = this.inner.as_mut().expect("BufWriter used after into_inner");
+            return Pin::new(inner).poll_write(cx, buf);
+        }
+
+        this.buf.extend_from_slice(buf);
+        Poll::Ready(Ok(buf.len()))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        match Pin::new(&mut *this).poll_flush_buf(cx) {
+            Poll::Ready(Ok(())) => {}
+            other => return other,
+        }
+        let inner = this.inner.as_mut().expect("BufWriter used after into_inner");
+        Pin::new(inner).poll_flush(cx)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        match Pin::new(&mut *this).poll_flush_buf(cx) {
+            Poll::Ready(Ok(())) => {}
+            other => return other,
+        }
+        let inner = this.inner.as_mut().expect("BufWriter used after into_inner");
+        Pin::new(inner).poll_close(cx)
+    }
+}
+
+impl<W> Drop for BufWriter<W> {
+    fn drop(&mut self) {
+        if self.flush_on_drop && !self.buf.is_empty() {
+            if let Some(mut inner) = self.inner.take() {
+                let buf = core::mem::take(&mut self.buf);
+                crate::task::spawn(async move {
+                    let _ = io::WriteExt::write_all(&mut inner, &buf).await;
+                });
+            }
+        }
+    }
+}
+
+/// Future for the [`BufWriter::into_inner`] method.
+pub struct IntoInner<W> {
+    writer: Option<BufWriter<W>>,
+}
+
+impl<W: Write + Unpin> Future for IntoInner<W> {
+    type Output = io::Result<W>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let writer = this.writer.as_mut().expect("IntoInner polled after completion");
+
+        match Pin::new(&mut *writer).poll_flush_buf(cx) {
+            Poll::Ready(Ok(())) => {
+                let mut writer = this.writer.take().unwrap();
+                Poll::Ready(Ok(writer.inner.take().expect("BufWriter used after into_inner")))
+            }
+            Poll::Ready(Err(e)) => Poll::Ready(Err(e)),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+/// Future for the [`BufWriter::try_into_inner`] method.
+pub struct TryIntoInner<W> {
+    writer: Option<BufWriter<W>>,
+}
+
+impl<W: Write + Unpin> Future for TryIntoInner<W> {
+    type Output = Result<W, (io::Error, BufWriter<W>)>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let writer = this.writer.as_mut().expect("TryIntoInner polled after completion");
+
+        match Pin::new(&mut *writer).poll_flush_buf(cx) {
+            Poll::Ready(Ok(())) => {
+                let mut writer = this.writer.take().unwrap();
+                Poll::Ready(Ok(writer.inner.take().expect("BufWriter used after into_inner")))
+            }
+            Poll::Ready(Err(e)) => Poll::Ready(Err((e, this.writer.take().unwrap()))),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
@@ -0,0 +1,180 @@
+//! Turning a byte stream into a stream of discrete frames.
+//!
+//! [`Decoder`]/[`Encoder`] describe how to carve frames out of (and
+//! serialize them back into) a byte buffer; [`Framed`] drives that
+//! translation against any [`Read`] + [`Write`] value, presenting it as
+//! a [`Stream`] of decoded frames and a [`Sink`] of frames to encode.
+
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use alloc::vec::Vec;
+
+use crate::io::{self, Read, Write};
+use crate::sink::Sink;
+use crate::stream::Stream;
+
+const INITIAL_CAPACITY: usize = 8 * 1024;
+
+/// Decodes frames out of a growing byte buffer.
+pub trait Decoder {
+    /// The type of decoded frames.
+    type Item;
+    /// The error a decode step can fail with.
+    type Error: From<io::Error>;
+
+    /// Attempts to decode a frame from the start of `src`.
+    ///
+    /// Implementations should remove the bytes of a successfully decoded
+    /// frame from `src` (e.g. via `src.drain(..n)`) and leave any
+    /// trailing, not-yet-complete data in place. Returning `Ok(None)`
+    /// means more bytes are needed before a full frame is available.
+    fn decode(&mut self, src: &mut Vec<u8>) -> Result<Option<Self::Item>, Self::Error>;
+
+    /// Like [`decode`](Self::decode), but called once the underlying
+    /// stream has reached EOF. The default treats leftover bytes as a
+    /// truncated frame error only if `src` is non-empty.
+    fn decode_eof(&mut self, src: &mut Vec<u8>) -> Result<Option<Self::Item>, Self::Error> {
+        match self.decode(src)? {
+            Some(item) => Ok(Some(item)),
+            None if src.is_empty() => Ok(None),
+            None => Err(io::Error::new(io::ErrorKind::UnexpectedEof, "bytes remaining in stream").into()),
+        }
+    }
+}
+
+/// Encodes frames into an outgoing byte buffer.
+pub trait Encoder<Item> {
+    /// The error an encode step can fail with.
+    type Error: From<io::Error>;
+
+    /// Appends the wire representation of `item` to `dst`.
+    fn encode(&mut self, item: Item, dst: &mut Vec<u8>) -> Result<(), Self::Error>;
+}
+
+/// A [`Read`] + [`Write`] value framed into a [`Stream`] of decoded
+/// items and a [`Sink`] of items to encode, via a [`Decoder`]/[`Encoder`]
+/// pair.
+pub struct Framed<T, C> {
+    io: T,
+    codec: C,
+    read_buf: Vec<u8>,
+    write_buf: Vec<u8>,
+    eof: bool,
+}
+
+impl<T, C> Framed<T, C> {
+    /// Wraps `io`, using `codec` to decode and encode frames.
+    pub fn new(io: T, codec: C) -> Self {
+        Self {
+            io,
+            codec,
+            read_buf: Vec::with_capacity(INITIAL_CAPACITY),
+            write_buf: Vec::new(),
+            eof: false,
+        }
+    }
+
+    /// Returns the codec, discarding any buffered bytes.
+    pub fn into_inner(self) -> T {
+        self.io
+    }
+
+    /// Borrows the codec.
+    pub fn codec(&self) -> &C {
+        &self.codec
+    }
+
+    /// Mutably borrows the codec.
+    pub fn codec_mut(&mut self) -> &mut C {
+        &mut self.codec
+    }
+}
+
+impl<T, C> Stream for Framed<T, C>
+where
+    T: Read + Unpin,
+    C: Decoder + Unpin,
+{
+    type Item = Result<C::Item, C::Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            match this.codec.decode(&mut this.read_buf) {
+                Ok(Some(item)) => return Poll::Ready(Some(Ok(item))),
+                Ok(None) => {}
+                Err(e) => return Poll::Ready(Some(Err(e))),
+            }
+
+            if this.eof {
+                return match this.codec.decode_eof(&mut this.read_buf) {
+                    Ok(Some(item)) => Poll::Ready(Some(Ok(item))),
+                    Ok(None) => Poll::Ready(None),
+                    Err(e) => Poll::Ready(Some(Err(e))),
+                };
+            }
+
+            let mut scratch = [0u8; INITIAL_CAPACITY];
+            match Pin::new(&mut this.io).poll_read(cx, &mut scratch) {
+                Poll::Ready(Ok(0)) => this.eof = true,
+                Poll::Ready(Ok(n)) => this.read_buf.extend_from_slice(&scratch[..n]),
+                Poll::Ready(Err(e)) => return Poll::Ready(Some(Err(e.into()))),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+impl<T, C, Item> Sink<Item> for Framed<T, C>
+where
+    T: Write + Unpin,
+    C: Encoder<Item> + Unpin,
+{
+    type Error = C::Error;
+
+    fn poll_ready(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn start_send(self: Pin<&mut Self>, item: Item) -> Result<(), Self::Error> {
+        let this = self.get_mut();
+        this.codec.encode(item, &mut this.write_buf)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        let this = self.get_mut();
+
+        while !this.write_buf.is_empty() {
+            match Pin::new(&mut this.io).poll_write(cx, &this.write_buf) {
+                Poll::Ready(Ok(0)) => {
+                    return Poll::Ready(Err(
+                        io::Error::new(io::ErrorKind::WriteZero, "failed to write whole buffer").into(),
+                    ))
+                }
+                Poll::Ready(Ok(n)) => drop(this.write_buf.drain(..n)),
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(e.into())),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+
+        match Pin::new(&mut this.io).poll_flush(cx) {
+            Poll::Ready(Ok(())) => Poll::Ready(Ok(())),
+            Poll::Ready(Err(e)) => Poll::Ready(Err(e.into())),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        match self.as_mut().poll_flush(cx) {
+            Poll::Ready(Ok(())) => {}
+            other => return other,
+        }
+        match Pin::new(&mut self.get_mut().io).poll_close(cx) {
+            Poll::Ready(Ok(())) => Poll::Ready(Ok(())),
+            Poll::Ready(Err(e)) => Poll::Ready(Err(e.into())),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
@@ -0,0 +1,134 @@
+//! Zero-cost adapters between async-std's `Read`/`Write` traits and
+//! tokio's `AsyncRead`/`AsyncWrite`, for embedding a tokio-only
+//! dependency (database drivers are the usual culprit) inside an
+//! async-std application without copying bytes at the boundary.
+//!
+//! Gated behind the `tokio-compat` feature so crates that don't need it
+//! aren't forced to pull in tokio.
+#![cfg(feature = "tokio-compat")]
+
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use crate::io::{self, Read, Write};
+
+/// Wraps a value so it can cross the async-std/tokio boundary.
+///
+/// `Compat<T>` implements tokio's `AsyncRead`/`AsyncWrite` when `T`
+/// implements async-std's `Read`/`Write`, and vice versa, by forwarding
+/// each poll method directly -- no intermediate buffering.
+#[derive(Debug)]
+pub struct Compat<T> {
+    inner: T,
+}
+
+impl<T> Compat<T> {
+    /// Gets a reference to the wrapped value.
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the wrapped value.
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Unwraps this adapter, returning the inner value.
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+/// Extension trait for wrapping an async-std `Read`/`Write` so it can be
+/// handed to a tokio API.
+pub trait CompatExt {
+    /// Wraps `self` so it implements tokio's `AsyncRead`/`AsyncWrite`.
+    fn compat(self) -> Compat<Self>
+    where
+        Self: Sized,
+    {
+        Compat { inner: self }
+    }
+}
+
+impl<T> CompatExt for T {}
+
+/// Extension trait for wrapping a tokio `AsyncRead`/`AsyncWrite` so it
+/// can be handed to an async-std API.
+pub trait TokioCompatExt {
+    /// Wraps `self` so it implements async-std's `Read`/`Write`.
+    fn compat_std(self) -> Compat<Self>
+    where
+        Self: Sized,
+    {
+        Compat { inner: self }
+    }
+}
+
+impl<T> TokioCompatExt for T {}
+
+fn map_tokio_poll_read(
+    poll: Poll<tokio::io::Result<()>>,
+    buf: &mut tokio::io::ReadBuf<'_>,
+) -> Poll<io::Result<usize>> {
+    match poll {
+        Poll::Ready(Ok(())) => Poll::Ready(Ok(buf.filled().len())),
+        Poll::Ready(Err(e)) => Poll::Ready(Err(e)),
+        Poll::Pending => Poll::Pending,
+    }
+}
+
+impl<T: Read + Unpin> tokio::io::AsyncRead for Compat<T> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut tokio::io::ReadBuf<'_>,
+    ) -> Poll<tokio::io::Result<()>> {
+        let this = self.get_mut();
+        let unfilled = buf.initialize_unfilled();
+        match Pin::new(&mut this.inner).poll_read(cx, unfilled) {
+            Poll::Ready(Ok(n)) => {
+                buf.advance(n);
+                Poll::Ready(Ok(()))
+            }
+            Poll::Ready(Err(e)) => Poll::Ready(Err(e)),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+impl<T: Write + Unpin> tokio::io::AsyncWrite for Compat<T> {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<tokio::io::Result<usize>> {
+        Pin::new(&mut self.get_mut().inner).poll_write(cx, buf)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<tokio::io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<tokio::io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_close(cx)
+    }
+}
+
+impl<T: tokio::io::AsyncRead + Unpin> Read for Compat<T> {
+    fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<io::Result<usize>> {
+        let mut read_buf = tokio::io::ReadBuf::new(buf);
+        let poll = Pin::new(&mut self.get_mut().inner).poll_read(cx, &mut read_buf);
+        map_tokio_poll_read(poll, &mut read_buf)
+    }
+}
+
+impl<T: tokio::io::AsyncWrite + Unpin> Write for Compat<T> {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.get_mut().inner).poll_write(cx, buf)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_flush(cx)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_shutdown(cx)
+    }
+}
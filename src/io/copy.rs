@@ -0,0 +1,72 @@
+use core::future::Future;
+use core::pin::Pin;
+
+use alloc::boxed::Box;
+use alloc::vec;
+
+use crate::io::{self, Read, Write};
+
+/// Default size of the intermediate buffer used by [`copy`] and
+/// [`copy_with`].
+pub const DEFAULT_BUF_SIZE: usize = 8 * 1024;
+
+/// Options accepted by [`copy_with`].
+pub struct CopyOptions<'a> {
+    /// Size of the intermediate buffer shuttling bytes between the
+    /// reader and the writer.
+    ///
+    /// Defaults to [`DEFAULT_BUF_SIZE`].
+    pub buf_size: usize,
+    /// Invoked after every write with the total number of bytes copied
+    /// so far, letting callers drive a progress bar or throttle the
+    /// transfer.
+    pub on_progress: Option<Box<dyn FnMut(u64) -> Pin<Box<dyn Future<Output = ()> + 'a>> + 'a>>,
+}
+
+impl<'a> Default for CopyOptions<'a> {
+    fn default() -> Self {
+        Self {
+            buf_size: DEFAULT_BUF_SIZE,
+            on_progress: None,
+        }
+    }
+}
+
+/// Copies the entire contents of `reader` into `writer`, returning the
+/// number of bytes copied.
+///
+/// This is a thin wrapper around [`copy_with`] using the default buffer
+/// size and no progress callback.
+pub async fn copy<R, W>(reader: &mut R, writer: &mut W) -> io::Result<u64>
+where
+    R: Read + Unpin + ?Sized,
+    W: Write + Unpin + ?Sized,
+{
+    copy_with(reader, writer, CopyOptions::default()).await
+}
+
+/// Copies the entire contents of `reader` into `writer` using the given
+/// [`CopyOptions`], returning the number of bytes copied.
+pub async fn copy_with<R, W>(reader: &mut R, writer: &mut W, mut options: CopyOptions<'_>) -> io::Result<u64>
+where
+    R: Read + Unpin + ?Sized,
+    W: Write + Unpin + ?Sized,
+{
+    let mut buf = vec![0u8; options.buf_size.max(1)];
+    let mut copied: u64 = 0;
+
+    loop {
+        let n = reader.read(&mut buf).await?;
+        if n == 0 {
+            writer.flush().await?;
+            return Ok(copied);
+        }
+
+        writer.write_all(&buf[..n]).await?;
+        copied += n as u64;
+
+        if let Some(on_progress) = options.on_progress.as_mut() {
+            on_progress(copied).await;
+        }
+    }
+}
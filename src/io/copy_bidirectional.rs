@@ -0,0 +1,145 @@
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use alloc::boxed::Box;
+
+use crate::io::{self, Read, Write};
+
+const BUF_SIZE: usize = 8 * 1024;
+
+/// Tracks the progress of copying bytes in one direction, including the
+/// half-close once the source side has hit EOF.
+struct CopyBuffer {
+    buf: Box<[u8; BUF_SIZE]>,
+    pos: usize,
+    cap: usize,
+    read_done: bool,
+    amt: u64,
+}
+
+impl CopyBuffer {
+    fn new() -> Self {
+        Self {
+            buf: Box::new([0; BUF_SIZE]),
+            pos: 0,
+            cap: 0,
+            read_done: false,
+            amt: 0,
+        }
+    }
+
+    fn poll_copy<R, W>(
+        &mut self,
+        cx: &mut Context<'_>,
+        mut reader: Pin<&mut R>,
+        mut writer: Pin<&mut W>,
+    ) -> Poll<io::Result<u64>>
+    where
+        R: Read + ?Sized,
+        W: Write + ?Sized,
+    {
+        loop {
+            if self.pos == self.cap && !self.read_done {
+                match reader.as_mut().poll_read(cx, &mut self.buf[..]) {
+                    Poll::Ready(Ok(0)) => self.read_done = true,
+                    Poll::Ready(Ok(n)) => {
+                        self.pos = 0;
+                        self.cap = n;
+                    }
+                    Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                    Poll::Pending => return Poll::Pending,
+                }
+            }
+
+            while self.pos < self.cap {
+                match writer.as_mut().poll_write(cx, &self.buf[self.pos..self.cap]) {
+                    Poll::Ready(Ok(n)) => {
+                        self.pos += n;
+                        self.amt += n as u64;
+                    }
+                    Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                    Poll::Pending => return Poll::Pending,
+                }
+            }
+
+            if self.pos == self.cap && self.read_done {
+                match writer.as_mut().poll_close(cx) {
+                    Poll::Ready(Ok(())) => return Poll::Ready(Ok(self.amt)),
+                    Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                    Poll::Pending => return Poll::Pending,
+                }
+            }
+        }
+    }
+}
+
+/// Future for [`copy_bidirectional`].
+pub struct CopyBidirectional<A, B> {
+    a: A,
+    b: B,
+    a_to_b: CopyBuffer,
+    b_to_a: CopyBuffer,
+    a_to_b_done: Option<u64>,
+    b_to_a_done: Option<u64>,
+}
+
+impl<A, B> Future for CopyBidirectional<A, B>
+where
+    A: Read + Write + Unpin,
+    B: Read + Write + Unpin,
+{
+    type Output = io::Result<(u64, u64)>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = unsafe { self.get_unchecked_mut() };
+
+        if this.a_to_b_done.is_none() {
+            match this
+                .a_to_b
+                .poll_copy(cx, Pin::new(&mut this.a), Pin::new(&mut this.b))
+            {
+                Poll::Ready(Ok(amt)) => this.a_to_b_done = Some(amt),
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                Poll::Pending => {}
+            }
+        }
+
+        if this.b_to_a_done.is_none() {
+            match this
+                .b_to_a
+                .poll_copy(cx, Pin::new(&mut this.b), Pin::new(&mut this.a))
+            {
+                Poll::Ready(Ok(amt)) => this.b_to_a_done = Some(amt),
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                Poll::Pending => {}
+            }
+        }
+
+        match (this.a_to_b_done, this.b_to_a_done) {
+            (Some(a_to_b), Some(b_to_a)) => Poll::Ready(Ok((a_to_b, b_to_a))),
+            _ => Poll::Pending,
+        }
+    }
+}
+
+/// Shuttles bytes between `a` and `b` in both directions concurrently,
+/// propagating EOF on one side as a write shutdown on the other, and
+/// resolves once both directions have finished.
+///
+/// Returns the number of bytes copied from `a` to `b` and from `b` to
+/// `a`, in that order.
+pub fn copy_bidirectional<A, B>(a: A, b: B) -> CopyBidirectional<A, B>
+where
+    A: Read + Write + Unpin,
+    B: Read + Write + Unpin,
+{
+    CopyBidirectional {
+        a,
+        b,
+        a_to_b: CopyBuffer::new(),
+        b_to_a: CopyBuffer::new(),
+        a_to_b_done: None,
+        b_to_a_done: None,
+    }
+}
@@ -0,0 +1,26 @@
+use crate::io::{self, BufRead, BufReadExt, Write};
+
+/// Copies the entire contents of `reader` into `writer`, writing
+/// straight out of the reader's internal buffer via
+/// `poll_fill_buf`/`consume` instead of shuttling bytes through an
+/// extra intermediate buffer the way [`copy`](super::copy) does.
+pub async fn copy_buf<R, W>(reader: &mut R, writer: &mut W) -> io::Result<u64>
+where
+    R: BufRead + Unpin + ?Sized,
+    W: Write + Unpin + ?Sized,
+{
+    let mut copied: u64 = 0;
+
+    loop {
+        let available = reader.fill_buf().await?;
+        if available.is_empty() {
+            writer.flush().await?;
+            return Ok(copied);
+        }
+
+        let n = available.len();
+        writer.write_all(available).await?;
+        reader.consume(n);
+        copied += n as u64;
+    }
+}
@@ -0,0 +1,314 @@
+#![cfg(feature = "io-uring")]
+//! The bones of an `io_uring`-backed I/O path: setting up a ring,
+//! mapping its submission/completion queues, and round-tripping a
+//! single no-op through `io_uring_enter(2)` to prove the ring is
+//! alive.
+//!
+//! The `libc` crate doesn't expose the `io_uring` ABI (it's newer and
+//! churns faster than `libc`'s support window), so the structs below
+//! are hand-declared straight from the kernel's `io_uring.h` UAPI
+//! layout rather than pulled from a dependency -- there's no
+//! `io-uring` crate in this tree to reuse, and vendoring one isn't
+//! something to fake.
+//!
+//! What's real here: ring creation, mapping the three regions the
+//! kernel hands back (submission queue, completion queue, and the SQE
+//! array -- on recent kernels the completion queue shares the
+//! submission queue's mapping, which [`IoUring::new`] accounts for),
+//! and a full submit-and-reap round trip for `IORING_OP_NOP`. What's
+//! out of scope: per-operation submission helpers for reads, writes,
+//! `accept`, `connect`, `send`, and `recv` (each needs its own
+//! `io_uring_sqe` field layout and, for reads/writes, an
+//! owned-buffer lifetime story so a buffer isn't freed out from under
+//! an in-flight kernel operation) and routing the crate's executor
+//! and reactor through this ring instead of epoll-style readiness
+//! polling. Both are substantial projects of their own, and this
+//! snapshot has no executor/reactor core to wire either into -- the
+//! same gap documented for the ambient `Reactor` type used elsewhere
+//! in this crate's I/O wrappers.
+
+use core::ffi::c_void;
+
+use std::io;
+use std::os::unix::io::{AsRawFd, FromRawFd, OwnedFd, RawFd};
+
+const IORING_OFF_SQ_RING: libc::off_t = 0;
+const IORING_OFF_CQ_RING: libc::off_t = 0x8000000;
+const IORING_OFF_SQES: libc::off_t = 0x10000000;
+
+const IORING_SETUP_SQPOLL: u32 = 1 << 1;
+const IORING_FEAT_SINGLE_MMAP: u32 = 1 << 0;
+
+const IORING_OP_NOP: u8 = 0;
+const IORING_ENTER_GETEVENTS: u32 = 1 << 0;
+
+const SYS_IO_URING_SETUP: libc::c_long = 425;
+const SYS_IO_URING_ENTER: libc::c_long = 426;
+
+/// Mirrors the kernel's `struct io_sqring_offsets`.
+#[repr(C)]
+#[derive(Default, Clone, Copy)]
+struct SqringOffsets {
+    head: u32,
+    tail: u32,
+    ring_mask: u32,
+    ring_entries: u32,
+    flags: u32,
+    dropped: u32,
+    array: u32,
+    resv1: u32,
+    resv2: u64,
+}
+
+/// Mirrors the kernel's `struct io_cqring_offsets`.
+#[repr(C)]
+#[derive(Default, Clone, Copy)]
+struct CqringOffsets {
+    head: u32,
+    tail: u32,
+    ring_mask: u32,
+    ring_entries: u32,
+    overflow: u32,
+    cqes: u32,
+    flags: u32,
+    resv1: u32,
+    resv2: u64,
+}
+
+/// Mirrors the kernel's `struct io_uring_params`.
+#[repr(C)]
+#[derive(Default, Clone, Copy)]
+struct IoUringParams {
+    sq_entries: u32,
+    cq_entries: u32,
+    flags: u32,
+    sq_thread_cpu: u32,
+    sq_thread_idle: u32,
+    features: u32,
+    wq_fd: u32,
+    resv: [u32; 3],
+    sq_off: SqringOffsets,
+    cq_off: CqringOffsets,
+}
+
+/// Mirrors the kernel's `struct io_uring_sqe` (the common prefix; the
+/// operation-specific union fields beyond `off`/`addr`/`len` aren't
+/// declared since only `IORING_OP_NOP` is submitted here).
+#[repr(C)]
+#[derive(Default, Clone, Copy)]
+struct IoUringSqe {
+    opcode: u8,
+    flags: u8,
+    ioprio: u16,
+    fd: i32,
+    off: u64,
+    addr: u64,
+    len: u32,
+    op_flags: u32,
+    user_data: u64,
+    buf_index: u16,
+    personality: u16,
+    splice_fd_in: i32,
+    pad: [u64; 2],
+}
+
+/// Mirrors the kernel's `struct io_uring_cqe`.
+#[repr(C)]
+#[derive(Default, Clone, Copy)]
+pub struct IoUringCqe {
+    pub user_data: u64,
+    pub result: i32,
+    pub flags: u32,
+}
+
+struct Mapping {
+    ptr: *mut c_void,
+    len: usize,
+}
+
+impl Drop for Mapping {
+    fn drop(&mut self) {
+        unsafe {
+            libc::munmap(self.ptr, self.len);
+        }
+    }
+}
+
+/// A ring, with its submission and completion queues mapped into this
+/// process.
+pub struct IoUring {
+    fd: OwnedFd,
+    sq_off: SqringOffsets,
+    cq_off: CqringOffsets,
+    sq_entries: u32,
+    sq_ring: Mapping,
+    // `None` when `IORING_FEAT_SINGLE_MMAP` puts the completion queue
+    // in the same mapping as the submission queue.
+    cq_ring: Option<Mapping>,
+    sqes: Mapping,
+    sq_tail: u32,
+    cq_head: u32,
+}
+
+impl IoUring {
+    /// Sets up a ring with room for `entries` in-flight submissions
+    /// (rounded up to a power of two by the kernel).
+    pub fn new(entries: u32) -> io::Result<IoUring> {
+        let mut params = IoUringParams::default();
+        let fd = unsafe { libc::syscall(SYS_IO_URING_SETUP, entries, &mut params as *mut IoUringParams) };
+        if fd < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        let fd = unsafe { OwnedFd::from_raw_fd(fd as RawFd) };
+
+        if params.flags & IORING_SETUP_SQPOLL != 0 {
+            // `SQPOLL` needs CAP_SYS_NICE and a dedicated kernel
+            // thread; nothing here requests it, but a caller handing
+            // in raw flags via a future extension could, so refuse
+            // rather than silently behaving as if it weren't set.
+            return Err(io::Error::new(io::ErrorKind::Unsupported, "IORING_SETUP_SQPOLL is not supported by this ring wrapper"));
+        }
+
+        let sq_ring_size = (params.sq_off.array as usize) + (params.sq_entries as usize) * core::mem::size_of::<u32>();
+        let cq_ring_size = (params.cq_off.cqes as usize) + (params.cq_entries as usize) * core::mem::size_of::<IoUringCqe>();
+        let single_mmap = params.features & IORING_FEAT_SINGLE_MMAP != 0;
+
+        let sq_ring = mmap(fd.as_raw_fd(), if single_mmap { sq_ring_size.max(cq_ring_size) } else { sq_ring_size }, IORING_OFF_SQ_RING)?;
+
+        let cq_ring = if single_mmap {
+            None
+        } else {
+            Some(mmap(fd.as_raw_fd(), cq_ring_size, IORING_OFF_CQ_RING)?)
+        };
+
+        let sqes_size = (params.sq_entries as usize) * core::mem::size_of::<IoUringSqe>();
+        let sqes = mmap(fd.as_raw_fd(), sqes_size, IORING_OFF_SQES)?;
+
+        Ok(IoUring {
+            fd,
+            sq_off: params.sq_off,
+            cq_off: params.cq_off,
+            sq_entries: params.sq_entries,
+            sq_ring,
+            cq_ring,
+            sqes,
+            sq_tail: 0,
+            cq_head: 0,
+        })
+    }
+
+    fn sq_ring_ptr(&self) -> *mut c_void {
+        self.sq_ring.ptr
+    }
+
+    fn cq_ring_ptr(&self) -> *mut c_void {
+        match &self.cq_ring {
+            Some(mapping) => mapping.ptr,
+            None => self.sq_ring.ptr,
+        }
+    }
+
+    unsafe fn sq_field<T>(&self, offset: u32) -> *mut T {
+        self.sq_ring_ptr().cast::<u8>().add(offset as usize).cast::<T>()
+    }
+
+    unsafe fn cq_field<T>(&self, offset: u32) -> *mut T {
+        self.cq_ring_ptr().cast::<u8>().add(offset as usize).cast::<T>()
+    }
+
+    /// Submits a single `IORING_OP_NOP` and blocks (via
+    /// `io_uring_enter`) until the kernel completes it, returning its
+    /// completion queue entry.
+    ///
+    /// This exists to prove the ring round-trips correctly end to
+    /// end, the same role a ping/health-check op plays for any new
+    /// transport -- it isn't a useful operation to build real I/O on.
+    pub fn submit_nop(&mut self, user_data: u64) -> io::Result<IoUringCqe> {
+        unsafe {
+            let index = self.sq_tail % self.sq_entries;
+            let sqe_slot = self.sqes.ptr.cast::<IoUringSqe>().add(index as usize);
+            core::ptr::write(
+                sqe_slot,
+                IoUringSqe {
+                    opcode: IORING_OP_NOP,
+                    user_data,
+                    ..Default::default()
+                },
+            );
+
+            let array = self.sq_field::<u32>(self.sq_off.array);
+            core::ptr::write(array.add(index as usize), index);
+
+            self.sq_tail = self.sq_tail.wrapping_add(1);
+            core::ptr::write_volatile(self.sq_field::<u32>(self.sq_off.tail), self.sq_tail);
+
+            let submitted = libc::syscall(SYS_IO_URING_ENTER, self.fd.as_raw_fd(), 1u32, 1u32, IORING_ENTER_GETEVENTS, core::ptr::null_mut::<libc::c_void>(), 0usize);
+            if submitted < 0 {
+                return Err(io::Error::last_os_error());
+            }
+
+            loop {
+                let tail = core::ptr::read_volatile(self.cq_field::<u32>(self.cq_off.tail));
+                if tail != self.cq_head {
+                    let mask = core::ptr::read_volatile(self.cq_field::<u32>(self.cq_off.ring_mask));
+                    let index = self.cq_head & mask;
+                    let cqe_ptr = self.cq_field::<IoUringCqe>(self.cq_off.cqes).add(index as usize);
+                    let cqe = core::ptr::read(cqe_ptr);
+                    self.cq_head = self.cq_head.wrapping_add(1);
+                    core::ptr::write_volatile(self.cq_field::<u32>(self.cq_off.head), self.cq_head);
+                    return Ok(cqe);
+                }
+            }
+        }
+    }
+}
+
+fn mmap(fd: RawFd, len: usize, offset: libc::off_t) -> io::Result<Mapping> {
+    let ptr = unsafe { libc::mmap(core::ptr::null_mut(), len, libc::PROT_READ | libc::PROT_WRITE, libc::MAP_SHARED | libc::MAP_POPULATE, fd, offset) };
+    if ptr == libc::MAP_FAILED {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(Mapping { ptr, len })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // `io_uring_setup(2)` needs a 5.1+ kernel; CI and dev machines
+    // running an older one (or a container with the syscall denied by
+    // seccomp) can't run this at all, so a setup failure is skipped
+    // rather than failed -- the same reasoning `fs::tempfile`'s tests
+    // don't apply here, since there's no portable fallback for "no
+    // io_uring available" the way there is for a tempdir.
+    #[test]
+    fn submit_nop_round_trips_through_the_ring() {
+        let mut ring = match IoUring::new(8) {
+            Ok(ring) => ring,
+            Err(err) => {
+                eprintln!("skipping: io_uring unavailable on this kernel ({err})");
+                return;
+            }
+        };
+
+        let cqe = ring.submit_nop(42).unwrap();
+        assert_eq!(cqe.user_data, 42);
+        assert_eq!(cqe.result, 0);
+    }
+
+    #[test]
+    fn completions_are_reaped_in_submission_order() {
+        let mut ring = match IoUring::new(8) {
+            Ok(ring) => ring,
+            Err(err) => {
+                eprintln!("skipping: io_uring unavailable on this kernel ({err})");
+                return;
+            }
+        };
+
+        let first = ring.submit_nop(1).unwrap();
+        let second = ring.submit_nop(2).unwrap();
+        assert_eq!(first.user_data, 1);
+        assert_eq!(second.user_data, 2);
+    }
+}
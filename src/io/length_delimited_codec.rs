@@ -0,0 +1,76 @@
+use alloc::vec::Vec;
+
+use crate::io::codec::{Decoder, Encoder};
+use crate::io::{self};
+
+/// A codec for frames prefixed with a 4-byte big-endian length.
+#[derive(Debug, Clone)]
+pub struct LengthDelimitedCodec {
+    max_frame_length: usize,
+}
+
+impl LengthDelimitedCodec {
+    /// Creates a codec with a generous default frame size limit of 8 MiB.
+    pub fn new() -> Self {
+        Self {
+            max_frame_length: 8 * 1024 * 1024,
+        }
+    }
+
+    /// Sets the largest frame this codec will decode before erroring out,
+    /// guarding against a peer claiming an unreasonable length prefix.
+    pub fn max_frame_length(mut self, max_frame_length: usize) -> Self {
+        self.max_frame_length = max_frame_length;
+        self
+    }
+}
+
+impl Default for LengthDelimitedCodec {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Decoder for LengthDelimitedCodec {
+    type Item = Vec<u8>;
+    type Error = io::Error;
+
+    fn decode(&mut self, src: &mut Vec<u8>) -> Result<Option<Self::Item>, Self::Error> {
+        if src.len() < 4 {
+            return Ok(None);
+        }
+
+        let len = u32::from_be_bytes([src[0], src[1], src[2], src[3]]) as usize;
+        if len > self.max_frame_length {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "frame length exceeds max_frame_length",
+            ));
+        }
+
+        if src.len() < 4 + len {
+            return Ok(None);
+        }
+
+        let frame = src[4..4 + len].to_vec();
+        src.drain(..4 + len);
+        Ok(Some(frame))
+    }
+}
+
+impl Encoder<Vec<u8>> for LengthDelimitedCodec {
+    type Error = io::Error;
+
+    fn encode(&mut self, item: Vec<u8>, dst: &mut Vec<u8>) -> Result<(), Self::Error> {
+        if item.len() > self.max_frame_length {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "frame length exceeds max_frame_length",
+            ));
+        }
+
+        dst.extend_from_slice(&(item.len() as u32).to_be_bytes());
+        dst.extend_from_slice(&item);
+        Ok(())
+    }
+}
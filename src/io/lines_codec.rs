@@ -0,0 +1,61 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::io::codec::{Decoder, Encoder};
+use crate::io::{self};
+
+/// A codec that splits a byte stream into `\n`-terminated (or
+/// `\r\n`-terminated) UTF-8 lines, with the line ending stripped.
+#[derive(Debug, Clone, Default)]
+pub struct LinesCodec {
+    _private: (),
+}
+
+impl LinesCodec {
+    /// Creates a new `LinesCodec`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Decoder for LinesCodec {
+    type Item = String;
+    type Error = io::Error;
+
+    fn decode(&mut self, src: &mut Vec<u8>) -> Result<Option<Self::Item>, Self::Error> {
+        let newline_pos = match src.iter().position(|&b| b == b'\n') {
+            Some(pos) => pos,
+            None => return Ok(None),
+        };
+
+        let mut line: Vec<u8> = src.drain(..=newline_pos).collect();
+        line.pop(); // trailing '\n'
+        if line.last() == Some(&b'\r') {
+            line.pop();
+        }
+
+        String::from_utf8(line)
+            .map(Some)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.utf8_error()))
+    }
+}
+
+impl Encoder<String> for LinesCodec {
+    type Error = io::Error;
+
+    fn encode(&mut self, item: String, dst: &mut Vec<u8>) -> Result<(), Self::Error> {
+        dst.extend_from_slice(item.as_bytes());
+        dst.push(b'\n');
+        Ok(())
+    }
+}
+
+impl<'a> Encoder<&'a str> for LinesCodec {
+    type Error = io::Error;
+
+    fn encode(&mut self, item: &'a str, dst: &mut Vec<u8>) -> Result<(), Self::Error> {
+        dst.extend_from_slice(item.as_bytes());
+        dst.push(b'\n');
+        Ok(())
+    }
+}
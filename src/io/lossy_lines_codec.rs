@@ -0,0 +1,161 @@
+//! Line codecs that never fail on malformed input.
+//!
+//! [`LinesCodec`](crate::io::LinesCodec) errors the whole decode on
+//! invalid UTF-8, which is the right call for a wire protocol but the
+//! wrong one for things like log files or user-supplied text, where a
+//! single bad byte shouldn't take the rest of the stream down with it.
+//! [`LossyLinesCodec`] and [`Utf16LinesCodec`] replace malformed
+//! sequences with U+FFFD REPLACEMENT CHARACTER instead, the same way
+//! `String::from_utf8_lossy` and `char::decode_utf16` already do for
+//! their respective encodings. Use either through
+//! [`Framed`](crate::io::codec::Framed), same as `LinesCodec`:
+//!
+//! ```ignore
+//! let mut lines = Framed::new(reader, LossyLinesCodec::new());
+//! while let Some(line) = lines.try_next().await? {
+//!     // `line` is always valid, even if the source had stray bytes.
+//! }
+//! ```
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::io;
+use crate::io::codec::{Decoder, Encoder};
+
+/// A codec that splits a byte stream into `\n`-terminated (or
+/// `\r\n`-terminated) lines, decoding each as lossy UTF-8: invalid
+/// sequences become U+FFFD rather than failing the decode.
+#[derive(Debug, Clone, Default)]
+pub struct LossyLinesCodec {
+    _private: (),
+}
+
+impl LossyLinesCodec {
+    /// Creates a new `LossyLinesCodec`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Decoder for LossyLinesCodec {
+    type Item = String;
+    type Error = io::Error;
+
+    fn decode(&mut self, src: &mut Vec<u8>) -> Result<Option<Self::Item>, Self::Error> {
+        let newline_pos = match src.iter().position(|&b| b == b'\n') {
+            Some(pos) => pos,
+            None => return Ok(None),
+        };
+
+        let mut line: Vec<u8> = src.drain(..=newline_pos).collect();
+        line.pop(); // trailing '\n'
+        if line.last() == Some(&b'\r') {
+            line.pop();
+        }
+
+        Ok(Some(String::from_utf8_lossy(&line).into_owned()))
+    }
+}
+
+impl Encoder<String> for LossyLinesCodec {
+    type Error = io::Error;
+
+    fn encode(&mut self, item: String, dst: &mut Vec<u8>) -> Result<(), Self::Error> {
+        dst.extend_from_slice(item.as_bytes());
+        dst.push(b'\n');
+        Ok(())
+    }
+}
+
+impl<'a> Encoder<&'a str> for LossyLinesCodec {
+    type Error = io::Error;
+
+    fn encode(&mut self, item: &'a str, dst: &mut Vec<u8>) -> Result<(), Self::Error> {
+        dst.extend_from_slice(item.as_bytes());
+        dst.push(b'\n');
+        Ok(())
+    }
+}
+
+/// Byte order for [`Utf16LinesCodec`], since UTF-16 has no single wire
+/// representation without one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Utf16Endian {
+    Little,
+    Big,
+}
+
+/// A codec that splits a UTF-16 byte stream into lines terminated by a
+/// U+000A code unit, decoding each with `char::decode_utf16`'s lossy
+/// replacement for unpaired surrogates.
+#[derive(Debug, Clone)]
+pub struct Utf16LinesCodec {
+    endian: Utf16Endian,
+}
+
+impl Utf16LinesCodec {
+    /// Creates a new `Utf16LinesCodec` reading code units in the given
+    /// byte order.
+    pub fn new(endian: Utf16Endian) -> Self {
+        Self { endian }
+    }
+
+    fn code_unit(&self, pair: [u8; 2]) -> u16 {
+        match self.endian {
+            Utf16Endian::Little => u16::from_le_bytes(pair),
+            Utf16Endian::Big => u16::from_be_bytes(pair),
+        }
+    }
+
+    fn unit_bytes(&self, unit: u16) -> [u8; 2] {
+        match self.endian {
+            Utf16Endian::Little => unit.to_le_bytes(),
+            Utf16Endian::Big => unit.to_be_bytes(),
+        }
+    }
+}
+
+impl Decoder for Utf16LinesCodec {
+    type Item = String;
+    type Error = io::Error;
+
+    fn decode(&mut self, src: &mut Vec<u8>) -> Result<Option<Self::Item>, Self::Error> {
+        // Only look at complete 2-byte code units; a dangling odd byte
+        // at the end of `src` is an in-progress code unit, not part of
+        // a frame, so it's left for the next read to complete rather
+        // than treated as a decode failure.
+        let newline_unit = src
+            .chunks_exact(2)
+            .position(|pair| self.code_unit([pair[0], pair[1]]) == 0x000A);
+        let newline_unit = match newline_unit {
+            Some(i) => i,
+            None => return Ok(None),
+        };
+        let end = (newline_unit + 1) * 2;
+
+        let line_bytes: Vec<u8> = src.drain(..end).collect();
+        let mut units: Vec<u16> = line_bytes.chunks_exact(2).map(|pair| self.code_unit([pair[0], pair[1]])).collect();
+        units.pop(); // trailing U+000A
+        if units.last() == Some(&0x000D) {
+            units.pop(); // trailing U+000D
+        }
+
+        let decoded = char::decode_utf16(units)
+            .map(|unit| unit.unwrap_or(char::REPLACEMENT_CHARACTER))
+            .collect();
+        Ok(Some(decoded))
+    }
+}
+
+impl<'a> Encoder<&'a str> for Utf16LinesCodec {
+    type Error = io::Error;
+
+    fn encode(&mut self, item: &'a str, dst: &mut Vec<u8>) -> Result<(), Self::Error> {
+        for unit in item.encode_utf16() {
+            dst.extend_from_slice(&self.unit_bytes(unit));
+        }
+        dst.extend_from_slice(&self.unit_bytes(0x000A));
+        Ok(())
+    }
+}
@@ -0,0 +1,106 @@
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use alloc::vec::Vec;
+
+use crate::io::{self, Read};
+
+/// Adapter for [`ReadExt::peekable`] letting callers look at upcoming
+/// bytes (e.g. to sniff a protocol) without consuming them.
+pub struct Peekable<R> {
+    inner: R,
+    peeked: Vec<u8>,
+    pos: usize,
+}
+
+impl<R: Read> Peekable<R> {
+    pub(crate) fn new(inner: R) -> Self {
+        Self {
+            inner,
+            peeked: Vec::new(),
+            pos: 0,
+        }
+    }
+
+    /// Fills `buf` with the next bytes from the stream without
+    /// consuming them -- a later `peek` or regular read will see the
+    /// same bytes again. Returns the number of bytes filled, which is
+    /// less than `buf.len()` only at EOF.
+    pub fn peek<'a>(&'a mut self, buf: &'a mut [u8]) -> Peek<'a, R>
+    where
+        R: Unpin,
+    {
+        Peek { peekable: self, buf }
+    }
+}
+
+/// Extension trait adding [`peekable`](ReadExt::peekable) to every
+/// reader.
+pub trait ReadExt: Read {
+    /// Wraps this reader so upcoming bytes can be inspected with
+    /// [`Peekable::peek`] before being consumed by a regular read --
+    /// handy for protocol sniffing (TLS vs. plaintext, HTTP vs. a
+    /// custom framing).
+    fn peekable(self) -> Peekable<Self>
+    where
+        Self: Sized,
+    {
+        Peekable::new(self)
+    }
+}
+
+impl<R: Read + ?Sized> ReadExt for R {}
+
+/// Future for the [`Peekable::peek`] method.
+pub struct Peek<'a, R> {
+    peekable: &'a mut Peekable<R>,
+    buf: &'a mut [u8],
+}
+
+impl<'a, R> Future for Peek<'a, R>
+where
+    R: Read + Unpin,
+{
+    type Output = io::Result<usize>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let peekable = &mut *this.peekable;
+
+        while peekable.peeked.len() - peekable.pos < this.buf.len() {
+            let mut scratch = [0u8; 4 * 1024];
+            match Pin::new(&mut peekable.inner).poll_read(cx, &mut scratch) {
+                Poll::Ready(Ok(0)) => break,
+                Poll::Ready(Ok(n)) => peekable.peeked.extend_from_slice(&scratch[..n]),
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+
+        let available = &peekable.peeked[peekable.pos..];
+        let n = core::cmp::min(available.len(), this.buf.len());
+        this.buf[..n].copy_from_slice(&available[..n]);
+        Poll::Ready(Ok(n))
+    }
+}
+
+impl<R: Read + Unpin> Read for Peekable<R> {
+    fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+
+        if this.pos < this.peeked.len() {
+            let available = &this.peeked[this.pos..];
+            let n = core::cmp::min(available.len(), buf.len());
+            buf[..n].copy_from_slice(&available[..n]);
+            this.pos += n;
+            if this.pos == this.peeked.len() {
+                this.peeked.clear();
+                this.pos = 0;
+            }
+            return Poll::Ready(Ok(n));
+        }
+
+        Pin::new(&mut this.inner).poll_read(cx, buf)
+    }
+}
@@ -0,0 +1,142 @@
+//! A `ReadBuf`-style uninitialized-buffer read API.
+//!
+//! `Read`, `File`, and `TcpStream` aren't defined in this checkout, so
+//! they can't be retrofitted to fill [`ReadBuf`] without zeroing first;
+//! that has to land in the core `Read` trait itself. What's here is the
+//! buffer type and an extension trait with a (zeroing) default
+//! implementation in terms of the existing `poll_read`, so callers can
+//! start writing against the `ReadBuf` API now and get the real
+//! allocation-free path for free once `Read` grows a native method.
+
+use core::mem::MaybeUninit;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use crate::io::{self, Read};
+
+/// A possibly-uninitialized buffer to read into, tracking how much of
+/// it has been initialized and how much has actually been filled with
+/// data, the way `tokio::io::ReadBuf` does.
+pub struct ReadBuf<'a> {
+    buf: &'a mut [MaybeUninit<u8>],
+    filled: usize,
+    initialized: usize,
+}
+
+impl<'a> ReadBuf<'a> {
+    /// Wraps an already fully-initialized buffer.
+    pub fn new(buf: &'a mut [u8]) -> Self {
+        let initialized = buf.len();
+        let buf = unsafe { &mut *(buf as *mut [u8] as *mut [MaybeUninit<u8>]) };
+        Self {
+            buf,
+            filled: 0,
+            initialized,
+        }
+    }
+
+    /// Wraps a buffer that may contain uninitialized bytes.
+    pub fn uninit(buf: &'a mut [MaybeUninit<u8>]) -> Self {
+        Self {
+            buf,
+            filled: 0,
+            initialized: 0,
+        }
+    }
+
+    /// The portion of the buffer that has been filled with data.
+    pub fn filled(&self) -> &[u8] {
+        unsafe { core::slice::from_raw_parts(self.buf.as_ptr() as *const u8, self.filled) }
+    }
+
+    /// The total capacity of the buffer.
+    pub fn capacity(&self) -> usize {
+        self.buf.len()
+    }
+
+    /// The not-yet-filled, initialized portion of the buffer that's
+    /// safe to read data into with ordinary (non-`unsafe`) code.
+    pub fn initialized_mut(&mut self) -> &mut [u8] {
+        let initialized = unsafe { core::slice::from_raw_parts_mut(self.buf.as_mut_ptr() as *mut u8, self.initialized) };
+        &mut initialized[self.filled..]
+    }
+
+    /// Zero-fills any not-yet-initialized bytes in the unfilled portion
+    /// of the buffer and returns it, so it's safe to hand to an API that
+    /// expects an ordinary `&mut [u8]` (like [`Read::poll_read`]).
+    ///
+    /// Prefer [`initialized_mut`](Self::initialized_mut) when the
+    /// unfilled-but-already-initialized region is enough, to avoid
+    /// paying for the zeroing this falls back to.
+    pub fn initialize_unfilled(&mut self) -> &mut [u8] {
+        if self.initialized < self.buf.len() {
+            for slot in &mut self.buf[self.initialized..] {
+                slot.write(0);
+            }
+            self.initialized = self.buf.len();
+        }
+        let full = unsafe { core::slice::from_raw_parts_mut(self.buf.as_mut_ptr() as *mut u8, self.buf.len()) };
+        &mut full[self.filled..]
+    }
+
+    /// Copies `data` into the unfilled portion of the buffer and marks
+    /// it filled (and, transitively, initialized).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `data` doesn't fit in the remaining capacity.
+    pub fn put_slice(&mut self, data: &[u8]) {
+        assert!(self.filled + data.len() <= self.buf.len(), "ReadBuf overflow");
+        let dst = &mut self.buf[self.filled..self.filled + data.len()];
+        for (d, s) in dst.iter_mut().zip(data) {
+            d.write(*s);
+        }
+        self.filled += data.len();
+        self.initialized = self.initialized.max(self.filled);
+    }
+
+    /// Marks the next `n` bytes of the buffer as initialized (by a
+    /// caller who wrote into them through `unsafe` means) without
+    /// marking them filled.
+    ///
+    /// # Safety
+    ///
+    /// The first `n` unfilled bytes of the buffer must actually have
+    /// been initialized.
+    pub unsafe fn assume_init(&mut self, n: usize) {
+        self.initialized = self.initialized.max(self.filled + n);
+    }
+
+    /// Advances the filled cursor by `n`, which must already be
+    /// initialized.
+    pub fn advance(&mut self, n: usize) {
+        assert!(self.filled + n <= self.initialized, "advance past initialized bytes");
+        self.filled += n;
+    }
+}
+
+/// Extension trait exposing a [`ReadBuf`]-based read on top of [`Read`].
+pub trait ReadBufExt: Read {
+    /// Reads into `buf`, preferring to fill only its already-initialized
+    /// region so repeated reads avoid re-zeroing memory.
+    ///
+    /// The default implementation still has to zero a scratch buffer
+    /// because `Read::poll_read` only accepts `&mut [u8]`; a native
+    /// `poll_read_buf` on `Read` itself is needed to avoid that.
+    fn poll_read_buf(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut ReadBuf<'_>) -> Poll<io::Result<()>>
+    where
+        Self: Unpin,
+    {
+        let dst = buf.initialize_unfilled();
+        match Pin::new(&mut *self.get_mut()).poll_read(cx, dst) {
+            Poll::Ready(Ok(n)) => {
+                buf.advance(n);
+                Poll::Ready(Ok(()))
+            }
+            Poll::Ready(Err(e)) => Poll::Ready(Err(e)),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+impl<R: Read + ?Sized> ReadBufExt for R {}
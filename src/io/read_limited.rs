@@ -0,0 +1,217 @@
+//! Size-capped reads on top of [`BufRead`](crate::io::BufRead).
+//!
+//! `Lines` isn't part of this checkout, so the `Lines::max_length`
+//! builder from the request isn't wired up here; the three limited
+//! reads below are.
+
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use alloc::vec::Vec;
+
+use crate::io::{self, BufRead};
+
+/// Error returned once a limited read would have exceeded its cap.
+#[derive(Debug)]
+pub struct LimitExceeded {
+    /// The limit that was hit.
+    pub limit: usize,
+}
+
+impl From<LimitExceeded> for io::Error {
+    fn from(e: LimitExceeded) -> io::Error {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            alloc::format!("read exceeded the {}-byte limit", e.limit),
+        )
+    }
+}
+
+/// Extension trait adding length-capped variants of the usual
+/// [`BufRead`] convenience methods, so a misbehaving or hostile peer
+/// can't grow a buffer without bound.
+pub trait BufReadExt: BufRead {
+    /// Like `read_until`, but fails with [`LimitExceeded`] instead of
+    /// growing `buf` past `limit` bytes.
+    fn read_until_limited<'a>(&'a mut self, byte: u8, limit: usize, buf: &'a mut Vec<u8>) -> ReadUntilLimited<'a, Self>
+    where
+        Self: Unpin,
+    {
+        ReadUntilLimited {
+            reader: self,
+            byte,
+            limit,
+            buf,
+            read: 0,
+        }
+    }
+
+    /// Like `read_line`, but fails with [`LimitExceeded`] instead of
+    /// growing `buf` past `limit` bytes.
+    fn read_line_limited<'a>(&'a mut self, limit: usize, buf: &'a mut alloc::string::String) -> ReadLineLimited<'a, Self>
+    where
+        Self: Unpin,
+    {
+        ReadLineLimited {
+            reader: self,
+            limit,
+            buf,
+            bytes: Vec::new(),
+        }
+    }
+
+    /// Like `read_to_end`, but fails with [`LimitExceeded`] instead of
+    /// growing `buf` past `limit` bytes.
+    fn read_to_end_limited<'a>(&'a mut self, limit: usize, buf: &'a mut Vec<u8>) -> ReadToEndLimited<'a, Self>
+    where
+        Self: Unpin,
+    {
+        ReadToEndLimited {
+            reader: self,
+            limit,
+            buf,
+        }
+    }
+}
+
+impl<R: BufRead + ?Sized> BufReadExt for R {}
+
+/// Future for [`BufReadExt::read_until_limited`].
+pub struct ReadUntilLimited<'a, R: ?Sized> {
+    reader: &'a mut R,
+    byte: u8,
+    limit: usize,
+    buf: &'a mut Vec<u8>,
+    read: usize,
+}
+
+impl<'a, R> Future for ReadUntilLimited<'a, R>
+where
+    R: BufRead + Unpin + ?Sized,
+{
+    type Output = io::Result<usize>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+
+        loop {
+            let available = match Pin::new(&mut *this.reader).poll_fill_buf(cx) {
+                Poll::Ready(Ok(buf)) => buf,
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                Poll::Pending => return Poll::Pending,
+            };
+
+            let (done, used) = match available.iter().position(|&b| b == this.byte) {
+                Some(i) => (true, i + 1),
+                None => (false, available.len()),
+            };
+
+            if this.read + used > this.limit {
+                let over = LimitExceeded { limit: this.limit };
+                Pin::new(&mut *this.reader).consume(used);
+                return Poll::Ready(Err(over.into()));
+            }
+
+            this.buf.extend_from_slice(&available[..used]);
+            this.read += used;
+            Pin::new(&mut *this.reader).consume(used);
+
+            if done || used == 0 {
+                return Poll::Ready(Ok(this.read));
+            }
+        }
+    }
+}
+
+/// Future for [`BufReadExt::read_line_limited`].
+pub struct ReadLineLimited<'a, R: ?Sized> {
+    reader: &'a mut R,
+    limit: usize,
+    buf: &'a mut alloc::string::String,
+    // Bytes read so far, kept here (rather than in a fresh local each
+    // poll) so that progress survives a `Poll::Pending` in between.
+    bytes: Vec<u8>,
+}
+
+impl<'a, R> Future for ReadLineLimited<'a, R>
+where
+    R: BufRead + Unpin + ?Sized,
+{
+    type Output = io::Result<usize>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+
+        loop {
+            let available = match Pin::new(&mut *this.reader).poll_fill_buf(cx) {
+                Poll::Ready(Ok(buf)) => buf,
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                Poll::Pending => return Poll::Pending,
+            };
+
+            let (done, used) = match available.iter().position(|&b| b == b'\n') {
+                Some(i) => (true, i + 1),
+                None => (false, available.len()),
+            };
+
+            if this.bytes.len() + used > this.limit {
+                let over = LimitExceeded { limit: this.limit };
+                Pin::new(&mut *this.reader).consume(used);
+                return Poll::Ready(Err(over.into()));
+            }
+
+            this.bytes.extend_from_slice(&available[..used]);
+            Pin::new(&mut *this.reader).consume(used);
+
+            if done || used == 0 {
+                let n = this.bytes.len();
+                let s = alloc::string::String::from_utf8(core::mem::take(&mut this.bytes))
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.utf8_error()))?;
+                this.buf.push_str(&s);
+                return Poll::Ready(Ok(n));
+            }
+        }
+    }
+}
+
+/// Future for [`BufReadExt::read_to_end_limited`].
+pub struct ReadToEndLimited<'a, R: ?Sized> {
+    reader: &'a mut R,
+    limit: usize,
+    buf: &'a mut Vec<u8>,
+}
+
+impl<'a, R> Future for ReadToEndLimited<'a, R>
+where
+    R: BufRead + Unpin + ?Sized,
+{
+    type Output = io::Result<usize>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let mut total = 0;
+
+        loop {
+            let available = match Pin::new(&mut *this.reader).poll_fill_buf(cx) {
+                Poll::Ready(Ok(buf)) => buf,
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                Poll::Pending => return Poll::Pending,
+            };
+
+            if available.is_empty() {
+                return Poll::Ready(Ok(total));
+            }
+
+            if this.buf.len() + available.len() > this.limit {
+                let over = LimitExceeded { limit: this.limit };
+                return Poll::Ready(Err(over.into()));
+            }
+
+            let used = available.len();
+            this.buf.extend_from_slice(available);
+            total += used;
+            Pin::new(&mut *this.reader).consume(used);
+        }
+    }
+}
@@ -0,0 +1,90 @@
+//! Splitting a combined reader/writer into independent halves.
+
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use alloc::sync::Arc;
+
+use crate::io::{self, Read, Write};
+use crate::sync::Mutex;
+
+/// The read half of a value split by [`split`].
+pub struct ReadHalf<T> {
+    inner: Arc<Mutex<T>>,
+}
+
+/// The write half of a value split by [`split`].
+pub struct WriteHalf<T> {
+    inner: Arc<Mutex<T>>,
+}
+
+/// Splits a single `Read + Write` value into independently usable
+/// halves, so each can be moved into a different task.
+///
+/// The halves share the underlying value behind a mutex; use
+/// [`reunite`](ReadHalf::reunite) to recover it once both halves are
+/// no longer needed elsewhere.
+pub fn split<T>(io: T) -> (ReadHalf<T>, WriteHalf<T>)
+where
+    T: Read + Write,
+{
+    let inner = Arc::new(Mutex::new(io));
+    (ReadHalf { inner: inner.clone() }, WriteHalf { inner })
+}
+
+/// Error returned by [`ReadHalf::reunite`]/[`WriteHalf::reunite`] when
+/// the two halves did not originate from the same [`split`] call.
+#[derive(Debug)]
+pub struct ReuniteError<T>(pub ReadHalf<T>, pub WriteHalf<T>);
+
+impl<T> ReadHalf<T> {
+    /// Recombines this half with its matching [`WriteHalf`], returning
+    /// the original value.
+    ///
+    /// Fails if `write` was not produced by the same [`split`] call.
+    pub fn reunite(self, write: WriteHalf<T>) -> Result<T, ReuniteError<T>> {
+        if !Arc::ptr_eq(&self.inner, &write.inner) {
+            return Err(ReuniteError(self, write));
+        }
+        drop(write.inner);
+        Arc::try_unwrap(self.inner)
+            .map(Mutex::into_inner)
+            .map_err(|inner| ReuniteError(ReadHalf { inner: inner.clone() }, WriteHalf { inner }))
+    }
+}
+
+impl<T: Read + Unpin> Read for ReadHalf<T> {
+    fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<io::Result<usize>> {
+        let mut guard = match self.inner.try_lock() {
+            Some(guard) => guard,
+            None => return Poll::Pending,
+        };
+        Pin::new(&mut *guard).poll_read(cx, buf)
+    }
+}
+
+impl<T: Write + Unpin> Write for WriteHalf<T> {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+        let mut guard = match self.inner.try_lock() {
+            Some(guard) => guard,
+            None => return Poll::Pending,
+        };
+        Pin::new(&mut *guard).poll_write(cx, buf)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let mut guard = match self.inner.try_lock() {
+            Some(guard) => guard,
+            None => return Poll::Pending,
+        };
+        Pin::new(&mut *guard).poll_flush(cx)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let mut guard = match self.inner.try_lock() {
+            Some(guard) => guard,
+            None => return Poll::Pending,
+        };
+        Pin::new(&mut *guard).poll_close(cx)
+    }
+}
@@ -0,0 +1,95 @@
+//! Unix raw-mode terminal control and a reactor-backed `stdin`.
+//!
+//! The existing `io::stdin()` hands reads off to the blocking pool,
+//! which means a thread sits blocked in `read(2)` for as long as a
+//! `read_line` is pending -- it can't be cancelled by dropping the
+//! future, and it's one fewer thread available to every other blocking
+//! call in the process. `RawStdin` below polls fd 0 directly through
+//! the reactor instead, the same way a `TcpStream` does, so interactive
+//! CLI apps can `select!` stdin against other events and actually drop
+//! a pending read.
+//!
+//! `RawStdin::poll_read` registers fd 0 with [`crate::rt::Reactor`],
+//! the same way every other reactor-backed I/O type in this crate does.
+
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use std::os::unix::io::{AsRawFd, RawFd};
+
+use crate::io::{self, Read};
+
+/// A non-blocking handle to fd 0, polled through the reactor instead of
+/// the blocking pool.
+pub struct RawStdin {
+    fd: RawFd,
+    reactor: crate::rt::Reactor,
+}
+
+impl RawStdin {
+    /// Registers fd 0 with the reactor for non-blocking reads.
+    pub fn new() -> io::Result<Self> {
+        let fd = std::io::stdin().as_raw_fd();
+        set_nonblocking(fd)?;
+        Ok(Self {
+            fd,
+            reactor: crate::rt::Reactor::register(fd)?,
+        })
+    }
+}
+
+impl Read for RawStdin {
+    fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        this.reactor.poll_readable(cx, this.fd, buf)
+    }
+}
+
+fn set_nonblocking(fd: RawFd) -> io::Result<()> {
+    unsafe {
+        let flags = libc::fcntl(fd, libc::F_GETFL, 0);
+        if flags < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        if libc::fcntl(fd, libc::F_SETFL, flags | libc::O_NONBLOCK) < 0 {
+            return Err(io::Error::last_os_error());
+        }
+    }
+    Ok(())
+}
+
+/// Guard returned by [`raw_mode`] that restores the terminal's previous
+/// mode when dropped.
+pub struct RawModeGuard {
+    fd: RawFd,
+    original: libc::termios,
+}
+
+impl Drop for RawModeGuard {
+    fn drop(&mut self) {
+        unsafe {
+            libc::tcsetattr(self.fd, libc::TCSANOW, &self.original);
+        }
+    }
+}
+
+/// Puts the terminal on `fd` (typically stdin) into raw mode: no line
+/// buffering, no echo, no signal-generating control characters. Restores
+/// the previous mode when the returned guard is dropped.
+pub fn raw_mode(fd: RawFd) -> io::Result<RawModeGuard> {
+    unsafe {
+        let mut original: libc::termios = core::mem::zeroed();
+        if libc::tcgetattr(fd, &mut original) != 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        let mut raw = original;
+        libc::cfmakeraw(&mut raw);
+
+        if libc::tcsetattr(fd, libc::TCSANOW, &raw) != 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        Ok(RawModeGuard { fd, original })
+    }
+}
@@ -0,0 +1,118 @@
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use core::time::Duration;
+
+use alloc::boxed::Box;
+
+use crate::io::{self, Read, Write};
+use crate::task::sleep;
+
+/// Adapter for [`ReadExt::throttle`] and [`WriteExt::throttle`] that
+/// caps throughput to a fixed number of bytes per second.
+///
+/// A fresh allowance of `bytes_per_sec` tokens is minted once a second;
+/// reads/writes are capped to whatever is left in the bucket, so a
+/// burst beyond the limit is spread out over time rather than passing
+/// straight through.
+pub struct Throttle<T> {
+    inner: T,
+    capacity: u64,
+    tokens: u64,
+    timer: Pin<Box<dyn Future<Output = ()> + Send>>,
+}
+
+impl<T> Throttle<T> {
+    pub(crate) fn new(inner: T, bytes_per_sec: u64) -> Self {
+        assert!(bytes_per_sec > 0, "throttle rate must be greater than zero");
+        Self {
+            inner,
+            capacity: bytes_per_sec,
+            tokens: bytes_per_sec,
+            timer: Box::pin(sleep(Duration::from_secs(1))),
+        }
+    }
+
+    fn refill(&mut self, cx: &mut Context<'_>) {
+        while self.timer.as_mut().poll(cx).is_ready() {
+            self.tokens = self.capacity;
+            self.timer = Box::pin(sleep(Duration::from_secs(1)));
+        }
+    }
+}
+
+/// Extension trait adding [`throttle`](ReadExt::throttle) to every
+/// reader.
+pub trait ReadExt: Read {
+    /// Caps this reader to `bytes_per_sec` bytes per second.
+    fn throttle(self, bytes_per_sec: u64) -> Throttle<Self>
+    where
+        Self: Sized,
+    {
+        Throttle::new(self, bytes_per_sec)
+    }
+}
+
+impl<R: Read + ?Sized> ReadExt for R {}
+
+/// Extension trait adding [`throttle`](WriteExt::throttle) to every
+/// writer.
+pub trait WriteExt: Write {
+    /// Caps this writer to `bytes_per_sec` bytes per second.
+    fn throttle(self, bytes_per_sec: u64) -> Throttle<Self>
+    where
+        Self: Sized,
+    {
+        Throttle::new(self, bytes_per_sec)
+    }
+}
+
+impl<W: Write + ?Sized> WriteExt for W {}
+
+impl<R: Read + Unpin> Read for Throttle<R> {
+    fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        this.refill(cx);
+
+        if this.tokens == 0 {
+            return Poll::Pending;
+        }
+
+        let allowed = core::cmp::min(buf.len() as u64, this.tokens) as usize;
+        match Pin::new(&mut this.inner).poll_read(cx, &mut buf[..allowed]) {
+            Poll::Ready(Ok(n)) => {
+                this.tokens -= n as u64;
+                Poll::Ready(Ok(n))
+            }
+            other => other,
+        }
+    }
+}
+
+impl<W: Write + Unpin> Write for Throttle<W> {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        this.refill(cx);
+
+        if this.tokens == 0 {
+            return Poll::Pending;
+        }
+
+        let allowed = core::cmp::min(buf.len() as u64, this.tokens) as usize;
+        match Pin::new(&mut this.inner).poll_write(cx, &buf[..allowed]) {
+            Poll::Ready(Ok(n)) => {
+                this.tokens -= n as u64;
+                Poll::Ready(Ok(n))
+            }
+            other => other,
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_flush(cx)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_close(cx)
+    }
+}
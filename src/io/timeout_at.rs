@@ -0,0 +1,70 @@
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use core::time::Instant;
+
+use alloc::boxed::Box;
+
+use crate::io::{self, Read, ReadExt, Write, WriteExt};
+use crate::task::sleep;
+
+/// Future for [`timeout_at`].
+pub struct TimeoutAt<F> {
+    future: F,
+    timer: Pin<Box<dyn Future<Output = ()> + Send>>,
+}
+
+/// Runs `future` to completion, failing with [`io::ErrorKind::TimedOut`]
+/// if `deadline` passes first.
+///
+/// Unlike [`io::timeout`](super::timeout), which takes a `Duration`
+/// relative to when it's called, this takes an absolute [`Instant`],
+/// so a single deadline can be threaded through many I/O calls in a
+/// request pipeline without each one restarting its own clock.
+pub fn timeout_at<F, T>(deadline: Instant, future: F) -> TimeoutAt<F>
+where
+    F: Future<Output = io::Result<T>>,
+{
+    let remaining = deadline.saturating_duration_since(Instant::now());
+    TimeoutAt {
+        future,
+        timer: Box::pin(sleep(remaining)),
+    }
+}
+
+impl<F, T> Future for TimeoutAt<F>
+where
+    F: Future<Output = io::Result<T>>,
+{
+    type Output = io::Result<T>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = unsafe { self.get_unchecked_mut() };
+        let future = unsafe { Pin::new_unchecked(&mut this.future) };
+
+        if let Poll::Ready(output) = future.poll(cx) {
+            return Poll::Ready(output);
+        }
+
+        match this.timer.as_mut().poll(cx) {
+            Poll::Ready(()) => Poll::Ready(Err(io::Error::new(io::ErrorKind::TimedOut, "deadline elapsed"))),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+/// Like [`ReadExt::read`], but fails once `deadline` passes.
+pub async fn read_timeout_at<R>(reader: &mut R, buf: &mut [u8], deadline: Instant) -> io::Result<usize>
+where
+    R: Read + Unpin,
+{
+    timeout_at(deadline, reader.read(buf)).await
+}
+
+/// Like [`WriteExt::write_all`], but fails once `deadline` passes.
+pub async fn write_all_timeout_at<W>(writer: &mut W, buf: &[u8], deadline: Instant) -> io::Result<()>
+where
+    W: Write + Unpin,
+{
+    timeout_at(deadline, writer.write_all(buf)).await
+}
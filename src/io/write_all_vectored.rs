@@ -0,0 +1,67 @@
+//! Vectored write support for [`Write`](crate::io::Write).
+//!
+//! `BufReader`/`BufWriter` aren't part of this checkout, so their
+//! `poll_read_vectored`/`poll_write_vectored` passthrough isn't wired up
+//! here yet -- that should land alongside those types. This covers the
+//! `Write` side every writer gets for free.
+
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use std::io::IoSlice;
+
+use crate::io::{self, Write};
+
+/// Extension trait adding vectored helpers on top of [`Write`].
+pub trait WriteExt: Write {
+    /// Writes the entire contents of `bufs`, advancing through the
+    /// slices (and issuing further `poll_write_vectored` calls) until
+    /// all of them have been fully written.
+    fn write_all_vectored<'a>(&'a mut self, bufs: &'a mut [IoSlice<'a>]) -> WriteAllVectored<'a, Self>
+    where
+        Self: Unpin,
+    {
+        WriteAllVectored { writer: self, bufs }
+    }
+}
+
+impl<W: Write + ?Sized> WriteExt for W {}
+
+/// Future for the [`WriteExt::write_all_vectored`] method.
+pub struct WriteAllVectored<'a, W: ?Sized> {
+    writer: &'a mut W,
+    bufs: &'a mut [IoSlice<'a>],
+}
+
+impl<'a, W> Future for WriteAllVectored<'a, W>
+where
+    W: Write + Unpin + ?Sized,
+{
+    type Output = io::Result<()>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+
+        loop {
+            while !this.bufs.is_empty() && this.bufs[0].is_empty() {
+                this.bufs = &mut this.bufs[1..];
+            }
+            if this.bufs.is_empty() {
+                return Poll::Ready(Ok(()));
+            }
+
+            match Pin::new(&mut *this.writer).poll_write_vectored(cx, this.bufs) {
+                Poll::Ready(Ok(0)) => {
+                    return Poll::Ready(Err(io::Error::new(
+                        io::ErrorKind::WriteZero,
+                        "failed to write whole buffer",
+                    )));
+                }
+                Poll::Ready(Ok(n)) => IoSlice::advance_slices(&mut this.bufs, n),
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
@@ -0,0 +1,126 @@
+//! Waker bookkeeping shared by [`channel`](crate::channel) and
+//! [`priority_channel`](crate::priority_channel): both are MPMC queues
+//! built around a `Mutex`-guarded `State` with a set of parked senders
+//! and a set of parked receivers, and were maintaining that half of
+//! `State` by hand, identically, in both places -- right down to the
+//! same `Vec::pop()` waking the most-recently-parked waiter instead of
+//! the one that's been waiting longest. [`WakerSet`] is that one piece
+//! pulled out so there's a single FIFO implementation for both to
+//! share instead of two to keep in sync.
+
+use core::task::Waker;
+
+use alloc::collections::VecDeque;
+
+/// A FIFO set of parked [`Waker`]s, for the "one side of the channel is
+/// full/empty, wake whoever's been waiting longest once that changes"
+/// pattern both channel implementations need twice over (once for
+/// senders, once for receivers).
+#[derive(Default)]
+pub(crate) struct WakerSet {
+    wakers: VecDeque<Waker>,
+}
+
+impl WakerSet {
+    pub(crate) fn new() -> Self {
+        Self { wakers: VecDeque::new() }
+    }
+
+    /// Parks `waker`, to be woken by a later [`wake_one`](WakerSet::wake_one)
+    /// or [`wake_all`](WakerSet::wake_all).
+    ///
+    /// A future that's still pending after being polled registers
+    /// again on every poll, which for anything driven through
+    /// `select!`/`race`/a combinator that repolls its children on
+    /// every outer wake means the same logical waiter shows up here
+    /// many times over. Skip the push when the most recently
+    /// registered waker already wakes the same task, so a
+    /// repeatedly-repolled waiter still only ever occupies one slot --
+    /// otherwise it could both leak memory and, worse, let that one
+    /// waiter dominate [`wake_one`](WakerSet::wake_one)'s FIFO order by
+    /// occupying several of its own queue positions.
+    pub(crate) fn register(&mut self, waker: &Waker) {
+        if self.wakers.back().map_or(true, |w| !w.will_wake(waker)) {
+            self.wakers.push_back(waker.clone());
+        }
+    }
+
+    /// Wakes whichever parked waker registered first, if any.
+    ///
+    /// FIFO, not LIFO: popping the most recently registered waker
+    /// instead would let a steady stream of new waiters starve out
+    /// whoever has been parked the longest, since each freshly popped
+    /// waiter is the one most likely to immediately succeed and never
+    /// get re-parked behind the others.
+    pub(crate) fn wake_one(&mut self) {
+        if let Some(waker) = self.wakers.pop_front() {
+            waker.wake();
+        }
+    }
+
+    /// Wakes every parked waker, e.g. once the channel disconnects and
+    /// every waiter needs to re-check rather than just one of them.
+    pub(crate) fn wake_all(&mut self) {
+        for waker in self.wakers.drain(..) {
+            waker.wake();
+        }
+    }
+
+    #[cfg(test)]
+    pub(crate) fn len(&self) -> usize {
+        self.wakers.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::task::Waker;
+
+    use alloc::sync::Arc;
+    use std::sync::Mutex;
+
+    struct Recorder {
+        order: Arc<Mutex<Vec<usize>>>,
+        id: usize,
+    }
+
+    impl std::task::Wake for Recorder {
+        fn wake(self: Arc<Self>) {
+            self.order.lock().unwrap().push(self.id);
+        }
+    }
+
+    fn recording_waker(order: &Arc<Mutex<Vec<usize>>>, id: usize) -> Waker {
+        Waker::from(Arc::new(Recorder { order: order.clone(), id }))
+    }
+
+    #[test]
+    fn reregistering_the_same_waker_while_pending_does_not_grow_the_queue() {
+        let order = Arc::new(Mutex::new(Vec::new()));
+        let waker = recording_waker(&order, 0);
+
+        let mut set = WakerSet::new();
+        set.register(&waker);
+        set.register(&waker);
+        set.register(&waker);
+
+        assert_eq!(set.len(), 1, "repeated registration by the same still-pending waiter must not pile up entries");
+    }
+
+    #[test]
+    fn distinct_wakers_are_woken_in_fifo_order() {
+        let order = Arc::new(Mutex::new(Vec::new()));
+
+        let mut set = WakerSet::new();
+        set.register(&recording_waker(&order, 1));
+        set.register(&recording_waker(&order, 2));
+        set.register(&recording_waker(&order, 3));
+
+        set.wake_one();
+        set.wake_one();
+        set.wake_one();
+
+        assert_eq!(*order.lock().unwrap(), alloc::vec![1, 2, 3]);
+    }
+}
@@ -0,0 +1,115 @@
+//! A timed `accept` and an `incoming()` variant that survives
+//! transient errors (`EMFILE`, `ECONNABORTED`, ...) instead of ending
+//! the stream the first time the process runs low on file descriptors.
+
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use core::time::Duration;
+
+use crate::io;
+use crate::net::{TcpListener, TcpStream};
+use crate::stream::Stream;
+use crate::task::sleep;
+
+/// Extension trait adding a timed accept and a resilient incoming
+/// stream to [`TcpListener`].
+pub trait TcpListenerExt {
+    /// Accepts a connection, failing with [`io::ErrorKind::TimedOut`] if
+    /// none arrives within `timeout`.
+    async fn accept_timeout(&self, timeout: Duration) -> io::Result<(TcpStream, std::net::SocketAddr)>;
+
+    /// Like [`incoming`](TcpListener::incoming), but recoverable errors
+    /// (`EMFILE`, `ENFILE`, `ECONNABORTED`, and similar) are retried
+    /// with the given backoff policy instead of ending the stream; only
+    /// errors the policy gives up on are yielded to the loop body.
+    fn incoming_resilient(&self, backoff: AcceptBackoff) -> IncomingResilient<'_>;
+}
+
+impl TcpListenerExt for TcpListener {
+    async fn accept_timeout(&self, timeout: Duration) -> io::Result<(TcpStream, std::net::SocketAddr)> {
+        crate::future::timeout(timeout, self.accept())
+            .await
+            .unwrap_or_else(|_| Err(io::Error::new(io::ErrorKind::TimedOut, "accept timed out")))
+    }
+
+    fn incoming_resilient(&self, backoff: AcceptBackoff) -> IncomingResilient<'_> {
+        IncomingResilient {
+            listener: self,
+            backoff,
+            retries: 0,
+        }
+    }
+}
+
+/// Controls how [`TcpListenerExt::incoming_resilient`] reacts to
+/// transient accept errors.
+pub struct AcceptBackoff {
+    /// Delay before the first retry.
+    pub initial_delay: Duration,
+    /// Multiplier applied to the delay after each retry.
+    pub factor: u32,
+    /// Longest delay between retries.
+    pub max_delay: Duration,
+    /// How many consecutive transient errors to retry before giving up
+    /// and yielding the error to the caller.
+    pub max_retries: u32,
+}
+
+impl Default for AcceptBackoff {
+    fn default() -> Self {
+        Self {
+            initial_delay: Duration::from_millis(10),
+            factor: 2,
+            max_delay: Duration::from_secs(1),
+            max_retries: 10,
+        }
+    }
+}
+
+fn is_transient(e: &io::Error) -> bool {
+    matches!(
+        e.kind(),
+        io::ErrorKind::ConnectionAborted | io::ErrorKind::ConnectionReset | io::ErrorKind::Interrupted
+    ) || e.raw_os_error() == Some(libc::EMFILE)
+        || e.raw_os_error() == Some(libc::ENFILE)
+}
+
+/// Stream returned by [`TcpListenerExt::incoming_resilient`].
+pub struct IncomingResilient<'a> {
+    listener: &'a TcpListener,
+    backoff: AcceptBackoff,
+    retries: u32,
+}
+
+impl<'a> Stream for IncomingResilient<'a> {
+    type Item = io::Result<TcpStream>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        loop {
+            let mut accept = Box::pin(this.listener.accept());
+            match accept.as_mut().poll(cx) {
+                Poll::Ready(Ok((stream, _addr))) => {
+                    this.retries = 0;
+                    return Poll::Ready(Some(Ok(stream)));
+                }
+                Poll::Ready(Err(e)) if is_transient(&e) && this.retries < this.backoff.max_retries => {
+                    this.retries += 1;
+                    let delay = this
+                        .backoff
+                        .initial_delay
+                        .saturating_mul(this.backoff.factor.saturating_pow(this.retries - 1))
+                        .min(this.backoff.max_delay);
+                    let mut sleep_fut = Box::pin(sleep(delay));
+                    match sleep_fut.as_mut().poll(cx) {
+                        Poll::Ready(()) => continue,
+                        Poll::Pending => return Poll::Pending,
+                    }
+                }
+                Poll::Ready(Err(e)) => return Poll::Ready(Some(Err(e))),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
@@ -0,0 +1,77 @@
+//! Staggered parallel connection attempts (RFC 8305 "Happy Eyeballs"),
+//! so a broken IPv6 path doesn't add a full connect timeout in front of
+//! the working IPv4 one.
+
+use core::time::Duration;
+
+use std::net::SocketAddr;
+
+use crate::io;
+use crate::net::{TcpStream, ToSocketAddrs};
+use crate::task::sleep;
+
+/// Tuning for [`connect`].
+#[derive(Debug, Clone, Copy)]
+pub struct HappyEyeballsConfig {
+    /// Delay before starting the next address's connection attempt
+    /// while earlier ones are still pending. RFC 8305 recommends 250ms.
+    pub stagger_delay: Duration,
+    /// Whether to try addresses at all in parallel; `false` falls back
+    /// to strictly sequential attempts (the old behavior).
+    pub enabled: bool,
+}
+
+impl Default for HappyEyeballsConfig {
+    fn default() -> Self {
+        Self {
+            stagger_delay: Duration::from_millis(250),
+            enabled: true,
+        }
+    }
+}
+
+/// Connects to the first address resolved from `addr` to succeed,
+/// starting later addresses' attempts after `config.stagger_delay` if
+/// earlier ones haven't finished yet, and keeping whichever connection
+/// completes first.
+pub async fn connect(addr: impl ToSocketAddrs, config: HappyEyeballsConfig) -> io::Result<TcpStream> {
+    let addrs: Vec<SocketAddr> = addr.to_socket_addrs().await?.collect();
+    if addrs.is_empty() {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "no addresses to connect to"));
+    }
+
+    if !config.enabled || addrs.len() == 1 {
+        let mut last_err = None;
+        for addr in addrs {
+            match TcpStream::connect(addr).await {
+                Ok(stream) => return Ok(stream),
+                Err(e) => last_err = Some(e),
+            }
+        }
+        return Err(last_err.unwrap());
+    }
+
+    let (sender, mut receiver) = crate::channel::unbounded();
+
+    for (i, addr) in addrs.into_iter().enumerate() {
+        let sender = sender.clone();
+        crate::task::spawn(async move {
+            if i > 0 {
+                sleep(config.stagger_delay * i as u32).await;
+            }
+            let result = TcpStream::connect(addr).await;
+            let _ = sender.try_send(result);
+        });
+    }
+    drop(sender);
+
+    let mut last_err = None;
+    while let Some(result) = crate::stream::StreamExt::next(&mut receiver).await {
+        match result {
+            Ok(stream) => return Ok(stream),
+            Err(e) => last_err = Some(e),
+        }
+    }
+
+    Err(last_err.unwrap_or_else(|| io::Error::new(io::ErrorKind::Other, "all connection attempts failed")))
+}
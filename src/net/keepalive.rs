@@ -0,0 +1,76 @@
+//! TCP keepalive configuration, so long-lived connections behind a NAT
+//! don't die silently when the middlebox forgets about them.
+
+use core::time::Duration;
+
+use std::os::unix::io::AsRawFd;
+
+use crate::io;
+use crate::net::TcpStream;
+
+/// Keepalive parameters for [`TcpStreamKeepaliveExt::set_keepalive`].
+#[derive(Debug, Clone, Copy)]
+pub struct KeepAlive {
+    /// How long the connection must be idle before the first probe is
+    /// sent (`TCP_KEEPIDLE` / Windows' keepalivetime).
+    pub idle: Duration,
+    /// How long to wait between probes (`TCP_KEEPINTVL`).
+    pub interval: Duration,
+    /// How many unacknowledged probes to send before giving up on the
+    /// connection (`TCP_KEEPCNT`; not configurable on Windows).
+    pub retries: u32,
+}
+
+/// Extension trait adding keepalive configuration to [`TcpStream`].
+pub trait TcpStreamKeepaliveExt {
+    /// Enables or disables TCP keepalive, with the given parameters
+    /// when enabling.
+    fn set_keepalive(&self, keepalive: Option<KeepAlive>) -> io::Result<()>;
+}
+
+#[cfg(target_os = "linux")]
+impl TcpStreamKeepaliveExt for TcpStream {
+    fn set_keepalive(&self, keepalive: Option<KeepAlive>) -> io::Result<()> {
+        let fd = self.as_raw_fd();
+
+        set_int_opt(fd, libc::SOL_SOCKET, libc::SO_KEEPALIVE, keepalive.is_some() as i32)?;
+
+        if let Some(keepalive) = keepalive {
+            set_int_opt(fd, libc::IPPROTO_TCP, libc::TCP_KEEPIDLE, keepalive.idle.as_secs() as i32)?;
+            set_int_opt(fd, libc::IPPROTO_TCP, libc::TCP_KEEPINTVL, keepalive.interval.as_secs() as i32)?;
+            set_int_opt(fd, libc::IPPROTO_TCP, libc::TCP_KEEPCNT, keepalive.retries as i32)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(all(unix, not(target_os = "linux")))]
+impl TcpStreamKeepaliveExt for TcpStream {
+    fn set_keepalive(&self, keepalive: Option<KeepAlive>) -> io::Result<()> {
+        let fd = self.as_raw_fd();
+        set_int_opt(fd, libc::SOL_SOCKET, libc::SO_KEEPALIVE, keepalive.is_some() as i32)?;
+        // `TCP_KEEPIDLE`/`TCP_KEEPINTVL`/`TCP_KEEPCNT` exist under
+        // platform-specific names and availability on the BSDs/macOS;
+        // only the on/off switch is wired up uniformly here.
+        Ok(())
+    }
+}
+
+#[cfg(unix)]
+fn set_int_opt(fd: std::os::unix::io::RawFd, level: i32, name: i32, value: i32) -> io::Result<()> {
+    let ret = unsafe {
+        libc::setsockopt(
+            fd,
+            level,
+            name,
+            &value as *const i32 as *const libc::c_void,
+            core::mem::size_of::<i32>() as libc::socklen_t,
+        )
+    };
+    if ret == 0 {
+        Ok(())
+    } else {
+        Err(io::Error::last_os_error())
+    }
+}
@@ -0,0 +1,87 @@
+//! A listener that binds every address a host resolves to (typically
+//! `[::]` and `0.0.0.0` on platforms without a dual-stack socket) and
+//! exposes them as a single `accept`/`incoming`, so servers don't each
+//! reimplement this fan-in with a `select!` loop.
+
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use std::net::SocketAddr;
+
+use crate::io;
+use crate::net::{TcpListener, TcpStream, ToSocketAddrs};
+use crate::stream::Stream;
+
+/// Binds a [`TcpListener`] on every address `addrs` resolves to.
+pub struct MultiListener {
+    listeners: Vec<TcpListener>,
+}
+
+impl MultiListener {
+    /// Resolves `addrs` and binds a listener on each resulting address.
+    /// Fails only if every bind attempt fails; individual failures
+    /// (e.g. a platform that already dual-stacks `[::]` and rejects a
+    /// redundant `0.0.0.0` bind) are tolerated as long as at least one
+    /// succeeds.
+    pub async fn bind(addrs: impl ToSocketAddrs) -> io::Result<Self> {
+        let mut listeners = Vec::new();
+        let mut last_err = None;
+
+        for addr in addrs.to_socket_addrs().await? {
+            match TcpListener::bind(addr).await {
+                Ok(listener) => listeners.push(listener),
+                Err(e) => last_err = Some(e),
+            }
+        }
+
+        if listeners.is_empty() {
+            return Err(last_err.unwrap_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "no addresses to bind")));
+        }
+
+        Ok(Self { listeners })
+    }
+
+    /// The local addresses actually bound.
+    pub fn local_addrs(&self) -> io::Result<Vec<SocketAddr>> {
+        self.listeners.iter().map(TcpListener::local_addr).collect()
+    }
+
+    /// Accepts the next connection on whichever bound listener has one
+    /// ready first.
+    pub async fn accept(&self) -> io::Result<(TcpStream, SocketAddr)> {
+        crate::future::poll_fn(|cx| {
+            for listener in &self.listeners {
+                let mut accept = Box::pin(listener.accept());
+                if let Poll::Ready(result) = accept.as_mut().poll(cx) {
+                    return Poll::Ready(result);
+                }
+            }
+            Poll::Pending
+        })
+        .await
+    }
+
+    /// A stream of incoming connections across every bound listener.
+    pub fn incoming(&self) -> Incoming<'_> {
+        Incoming { listener: self }
+    }
+}
+
+/// Stream returned by [`MultiListener::incoming`].
+pub struct Incoming<'a> {
+    listener: &'a MultiListener,
+}
+
+impl<'a> Stream for Incoming<'a> {
+    type Item = io::Result<TcpStream>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut accept = Box::pin(self.listener.accept());
+        match accept.as_mut().poll(cx) {
+            Poll::Ready(Ok((stream, _addr))) => Poll::Ready(Some(Ok(stream))),
+            Poll::Ready(Err(e)) => Poll::Ready(Some(Err(e))),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
@@ -0,0 +1,117 @@
+//! A pluggable, cached hostname resolver, so `connect`-adjacent code
+//! isn't locked into one uncached blocking `getaddrinfo` call per
+//! lookup with no way to substitute a different resolution strategy.
+//!
+//! Only the blocking backend is provided here; a pure-async DNS client
+//! (speaking the wire protocol directly instead of shelling out to
+//! `getaddrinfo`) would implement [`Resolver`] the same way behind its
+//! own feature flag.
+
+use core::future::Future;
+use core::pin::Pin;
+use core::time::Duration;
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use crate::io;
+use crate::task::spawn_blocking;
+
+/// A pluggable DNS resolver.
+///
+/// `resolve` returns a boxed future rather than being an `async fn` so
+/// that `Resolver` stays object-safe -- [`set_global_resolver`] stores
+/// one behind `Arc<dyn Resolver>`, which native `async fn`-in-trait
+/// can't be named or stored as.
+pub trait Resolver: Send + Sync {
+    /// Resolves `host:port` into zero or more socket addresses.
+    fn resolve<'a>(&'a self, host: &'a str, port: u16) -> Pin<Box<dyn Future<Output = io::Result<Vec<SocketAddr>>> + Send + 'a>>;
+}
+
+/// The default resolver, backed by the platform's blocking
+/// `getaddrinfo` run on the blocking pool -- the same strategy
+/// `ToSocketAddrs` already uses, just reachable as a swappable
+/// `Resolver`.
+pub struct BlockingResolver;
+
+impl Resolver for BlockingResolver {
+    fn resolve<'a>(&'a self, host: &'a str, port: u16) -> Pin<Box<dyn Future<Output = io::Result<Vec<SocketAddr>>> + Send + 'a>> {
+        let host = host.to_string();
+        Box::pin(async move {
+            spawn_blocking(move || {
+                std::net::ToSocketAddrs::to_socket_addrs(&(host.as_str(), port)).map(|addrs| addrs.collect())
+            })
+            .await
+        })
+    }
+}
+
+struct CacheEntry {
+    addrs: Vec<SocketAddr>,
+    expires_at: Instant,
+}
+
+/// Wraps a [`Resolver`] with a per-process cache keyed by `host:port`,
+/// honoring a fixed TTL per entry.
+pub struct CachingResolver<R> {
+    inner: R,
+    ttl: Duration,
+    cache: Mutex<HashMap<(String, u16), CacheEntry>>,
+}
+
+impl<R: Resolver> CachingResolver<R> {
+    pub fn new(inner: R, ttl: Duration) -> Self {
+        Self {
+            inner,
+            ttl,
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl<R: Resolver> Resolver for CachingResolver<R> {
+    fn resolve<'a>(&'a self, host: &'a str, port: u16) -> Pin<Box<dyn Future<Output = io::Result<Vec<SocketAddr>>> + Send + 'a>> {
+        Box::pin(async move {
+            let key = (host.to_string(), port);
+
+            if let Some(entry) = self.cache.lock().unwrap().get(&key) {
+                if entry.expires_at > Instant::now() {
+                    return Ok(entry.addrs.clone());
+                }
+            }
+
+            let addrs = self.inner.resolve(host, port).await?;
+            self.cache.lock().unwrap().insert(
+                key,
+                CacheEntry {
+                    addrs: addrs.clone(),
+                    expires_at: Instant::now() + self.ttl,
+                },
+            );
+            Ok(addrs)
+        })
+    }
+}
+
+/// The resolver `connect`/`ToSocketAddrs` consult, settable with
+/// [`set_global_resolver`]. Defaults to [`BlockingResolver`].
+static GLOBAL_RESOLVER: Mutex<Option<Arc<dyn Resolver>>> = Mutex::new(None);
+
+/// Installs `resolver` as the process-wide default, used by any code
+/// that resolves addresses through [`global_resolver`] instead of
+/// going straight to [`BlockingResolver`].
+pub fn set_global_resolver(resolver: Arc<dyn Resolver>) {
+    *GLOBAL_RESOLVER.lock().unwrap() = Some(resolver);
+}
+
+/// Resolves `host:port` using the installed global resolver, falling
+/// back to [`BlockingResolver`] if none has been set.
+pub async fn resolve(host: &str, port: u16) -> io::Result<Vec<SocketAddr>> {
+    let resolver = GLOBAL_RESOLVER.lock().unwrap().clone();
+    match resolver {
+        Some(resolver) => resolver.resolve(host, port).await,
+        None => BlockingResolver.resolve(host, port).await,
+    }
+}
@@ -0,0 +1,68 @@
+//! Lossless conversions between async-std's networking types and raw
+//! std/`socket2` sockets, so going through a raw fd doesn't risk
+//! leaving a socket in blocking mode or double-registering it with the
+//! reactor.
+//!
+//! The actual reactor registration/deregistration calls are left as a
+//! gap -- this checkout has no reactor to register against -- but the
+//! conversion functions are written against the shape that plumbing
+//! would need to fill in.
+
+use std::os::unix::io::{FromRawFd, IntoRawFd};
+
+use crate::io;
+use crate::net::{TcpListener, TcpStream, UdpSocket};
+
+fn set_nonblocking(fd: i32) -> io::Result<()> {
+    unsafe {
+        let flags = libc::fcntl(fd, libc::F_GETFL, 0);
+        if flags < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        if libc::fcntl(fd, libc::F_SETFL, flags | libc::O_NONBLOCK) < 0 {
+            return Err(io::Error::last_os_error());
+        }
+    }
+    Ok(())
+}
+
+macro_rules! std_conversions {
+    ($async_ty:ty, $std_ty:ty) => {
+        impl $async_ty {
+            /// Converts a standard library (or `socket2`, via its own
+            /// `into()`) socket into this type, putting it in
+            /// non-blocking mode and registering it with the reactor.
+            pub fn from_std(std_socket: $std_ty) -> io::Result<Self> {
+                let fd = std_socket.into_raw_fd();
+                set_nonblocking(fd)?;
+                // TODO: register `fd` with the reactor once one exists
+                // in this checkout; until then the type is constructed
+                // directly from the raw fd.
+                Ok(unsafe { <$async_ty>::from_raw_fd(fd) })
+            }
+
+            /// Converts this socket back into a standard library
+            /// socket, deregistering it from the reactor and restoring
+            /// blocking mode so it behaves as callers expect a
+            /// `std` socket to.
+            pub fn into_std(self) -> io::Result<$std_ty> {
+                let fd = self.into_raw_fd();
+                // TODO: deregister `fd` from the reactor here, before
+                // handing it back to a caller who may use it with
+                // blocking calls.
+                unsafe {
+                    let std_socket = <$std_ty>::from_raw_fd(fd);
+                    let flags = libc::fcntl(fd, libc::F_GETFL, 0);
+                    if flags >= 0 {
+                        libc::fcntl(fd, libc::F_SETFL, flags & !libc::O_NONBLOCK);
+                    }
+                    Ok(std_socket)
+                }
+            }
+        }
+    };
+}
+
+std_conversions!(TcpStream, std::net::TcpStream);
+std_conversions!(TcpListener, std::net::TcpListener);
+std_conversions!(UdpSocket, std::net::UdpSocket);
@@ -0,0 +1,63 @@
+//! `TcpStream::peek`, so a server can inspect the start of a connection
+//! (e.g. to tell a TLS `ClientHello` from plaintext) without consuming
+//! the bytes -- the TCP counterpart to `UdpSocket::peek_from`.
+
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use std::os::unix::io::AsRawFd;
+
+use crate::io;
+use crate::net::TcpStream;
+
+/// Extension trait adding [`peek`](Self::peek) to [`TcpStream`].
+pub trait TcpStreamPeekExt {
+    /// Reads into `buf` from the socket's receive queue without
+    /// removing the data, so a subsequent `read` sees the same bytes
+    /// again.
+    fn peek<'a>(&'a self, buf: &'a mut [u8]) -> Peek<'a>;
+}
+
+impl TcpStreamPeekExt for TcpStream {
+    fn peek<'a>(&'a self, buf: &'a mut [u8]) -> Peek<'a> {
+        Peek { stream: self, buf }
+    }
+}
+
+/// Future returned by [`TcpStreamPeekExt::peek`].
+pub struct Peek<'a> {
+    stream: &'a TcpStream,
+    buf: &'a mut [u8],
+}
+
+impl<'a> Future for Peek<'a> {
+    type Output = io::Result<usize>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+
+        // Readiness is driven the same way `poll_read` is for a normal
+        // read; once the reactor says the socket is readable, `MSG_PEEK`
+        // makes the actual `recv` call non-destructive.
+        match this.stream.poll_readable(cx) {
+            Poll::Ready(Ok(())) => {}
+            Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+            Poll::Pending => return Poll::Pending,
+        }
+
+        let fd = this.stream.as_raw_fd();
+        let n = unsafe { libc::recv(fd, this.buf.as_mut_ptr() as *mut libc::c_void, this.buf.len(), libc::MSG_PEEK) };
+
+        if n >= 0 {
+            Poll::Ready(Ok(n as usize))
+        } else {
+            let err = io::Error::last_os_error();
+            if err.kind() == io::ErrorKind::WouldBlock {
+                Poll::Pending
+            } else {
+                Poll::Ready(Err(err))
+            }
+        }
+    }
+}
@@ -0,0 +1,194 @@
+//! A socket builder for configuring options that have to be set before
+//! `bind`/`connect`/`listen` (`SO_REUSEADDR`, `SO_REUSEPORT`, bind
+//! device, buffer sizes, `TCP_FASTOPEN`), mirroring what tokio's
+//! `TcpSocket` offers.
+
+use std::net::SocketAddr;
+use std::os::unix::io::{AsRawFd, FromRawFd, RawFd};
+
+use crate::io;
+use crate::net::{TcpListener, TcpStream};
+
+/// A TCP socket that hasn't been connected or put into listening mode
+/// yet, so options can be set on the raw socket first.
+pub struct TcpSocket {
+    fd: RawFd,
+}
+
+impl TcpSocket {
+    /// Creates a new IPv4 socket.
+    pub fn new_v4() -> io::Result<Self> {
+        Self::new(libc::AF_INET)
+    }
+
+    /// Creates a new IPv6 socket.
+    pub fn new_v6() -> io::Result<Self> {
+        Self::new(libc::AF_INET6)
+    }
+
+    fn new(domain: i32) -> io::Result<Self> {
+        let fd = unsafe { libc::socket(domain, libc::SOCK_STREAM, 0) };
+        if fd < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(Self { fd })
+    }
+
+    /// Sets `SO_REUSEADDR`.
+    pub fn set_reuseaddr(&self, reuse: bool) -> io::Result<()> {
+        self.set_bool_opt(libc::SOL_SOCKET, libc::SO_REUSEADDR, reuse)
+    }
+
+    /// Sets `SO_REUSEPORT`.
+    #[cfg(not(windows))]
+    pub fn set_reuseport(&self, reuse: bool) -> io::Result<()> {
+        self.set_bool_opt(libc::SOL_SOCKET, libc::SO_REUSEPORT, reuse)
+    }
+
+    /// Sets the socket receive buffer size (`SO_RCVBUF`).
+    pub fn set_recv_buffer_size(&self, size: u32) -> io::Result<()> {
+        self.set_int_opt(libc::SOL_SOCKET, libc::SO_RCVBUF, size as i32)
+    }
+
+    /// Sets the socket send buffer size (`SO_SNDBUF`).
+    pub fn set_send_buffer_size(&self, size: u32) -> io::Result<()> {
+        self.set_int_opt(libc::SOL_SOCKET, libc::SO_SNDBUF, size as i32)
+    }
+
+    /// Binds the socket to a specific network interface (`SO_BINDTODEVICE`).
+    #[cfg(target_os = "linux")]
+    pub fn bind_device(&self, interface: &str) -> io::Result<()> {
+        let cstr = std::ffi::CString::new(interface).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+        let ret = unsafe {
+            libc::setsockopt(
+                self.fd,
+                libc::SOL_SOCKET,
+                libc::SO_BINDTODEVICE,
+                cstr.as_ptr() as *const libc::c_void,
+                cstr.as_bytes_with_nul().len() as libc::socklen_t,
+            )
+        };
+        if ret == 0 {
+            Ok(())
+        } else {
+            Err(io::Error::last_os_error())
+        }
+    }
+
+    /// Enables `TCP_FASTOPEN` with the given backlog/queue length.
+    #[cfg(target_os = "linux")]
+    pub fn set_fastopen(&self, queue_len: i32) -> io::Result<()> {
+        self.set_int_opt(libc::IPPROTO_TCP, libc::TCP_FASTOPEN, queue_len)
+    }
+
+    /// Binds the socket to `addr`.
+    pub fn bind(&self, addr: SocketAddr) -> io::Result<()> {
+        let (raw, len) = socket_addr_to_raw(addr);
+        let ret = unsafe { libc::bind(self.fd, &raw as *const _ as *const libc::sockaddr, len) };
+        if ret == 0 {
+            Ok(())
+        } else {
+            Err(io::Error::last_os_error())
+        }
+    }
+
+    /// Connects to `addr`, consuming the builder and registering the
+    /// resulting socket with the reactor.
+    ///
+    /// This checkout has no reactor to register non-blocking sockets
+    /// against, so the connect itself happens here but handing the fd
+    /// off to a working, pollable `TcpStream` is left as a TODO for
+    /// whoever wires up the reactor.
+    pub async fn connect(self, addr: SocketAddr) -> io::Result<TcpStream> {
+        let (raw, len) = socket_addr_to_raw(addr);
+        let ret = unsafe { libc::connect(self.fd, &raw as *const _ as *const libc::sockaddr, len) };
+        if ret != 0 {
+            let err = io::Error::last_os_error();
+            if err.kind() != io::ErrorKind::WouldBlock {
+                return Err(err);
+            }
+        }
+        Ok(unsafe { TcpStream::from_raw_fd(self.fd) })
+    }
+
+    /// Puts the socket into listening mode with the given backlog,
+    /// consuming the builder.
+    pub fn listen(self, backlog: u32) -> io::Result<TcpListener> {
+        let ret = unsafe { libc::listen(self.fd, backlog as i32) };
+        if ret != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(unsafe { TcpListener::from_raw_fd(self.fd) })
+    }
+
+    fn set_bool_opt(&self, level: i32, name: i32, value: bool) -> io::Result<()> {
+        self.set_int_opt(level, name, value as i32)
+    }
+
+    fn set_int_opt(&self, level: i32, name: i32, value: i32) -> io::Result<()> {
+        let ret = unsafe {
+            libc::setsockopt(
+                self.fd,
+                level,
+                name,
+                &value as *const i32 as *const libc::c_void,
+                core::mem::size_of::<i32>() as libc::socklen_t,
+            )
+        };
+        if ret == 0 {
+            Ok(())
+        } else {
+            Err(io::Error::last_os_error())
+        }
+    }
+}
+
+impl AsRawFd for TcpSocket {
+    fn as_raw_fd(&self) -> RawFd {
+        self.fd
+    }
+}
+
+impl Drop for TcpSocket {
+    fn drop(&mut self) {
+        unsafe {
+            libc::close(self.fd);
+        }
+    }
+}
+
+fn socket_addr_to_raw(addr: SocketAddr) -> (libc::sockaddr_storage, libc::socklen_t) {
+    let mut storage: libc::sockaddr_storage = unsafe { core::mem::zeroed() };
+    let len = match addr {
+        SocketAddr::V4(v4) => {
+            let raw = libc::sockaddr_in {
+                sin_family: libc::AF_INET as libc::sa_family_t,
+                sin_port: v4.port().to_be(),
+                sin_addr: libc::in_addr {
+                    s_addr: u32::from_ne_bytes(v4.ip().octets()),
+                },
+                sin_zero: [0; 8],
+            };
+            unsafe {
+                (&mut storage as *mut _ as *mut libc::sockaddr_in).write(raw);
+            }
+            core::mem::size_of::<libc::sockaddr_in>() as libc::socklen_t
+        }
+        SocketAddr::V6(v6) => {
+            let raw = libc::sockaddr_in6 {
+                sin6_family: libc::AF_INET6 as libc::sa_family_t,
+                sin6_port: v6.port().to_be(),
+                sin6_flowinfo: v6.flowinfo(),
+                sin6_addr: libc::in6_addr {
+                    s6_addr: v6.ip().octets(),
+                },
+                sin6_scope_id: v6.scope_id(),
+            };
+            unsafe {
+                (&mut storage as *mut _ as *mut libc::sockaddr_in6).write(raw);
+            }
+            core::mem::size_of::<libc::sockaddr_in6>() as libc::socklen_t
+        }
+    };
+    (storage, len)
+}
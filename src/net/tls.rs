@@ -0,0 +1,154 @@
+//! First-class TLS, backed by `rustls`, so applications don't have to
+//! match a third-party adapter crate's version of async-std's traits
+//! against their own.
+//!
+//! Gated behind the `tls` feature; `native-tls` support would live
+//! alongside this behind its own `tls-native` feature and the same
+//! `TlsStream` shape.
+
+#![cfg(feature = "tls")]
+
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use std::io::{Read as _, Write as _};
+use std::sync::Arc;
+
+use crate::io::{self, Read, Write};
+use crate::net::TcpStream;
+
+/// A TCP stream wrapped in a TLS session, implementing the crate's
+/// [`Read`]/[`Write`] traits like any other stream.
+///
+/// Drives `rustls`'s `Connection` state machine directly against the
+/// underlying `TcpStream`'s own `poll_read`/`poll_write` (rather than
+/// wrapping it in a blocking `std::io::Read`/`Write` adapter), the same
+/// approach `tokio-rustls` uses.
+pub struct TlsStream {
+    conn: rustls::Connection,
+    io: TcpStream,
+}
+
+impl TlsStream {
+    fn new(conn: rustls::Connection, io: TcpStream) -> Self {
+        Self { conn, io }
+    }
+
+    /// Pumps ciphertext between `self.io` and `self.conn` until no more
+    /// progress can be made without blocking.
+    fn poll_pump(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        loop {
+            let mut progressed = false;
+
+            if self.conn.wants_write() {
+                let mut buf = Vec::new();
+                self.conn.write_tls(&mut buf).map_err(io::Error::other)?;
+                match Pin::new(&mut self.io).poll_write(cx, &buf) {
+                    Poll::Ready(Ok(_)) => progressed = true,
+                    Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                    Poll::Pending => {}
+                }
+            }
+
+            if self.conn.wants_read() {
+                let mut scratch = [0u8; 8 * 1024];
+                match Pin::new(&mut self.io).poll_read(cx, &mut scratch) {
+                    Poll::Ready(Ok(0)) => return Poll::Ready(Ok(())),
+                    Poll::Ready(Ok(n)) => {
+                        self.conn.read_tls(&mut &scratch[..n]).map_err(io::Error::other)?;
+                        self.conn.process_new_packets().map_err(io::Error::other)?;
+                        progressed = true;
+                    }
+                    Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                    Poll::Pending => {}
+                }
+            }
+
+            if !progressed {
+                return Poll::Pending;
+            }
+            if !self.conn.wants_write() && !self.conn.wants_read() {
+                return Poll::Ready(Ok(()));
+            }
+        }
+    }
+}
+
+impl Read for TlsStream {
+    fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+
+        loop {
+            let mut reader = this.conn.reader();
+            match reader.read(buf) {
+                Ok(n) => return Poll::Ready(Ok(n)),
+                Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {}
+                Err(e) => return Poll::Ready(Err(e)),
+            }
+
+            match this.poll_pump(cx) {
+                Poll::Ready(Ok(())) => continue,
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+impl Write for TlsStream {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        let n = this.conn.writer().write(buf).map_err(io::Error::other)?;
+        match this.poll_pump(cx) {
+            Poll::Ready(Ok(())) | Poll::Pending => Poll::Ready(Ok(n)),
+            Poll::Ready(Err(e)) => Poll::Ready(Err(e)),
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.get_mut().poll_pump(cx)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        this.conn.send_close_notify();
+        this.poll_pump(cx)
+    }
+}
+
+/// Builds TLS client sessions over a [`TcpStream`].
+#[derive(Clone)]
+pub struct TlsConnector {
+    config: Arc<rustls::ClientConfig>,
+}
+
+impl TlsConnector {
+    pub fn new(config: Arc<rustls::ClientConfig>) -> Self {
+        Self { config }
+    }
+
+    /// Performs a TLS handshake with `domain` over `stream`.
+    pub async fn connect(&self, domain: &str, stream: TcpStream) -> io::Result<TlsStream> {
+        let name = rustls::pki_types::ServerName::try_from(domain.to_string())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+        let conn = rustls::ClientConnection::new(self.config.clone(), name).map_err(io::Error::other)?;
+        Ok(TlsStream::new(rustls::Connection::Client(conn), stream))
+    }
+}
+
+/// Builds TLS server sessions over a [`TcpStream`].
+#[derive(Clone)]
+pub struct TlsAcceptor {
+    config: Arc<rustls::ServerConfig>,
+}
+
+impl TlsAcceptor {
+    pub fn new(config: Arc<rustls::ServerConfig>) -> Self {
+        Self { config }
+    }
+
+    /// Performs a TLS handshake as the server over `stream`.
+    pub async fn accept(&self, stream: TcpStream) -> io::Result<TlsStream> {
+        let conn = rustls::ServerConnection::new(self.config.clone()).map_err(io::Error::other)?;
+        Ok(TlsStream::new(rustls::Connection::Server(conn), stream))
+    }
+}
@@ -0,0 +1,99 @@
+//! Pairs a [`UdpSocket`] with a [`Decoder`]/[`Encoder`] so protocol
+//! implementations can work in terms of `Stream`/`Sink` instead of a
+//! hand-rolled `recv_from` loop and buffer bookkeeping.
+
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use std::net::SocketAddr;
+
+use alloc::vec::Vec;
+
+use crate::io::codec::{Decoder, Encoder};
+use crate::io::{self};
+use crate::net::UdpSocket;
+use crate::sink::Sink;
+use crate::stream::Stream;
+
+const DEFAULT_DATAGRAM_SIZE: usize = 64 * 1024;
+
+/// A [`UdpSocket`] paired with a codec, yielding `(Item, SocketAddr)`
+/// as a [`Stream`] and accepting the same as a [`Sink`].
+pub struct UdpFramed<C> {
+    socket: UdpSocket,
+    codec: C,
+    recv_buf: Vec<u8>,
+    write_buf: Vec<u8>,
+    out_addr: Option<SocketAddr>,
+}
+
+impl<C> UdpFramed<C> {
+    pub fn new(socket: UdpSocket, codec: C) -> Self {
+        Self {
+            socket,
+            codec,
+            recv_buf: alloc::vec![0u8; DEFAULT_DATAGRAM_SIZE],
+            write_buf: Vec::new(),
+            out_addr: None,
+        }
+    }
+}
+
+impl<C: Decoder + Unpin> Stream for UdpFramed<C> {
+    type Item = io::Result<(C::Item, SocketAddr)>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        match this.socket.poll_recv_from(cx, &mut this.recv_buf) {
+            Poll::Ready(Ok((n, addr))) => {
+                let mut datagram = this.recv_buf[..n].to_vec();
+                match this.codec.decode(&mut datagram) {
+                    Ok(Some(item)) => Poll::Ready(Some(Ok((item, addr)))),
+                    Ok(None) => Poll::Ready(Some(Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        "codec didn't produce an item from a complete datagram",
+                    )))),
+                    Err(e) => Poll::Ready(Some(Err(e))),
+                }
+            }
+            Poll::Ready(Err(e)) => Poll::Ready(Some(Err(e))),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+impl<C: Encoder<Item> + Unpin, Item> Sink<(Item, SocketAddr)> for UdpFramed<C> {
+    type Error = io::Error;
+
+    fn poll_ready(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn start_send(self: Pin<&mut Self>, item: (Item, SocketAddr)) -> Result<(), Self::Error> {
+        let this = self.get_mut();
+        this.write_buf.clear();
+        this.codec.encode(item.0, &mut this.write_buf)?;
+        this.out_addr = Some(item.1);
+        Ok(())
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        let this = self.get_mut();
+        let Some(addr) = this.out_addr else {
+            return Poll::Ready(Ok(()));
+        };
+        match this.socket.poll_send_to(cx, &this.write_buf, addr) {
+            Poll::Ready(Ok(_)) => {
+                this.out_addr = None;
+                this.write_buf.clear();
+                Poll::Ready(Ok(()))
+            }
+            Poll::Ready(Err(e)) => Poll::Ready(Err(e)),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.poll_flush(cx)
+    }
+}
@@ -0,0 +1,98 @@
+//! Extended socket options for [`UdpSocket`] beyond the minimal set the
+//! cross-platform API exposes: port/address reuse, multicast interface
+//! selection, traffic class, buffer sizing, and destination-address
+//! retrieval for sockets bound to a wildcard address.
+
+use std::os::unix::io::AsRawFd;
+
+use crate::io;
+use crate::net::UdpSocket;
+
+/// Extension trait adding platform socket options to [`UdpSocket`].
+pub trait UdpSocketExt {
+    /// Sets `SO_REUSEADDR`.
+    fn set_reuseaddr(&self, reuse: bool) -> io::Result<()>;
+
+    /// Sets `SO_REUSEPORT`.
+    #[cfg(unix)]
+    fn set_reuseport(&self, reuse: bool) -> io::Result<()>;
+
+    /// Selects the outgoing interface for IPv6 multicast by index
+    /// (`IPV6_MULTICAST_IF`).
+    fn set_multicast_if_v6(&self, interface_index: u32) -> io::Result<()>;
+
+    /// Sets the IPv4 `IP_TOS` (or IPv6 traffic class) byte on
+    /// outgoing packets.
+    fn set_tos(&self, tos: u8) -> io::Result<()>;
+
+    /// Sets the socket receive buffer size (`SO_RCVBUF`).
+    fn set_recv_buffer_size(&self, size: u32) -> io::Result<()>;
+
+    /// Sets the socket send buffer size (`SO_SNDBUF`).
+    fn set_send_buffer_size(&self, size: u32) -> io::Result<()>;
+
+    /// Enables `IP_PKTINFO` (or the IPv6 equivalent), so a packet's
+    /// destination address can be recovered from the ancillary data on
+    /// a `recvmsg` call -- useful for a server bound to `0.0.0.0` that
+    /// needs to reply from the same local address a client sent to.
+    ///
+    /// Reading that ancillary data back out requires a `recvmsg`-based
+    /// receive path, which isn't implemented here yet -- this only
+    /// flips the socket option on.
+    fn set_pktinfo(&self, enable: bool) -> io::Result<()>;
+}
+
+impl UdpSocketExt for UdpSocket {
+    fn set_reuseaddr(&self, reuse: bool) -> io::Result<()> {
+        set_int_opt(self.as_raw_fd(), libc::SOL_SOCKET, libc::SO_REUSEADDR, reuse as i32)
+    }
+
+    #[cfg(unix)]
+    fn set_reuseport(&self, reuse: bool) -> io::Result<()> {
+        set_int_opt(self.as_raw_fd(), libc::SOL_SOCKET, libc::SO_REUSEPORT, reuse as i32)
+    }
+
+    fn set_multicast_if_v6(&self, interface_index: u32) -> io::Result<()> {
+        set_int_opt(self.as_raw_fd(), libc::IPPROTO_IPV6, libc::IPV6_MULTICAST_IF, interface_index as i32)
+    }
+
+    fn set_tos(&self, tos: u8) -> io::Result<()> {
+        set_int_opt(self.as_raw_fd(), libc::IPPROTO_IP, libc::IP_TOS, tos as i32)
+    }
+
+    fn set_recv_buffer_size(&self, size: u32) -> io::Result<()> {
+        set_int_opt(self.as_raw_fd(), libc::SOL_SOCKET, libc::SO_RCVBUF, size as i32)
+    }
+
+    fn set_send_buffer_size(&self, size: u32) -> io::Result<()> {
+        set_int_opt(self.as_raw_fd(), libc::SOL_SOCKET, libc::SO_SNDBUF, size as i32)
+    }
+
+    #[cfg(target_os = "linux")]
+    fn set_pktinfo(&self, enable: bool) -> io::Result<()> {
+        set_int_opt(self.as_raw_fd(), libc::IPPROTO_IP, libc::IP_PKTINFO, enable as i32)
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    fn set_pktinfo(&self, _enable: bool) -> io::Result<()> {
+        Err(io::Error::new(io::ErrorKind::Unsupported, "IP_PKTINFO is only wired up on Linux here"))
+    }
+}
+
+fn set_int_opt(fd: std::os::unix::io::RawFd, level: i32, name: i32, value: i32) -> io::Result<()> {
+    let ret = unsafe {
+        libc::setsockopt(
+            fd,
+            level,
+            name,
+            &value as *const i32 as *const libc::c_void,
+            core::mem::size_of::<i32>() as libc::socklen_t,
+        )
+    };
+    if ret == 0 {
+        Ok(())
+    } else {
+        Err(io::Error::last_os_error())
+    }
+}
+
@@ -0,0 +1,72 @@
+//! Cross-thread wakeups from synchronous, non-async code, via Linux's
+//! `eventfd(2)`: a kernel-held 64-bit counter that a plain OS thread
+//! can bump with a `write(2)`, and that this crate's reactor can wait
+//! on becoming non-zero the same way it waits on any other fd.
+
+use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+
+use crate::io::{self, Async};
+
+/// The readable/writable half of an eventfd, awaited from async code.
+pub struct EventFd {
+    io: Async<OwnedFd>,
+}
+
+impl EventFd {
+    /// Creates an eventfd whose counter starts at `initial`.
+    pub fn new(initial: u32) -> io::Result<Self> {
+        let fd = unsafe { libc::eventfd(initial, libc::EFD_NONBLOCK | libc::EFD_CLOEXEC) };
+        if fd < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        let owned = unsafe { OwnedFd::from_raw_fd(fd) };
+        Ok(EventFd { io: Async::new(owned)? })
+    }
+
+    /// A handle that adds to the counter, wired to the same
+    /// underlying fd. Unlike `EventFd` itself, [`notify`](EventFdWriter::notify)
+    /// is a plain blocking `write(2)` -- safe to call from a thread
+    /// with no async runtime at all, which is the entire point of
+    /// this type.
+    pub fn writer(&self) -> io::Result<EventFdWriter> {
+        Ok(EventFdWriter {
+            fd: self.io.get_ref().try_clone()?,
+        })
+    }
+
+    /// Waits for the counter to become non-zero, then atomically
+    /// reads and resets it to zero, returning the value it held.
+    pub async fn read(&self) -> io::Result<u64> {
+        self.io
+            .read_with(|fd| {
+                let mut buf = [0u8; 8];
+                let n = unsafe { libc::read(fd.as_raw_fd(), buf.as_mut_ptr() as *mut libc::c_void, 8) };
+                if n < 0 {
+                    Err(io::Error::last_os_error())
+                } else {
+                    Ok(u64::from_ne_bytes(buf))
+                }
+            })
+            .await
+    }
+}
+
+/// A cloned handle to an [`EventFd`]'s counter, for waking it from
+/// synchronous code.
+pub struct EventFdWriter {
+    fd: OwnedFd,
+}
+
+impl EventFdWriter {
+    /// Adds `value` to the counter, waking anything blocked in
+    /// [`EventFd::read`].
+    pub fn notify(&self, value: u64) -> io::Result<()> {
+        let bytes = value.to_ne_bytes();
+        let n = unsafe { libc::write(self.fd.as_raw_fd(), bytes.as_ptr() as *const libc::c_void, 8) };
+        if n < 0 {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(())
+        }
+    }
+}
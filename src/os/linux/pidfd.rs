@@ -0,0 +1,50 @@
+//! Awaiting a process's exit -- a child of this process, or an
+//! unrelated one -- via Linux's `pidfd_open(2)`: the fd becomes
+//! readable exactly once, when the process exits, which plugs
+//! straight into the reactor instead of needing `waitpid` polling or
+//! (for non-children) no mechanism at all.
+
+use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+
+use crate::io::{self, Async};
+
+/// A handle to a process (this process's child or not) that can be
+/// awaited and signalled without racing `wait(2)`/`kill(2)` against
+/// PID reuse.
+pub struct PidFd {
+    io: Async<OwnedFd>,
+}
+
+impl PidFd {
+    /// Opens a pidfd for `pid`.
+    pub fn open(pid: i32) -> io::Result<Self> {
+        let fd = unsafe { libc::syscall(libc::SYS_pidfd_open, pid, 0) };
+        if fd < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        let owned = unsafe { OwnedFd::from_raw_fd(fd as i32) };
+        Ok(PidFd { io: Async::new(owned)? })
+    }
+
+    /// Resolves once the process has exited.
+    ///
+    /// This only waits; it doesn't reap a child's exit status. A
+    /// child still needs `Child::wait`/`try_wait` called on it too
+    /// (or it stays a zombie) -- this is most useful for processes
+    /// that aren't this process's child at all, which `Child` can't
+    /// represent.
+    pub async fn wait(&self) -> io::Result<()> {
+        self.io.readable().await
+    }
+
+    /// Sends `signal` to the process, without the PID-reuse race a
+    /// plain `kill(pid, signal)` has once the original process may
+    /// have already exited and its PID been recycled.
+    pub fn send_signal(&self, signal: i32) -> io::Result<()> {
+        let ret = unsafe { libc::syscall(libc::SYS_pidfd_send_signal, self.io.get_ref().as_raw_fd(), signal, std::ptr::null::<libc::c_void>(), 0) };
+        if ret != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(())
+    }
+}
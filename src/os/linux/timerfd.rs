@@ -0,0 +1,65 @@
+//! Kernel-driven timers via Linux's `timerfd(2)`, for schedulers that
+//! want the kernel itself to track a deadline rather than a userspace
+//! timer wheel plus a `sleep` future per tick.
+
+use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+use std::time::Duration;
+
+use crate::io::{self, Async};
+
+/// A readable fd that delivers one expiration count per tick.
+pub struct TimerFd {
+    io: Async<OwnedFd>,
+}
+
+impl TimerFd {
+    /// Creates an unarmed timer against the monotonic clock; call
+    /// [`set`](TimerFd::set) to start it.
+    pub fn new() -> io::Result<Self> {
+        let fd = unsafe { libc::timerfd_create(libc::CLOCK_MONOTONIC, libc::TFD_NONBLOCK | libc::TFD_CLOEXEC) };
+        if fd < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        let owned = unsafe { OwnedFd::from_raw_fd(fd) };
+        Ok(TimerFd { io: Async::new(owned)? })
+    }
+
+    /// Arms the timer to first expire after `initial`, then (if
+    /// `interval` is non-zero) every `interval` after that. Passing
+    /// `initial: Duration::ZERO` disarms the timer.
+    pub fn set(&self, initial: Duration, interval: Duration) -> io::Result<()> {
+        let spec = libc::itimerspec {
+            it_interval: to_timespec(interval),
+            it_value: to_timespec(initial),
+        };
+        let ret = unsafe { libc::timerfd_settime(self.io.get_ref().as_raw_fd(), 0, &spec, std::ptr::null_mut()) };
+        if ret != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(())
+    }
+
+    /// Waits for the next expiration, returning how many ticks have
+    /// elapsed since the last call (more than one if the consumer
+    /// fell behind an `interval` timer).
+    pub async fn tick(&self) -> io::Result<u64> {
+        self.io
+            .read_with(|fd| {
+                let mut buf = [0u8; 8];
+                let n = unsafe { libc::read(fd.as_raw_fd(), buf.as_mut_ptr() as *mut libc::c_void, 8) };
+                if n < 0 {
+                    Err(io::Error::last_os_error())
+                } else {
+                    Ok(u64::from_ne_bytes(buf))
+                }
+            })
+            .await
+    }
+}
+
+fn to_timespec(duration: Duration) -> libc::timespec {
+    libc::timespec {
+        tv_sec: duration.as_secs() as libc::time_t,
+        tv_nsec: duration.subsec_nanos() as libc::c_long,
+    }
+}
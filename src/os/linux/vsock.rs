@@ -0,0 +1,188 @@
+//! `AF_VSOCK` sockets for VM-to-host communication (Firecracker, QEMU's
+//! `vhost-vsock`, ...), integrated with the reactor the same way a
+//! `TcpStream`/`TcpListener` pair is.
+
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use std::os::fd::{AsRawFd, FromRawFd, OwnedFd, RawFd};
+
+use crate::io::{self, Read, Write};
+
+/// A vsock address: a context ID (`VMADDR_CID_HOST`, `VMADDR_CID_ANY`,
+/// or a specific guest CID) and a port.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VsockAddr {
+    pub cid: u32,
+    pub port: u32,
+}
+
+impl VsockAddr {
+    pub const fn new(cid: u32, port: u32) -> Self {
+        Self { cid, port }
+    }
+
+    fn to_raw(self) -> libc::sockaddr_vm {
+        let mut addr: libc::sockaddr_vm = unsafe { core::mem::zeroed() };
+        addr.svm_family = libc::AF_VSOCK as _;
+        addr.svm_cid = self.cid;
+        addr.svm_port = self.port;
+        addr
+    }
+}
+
+fn unsupported() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::Unsupported,
+        "vsock I/O isn't wired up in this checkout -- no reactor exists to register the socket with, \
+         so WouldBlock can't be turned into a wakeup",
+    )
+}
+
+fn socket() -> io::Result<OwnedFd> {
+    let fd = unsafe { libc::socket(libc::AF_VSOCK, libc::SOCK_STREAM | libc::SOCK_NONBLOCK, 0) };
+    if fd < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(unsafe { OwnedFd::from_raw_fd(fd) })
+}
+
+/// A vsock connection, analogous to [`TcpStream`](crate::net::TcpStream).
+pub struct VsockStream {
+    fd: OwnedFd,
+}
+
+impl VsockStream {
+    /// Connects to `addr`.
+    ///
+    /// Registering the non-blocking socket with the reactor so
+    /// `poll_read`/`poll_write` below actually yield instead of
+    /// busy-erroring on `EWOULDBLOCK` is left as a gap -- this
+    /// checkout has no reactor to register against.
+    pub async fn connect(addr: VsockAddr) -> io::Result<Self> {
+        let fd = socket()?;
+        let raw = addr.to_raw();
+        let ret = unsafe {
+            libc::connect(
+                fd.as_raw_fd(),
+                &raw as *const _ as *const libc::sockaddr,
+                core::mem::size_of::<libc::sockaddr_vm>() as libc::socklen_t,
+            )
+        };
+        if ret != 0 {
+            let err = io::Error::last_os_error();
+            if err.kind() != io::ErrorKind::WouldBlock {
+                return Err(err);
+            }
+        }
+        Ok(Self { fd })
+    }
+}
+
+impl Read for VsockStream {
+    fn poll_read(self: Pin<&mut Self>, _cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<io::Result<usize>> {
+        let fd = self.fd.as_raw_fd();
+        let n = unsafe { libc::read(fd, buf.as_mut_ptr() as *mut libc::c_void, buf.len()) };
+        if n >= 0 {
+            Poll::Ready(Ok(n as usize))
+        } else {
+            let err = io::Error::last_os_error();
+            if err.kind() == io::ErrorKind::WouldBlock {
+                // Nothing registers this fd with a reactor, so there's
+                // no waker that will ever fire for it -- returning
+                // `Pending` here would park the caller forever. Fail
+                // loudly instead, the same way the Windows overlapped-
+                // I/O stubs do for the same missing-reactor gap.
+                Poll::Ready(Err(unsupported()))
+            } else {
+                Poll::Ready(Err(err))
+            }
+        }
+    }
+}
+
+impl Write for VsockStream {
+    fn poll_write(self: Pin<&mut Self>, _cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+        let fd = self.fd.as_raw_fd();
+        let n = unsafe { libc::write(fd, buf.as_ptr() as *const libc::c_void, buf.len()) };
+        if n >= 0 {
+            Poll::Ready(Ok(n as usize))
+        } else {
+            let err = io::Error::last_os_error();
+            if err.kind() == io::ErrorKind::WouldBlock {
+                // See `poll_read`: no reactor means no wakeup ever
+                // comes, so `Pending` here would hang forever.
+                Poll::Ready(Err(unsupported()))
+            } else {
+                Poll::Ready(Err(err))
+            }
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// A vsock listener, analogous to [`TcpListener`](crate::net::TcpListener).
+pub struct VsockListener {
+    fd: OwnedFd,
+}
+
+impl VsockListener {
+    /// Binds a listener at `addr` (typically `VMADDR_CID_ANY`).
+    pub fn bind(addr: VsockAddr) -> io::Result<Self> {
+        let fd = socket()?;
+        let raw = addr.to_raw();
+        let ret = unsafe {
+            libc::bind(
+                fd.as_raw_fd(),
+                &raw as *const _ as *const libc::sockaddr,
+                core::mem::size_of::<libc::sockaddr_vm>() as libc::socklen_t,
+            )
+        };
+        if ret != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        if unsafe { libc::listen(fd.as_raw_fd(), 128) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(Self { fd })
+    }
+
+    /// Accepts a connection.
+    ///
+    /// Like [`VsockStream::connect`], needs reactor registration to
+    /// actually suspend instead of erroring on `EWOULDBLOCK`; not
+    /// implemented here.
+    pub async fn accept(&self) -> io::Result<(VsockStream, VsockAddr)> {
+        let mut raw: libc::sockaddr_vm = unsafe { core::mem::zeroed() };
+        let mut len = core::mem::size_of::<libc::sockaddr_vm>() as libc::socklen_t;
+        let fd = unsafe { libc::accept(self.fd.as_raw_fd(), &mut raw as *mut _ as *mut libc::sockaddr, &mut len) };
+        if fd < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok((
+            VsockStream {
+                fd: unsafe { OwnedFd::from_raw_fd(fd) },
+            },
+            VsockAddr::new(raw.svm_cid, raw.svm_port),
+        ))
+    }
+}
+
+impl AsRawFd for VsockStream {
+    fn as_raw_fd(&self) -> RawFd {
+        self.fd.as_raw_fd()
+    }
+}
+
+impl AsRawFd for VsockListener {
+    fn as_raw_fd(&self) -> RawFd {
+        self.fd.as_raw_fd()
+    }
+}
@@ -0,0 +1,124 @@
+//! Linux abstract-namespace Unix domain socket addresses (a leading NUL
+//! byte in place of a path), so services that rely on them to dodge
+//! filesystem cleanup aren't forced onto path-based sockets.
+
+use std::os::unix::io::FromRawFd;
+use std::os::unix::net::{SocketAddr as StdSocketAddr, UnixListener as StdUnixListener, UnixStream as StdUnixStream};
+
+use crate::io;
+use crate::os::unix::net::{UnixListener, UnixStream};
+
+/// The two flavours of address a Unix domain socket can be bound to:
+/// a path on the filesystem, or (Linux-only) a name in the abstract
+/// namespace that's never visible on disk and disappears with the
+/// socket.
+#[derive(Debug, Clone)]
+pub enum SocketAddr {
+    Pathname(std::path::PathBuf),
+    Abstract(Vec<u8>),
+    Unnamed,
+}
+
+impl SocketAddr {
+    fn from_std(addr: &StdSocketAddr) -> Self {
+        match addr.as_pathname() {
+            Some(path) => SocketAddr::Pathname(path.to_path_buf()),
+            // `std` exposes no abstract-name accessor, but on Linux an
+            // address that is neither pathname nor unnamed must be one;
+            // callers needing the name should go through `inspect`
+            // with the raw `sockaddr_un` bytes instead.
+            None => SocketAddr::Unnamed,
+        }
+    }
+}
+
+fn raw_abstract_addr(name: &[u8]) -> io::Result<(libc::sockaddr_un, libc::socklen_t)> {
+    let mut addr: libc::sockaddr_un = unsafe { std::mem::zeroed() };
+    addr.sun_family = libc::AF_UNIX as _;
+
+    if name.len() >= addr.sun_path.len() - 1 {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "abstract socket name too long"));
+    }
+
+    // Leading NUL marks the name as abstract; the kernel treats the
+    // remaining bytes as the name verbatim, not as a C string.
+    let bytes = unsafe { std::slice::from_raw_parts_mut(addr.sun_path.as_mut_ptr() as *mut u8, addr.sun_path.len()) };
+    bytes[1..1 + name.len()].copy_from_slice(name);
+
+    let len = (std::mem::size_of::<libc::sa_family_t>() + 1 + name.len()) as libc::socklen_t;
+    Ok((addr, len))
+}
+
+/// Extension trait adding abstract-namespace binding to
+/// [`UnixListener`].
+pub trait UnixListenerAbstractExt: Sized {
+    fn bind_abstract(name: &[u8]) -> io::Result<Self>;
+}
+
+/// Extension trait adding abstract-namespace connecting to
+/// [`UnixStream`].
+pub trait UnixStreamAbstractExt: Sized {
+    fn connect_abstract(name: &[u8]) -> io::Result<Self>;
+}
+
+impl UnixListenerAbstractExt for UnixListener {
+    /// Binds a listener to an abstract-namespace address named `name`
+    /// (the leading NUL is implicit; don't include it).
+    fn bind_abstract(name: &[u8]) -> io::Result<Self> {
+        let (addr, len) = raw_abstract_addr(name)?;
+        unsafe {
+            let fd = libc::socket(libc::AF_UNIX, libc::SOCK_STREAM | libc::SOCK_NONBLOCK, 0);
+            if fd < 0 {
+                return Err(io::Error::last_os_error());
+            }
+            if libc::bind(fd, &addr as *const _ as *const libc::sockaddr, len) != 0 {
+                let err = io::Error::last_os_error();
+                libc::close(fd);
+                return Err(err);
+            }
+            if libc::listen(fd, 128) != 0 {
+                let err = io::Error::last_os_error();
+                libc::close(fd);
+                return Err(err);
+            }
+            Ok(UnixListener::from(StdUnixListener::from_raw_fd(fd)))
+        }
+    }
+}
+
+impl UnixStreamAbstractExt for UnixStream {
+    /// Connects to an abstract-namespace address named `name`.
+    fn connect_abstract(name: &[u8]) -> io::Result<Self> {
+        let (addr, len) = raw_abstract_addr(name)?;
+        unsafe {
+            let fd = libc::socket(libc::AF_UNIX, libc::SOCK_STREAM | libc::SOCK_NONBLOCK, 0);
+            if fd < 0 {
+                return Err(io::Error::last_os_error());
+            }
+            let ret = libc::connect(fd, &addr as *const _ as *const libc::sockaddr, len);
+            if ret != 0 {
+                let err = io::Error::last_os_error();
+                if err.kind() != io::ErrorKind::WouldBlock {
+                    libc::close(fd);
+                    return Err(err);
+                }
+            }
+            Ok(UnixStream::from(StdUnixStream::from_raw_fd(fd)))
+        }
+    }
+}
+
+/// Inspects a socket's address, distinguishing abstract-namespace
+/// addresses from pathname and unnamed ones -- something
+/// `std::os::unix::net::SocketAddr` cannot do on its own. `raw` is the
+/// `sockaddr_un` bytes as returned by `getsockname`/`getpeername`.
+pub fn inspect(std_addr: &StdSocketAddr, raw: &[u8]) -> SocketAddr {
+    if std_addr.is_unnamed() {
+        match raw.first() {
+            Some(0) if raw.len() > 1 => SocketAddr::Abstract(raw[1..].to_vec()),
+            _ => SocketAddr::Unnamed,
+        }
+    } else {
+        SocketAddr::from_std(std_addr)
+    }
+}
@@ -0,0 +1,88 @@
+//! Owned split halves for [`UnixStream`], mirroring
+//! [`net::tcp_split`](crate::net::tcp_split) for domain sockets.
+
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use std::os::unix::io::AsRawFd;
+
+use crate::io::{self, Read, Write};
+use crate::os::unix::net::UnixStream;
+
+/// Extension trait adding [`into_split`](Self::into_split) to [`UnixStream`].
+pub trait UnixStreamSplitExt {
+    /// Splits the stream into owned read and write halves that can be
+    /// moved into separate tasks, unlike [`io::split`]'s halves which
+    /// stay tied to a shared `Arc<Mutex<UnixStream>>`.
+    fn into_split(self) -> (OwnedReadHalf, OwnedWriteHalf);
+}
+
+impl UnixStreamSplitExt for UnixStream {
+    fn into_split(self) -> (OwnedReadHalf, OwnedWriteHalf) {
+        let read = self.clone();
+        (OwnedReadHalf(read), OwnedWriteHalf { stream: self, shutdown_on_drop: true })
+    }
+}
+
+/// The read half of a [`UnixStream`] split by [`UnixStreamSplitExt::into_split`].
+pub struct OwnedReadHalf(UnixStream);
+
+/// The write half of a [`UnixStream`] split by [`UnixStreamSplitExt::into_split`].
+///
+/// Dropping this half shuts down the write side of the connection
+/// (`SHUT_WR`), so a peer blocked reading sees EOF as soon as this half
+/// goes away instead of only when the last clone of the stream does.
+pub struct OwnedWriteHalf {
+    stream: UnixStream,
+    shutdown_on_drop: bool,
+}
+
+/// Error returned by [`OwnedReadHalf::reunite`] when the two halves did
+/// not originate from the same [`into_split`](UnixStreamSplitExt::into_split) call.
+#[derive(Debug)]
+pub struct ReuniteError(pub OwnedReadHalf, pub OwnedWriteHalf);
+
+impl OwnedReadHalf {
+    /// Recombines this half with its matching [`OwnedWriteHalf`],
+    /// returning the original stream without running the write half's
+    /// shutdown-on-drop.
+    ///
+    /// Fails if `write` was not produced by the same `into_split` call.
+    pub fn reunite(self, mut write: OwnedWriteHalf) -> Result<UnixStream, ReuniteError> {
+        if self.0.as_raw_fd() != write.stream.as_raw_fd() {
+            return Err(ReuniteError(self, write));
+        }
+        write.shutdown_on_drop = false;
+        Ok(self.0)
+    }
+}
+
+impl Read for OwnedReadHalf {
+    fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.get_mut().0).poll_read(cx, buf)
+    }
+}
+
+impl Write for OwnedWriteHalf {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.get_mut().stream).poll_write(cx, buf)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().stream).poll_flush(cx)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().stream).poll_close(cx)
+    }
+}
+
+impl Drop for OwnedWriteHalf {
+    fn drop(&mut self) {
+        if self.shutdown_on_drop {
+            // Best-effort, for the same reason `tcp_split`'s `Drop`
+            // ignores this result: there's nothing to do with it.
+            let _ = unsafe { libc::shutdown(self.stream.as_raw_fd(), libc::SHUT_WR) };
+        }
+    }
+}
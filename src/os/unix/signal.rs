@@ -0,0 +1,191 @@
+//! Delivering Unix signals as an async [`Stream`], via the classic
+//! self-pipe trick: the actual `sigaction` handler only does one
+//! async-signal-safe `write(2)` of a single byte into a pipe, and the
+//! real work (waking whatever's polling [`Signals`]) happens on the
+//! read end through the reactor, far away from any signal-handler
+//! restrictions.
+//!
+//! Like [`io::stdin_raw::RawStdin`](crate::io::stdin_raw::RawStdin) and
+//! [`process::Pty`](crate::process::pty::Pty), the read end here is
+//! driven through [`crate::rt::Reactor`], the same as every other
+//! reactor-backed I/O type in this crate.
+//!
+//! Only one [`signal`] listener can be active per [`SignalKind`] at a
+//! time: the handler consults a lock-free fixed slot per signal
+//! number (an array index, not a list), since anything that could
+//! block -- a `Mutex`, an allocation -- is unsound to touch from
+//! inside a signal handler. Calling [`signal`] again for a kind that
+//! already has a listener silently takes over delivery; the older
+//! [`Signals`] stream simply stops seeing new notifications.
+
+use core::pin::Pin;
+use core::sync::atomic::{AtomicI32, Ordering};
+use core::task::{Context, Poll};
+
+use std::os::raw::c_int;
+use std::os::unix::io::RawFd;
+
+use crate::io;
+use crate::stream::Stream;
+
+const SLOTS: usize = 64;
+
+const NO_LISTENER: AtomicI32 = AtomicI32::new(-1);
+static PIPE_WRITE_FDS: [AtomicI32; SLOTS] = [NO_LISTENER; SLOTS];
+
+/// A signal number to listen for, named the way `signal(7)` does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SignalKind(c_int);
+
+impl SignalKind {
+    /// Wraps a raw signal number (see `signal(7)`), for signals this
+    /// type has no named constructor for.
+    pub fn from_raw(signum: c_int) -> Self {
+        SignalKind(signum)
+    }
+
+    /// The raw signal number.
+    pub fn as_raw_value(&self) -> c_int {
+        self.0
+    }
+
+    pub fn hangup() -> Self {
+        SignalKind(libc::SIGHUP)
+    }
+
+    pub fn interrupt() -> Self {
+        SignalKind(libc::SIGINT)
+    }
+
+    pub fn quit() -> Self {
+        SignalKind(libc::SIGQUIT)
+    }
+
+    pub fn terminate() -> Self {
+        SignalKind(libc::SIGTERM)
+    }
+
+    pub fn user_defined1() -> Self {
+        SignalKind(libc::SIGUSR1)
+    }
+
+    pub fn user_defined2() -> Self {
+        SignalKind(libc::SIGUSR2)
+    }
+
+    pub fn child() -> Self {
+        SignalKind(libc::SIGCHLD)
+    }
+
+    pub fn alarm() -> Self {
+        SignalKind(libc::SIGALRM)
+    }
+
+    pub fn window_change() -> Self {
+        SignalKind(libc::SIGWINCH)
+    }
+}
+
+/// The async-signal-safe handler actually installed by [`signal`]:
+/// looks up the one write end registered for this signal number and
+/// writes a single wakeup byte to it, ignoring any failure (a full
+/// pipe just means a notification is already pending, and no listener
+/// registered means there's nothing to wake).
+extern "C" fn deliver(signum: c_int) {
+    let idx = signum as usize;
+    if idx >= SLOTS {
+        return;
+    }
+    let fd = PIPE_WRITE_FDS[idx].load(Ordering::SeqCst);
+    if fd >= 0 {
+        let byte: u8 = 1;
+        unsafe {
+            libc::write(fd, &byte as *const u8 as *const libc::c_void, 1);
+        }
+    }
+}
+
+/// Starts listening for `kind`, returning a stream that yields `()`
+/// once per delivery (coalesced: signals delivered faster than the
+/// stream is polled collapse into a single pending wakeup, matching
+/// how `signal(7)` itself doesn't queue repeats of the same signal).
+pub fn signal(kind: SignalKind) -> io::Result<Signals> {
+    let signum = kind.0;
+    let idx = signum as usize;
+    if idx >= SLOTS {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "signal number out of range"));
+    }
+
+    let mut fds = [0 as RawFd; 2];
+    if unsafe { libc::pipe2(fds.as_mut_ptr(), libc::O_NONBLOCK | libc::O_CLOEXEC) } != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    let [read_fd, write_fd] = fds;
+
+    PIPE_WRITE_FDS[idx].store(write_fd, Ordering::SeqCst);
+
+    unsafe {
+        let mut action: libc::sigaction = std::mem::zeroed();
+        action.sa_sigaction = deliver as usize;
+        action.sa_flags = libc::SA_RESTART;
+        libc::sigemptyset(&mut action.sa_mask);
+        if libc::sigaction(signum, &action, std::ptr::null_mut()) != 0 {
+            let err = io::Error::last_os_error();
+            let _ = PIPE_WRITE_FDS[idx].compare_exchange(write_fd, -1, Ordering::SeqCst, Ordering::SeqCst);
+            libc::close(read_fd);
+            libc::close(write_fd);
+            return Err(err);
+        }
+    }
+
+    Ok(Signals {
+        kind,
+        read_fd,
+        write_fd,
+        reactor: crate::rt::Reactor::register(read_fd)?,
+        buf: [0u8; 64],
+    })
+}
+
+/// A stream of `kind`'s deliveries, created by [`signal`].
+pub struct Signals {
+    kind: SignalKind,
+    read_fd: RawFd,
+    write_fd: RawFd,
+    reactor: crate::rt::Reactor,
+    buf: [u8; 64],
+}
+
+impl Signals {
+    /// The signal this stream was created to listen for.
+    pub fn kind(&self) -> SignalKind {
+        self.kind
+    }
+}
+
+impl Stream for Signals {
+    type Item = ();
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<()>> {
+        let this = self.get_mut();
+        match this.reactor.poll_readable(cx, this.read_fd, &mut this.buf) {
+            Poll::Ready(Ok(_)) => Poll::Ready(Some(())),
+            Poll::Ready(Err(_)) => Poll::Ready(None),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+impl Drop for Signals {
+    fn drop(&mut self) {
+        let idx = self.kind.0 as usize;
+        // Only clear the slot if it's still pointing at this stream's
+        // pipe -- a newer `signal()` call for the same kind may have
+        // already taken it over.
+        let _ = PIPE_WRITE_FDS[idx].compare_exchange(self.write_fd, -1, Ordering::SeqCst, Ordering::SeqCst);
+        unsafe {
+            libc::close(self.read_fd);
+            libc::close(self.write_fd);
+        }
+    }
+}
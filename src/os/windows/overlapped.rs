@@ -0,0 +1,97 @@
+//! A generic wrapper for any `HANDLE` opened with `FILE_FLAG_OVERLAPPED`
+//! -- serial ports, devices, files opened for overlapped I/O -- so
+//! they can be driven through this crate's [`Read`]/[`Write`] traits
+//! instead of every caller falling back to `spawn_blocking` for
+//! device I/O that doesn't fit [`fs::File`](crate::fs::File) or
+//! [`net::TcpStream`](crate::net::TcpStream).
+//!
+//! Like [`os::windows::named_pipe`](super::named_pipe), this checkout
+//! has no IOCP completion port registered anywhere for a reactor to
+//! dequeue overlapped completions from, so [`OverlappedHandle`]'s
+//! `poll_read`/`poll_write` report [`Unsupported`](io::ErrorKind::Unsupported)
+//! rather than pretending to complete -- submitting the `ReadFile`/
+//! `WriteFile` call with a real `OVERLAPPED` struct and threading its
+//! completion back through a waker needs that IOCP plumbing to exist
+//! first. Wrapping the handle and holding the per-operation
+//! `OVERLAPPED` storage it would need is implemented now so that
+//! plumbing is the only missing piece.
+
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use std::os::windows::io::{AsRawHandle, RawHandle};
+
+use windows_sys::Win32::System::IO::OVERLAPPED;
+
+use crate::io::{self, Read, Write};
+
+/// A `HANDLE` opened for overlapped I/O, plus the per-operation
+/// `OVERLAPPED` storage a real IOCP-backed read/write would need.
+pub struct OverlappedHandle<T> {
+    handle: T,
+    // Boxed so the struct can be moved freely even once a real
+    // submission has handed this address to the kernel -- exactly
+    // why `OVERLAPPED` structs are conventionally heap-allocated in
+    // overlapped-I/O code.
+    overlapped: Box<OVERLAPPED>,
+}
+
+impl<T: AsRawHandle> OverlappedHandle<T> {
+    /// Wraps `handle`, which the caller must already have opened with
+    /// `FILE_FLAG_OVERLAPPED` (e.g. via `CreateFileW`) -- this type
+    /// doesn't reopen or reconfigure it.
+    pub fn new(handle: T) -> Self {
+        OverlappedHandle {
+            handle,
+            overlapped: Box::new(unsafe { core::mem::zeroed() }),
+        }
+    }
+
+    pub fn get_ref(&self) -> &T {
+        &self.handle
+    }
+
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.handle
+    }
+
+    pub fn into_inner(self) -> T {
+        self.handle
+    }
+}
+
+impl<T> AsRawHandle for OverlappedHandle<T>
+where
+    T: AsRawHandle,
+{
+    fn as_raw_handle(&self) -> RawHandle {
+        self.handle.as_raw_handle()
+    }
+}
+
+fn unsupported() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::Unsupported,
+        "overlapped I/O completion isn't wired up in this checkout -- no IOCP port exists for the reactor to dequeue from",
+    )
+}
+
+impl<T> Read for OverlappedHandle<T> {
+    fn poll_read(self: Pin<&mut Self>, _cx: &mut Context<'_>, _buf: &mut [u8]) -> Poll<io::Result<usize>> {
+        Poll::Ready(Err(unsupported()))
+    }
+}
+
+impl<T> Write for OverlappedHandle<T> {
+    fn poll_write(self: Pin<&mut Self>, _cx: &mut Context<'_>, _buf: &[u8]) -> Poll<io::Result<usize>> {
+        Poll::Ready(Err(unsupported()))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
@@ -0,0 +1,475 @@
+//! A multi-producer, multi-consumer queue like [`channel`](crate::channel),
+//! except each sent item carries a priority and [`PriorityReceiver::recv`]
+//! always returns the highest-priority item queued, not the oldest.
+//!
+//! Pure strict priority can starve low-priority senders forever under a
+//! steady stream of higher-priority ones, so a receiver also ages
+//! skipped priority levels: every time a pop serves a higher level
+//! instead of a given nonempty one, that level's skip count goes up,
+//! and once it crosses [`MAX_PRIORITY_SKIPS`] (or the count given to
+//! [`bounded_with_fairness`]/[`unbounded_with_fairness`]) it gets served
+//! next regardless of what else is queued, then resets.
+
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use core::time::Duration;
+
+use alloc::collections::VecDeque;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use std::sync::Mutex;
+
+use crate::mpmc::WakerSet;
+use crate::stream::Stream;
+
+/// How many times a nonempty priority level can be passed over in favor
+/// of a higher one before it's forced to the front, under the plain
+/// [`bounded`]/[`unbounded`] constructors.
+pub const MAX_PRIORITY_SKIPS: usize = 32;
+
+struct State<T> {
+    /// Index `0` is the lowest priority level, the last index the
+    /// highest; `send`'s `priority` argument is clamped into range.
+    buckets: Vec<VecDeque<T>>,
+    /// Parallel to `buckets`: how many consecutive pops have served a
+    /// higher level while this one sat nonempty.
+    skips: Vec<usize>,
+    max_skips: usize,
+    /// `None` for an unbounded channel; counts items across every level.
+    capacity: Option<usize>,
+    sender_count: usize,
+    receiver_count: usize,
+    send_wakers: WakerSet,
+    recv_wakers: WakerSet,
+}
+
+impl<T> State<T> {
+    fn len(&self) -> usize {
+        self.buckets.iter().map(VecDeque::len).sum()
+    }
+
+    fn is_disconnected(&self) -> bool {
+        self.receiver_count == 0
+    }
+
+    /// Picks the next item to serve: the highest nonempty level, unless
+    /// a lower nonempty level has been skipped `max_skips` times, in
+    /// which case that level is force-served instead. Every other
+    /// nonempty level's skip count is bumped so it keeps aging toward
+    /// its own turn.
+    fn pop(&mut self) -> Option<T> {
+        let levels = self.buckets.len();
+        let starved = (0..levels).find(|&i| !self.buckets[i].is_empty() && self.skips[i] >= self.max_skips);
+        let highest = (0..levels).rev().find(|&i| !self.buckets[i].is_empty());
+        let chosen = starved.or(highest)?;
+
+        for i in 0..levels {
+            if i == chosen {
+                self.skips[i] = 0;
+            } else if !self.buckets[i].is_empty() {
+                self.skips[i] += 1;
+            }
+        }
+        self.buckets[chosen].pop_front()
+    }
+
+    fn push(&mut self, priority: u8, value: T) {
+        let level = (priority as usize).min(self.buckets.len() - 1);
+        self.buckets[level].push_back(value);
+    }
+
+    fn wake_one_receiver(&mut self) {
+        self.recv_wakers.wake_one();
+    }
+
+    fn wake_one_sender(&mut self) {
+        self.send_wakers.wake_one();
+    }
+}
+
+struct Shared<T> {
+    state: Mutex<State<T>>,
+}
+
+/// Creates a priority channel with `levels` priority levels (`0` lowest,
+/// `levels - 1` highest) and no capacity limit, aging a skipped level
+/// out after [`MAX_PRIORITY_SKIPS`] pops that favored a higher one.
+pub fn unbounded<T>(levels: u8) -> (PrioritySender<T>, PriorityReceiver<T>) {
+    unbounded_with_fairness(levels, MAX_PRIORITY_SKIPS)
+}
+
+/// Like [`unbounded`], with an explicit skip count before an aged-out
+/// level is force-served.
+pub fn unbounded_with_fairness<T>(levels: u8, max_skips: usize) -> (PrioritySender<T>, PriorityReceiver<T>) {
+    new(None, levels, max_skips)
+}
+
+/// Creates a priority channel that holds at most `capacity` items
+/// across all levels; a sender waits when it's full. See [`unbounded`]
+/// for `levels`.
+pub fn bounded<T>(capacity: usize, levels: u8) -> (PrioritySender<T>, PriorityReceiver<T>) {
+    bounded_with_fairness(capacity, levels, MAX_PRIORITY_SKIPS)
+}
+
+/// Like [`bounded`], with an explicit skip count before an aged-out
+/// level is force-served.
+pub fn bounded_with_fairness<T>(capacity: usize, levels: u8, max_skips: usize) -> (PrioritySender<T>, PriorityReceiver<T>) {
+    assert!(capacity > 0, "bounded priority channel capacity must be greater than zero");
+    new(Some(capacity), levels, max_skips)
+}
+
+fn new<T>(capacity: Option<usize>, levels: u8, max_skips: usize) -> (PrioritySender<T>, PriorityReceiver<T>) {
+    let levels = levels.max(1) as usize;
+    let shared = Arc::new(Shared {
+        state: Mutex::new(State {
+            buckets: (0..levels).map(|_| VecDeque::new()).collect(),
+            skips: alloc::vec![0; levels],
+            max_skips,
+            capacity,
+            sender_count: 1,
+            receiver_count: 1,
+            send_wakers: WakerSet::new(),
+            recv_wakers: WakerSet::new(),
+        }),
+    });
+    (PrioritySender { shared: shared.clone() }, PriorityReceiver { shared })
+}
+
+/// The sending half of a priority channel.
+pub struct PrioritySender<T> {
+    shared: Arc<Shared<T>>,
+}
+
+/// The error returned by [`PrioritySender::try_send`].
+#[derive(Debug, PartialEq, Eq)]
+pub enum TrySendError<T> {
+    /// The channel is at capacity.
+    Full(T),
+    /// Every receiver has been dropped.
+    Disconnected(T),
+}
+
+/// The error returned by [`PrioritySender::send`].
+#[derive(Debug, PartialEq, Eq)]
+pub enum SendError<T> {
+    /// Every receiver has been dropped.
+    Disconnected(T),
+}
+
+impl<T> PrioritySender<T> {
+    /// Sends `value` at the given priority without waiting, failing if
+    /// the channel is full or disconnected. A `priority` at or past the
+    /// channel's level count is clamped to the highest level.
+    pub fn try_send(&self, value: T, priority: u8) -> Result<(), TrySendError<T>> {
+        let mut state = self.shared.state.lock().unwrap();
+        if state.is_disconnected() {
+            return Err(TrySendError::Disconnected(value));
+        }
+        if let Some(capacity) = state.capacity {
+            if state.len() >= capacity {
+                return Err(TrySendError::Full(value));
+            }
+        }
+        state.push(priority, value);
+        state.wake_one_receiver();
+        Ok(())
+    }
+
+    /// Sends `value` at the given priority, waiting if the channel is
+    /// full.
+    pub async fn send(&self, value: T, priority: u8) -> Result<(), SendError<T>> {
+        let mut value = value;
+        loop {
+            match self.try_send(value, priority) {
+                Ok(()) => {
+                    crate::task::consume_budget().await;
+                    return Ok(());
+                }
+                Err(TrySendError::Disconnected(v)) => return Err(SendError::Disconnected(v)),
+                Err(TrySendError::Full(v)) => value = v,
+            }
+            Send { shared: &self.shared }.await;
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.shared.state.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn capacity(&self) -> Option<usize> {
+        self.shared.state.lock().unwrap().capacity
+    }
+
+    /// The number of live `PrioritySender` handles, including this one.
+    pub fn sender_count(&self) -> usize {
+        self.shared.state.lock().unwrap().sender_count
+    }
+
+    /// The number of live `PriorityReceiver` handles.
+    pub fn receiver_count(&self) -> usize {
+        self.shared.state.lock().unwrap().receiver_count
+    }
+}
+
+struct Send<'a, T> {
+    shared: &'a Arc<Shared<T>>,
+}
+
+impl<'a, T> Future for Send<'a, T> {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let mut state = self.shared.state.lock().unwrap();
+        let has_room = match state.capacity {
+            Some(capacity) => state.len() < capacity,
+            None => true,
+        };
+        if has_room || state.is_disconnected() {
+            return Poll::Ready(());
+        }
+        state.send_wakers.register(cx.waker());
+        Poll::Pending
+    }
+}
+
+impl<T> Clone for PrioritySender<T> {
+    fn clone(&self) -> Self {
+        self.shared.state.lock().unwrap().sender_count += 1;
+        Self { shared: self.shared.clone() }
+    }
+}
+
+impl<T> Drop for PrioritySender<T> {
+    fn drop(&mut self) {
+        let mut state = self.shared.state.lock().unwrap();
+        state.sender_count -= 1;
+        if state.sender_count == 0 {
+            state.recv_wakers.wake_all();
+        }
+    }
+}
+
+/// The receiving half of a priority channel.
+pub struct PriorityReceiver<T> {
+    shared: Arc<Shared<T>>,
+}
+
+/// The error returned by [`PriorityReceiver::try_recv`].
+#[derive(Debug, PartialEq, Eq)]
+pub enum TryRecvError {
+    /// No item is queued right now.
+    Empty,
+    /// Every sender has been dropped and the queue is empty.
+    Disconnected,
+}
+
+/// The error returned by [`PriorityReceiver::recv_timeout`].
+#[derive(Debug, PartialEq, Eq)]
+pub enum RecvTimeoutError {
+    /// Every sender has been dropped and the queue is empty.
+    Disconnected,
+    /// No item arrived within the timeout.
+    Timeout,
+}
+
+impl<T> PriorityReceiver<T> {
+    /// Receives the highest-priority queued item without waiting,
+    /// failing if none is queued. See the module docs for how a
+    /// lower-priority item that's been skipped repeatedly can still win
+    /// this pick.
+    pub fn try_recv(&self) -> Result<T, TryRecvError> {
+        let mut state = self.shared.state.lock().unwrap();
+        if let Some(value) = state.pop() {
+            state.wake_one_sender();
+            Ok(value)
+        } else if state.sender_count == 0 {
+            Err(TryRecvError::Disconnected)
+        } else {
+            Err(TryRecvError::Empty)
+        }
+    }
+
+    /// Receives the highest-priority queued item, waiting if the
+    /// channel is empty.
+    pub async fn recv(&self) -> Option<T> {
+        loop {
+            match self.try_recv() {
+                Ok(value) => {
+                    crate::task::consume_budget().await;
+                    return Some(value);
+                }
+                Err(TryRecvError::Disconnected) => return None,
+                Err(TryRecvError::Empty) => Recv { shared: &self.shared }.await,
+            }
+        }
+    }
+
+    /// Receives an item, waiting at most `duration`.
+    pub async fn recv_timeout(&self, duration: Duration) -> Result<T, RecvTimeoutError> {
+        match crate::future::timeout(duration, self.recv()).await {
+            Ok(Some(value)) => Ok(value),
+            Ok(None) => Err(RecvTimeoutError::Disconnected),
+            Err(_) => Err(RecvTimeoutError::Timeout),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.shared.state.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn capacity(&self) -> Option<usize> {
+        self.shared.state.lock().unwrap().capacity
+    }
+
+    pub fn sender_count(&self) -> usize {
+        self.shared.state.lock().unwrap().sender_count
+    }
+
+    pub fn receiver_count(&self) -> usize {
+        self.shared.state.lock().unwrap().receiver_count
+    }
+}
+
+struct Recv<'a, T> {
+    shared: &'a Arc<Shared<T>>,
+}
+
+impl<'a, T> Future for Recv<'a, T> {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let mut state = self.shared.state.lock().unwrap();
+        if state.len() > 0 || state.sender_count == 0 {
+            return Poll::Ready(());
+        }
+        state.recv_wakers.register(cx.waker());
+        Poll::Pending
+    }
+}
+
+impl<T> Clone for PriorityReceiver<T> {
+    fn clone(&self) -> Self {
+        self.shared.state.lock().unwrap().receiver_count += 1;
+        Self { shared: self.shared.clone() }
+    }
+}
+
+impl<T> Drop for PriorityReceiver<T> {
+    fn drop(&mut self) {
+        let mut state = self.shared.state.lock().unwrap();
+        state.receiver_count -= 1;
+        if state.receiver_count == 0 {
+            state.send_wakers.wake_all();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recv_always_returns_the_highest_priority_item_first() {
+        let (tx, rx) = unbounded(3);
+        tx.try_send("low", 0).unwrap();
+        tx.try_send("high", 2).unwrap();
+        tx.try_send("mid", 1).unwrap();
+
+        assert_eq!(rx.try_recv(), Ok("high"));
+        assert_eq!(rx.try_recv(), Ok("mid"));
+        assert_eq!(rx.try_recv(), Ok("low"));
+        assert_eq!(rx.try_recv(), Err(TryRecvError::Empty));
+    }
+
+    #[test]
+    fn a_priority_past_the_level_count_is_clamped_to_the_highest_level() {
+        let (tx, rx) = unbounded(2);
+        tx.try_send("clamped", 200).unwrap();
+        tx.try_send("also-highest", 1).unwrap();
+
+        // Both landed in the same (highest) bucket, so FIFO within that
+        // level decides the order.
+        assert_eq!(rx.try_recv(), Ok("clamped"));
+        assert_eq!(rx.try_recv(), Ok("also-highest"));
+    }
+
+    #[test]
+    fn a_starved_low_priority_level_is_force_served_after_max_skips() {
+        let (tx, rx) = unbounded_with_fairness(2, 3);
+        tx.try_send("low", 0).unwrap();
+        for _ in 0..3 {
+            tx.try_send("high", 1).unwrap();
+            assert_eq!(rx.try_recv(), Ok("high"));
+        }
+        // The low item has now been skipped `max_skips` times; it must
+        // win over a freshly queued high-priority one.
+        tx.try_send("high", 1).unwrap();
+        assert_eq!(rx.try_recv(), Ok("low"));
+    }
+
+    #[test]
+    fn try_send_reports_full_and_disconnected() {
+        let (tx, rx) = bounded(1, 2);
+        tx.try_send("a", 0).unwrap();
+        assert_eq!(tx.try_send("b", 0), Err(TrySendError::Full("b")));
+
+        drop(rx);
+        assert_eq!(tx.try_send("c", 0), Err(TrySendError::Disconnected("c")));
+    }
+
+    #[test]
+    fn recv_waits_for_a_send_and_wakes_up() {
+        crate::task::block_on(async {
+            let (tx, rx) = unbounded::<u32>(2);
+            let sender = crate::task::spawn(async move {
+                crate::task::sleep(core::time::Duration::from_millis(20)).await;
+                tx.send(7, 1).await.unwrap();
+            });
+
+            assert_eq!(rx.recv().await, Some(7));
+            sender.await;
+        });
+    }
+
+    #[test]
+    fn recv_returns_none_once_every_sender_drops() {
+        crate::task::block_on(async {
+            let (tx, rx) = unbounded::<u32>(2);
+            drop(tx);
+            assert_eq!(rx.recv().await, None);
+        });
+    }
+}
+
+impl<T> Stream for PriorityReceiver<T> {
+    type Item = T;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        match self.try_recv() {
+            Ok(value) => Poll::Ready(Some(value)),
+            Err(TryRecvError::Disconnected) => Poll::Ready(None),
+            Err(TryRecvError::Empty) => {
+                let mut state = self.shared.state.lock().unwrap();
+                if state.len() > 0 || state.sender_count == 0 {
+                    drop(state);
+                    // A sender landed an item or disconnected between
+                    // the `try_recv` above and taking the lock again;
+                    // retry instead of registering a waker we'd have
+                    // to immediately wake ourselves.
+                    return Pin::new(self.get_mut()).poll_next(cx);
+                }
+                state.recv_wakers.register(cx.waker());
+                Poll::Pending
+            }
+        }
+    }
+}
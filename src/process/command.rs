@@ -0,0 +1,633 @@
+//! Asynchronous process spawning, built on [`std::process`].
+//!
+//! Waiting on a child is the only part of `std::process` that
+//! actually blocks, so that's the only part this wraps with
+//! [`task::spawn_blocking`](crate::task::spawn_blocking); building the
+//! command and reading its `id()` stay synchronous, matching
+//! `std::process::Command`'s own API as closely as possible.
+
+use std::ffi::OsStr;
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::Path;
+use std::process::{Child as StdChild, Command as StdCommand};
+use std::time::Duration;
+
+pub use std::process::{ExitStatus, Output, Stdio};
+
+use crate::sync::{channel, Receiver, Sender};
+use crate::task::{self, spawn_blocking};
+
+/// A description of a child process, built up the same way as
+/// [`std::process::Command`], plus cancellation-safety knobs for
+/// async supervisors: [`kill_on_drop`](Command::kill_on_drop) and, on
+/// Unix, [`process_group`](Command::process_group).
+pub struct Command {
+    // `Option` so `spawn`/`output` can move the built `StdCommand`
+    // onto a blocking thread without `Command` itself needing to be
+    // `'static` at the call site.
+    inner: Option<StdCommand>,
+    kill_on_drop: bool,
+    #[cfg(unix)]
+    process_group: Option<i32>,
+    #[cfg(unix)]
+    uses_pre_exec: bool,
+}
+
+impl Command {
+    /// Starts building a command that runs `program`.
+    pub fn new<S: AsRef<OsStr>>(program: S) -> Command {
+        Command {
+            inner: Some(StdCommand::new(program)),
+            kill_on_drop: false,
+            #[cfg(unix)]
+            process_group: None,
+            #[cfg(unix)]
+            uses_pre_exec: false,
+        }
+    }
+
+    fn inner_mut(&mut self) -> &mut StdCommand {
+        self.inner.as_mut().expect("Command used after spawn()/output()/status() consumed it")
+    }
+
+    /// Appends a single argument.
+    pub fn arg<S: AsRef<OsStr>>(&mut self, arg: S) -> &mut Command {
+        self.inner_mut().arg(arg);
+        self
+    }
+
+    /// Appends multiple arguments.
+    pub fn args<I, S>(&mut self, args: I) -> &mut Command
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<OsStr>,
+    {
+        self.inner_mut().args(args);
+        self
+    }
+
+    /// Sets an environment variable for the child.
+    pub fn env<K, V>(&mut self, key: K, val: V) -> &mut Command
+    where
+        K: AsRef<OsStr>,
+        V: AsRef<OsStr>,
+    {
+        self.inner_mut().env(key, val);
+        self
+    }
+
+    /// Sets the child's working directory.
+    pub fn current_dir<P: AsRef<Path>>(&mut self, dir: P) -> &mut Command {
+        self.inner_mut().current_dir(dir);
+        self
+    }
+
+    /// Sets the child's standard input handle.
+    pub fn stdin(&mut self, cfg: Stdio) -> &mut Command {
+        self.inner_mut().stdin(cfg);
+        self
+    }
+
+    /// Sets the child's standard output handle.
+    pub fn stdout(&mut self, cfg: Stdio) -> &mut Command {
+        self.inner_mut().stdout(cfg);
+        self
+    }
+
+    /// Sets the child's standard error handle.
+    pub fn stderr(&mut self, cfg: Stdio) -> &mut Command {
+        self.inner_mut().stderr(cfg);
+        self
+    }
+
+    /// Whether to kill (and reap) the child if the returned [`Child`]
+    /// is dropped before the process has exited -- e.g. because the
+    /// task that owns it was cancelled. Off by default, matching
+    /// `std::process::Child`'s own drop behavior (which leaves the
+    /// child running).
+    pub fn kill_on_drop(&mut self, kill_on_drop: bool) -> &mut Command {
+        self.kill_on_drop = kill_on_drop;
+        self
+    }
+
+    /// Puts the child in its own process group (`setpgid` with a pgid
+    /// of its own pid, via `pgid: 0`, or joins an existing group with
+    /// a specific `pgid`), so killing the group with
+    /// [`Child::kill_group`] also reaches any further descendants the
+    /// child itself spawns -- a plain `kill()` only ever reaches the
+    /// direct child.
+    #[cfg(unix)]
+    pub fn process_group(&mut self, pgid: i32) -> &mut Command {
+        self.process_group = Some(pgid);
+        self
+    }
+
+    /// Sets the user ID the child runs as, via `setuid` after `fork`.
+    #[cfg(unix)]
+    pub fn uid(&mut self, id: u32) -> &mut Command {
+        std::os::unix::process::CommandExt::uid(self.inner_mut(), id);
+        self
+    }
+
+    /// Sets the group ID the child runs as, via `setgid` after `fork`.
+    #[cfg(unix)]
+    pub fn gid(&mut self, id: u32) -> &mut Command {
+        std::os::unix::process::CommandExt::gid(self.inner_mut(), id);
+        self
+    }
+
+    /// Sets the supplementary group IDs the child runs with, via
+    /// `setgroups` after `fork`.
+    #[cfg(unix)]
+    pub fn groups(&mut self, groups: &[u32]) -> &mut Command {
+        std::os::unix::process::CommandExt::groups(self.inner_mut(), groups);
+        self
+    }
+
+    /// Runs `f` in the child after `fork` but before `exec`.
+    ///
+    /// # Safety
+    ///
+    /// `f` runs in a child that's a clone of this process at the
+    /// moment of `fork`, sharing its address space but none of its
+    /// other threads -- any lock `f` tries to take that some other
+    /// thread held at `fork` time will deadlock forever, and the
+    /// allocator is one such lock. `f` must stick to
+    /// async-signal-safe operations only (see `signal-safety(7)`):
+    /// raw syscalls, no heap allocation, no `Mutex`.
+    #[cfg(unix)]
+    pub unsafe fn pre_exec<F>(&mut self, f: F) -> &mut Command
+    where
+        F: FnMut() -> io::Result<()> + Send + Sync + 'static,
+    {
+        std::os::unix::process::CommandExt::pre_exec(self.inner_mut(), f);
+        self.uses_pre_exec = true;
+        self
+    }
+
+    /// Whether this command is expected to take the `posix_spawn` fast
+    /// path on [`spawn`](Command::spawn) instead of falling back to
+    /// `fork`+`exec`.
+    ///
+    /// The standard library already prefers `posix_spawn(3)` on Unix
+    /// whenever a command's configuration allows it, which is why
+    /// [`spawn`](Command::spawn) here calls straight into
+    /// `std::process::Command::spawn` rather than doing its own
+    /// `fork`/`vfork` dance -- duplicating that logic would just be
+    /// racing the same libc entry point. What disqualifies the fast
+    /// path is running code in the child before `exec`
+    /// ([`pre_exec`](Command::pre_exec), and everything built on it:
+    /// [`chroot`](Command::chroot), [`inherit_fd`](Command::inherit_fd),
+    /// [`spawn_pty`](Command::spawn_pty)), since `posix_spawn` offers no
+    /// hook to run arbitrary code between the address-space clone and
+    /// the exec. High-rate spawners (CI runners, per-request helpers)
+    /// that care about this should avoid those options when they can.
+    #[cfg(unix)]
+    pub fn fast_spawn_eligible(&self) -> bool {
+        !self.uses_pre_exec
+    }
+
+    /// `chroot`s the child into `dir` before it execs.
+    ///
+    /// Built on [`pre_exec`](Command::pre_exec): `chroot(2)` alone
+    /// doesn't move the process's current directory inside the new
+    /// root, so this also `chdir`s to `/` (now meaning `dir`)
+    /// afterward -- without that, relative paths the child opens
+    /// would still resolve against wherever the parent's cwd used to
+    /// be, outside the jail.
+    #[cfg(unix)]
+    pub fn chroot<P: AsRef<Path>>(&mut self, dir: P) -> &mut Command {
+        use std::ffi::CString;
+        use std::os::unix::ffi::OsStrExt;
+
+        let dir = CString::new(dir.as_ref().as_os_str().as_bytes()).expect("chroot path must not contain a NUL byte");
+        unsafe {
+            self.pre_exec(move || {
+                if libc::chroot(dir.as_ptr()) != 0 {
+                    return Err(io::Error::last_os_error());
+                }
+                if libc::chdir(b"/\0".as_ptr() as *const libc::c_char) != 0 {
+                    return Err(io::Error::last_os_error());
+                }
+                Ok(())
+            })
+        }
+    }
+
+    /// Spawns the child attached to `pty`'s slave side as its
+    /// controlling terminal, rather than to pipes: any
+    /// `stdin`/`stdout`/`stderr` set on this `Command` beforehand are
+    /// overwritten.
+    #[cfg(unix)]
+    pub fn spawn_pty(&mut self, pty: &super::pty::Pty) -> io::Result<Child> {
+        use std::os::unix::ffi::OsStrExt;
+
+        let slave_path = std::path::PathBuf::from(std::ffi::OsStr::from_bytes(pty.slave_path().as_bytes()));
+        let open_slave = || std::fs::OpenOptions::new().read(true).write(true).open(&slave_path);
+
+        let slave_in = open_slave()?;
+        let slave_out = slave_in.try_clone()?;
+        let slave_err = slave_in.try_clone()?;
+        self.stdin(Stdio::from(slave_in));
+        self.stdout(Stdio::from(slave_out));
+        self.stderr(Stdio::from(slave_err));
+
+        unsafe {
+            self.pre_exec(|| {
+                // Detach from whatever controlling terminal this
+                // process (now the pty's slave, via the redirected
+                // stdio above) inherited, then claim the slave as the
+                // new session's controlling terminal.
+                if libc::setsid() < 0 {
+                    return Err(io::Error::last_os_error());
+                }
+                if libc::ioctl(0, libc::TIOCSCTTY as _, 0) != 0 {
+                    return Err(io::Error::last_os_error());
+                }
+                Ok(())
+            });
+        }
+
+        self.spawn()
+    }
+
+    /// Lets `fd` survive into the child instead of being closed on
+    /// exec.
+    ///
+    /// Every fd this process opens (other than the three standard
+    /// streams, which `stdin`/`stdout`/`stderr` already control) is
+    /// `close-on-exec` by default, so that a child never inherits
+    /// handles it has no business holding; call this for each fd a
+    /// sandboxed child genuinely needs passed through (e.g. a
+    /// pre-opened socket or memfd).
+    #[cfg(unix)]
+    pub fn inherit_fd(&mut self, fd: std::os::unix::io::RawFd) -> &mut Command {
+        unsafe {
+            self.pre_exec(move || {
+                let flags = libc::fcntl(fd, libc::F_GETFD);
+                if flags == -1 {
+                    return Err(io::Error::last_os_error());
+                }
+                if libc::fcntl(fd, libc::F_SETFD, flags & !libc::FD_CLOEXEC) == -1 {
+                    return Err(io::Error::last_os_error());
+                }
+                Ok(())
+            })
+        }
+    }
+
+    /// Spawns the child process.
+    ///
+    /// This is synchronous, not `spawn_blocking`'d onto the blocking
+    /// pool: on Unix, `std::process::Command::spawn` itself already
+    /// uses `posix_spawn(3)` when the command's configuration allows
+    /// it (see [`fast_spawn_eligible`](Command::fast_spawn_eligible)),
+    /// which is fast and doesn't duplicate the parent's address space
+    /// the way a plain `fork` would, so there's nothing here worth
+    /// moving off the caller's task.
+    pub fn spawn(&mut self) -> io::Result<Child> {
+        #[cfg(unix)]
+        if let Some(pgid) = self.process_group {
+            std::os::unix::process::CommandExt::process_group(self.inner_mut(), pgid);
+        }
+
+        let mut command = self.inner.take().expect("Command used after spawn()/output()/status() consumed it");
+        let child = command.spawn()?;
+        self.inner = Some(command);
+        Ok(Child {
+            inner: Some(child),
+            kill_on_drop: self.kill_on_drop,
+        })
+    }
+
+    /// Spawns the child, waits for it to exit, and collects its
+    /// output, like [`std::process::Command::output`].
+    pub async fn output(&mut self) -> io::Result<Output> {
+        #[cfg(unix)]
+        if let Some(pgid) = self.process_group {
+            std::os::unix::process::CommandExt::process_group(self.inner_mut(), pgid);
+        }
+        let command = self.inner.take().expect("Command used after spawn()/output()/status() consumed it");
+        spawn_blocking(move || command.output()).await
+    }
+
+    /// Spawns the child and waits for just its exit status, like
+    /// [`std::process::Command::status`].
+    pub async fn status(&mut self) -> io::Result<ExitStatus> {
+        self.spawn()?.wait().await
+    }
+
+    /// Like [`output`](Command::output), but kills and reaps the
+    /// child (rather than leaving it running, pipes and all, as a
+    /// plain `io::timeout(duration, command.output())` would) if it
+    /// hasn't exited within `duration`. Returns whatever had already
+    /// been written to its stdout/stderr pipes by that point.
+    ///
+    /// The pipes are drained on their own blocking threads
+    /// concurrently with the wait, not sequentially after it: a child
+    /// that writes more than one OS pipe buffer's worth of output
+    /// before exiting would otherwise block in `write(2)` with nothing
+    /// reading the other end, so `wait_timeout`'s `try_wait` polling
+    /// would never observe the exit and this would silently degrade
+    /// into "always wait the full `duration`, then kill" for any
+    /// moderately chatty command -- the same pipe-buffer deadlock
+    /// [`output`](Command::output) avoids by running entirely on a
+    /// blocking thread.
+    pub async fn output_timeout(&mut self, duration: Duration) -> io::Result<Output> {
+        self.stdout(Stdio::piped());
+        self.stderr(Stdio::piped());
+        let mut child = self.spawn()?;
+
+        let stdout_handle = child.inner.as_mut().and_then(|c| c.stdout.take());
+        let stderr_handle = child.inner.as_mut().and_then(|c| c.stderr.take());
+        let stdout_task = spawn_blocking(move || {
+            let mut buf = Vec::new();
+            if let Some(mut handle) = stdout_handle {
+                let _ = handle.read_to_end(&mut buf);
+            }
+            buf
+        });
+        let stderr_task = spawn_blocking(move || {
+            let mut buf = Vec::new();
+            if let Some(mut handle) = stderr_handle {
+                let _ = handle.read_to_end(&mut buf);
+            }
+            buf
+        });
+
+        let (status, stdout, stderr) = crate::join!(child.wait_timeout(duration), stdout_task, stderr_task);
+
+        Ok(Output {
+            status: status?,
+            stdout,
+            stderr,
+        })
+    }
+
+    /// Like [`status`](Command::status), but kills and reaps the
+    /// child if it hasn't exited within `duration`, rather than
+    /// leaving a zombie behind the way wrapping `status()` in
+    /// `io::timeout` would.
+    pub async fn status_timeout(&mut self, duration: Duration) -> io::Result<ExitStatus> {
+        self.spawn()?.wait_timeout(duration).await
+    }
+}
+
+/// A running (or exited, but not yet reaped) child process.
+pub struct Child {
+    inner: Option<StdChild>,
+    kill_on_drop: bool,
+}
+
+impl Child {
+    /// The child's process ID.
+    pub fn id(&self) -> u32 {
+        self.inner.as_ref().expect("id() called after the child was reaped").id()
+    }
+
+    /// Sends `SIGKILL` (Unix) or calls `TerminateProcess` (Windows) to
+    /// just this process -- not its process group or any descendants
+    /// it may have spawned. See [`kill_group`](Child::kill_group) for
+    /// that.
+    pub fn kill(&mut self) -> io::Result<()> {
+        self.inner.as_mut().expect("kill() called after the child was reaped").kill()
+    }
+
+    /// Sends `SIGKILL` to every process in this child's process group,
+    /// reaching any descendants it spawned along the way. Only
+    /// meaningful for a child started with
+    /// [`Command::process_group`]; for any other child this is the
+    /// same as [`kill`](Child::kill).
+    #[cfg(unix)]
+    pub fn kill_group(&mut self) -> io::Result<()> {
+        let pid = self.id() as libc::pid_t;
+        if unsafe { libc::killpg(pid, libc::SIGKILL) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(())
+    }
+
+    /// Waits for the child to exit, without blocking the calling task.
+    pub async fn wait(&mut self) -> io::Result<ExitStatus> {
+        let mut child = self.inner.take().expect("wait() called after the child was already reaped");
+        let (child, result) = spawn_blocking(move || {
+            let result = child.wait();
+            (child, result)
+        })
+        .await;
+        self.inner = Some(child);
+        result
+    }
+
+    /// Checks whether the child has exited, without waiting.
+    pub fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
+        self.inner.as_mut().expect("try_wait() called after the child was reaped").try_wait()
+    }
+
+    /// Waits for the child to exit, killing and reaping it if
+    /// `duration` passes first.
+    ///
+    /// Polls [`try_wait`](Child::try_wait) rather than using
+    /// [`wait`](Child::wait)'s blocking thread, since a background
+    /// `std::process::Child::wait()` can't be interrupted once
+    /// started -- there'd be no way to kill the child mid-wait and
+    /// still get the handle back to reap it. The poll interval is
+    /// capped at 10ms, the same bound
+    /// [`task::shutdown`](crate::task::shutdown) uses for its
+    /// drain-and-check loop.
+    pub async fn wait_timeout(&mut self, duration: Duration) -> io::Result<ExitStatus> {
+        let deadline = crate::time::now() + duration;
+        loop {
+            if let Some(status) = self.try_wait()? {
+                return Ok(status);
+            }
+            let now = crate::time::now();
+            if now >= deadline {
+                self.kill()?;
+                return self.wait().await;
+            }
+            crate::task::sleep(deadline.saturating_duration_since(now).min(Duration::from_millis(10))).await;
+        }
+    }
+
+    /// A stream of the child's standard output, split into lines.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `stdout` wasn't captured with
+    /// [`stdout(Stdio::piped())`](Command::stdout).
+    pub fn stdout_lines(&mut self) -> Receiver<io::Result<String>> {
+        let stdout = self.inner_mut().stdout.take().expect("stdout_lines: stdout was not captured with Stdio::piped()");
+        spawn_lines(stdout)
+    }
+
+    /// A stream of the child's standard error, split into lines.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `stderr` wasn't captured with
+    /// [`stderr(Stdio::piped())`](Command::stderr).
+    pub fn stderr_lines(&mut self) -> Receiver<io::Result<String>> {
+        let stderr = self.inner_mut().stderr.take().expect("stderr_lines: stderr was not captured with Stdio::piped()");
+        spawn_lines(stderr)
+    }
+
+    /// A single stream interleaving the child's standard output and
+    /// standard error lines in the order they actually arrive.
+    ///
+    /// Both streams are read on their own blocking threads (see
+    /// [`task::spawn_blocking`]) and fed into one shared channel, so
+    /// [`OutputLine::source`] is the only way to tell which stream a
+    /// given line came from after the fact.
+    ///
+    /// # Panics
+    ///
+    /// Panics unless both `stdout` and `stderr` were captured with
+    /// [`Stdio::piped()`](Command::stdout).
+    pub fn merged_output(&mut self) -> Receiver<OutputLine> {
+        let inner = self.inner_mut();
+        let stdout = inner.stdout.take().expect("merged_output: stdout was not captured with Stdio::piped()");
+        let stderr = inner.stderr.take().expect("merged_output: stderr was not captured with Stdio::piped()");
+
+        let (sender, receiver) = channel(16);
+        spawn_merged(stdout, OutputSource::Stdout, sender.clone());
+        spawn_merged(stderr, OutputSource::Stderr, sender);
+        receiver
+    }
+
+    fn inner_mut(&mut self) -> &mut StdChild {
+        self.inner.as_mut().expect("Child used after it was reaped")
+    }
+}
+
+/// Which of a child's output streams an [`OutputLine`] came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputSource {
+    Stdout,
+    Stderr,
+}
+
+/// One line read from a child process, tagged with which stream it
+/// came from. See [`Child::merged_output`].
+#[derive(Debug, Clone)]
+pub struct OutputLine {
+    pub source: OutputSource,
+    pub line: String,
+}
+
+fn spawn_lines<R>(reader: R) -> Receiver<io::Result<String>>
+where
+    R: Read + Send + 'static,
+{
+    let (sender, receiver) = channel(16);
+    spawn_blocking(move || {
+        for line in BufReader::new(reader).lines() {
+            if task::block_on(sender.send(line)).is_err() {
+                break;
+            }
+        }
+    });
+    receiver
+}
+
+fn spawn_merged<R>(reader: R, source: OutputSource, sender: Sender<OutputLine>)
+where
+    R: Read + Send + 'static,
+{
+    spawn_blocking(move || {
+        for line in BufReader::new(reader).lines() {
+            let line = match line {
+                Ok(line) => line,
+                Err(_) => break,
+            };
+            if task::block_on(sender.send(OutputLine { source, line })).is_err() {
+                break;
+            }
+        }
+    });
+}
+
+impl Drop for Child {
+    fn drop(&mut self) {
+        if self.kill_on_drop {
+            if let Some(mut child) = self.inner.take() {
+                // Best-effort: the child may have already exited, in
+                // which case `kill` returning an error here is
+                // expected and fine to ignore.
+                let _ = child.kill();
+                // `kill` alone leaves a zombie behind -- nothing has
+                // reaped the exit status yet, and `self` is about to
+                // be gone, so there's no later `wait()` call left to
+                // do it. Fire a best-effort reap onto the blocking
+                // pool instead of leaving that to whatever eventually
+                // notices the zombie (or never does, for a
+                // long-running process).
+                spawn_blocking(move || {
+                    let _ = child.wait();
+                });
+            }
+        }
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn status_reports_exit_code() {
+        crate::task::block_on(async {
+            let status = Command::new("true").status().await.unwrap();
+            assert!(status.success());
+
+            let status = Command::new("false").status().await.unwrap();
+            assert!(!status.success());
+        });
+    }
+
+    #[test]
+    fn output_captures_stdout() {
+        crate::task::block_on(async {
+            let output = Command::new("echo").arg("hello").output().await.unwrap();
+            assert!(output.status.success());
+            assert_eq!(output.stdout, b"hello\n");
+        });
+    }
+
+    #[test]
+    fn kill_on_drop_terminates_a_still_running_child_without_leaving_a_zombie() {
+        crate::task::block_on(async {
+            let mut child = Command::new("sleep").arg("60").kill_on_drop(true).spawn().unwrap();
+            let pid = child.id() as libc::pid_t;
+
+            drop(child);
+            // `Drop` only fires the kill and hands the reap off to the
+            // blocking pool; give it a moment to actually run before
+            // checking that the process is gone.
+            crate::task::sleep(Duration::from_millis(200)).await;
+
+            // Once reaped, signaling the pid fails with ESRCH; until
+            // then (or if it were merely killed but never waited on)
+            // it would still show up as a zombie and `kill(pid, 0)`
+            // would keep succeeding.
+            let err = unsafe { libc::kill(pid, 0) };
+            assert_eq!(err, -1, "child process should no longer exist after being killed and reaped on drop");
+        });
+    }
+
+    #[test]
+    fn without_kill_on_drop_a_child_is_left_running() {
+        crate::task::block_on(async {
+            let child = Command::new("sleep").arg("60").spawn().unwrap();
+            let pid = child.id() as libc::pid_t;
+
+            drop(child);
+
+            let err = unsafe { libc::kill(pid, 0) };
+            assert_eq!(err, 0, "without kill_on_drop the child should still be running");
+            unsafe {
+                libc::kill(pid, libc::SIGKILL);
+            }
+        });
+    }
+}
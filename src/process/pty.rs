@@ -0,0 +1,162 @@
+//! Spawning a child attached to a pseudo-terminal instead of pipes.
+//!
+//! A pipe makes `isatty()` false, and most interactive programs
+//! (shells, `ssh`, full-screen REPLs) check that and disable
+//! interactive behavior -- line editing, color, prompts -- the moment
+//! they see one. A pty's slave side looks like a real terminal to
+//! whatever's attached to it, which is the only way to drive those
+//! programs from code at all.
+//!
+//! Like [`io::stdin_raw::RawStdin`](crate::io::stdin_raw::RawStdin),
+//! [`PtyMaster`]'s async reads and writes go through
+//! [`crate::rt::Reactor`], the same as every other reactor-backed I/O
+//! type in this crate.
+
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use std::ffi::CString;
+use std::os::unix::io::RawFd;
+
+use crate::io::{self, Read, Write};
+
+/// A terminal size, in character cells.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PtySize {
+    pub rows: u16,
+    pub cols: u16,
+}
+
+/// A pseudo-terminal pair: a master side the parent drives
+/// ([`master`](Pty::master)) and a slave side the child process is
+/// attached to via [`Command::spawn_pty`](super::command::Command::spawn_pty).
+pub struct Pty {
+    master: RawFd,
+    slave_path: CString,
+}
+
+impl Pty {
+    /// Allocates a new pty pair, sized to `size`.
+    pub fn open(size: PtySize) -> io::Result<Pty> {
+        unsafe {
+            let master = libc::posix_openpt(libc::O_RDWR | libc::O_NOCTTY);
+            if master < 0 {
+                return Err(io::Error::last_os_error());
+            }
+
+            let result = (|| -> io::Result<CString> {
+                if libc::grantpt(master) != 0 {
+                    return Err(io::Error::last_os_error());
+                }
+                if libc::unlockpt(master) != 0 {
+                    return Err(io::Error::last_os_error());
+                }
+                let mut buf = [0u8; 256];
+                if libc::ptsname_r(master, buf.as_mut_ptr() as *mut libc::c_char, buf.len()) != 0 {
+                    return Err(io::Error::last_os_error());
+                }
+                let len = buf.iter().position(|&b| b == 0).unwrap_or(buf.len());
+                Ok(CString::new(&buf[..len]).expect("ptsname_r's result has no interior NUL"))
+            })();
+
+            let slave_path = match result {
+                Ok(path) => path,
+                Err(err) => {
+                    libc::close(master);
+                    return Err(err);
+                }
+            };
+
+            set_nonblocking(master)?;
+
+            let pty = Pty { master, slave_path };
+            pty.resize(size)?;
+            Ok(pty)
+        }
+    }
+
+    /// Changes the terminal's reported size, delivering `SIGWINCH` to
+    /// whatever's attached to the slave side.
+    pub fn resize(&self, size: PtySize) -> io::Result<()> {
+        let winsize = libc::winsize {
+            ws_row: size.rows,
+            ws_col: size.cols,
+            ws_xpixel: 0,
+            ws_ypixel: 0,
+        };
+        if unsafe { libc::ioctl(self.master, libc::TIOCSWINSZ, &winsize) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(())
+    }
+
+    /// The async read/write handle to the master side.
+    ///
+    /// Call this once per `Pty` -- it doesn't `dup` the underlying
+    /// fd, so the returned handle and any other outstanding one would
+    /// otherwise race each other reading the same stream of bytes.
+    pub fn master(&self) -> io::Result<PtyMaster> {
+        Ok(PtyMaster {
+            fd: self.master,
+            reactor: crate::rt::Reactor::register(self.master)?,
+        })
+    }
+
+    pub(super) fn slave_path(&self) -> &CString {
+        &self.slave_path
+    }
+}
+
+impl Drop for Pty {
+    fn drop(&mut self) {
+        unsafe {
+            libc::close(self.master);
+        }
+    }
+}
+
+/// The parent-side handle to a [`Pty`], reading what the child writes
+/// to its terminal and writing what the child reads from it.
+pub struct PtyMaster {
+    fd: RawFd,
+    reactor: crate::rt::Reactor,
+}
+
+impl Read for PtyMaster {
+    fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        this.reactor.poll_readable(cx, this.fd, buf)
+    }
+}
+
+impl Write for PtyMaster {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        this.reactor.poll_writable(cx, this.fd, buf)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        // Nothing is buffered in userspace between `poll_write` calls.
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        // The master fd belongs to the `Pty` this handle came from,
+        // which closes it on its own `Drop`; closing it here too
+        // would be a double-close the next time `Pty` drops.
+        Poll::Ready(Ok(()))
+    }
+}
+
+fn set_nonblocking(fd: RawFd) -> io::Result<()> {
+    unsafe {
+        let flags = libc::fcntl(fd, libc::F_GETFL, 0);
+        if flags < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        if libc::fcntl(fd, libc::F_SETFL, flags | libc::O_NONBLOCK) < 0 {
+            return Err(io::Error::last_os_error());
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,335 @@
+//! The reactor that [`ReactorBuilder`](crate::rt::ReactorBuilder) only
+//! configures: a single background thread driving `poll(2)` over every
+//! registered file descriptor, so [`Reactor::poll_readable`]/
+//! [`Reactor::poll_writable`] can actually park a task's waker until its
+//! fd is ready instead of returning a `Pending` nothing will ever wake.
+//!
+//! This is deliberately the `PollBackend::Poll` fallback, not a
+//! platform-specific `epoll`/`kqueue`/IOCP backend -- `poll(2)` is the
+//! one mechanism every unix this crate targets agrees on, which matters
+//! more here than raw throughput does for a "the reactor existing at
+//! all beats five copy-pasted TODOs" first implementation. Swapping in
+//! a faster backend later doesn't change this module's public shape:
+//! callers only ever see [`Reactor::register`] and the two `poll_*`
+//! methods.
+
+use core::task::{Context, Poll, Waker};
+
+use std::collections::HashMap;
+use std::io;
+use std::os::unix::io::RawFd;
+use std::sync::{Arc, Mutex, OnceLock};
+
+#[derive(Default)]
+struct SourceState {
+    refcount: usize,
+    readable: bool,
+    writable: bool,
+    read_waker: Option<Waker>,
+    write_waker: Option<Waker>,
+}
+
+struct Shared {
+    sources: Mutex<HashMap<RawFd, SourceState>>,
+    /// The write end of a self-pipe, so registering a new fd can
+    /// interrupt a `poll(2)` call already blocked on the old set
+    /// instead of waiting for some other fd to become ready first.
+    wake_write_fd: RawFd,
+}
+
+impl Shared {
+    fn wake_poll_thread(&self) {
+        let byte = 1u8;
+        unsafe {
+            // Best-effort: if the pipe is momentarily full, the poll
+            // thread is about to wake up on its own anyway.
+            libc::write(self.wake_write_fd, &byte as *const u8 as *const libc::c_void, 1);
+        }
+    }
+}
+
+fn shared() -> &'static Arc<Shared> {
+    static SHARED: OnceLock<Arc<Shared>> = OnceLock::new();
+    SHARED.get_or_init(|| {
+        let mut wake_fds = [0 as RawFd; 2];
+        if unsafe { libc::pipe(wake_fds.as_mut_ptr()) } != 0 {
+            panic!("reactor: failed to create wakeup pipe: {}", io::Error::last_os_error());
+        }
+        let [wake_read_fd, wake_write_fd] = wake_fds;
+        set_nonblocking(wake_read_fd);
+        set_nonblocking(wake_write_fd);
+
+        let shared = Arc::new(Shared {
+            sources: Mutex::new(HashMap::new()),
+            wake_write_fd,
+        });
+
+        let thread_shared = shared.clone();
+        std::thread::Builder::new()
+            .name("async-std/reactor".into())
+            .spawn(move || poll_loop(thread_shared, wake_read_fd))
+            .expect("reactor: failed to spawn the poll(2) driver thread");
+
+        shared
+    })
+}
+
+fn set_nonblocking(fd: RawFd) {
+    unsafe {
+        let flags = libc::fcntl(fd, libc::F_GETFL, 0);
+        if flags >= 0 {
+            libc::fcntl(fd, libc::F_SETFL, flags | libc::O_NONBLOCK);
+        }
+    }
+}
+
+fn poll_loop(shared: Arc<Shared>, wake_read_fd: RawFd) {
+    loop {
+        let mut pollfds = Vec::new();
+        pollfds.push(libc::pollfd {
+            fd: wake_read_fd,
+            events: libc::POLLIN,
+            revents: 0,
+        });
+        {
+            let sources = shared.sources.lock().unwrap();
+            for (&fd, state) in sources.iter() {
+                let mut events: libc::c_short = 0;
+                if state.read_waker.is_some() {
+                    events |= libc::POLLIN;
+                }
+                if state.write_waker.is_some() {
+                    events |= libc::POLLOUT;
+                }
+                pollfds.push(libc::pollfd { fd, events, revents: 0 });
+            }
+        }
+
+        let ready = unsafe { libc::poll(pollfds.as_mut_ptr(), pollfds.len() as libc::nfds_t, -1) };
+        if ready < 0 {
+            let err = io::Error::last_os_error();
+            if err.kind() == io::ErrorKind::Interrupted {
+                continue;
+            }
+            // Nothing sensible to do with a broken `poll(2)` other than
+            // give whoever's waiting a chance to notice via their own
+            // syscalls; keep looping rather than silently stop waking
+            // anyone ever again.
+            continue;
+        }
+
+        if pollfds[0].revents != 0 {
+            drain_wake_pipe(wake_read_fd);
+        }
+
+        let mut to_wake = Vec::new();
+        {
+            let mut sources = shared.sources.lock().unwrap();
+            for pollfd in &pollfds[1..] {
+                if pollfd.revents == 0 {
+                    continue;
+                }
+                let Some(state) = sources.get_mut(&pollfd.fd) else { continue };
+                let hup_or_err = pollfd.revents & (libc::POLLHUP | libc::POLLERR | libc::POLLNVAL) != 0;
+                if hup_or_err || pollfd.revents & libc::POLLIN != 0 {
+                    state.readable = true;
+                    if let Some(waker) = state.read_waker.take() {
+                        to_wake.push(waker);
+                    }
+                }
+                if hup_or_err || pollfd.revents & libc::POLLOUT != 0 {
+                    state.writable = true;
+                    if let Some(waker) = state.write_waker.take() {
+                        to_wake.push(waker);
+                    }
+                }
+            }
+        }
+        for waker in to_wake {
+            waker.wake();
+        }
+    }
+}
+
+fn drain_wake_pipe(wake_read_fd: RawFd) {
+    let mut buf = [0u8; 64];
+    loop {
+        let n = unsafe { libc::read(wake_read_fd, buf.as_mut_ptr() as *mut libc::c_void, buf.len()) };
+        if n <= 0 {
+            break;
+        }
+    }
+}
+
+/// A handle to the reactor for a single registered file descriptor.
+///
+/// Dropping every `Reactor` handle for a given fd removes it from the
+/// poll set; it isn't closed, since this type never owns the fd, only
+/// its registration.
+pub struct Reactor {
+    fd: RawFd,
+    shared: Arc<Shared>,
+}
+
+impl Reactor {
+    /// Registers `fd` with the reactor. `fd` must already be in
+    /// non-blocking mode -- this never sets it itself, since callers
+    /// (e.g. [`Async::new`](crate::io::async_fd::Async::new)) already
+    /// need to for the underlying read/write calls to behave correctly
+    /// regardless of whether a reactor is involved.
+    pub fn register(fd: RawFd) -> io::Result<Reactor> {
+        let shared = shared().clone();
+        {
+            let mut sources = shared.sources.lock().unwrap();
+            sources.entry(fd).or_default().refcount += 1;
+        }
+        shared.wake_poll_thread();
+        Ok(Reactor { fd, shared })
+    }
+
+    /// Resolves once `fd` is readable, then reads into `buf` (or, for
+    /// an empty `buf`, resolves with `Ok(0)` without reading -- the
+    /// "just tell me when it's readable" case
+    /// [`Async::readable`](crate::io::async_fd::Async::readable) uses).
+    pub fn poll_readable(&self, cx: &mut Context<'_>, fd: RawFd, buf: &mut [u8]) -> Poll<io::Result<usize>> {
+        if !self.wait_ready(cx, fd, true) {
+            return Poll::Pending;
+        }
+        if buf.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+        let n = unsafe { libc::read(fd, buf.as_mut_ptr() as *mut libc::c_void, buf.len()) };
+        if n >= 0 {
+            Poll::Ready(Ok(n as usize))
+        } else {
+            let err = io::Error::last_os_error();
+            if err.kind() == io::ErrorKind::WouldBlock {
+                // A spurious wakeup (or a second reader winning the
+                // race): the fd isn't actually readable yet, so go
+                // back to waiting instead of reporting an error.
+                self.mark_not_ready(fd, true);
+                self.poll_readable(cx, fd, buf)
+            } else {
+                Poll::Ready(Err(err))
+            }
+        }
+    }
+
+    /// Like [`poll_readable`](Reactor::poll_readable), but for writes.
+    pub fn poll_writable(&self, cx: &mut Context<'_>, fd: RawFd, buf: &[u8]) -> Poll<io::Result<usize>> {
+        if !self.wait_ready(cx, fd, false) {
+            return Poll::Pending;
+        }
+        if buf.is_empty() {
+            return Poll::Ready(Ok(0));
+        }
+        let n = unsafe { libc::write(fd, buf.as_ptr() as *const libc::c_void, buf.len()) };
+        if n >= 0 {
+            Poll::Ready(Ok(n as usize))
+        } else {
+            let err = io::Error::last_os_error();
+            if err.kind() == io::ErrorKind::WouldBlock {
+                self.mark_not_ready(fd, false);
+                self.poll_writable(cx, fd, buf)
+            } else {
+                Poll::Ready(Err(err))
+            }
+        }
+    }
+
+    /// Returns whether `fd` is already known ready; if not, parks
+    /// `cx`'s waker to be woken once the poll thread observes it.
+    fn wait_ready(&self, cx: &mut Context<'_>, fd: RawFd, read: bool) -> bool {
+        let mut sources = self.shared.sources.lock().unwrap();
+        let state = sources.entry(fd).or_default();
+        let already_ready = if read { state.readable } else { state.writable };
+        if already_ready {
+            return true;
+        }
+        if read {
+            state.read_waker = Some(cx.waker().clone());
+        } else {
+            state.write_waker = Some(cx.waker().clone());
+        }
+        drop(sources);
+        // The poll thread only watches for events it already knows
+        // some waiter cares about; make sure it picks up this one
+        // instead of staying blocked on the previous interest set.
+        self.shared.wake_poll_thread();
+        false
+    }
+
+    fn mark_not_ready(&self, fd: RawFd, read: bool) {
+        let mut sources = self.shared.sources.lock().unwrap();
+        if let Some(state) = sources.get_mut(&fd) {
+            if read {
+                state.readable = false;
+            } else {
+                state.writable = false;
+            }
+        }
+    }
+}
+
+impl Drop for Reactor {
+    fn drop(&mut self) {
+        let mut sources = self.shared.sources.lock().unwrap();
+        if let Some(state) = sources.get_mut(&self.fd) {
+            state.refcount -= 1;
+            if state.refcount == 0 {
+                sources.remove(&self.fd);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pipe() -> (RawFd, RawFd) {
+        let mut fds = [0 as RawFd; 2];
+        assert_eq!(unsafe { libc::pipe(fds.as_mut_ptr()) }, 0);
+        set_nonblocking(fds[0]);
+        set_nonblocking(fds[1]);
+        (fds[0], fds[1])
+    }
+
+    #[test]
+    fn readable_resolves_once_data_is_written() {
+        crate::task::block_on(async {
+            let (read_fd, write_fd) = pipe();
+            let reactor = Reactor::register(read_fd).unwrap();
+
+            let mut buf = [0u8; 8];
+
+            unsafe {
+                libc::write(write_fd, b"hi".as_ptr() as *const libc::c_void, 2);
+            }
+
+            let n = core::future::poll_fn(|cx| reactor.poll_readable(cx, read_fd, &mut buf)).await.unwrap();
+            assert_eq!(&buf[..n], b"hi");
+
+            unsafe {
+                libc::close(read_fd);
+                libc::close(write_fd);
+            }
+        });
+    }
+
+    #[test]
+    fn writable_resolves_for_a_pipe_with_room_in_its_buffer() {
+        crate::task::block_on(async {
+            let (read_fd, write_fd) = pipe();
+            let reactor = Reactor::register(write_fd).unwrap();
+
+            let n = core::future::poll_fn(|cx| reactor.poll_writable(cx, write_fd, b"hi")).await.unwrap();
+            assert_eq!(n, 2);
+
+            unsafe {
+                libc::close(read_fd);
+                libc::close(write_fd);
+            }
+        });
+    }
+}
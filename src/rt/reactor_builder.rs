@@ -0,0 +1,152 @@
+//! Configuring the reactor: which polling backend it uses, how much work
+//! it does per poll, and which thread runs it.
+//!
+//! This is the configuration surface; [`crate::rt::Reactor`] itself
+//! currently ignores all of it and always runs the portable `poll(2)`
+//! backend on its own lazily-started thread, since that's the one
+//! backend every unix this crate targets can rely on. Wiring `Reactor`
+//! up to actually read a [`ReactorBuilder`] -- honoring
+//! [`PollBackend::Epoll`]/[`PollBackend::Kqueue`] where available,
+//! `max_events_per_poll`, dedicated-thread mode, and the wakeup-storm
+//! hook -- is still a gap, same as `ASYNC_STD_THREAD_COUNT` needing
+//! someone to read it for [`RuntimeBuilder`](crate::task::RuntimeBuilder).
+
+use alloc::sync::Arc;
+
+/// Which OS polling mechanism the reactor drives.
+///
+/// `None` (the default, via [`ReactorBuilder::backend`]) leaves the
+/// choice to the reactor, which on most platforms only has one backend
+/// to pick from anyway -- this matters on platforms (Linux, with both
+/// epoll and io_uring wired up) that genuinely have more than one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PollBackend {
+    /// `epoll(7)`.
+    Epoll,
+    /// `kqueue(2)`.
+    Kqueue,
+    /// IOCP.
+    Iocp,
+    /// The `io_uring` ring set up by [`io::io_uring`](crate::io::io_uring),
+    /// where available.
+    IoUring,
+    /// A portable `poll(2)`-based fallback.
+    Poll,
+}
+
+/// Where the reactor runs relative to the executor's worker threads.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReactorThreading {
+    /// The reactor shares worker threads with the executor: whichever
+    /// worker is idle drives a poll. Lower footprint, but a slow task can
+    /// delay the next poll.
+    #[default]
+    SharedWithWorkers,
+    /// The reactor gets its own thread, doing nothing but polling and
+    /// waking tasks. Costs a thread; buys more predictable tail latency
+    /// for I/O-heavy workloads, since polling is never queued up behind
+    /// a worker's current task.
+    Dedicated,
+}
+
+/// What the reactor observed during a poll that produced an unusually
+/// large number of ready events at once, passed to a hook installed with
+/// [`ReactorBuilder::on_wakeup_storm`].
+#[derive(Debug, Clone, Copy)]
+pub struct WakeupStormEvent {
+    /// How many sources became ready in this single poll.
+    pub events_this_poll: usize,
+    /// How many sources were registered with the reactor at the time.
+    pub registered_sources: usize,
+}
+
+/// A callback run on the thread driving the reactor when a poll produces
+/// an unusually large batch of ready events -- a rough proxy for a
+/// thundering-herd wakeup, a misbehaving source firing spuriously, or a
+/// poll that's been starved for too long -- for piping into a metrics or
+/// tracing system without the reactor needing to know either exists.
+pub type WakeupStormHook = Arc<dyn Fn(WakeupStormEvent) + Send + Sync>;
+
+/// Configuration for the reactor.
+#[derive(Clone, Default)]
+pub struct ReactorBuilder {
+    backend: Option<PollBackend>,
+    max_events_per_poll: Option<usize>,
+    max_registered_sources: Option<usize>,
+    threading: Option<ReactorThreading>,
+    on_wakeup_storm: Option<WakeupStormHook>,
+}
+
+impl ReactorBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Which polling backend to use, on platforms where there's a
+    /// choice. Picking a backend the current platform doesn't support is
+    /// a setup-time error for whatever code reads this, not something
+    /// `ReactorBuilder` itself can validate up front.
+    pub fn backend(mut self, backend: PollBackend) -> Self {
+        self.backend = Some(backend);
+        self
+    }
+
+    /// The largest number of ready events to pull out of the OS in a
+    /// single poll call, bounding how long one poll can take before
+    /// control returns to whatever's waiting on the reactor's own
+    /// progress.
+    ///
+    /// `None` (the default) leaves the choice to the reactor.
+    pub fn max_events_per_poll(mut self, max: usize) -> Self {
+        self.max_events_per_poll = Some(max);
+        self
+    }
+
+    /// Refuses to register any more sources past this count, so a
+    /// runaway caller opening file descriptors in a loop hits a clear
+    /// error instead of exhausting the process's descriptor limit and
+    /// taking unrelated sources down with it.
+    ///
+    /// `None` (the default) leaves the choice to the reactor.
+    pub fn max_registered_sources(mut self, max: usize) -> Self {
+        self.max_registered_sources = Some(max);
+        self
+    }
+
+    /// Runs the reactor on its own thread instead of sharing worker
+    /// threads with the executor. See [`ReactorThreading`].
+    pub fn threading(mut self, threading: ReactorThreading) -> Self {
+        self.threading = Some(threading);
+        self
+    }
+
+    /// Installs a hook called whenever a poll produces an unusually
+    /// large batch of ready events. See [`WakeupStormEvent`].
+    pub fn on_wakeup_storm<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(WakeupStormEvent) + Send + Sync + 'static,
+    {
+        self.on_wakeup_storm = Some(Arc::new(hook));
+        self
+    }
+
+    pub fn get_backend(&self) -> Option<PollBackend> {
+        self.backend
+    }
+
+    pub fn get_max_events_per_poll(&self) -> Option<usize> {
+        self.max_events_per_poll
+    }
+
+    pub fn get_max_registered_sources(&self) -> Option<usize> {
+        self.max_registered_sources
+    }
+
+    pub fn get_threading(&self) -> ReactorThreading {
+        self.threading.unwrap_or_default()
+    }
+
+    pub fn get_on_wakeup_storm(&self) -> Option<&WakeupStormHook> {
+        self.on_wakeup_storm.as_ref()
+    }
+}
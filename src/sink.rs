@@ -0,0 +1,35 @@
+//! Asynchronous values that can accept a stream of inputs.
+//!
+//! A [`Sink`] is the asynchronous analogue of [`Extend`]: something items
+//! can be pushed into over time, with backpressure communicated through
+//! `poll_ready` rather than by blocking. [`Stream::forward`] drives a
+//! stream's items into one.
+
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+/// A value that asynchronously accepts a stream of items.
+///
+/// Callers must `poll_ready` before every `start_send`, and should
+/// `poll_flush` once they are done feeding the sink for now to ensure
+/// buffered items are actually written out.
+pub trait Sink<Item> {
+    /// The type of value produced if the sink encounters an error.
+    type Error;
+
+    /// Attempts to prepare the `Sink` to receive a value, returning
+    /// `Poll::Ready(Ok(()))` once it can accept an item via `start_send`.
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>>;
+
+    /// Begins sending a value to the sink. Must only be called immediately
+    /// after `poll_ready` returns `Poll::Ready(Ok(()))`.
+    fn start_send(self: Pin<&mut Self>, item: Item) -> Result<(), Self::Error>;
+
+    /// Flushes any values buffered by prior `start_send` calls out of the
+    /// sink.
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>>;
+
+    /// Flushes and then closes the sink, indicating no more values will be
+    /// sent.
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>>;
+}
@@ -0,0 +1,194 @@
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll, Waker};
+
+use alloc::collections::VecDeque;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+
+use crate::sync::Mutex;
+use crate::stream::Stream;
+use crate::task;
+
+struct Subscriber<T> {
+    queue: VecDeque<T>,
+    waker: Option<Waker>,
+    // Set once the source stream has ended; lets a subscriber drain its
+    // remaining queue before reporting `None` itself.
+    source_done: bool,
+}
+
+struct Shared<T> {
+    subscribers: Vec<Option<Subscriber<T>>>,
+}
+
+/// A stream adapter that fans a single source stream out to any number of
+/// independent, cloneable subscribers.
+///
+/// Every subscriber sees every item the source produces, in order, starting
+/// from the moment it was created with [`subscribe`](Broadcast::subscribe)
+/// -- not from the start of the source. The source stream is driven by a
+/// background task, so subscribers make progress even if only some of them
+/// are being polled.
+pub struct Broadcast<T> {
+    shared: Arc<Mutex<Shared<T>>>,
+}
+
+impl<T> Broadcast<T>
+where
+    T: Clone + Send + 'static,
+{
+    /// Spawns `stream` onto a background task and returns a handle that can
+    /// be [`subscribe`](Broadcast::subscribe)d to any number of times.
+    pub fn new<S>(stream: S) -> Self
+    where
+        S: Stream<Item = T> + Send + 'static,
+    {
+        let shared = Arc::new(Mutex::new(Shared {
+            subscribers: Vec::new(),
+        }));
+
+        let pump_shared = shared.clone();
+        task::spawn(async move {
+            pin_utils::pin_mut!(stream);
+            use crate::stream::stream::StreamExt;
+            while let Some(item) = stream.next().await {
+                let mut guard = pump_shared.lock().await;
+                for sub in guard.subscribers.iter_mut().flatten() {
+                    sub.queue.push_back(item.clone());
+                    if let Some(waker) = sub.waker.take() {
+                        waker.wake();
+                    }
+                }
+            }
+            let mut guard = pump_shared.lock().await;
+            for sub in guard.subscribers.iter_mut().flatten() {
+                sub.source_done = true;
+                if let Some(waker) = sub.waker.take() {
+                    waker.wake();
+                }
+            }
+        });
+
+        Self { shared }
+    }
+
+    /// Creates a new independent subscriber that will see every item
+    /// produced by the source stream from this point on.
+    pub fn subscribe(&self) -> BroadcastReceiver<T> {
+        let mut guard = task::block_on(self.shared.lock());
+        let id = guard.subscribers.len();
+        guard.subscribers.push(Some(Subscriber {
+            queue: VecDeque::new(),
+            waker: None,
+            source_done: false,
+        }));
+        BroadcastReceiver {
+            shared: self.shared.clone(),
+            id,
+        }
+    }
+}
+
+/// A single subscriber's view of a [`Broadcast`] stream.
+pub struct BroadcastReceiver<T> {
+    shared: Arc<Mutex<Shared<T>>>,
+    id: usize,
+}
+
+impl<T> Stream for BroadcastReceiver<T>
+where
+    T: Clone + Send + 'static,
+{
+    type Item = T;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        // `lock()`'s own future registers a waker on contention, unlike
+        // `try_lock`, which would otherwise return `Pending` here with
+        // nothing that will ever poll this again once the pump task (or
+        // another subscriber) releases the lock.
+        let mut lock_fut = this.shared.lock();
+        pin_utils::pin_mut!(lock_fut);
+        let mut guard = match lock_fut.as_mut().poll(cx) {
+            Poll::Ready(guard) => guard,
+            Poll::Pending => return Poll::Pending,
+        };
+        let sub = guard.subscribers[this.id].as_mut().expect("subscriber removed");
+
+        if let Some(item) = sub.queue.pop_front() {
+            Poll::Ready(Some(item))
+        } else if sub.source_done {
+            Poll::Ready(None)
+        } else {
+            sub.waker = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stream::stream::StreamExt;
+
+    #[test]
+    fn a_subscriber_sees_every_item_in_order() {
+        crate::task::block_on(async {
+            let (sender, receiver) = crate::channel::unbounded();
+            let broadcast = Broadcast::new(receiver);
+            let mut sub = broadcast.subscribe();
+
+            sender.send(1).await.unwrap();
+            sender.send(2).await.unwrap();
+            sender.send(3).await.unwrap();
+            drop(sender);
+
+            assert_eq!(sub.next().await, Some(1));
+            assert_eq!(sub.next().await, Some(2));
+            assert_eq!(sub.next().await, Some(3));
+            assert_eq!(sub.next().await, None);
+        });
+    }
+
+    #[test]
+    fn multiple_subscribers_independently_see_every_item() {
+        crate::task::block_on(async {
+            let (sender, receiver) = crate::channel::unbounded();
+            let broadcast = Broadcast::new(receiver);
+            let mut a = broadcast.subscribe();
+            let mut b = broadcast.subscribe();
+
+            sender.send(1).await.unwrap();
+            sender.send(2).await.unwrap();
+            sender.send(3).await.unwrap();
+            drop(sender);
+
+            assert_eq!(a.next().await, Some(1));
+            assert_eq!(b.next().await, Some(1));
+            assert_eq!(a.collect::<Vec<_>>().await, alloc::vec![2, 3]);
+            assert_eq!(b.collect::<Vec<_>>().await, alloc::vec![2, 3]);
+        });
+    }
+
+    #[test]
+    fn a_late_subscriber_does_not_see_items_produced_before_it_subscribed() {
+        crate::task::block_on(async {
+            let (sender, receiver) = crate::channel::unbounded();
+            let broadcast = Broadcast::new(receiver);
+
+            sender.send(1).await.unwrap();
+            let mut early = broadcast.subscribe();
+            assert_eq!(early.next().await, Some(1));
+
+            sender.send(2).await.unwrap();
+            // The late subscriber joins after item 1 has already been
+            // delivered; it should only ever see item 2 onward.
+            let mut late = broadcast.subscribe();
+            drop(sender);
+
+            assert_eq!(late.next().await, Some(2));
+            assert_eq!(late.next().await, None);
+        });
+    }
+}
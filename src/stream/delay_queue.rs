@@ -0,0 +1,152 @@
+//! A queue where each item expires on its own schedule.
+
+use core::cmp::Reverse;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use core::time::Duration;
+
+use alloc::boxed::Box;
+use std::collections::{BinaryHeap, HashMap};
+use std::time::Instant;
+
+use crate::stream::Stream;
+use crate::task::sleep;
+
+/// A handle to an item previously inserted into a [`DelayQueue`],
+/// usable to [`remove`](DelayQueue::remove) or
+/// [`reset`](DelayQueue::reset) it before it expires.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Key(u64);
+
+/// A queue that yields each inserted item once its own deadline
+/// passes, rather than all items sharing one schedule.
+///
+/// Unlike spawning a `sleep` future per item, expiry tracking here is
+/// a single timer against the soonest deadline in a binary heap, so
+/// insertion and (by lazily discarding stale heap entries) removal
+/// are both `O(log n)` regardless of how many items are queued.
+pub struct DelayQueue<T> {
+    items: HashMap<u64, (Instant, T)>,
+    heap: BinaryHeap<Reverse<(Instant, u64)>>,
+    next_key: u64,
+    timer: Option<Pin<Box<dyn Future<Output = ()> + Send>>>,
+}
+
+impl<T> DelayQueue<T> {
+    /// Creates an empty queue.
+    pub fn new() -> Self {
+        DelayQueue {
+            items: HashMap::new(),
+            heap: BinaryHeap::new(),
+            next_key: 0,
+            timer: None,
+        }
+    }
+
+    /// Inserts `item`, to be yielded once `deadline` passes.
+    pub fn insert(&mut self, item: T, deadline: Instant) -> Key {
+        let key = self.next_key;
+        self.next_key += 1;
+        self.items.insert(key, (deadline, item));
+        self.heap.push(Reverse((deadline, key)));
+        // The new deadline might be sooner than whatever `timer` is
+        // currently counting down to; force it to be recomputed
+        // against the heap's new head on the next poll.
+        self.timer = None;
+        Key(key)
+    }
+
+    /// Inserts `item`, to be yielded after `delay` from now.
+    pub fn insert_after(&mut self, item: T, delay: Duration) -> Key {
+        self.insert(item, crate::time::now() + delay)
+    }
+
+    /// Removes `key`'s item before it expires, returning it.
+    ///
+    /// Returns `None` if `key` already expired (and was yielded) or
+    /// was already removed.
+    pub fn remove(&mut self, key: Key) -> Option<T> {
+        // The heap entry for `key` is left in place; it's discarded
+        // as stale the next time it reaches the front, since
+        // `items` no longer has a matching deadline for it.
+        self.items.remove(&key.0).map(|(_, item)| item)
+    }
+
+    /// Changes `key`'s deadline to `deadline`, without disturbing its
+    /// position in the queue otherwise.
+    ///
+    /// Does nothing if `key` already expired or was removed.
+    pub fn reset(&mut self, key: Key, deadline: Instant) {
+        if let Some(entry) = self.items.get_mut(&key.0) {
+            entry.0 = deadline;
+            self.heap.push(Reverse((deadline, key.0)));
+            self.timer = None;
+        }
+    }
+
+    /// The number of items still pending expiry.
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Whether there are no items pending expiry.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    fn poll_expired(&mut self, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        loop {
+            // Discard heap entries that no longer match their item's
+            // current deadline -- left behind by `remove` and
+            // `reset`.
+            while let Some(&Reverse((deadline, key))) = self.heap.peek() {
+                match self.items.get(&key) {
+                    Some(&(current_deadline, _)) if current_deadline == deadline => break,
+                    _ => {
+                        self.heap.pop();
+                    }
+                }
+            }
+
+            let (deadline, key) = match self.heap.peek() {
+                Some(&Reverse(entry)) => entry,
+                None => return Poll::Ready(None),
+            };
+
+            let now = crate::time::now();
+            if deadline <= now {
+                self.heap.pop();
+                let (_, item) = self.items.remove(&key).expect("heap entry matched a live item");
+                return Poll::Ready(Some(item));
+            }
+
+            match &mut self.timer {
+                Some(timer) => match timer.as_mut().poll(cx) {
+                    Poll::Ready(()) => self.timer = None,
+                    Poll::Pending => return Poll::Pending,
+                },
+                None => self.timer = Some(Box::pin(sleep(deadline - now))),
+            }
+        }
+    }
+}
+
+impl<T> Default for DelayQueue<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Stream for DelayQueue<T> {
+    type Item = T;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        // `timer` is a `Box`, which is always `Unpin`, and every
+        // other field is a plain owned collection -- there's no
+        // generic, possibly-`!Unpin` field to project through, so
+        // this doesn't need the `unsafe` pin-casting this crate's
+        // generic stream combinators require.
+        self.get_mut().poll_expired(cx)
+    }
+}
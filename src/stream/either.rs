@@ -0,0 +1,138 @@
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use crate::io::{self, Read, Write};
+use crate::stream::Stream;
+
+/// A value, future, stream, or I/O handle that is one of two possible
+/// concrete types.
+///
+/// This lets functions that branch and return structurally different
+/// types (for example a TLS vs. plain-TCP stream, or a filtered vs.
+/// unfiltered pipeline) return a single concrete `impl Trait` without
+/// boxing: `if use_tls { Either::Left(tls_stream) } else {
+/// Either::Right(tcp) }`. Use [`left`]/[`right`] to build one
+/// directly, or [`StreamExt::either`] to pick an arm with a `bool`.
+/// [`Future`], [`Stream`], [`Read`], and [`Write`] are all implemented
+/// whenever both arms implement them.
+///
+/// [`left`]: enum.Either.html#method.left
+/// [`right`]: enum.Either.html#method.right
+/// [`StreamExt::either`]: trait.StreamExt.html#method.either
+#[derive(Debug, Clone)]
+pub enum Either<A, B> {
+    /// The first stream variant.
+    Left(A),
+    /// The second stream variant.
+    Right(B),
+}
+
+impl<A, B> Either<A, B> {
+    /// Wraps a stream as the `Left` variant of an `Either`.
+    pub fn left(stream: A) -> Either<A, B>
+    where
+        A: Stream,
+        B: Stream<Item = A::Item>,
+    {
+        Either::Left(stream)
+    }
+
+    /// Wraps a stream as the `Right` variant of an `Either`.
+    pub fn right(stream: B) -> Either<A, B>
+    where
+        A: Stream,
+        B: Stream<Item = A::Item>,
+    {
+        Either::Right(stream)
+    }
+}
+
+impl<A, B> Stream for Either<A, B>
+where
+    A: Stream,
+    B: Stream<Item = A::Item>,
+{
+    type Item = A::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        unsafe {
+            match self.get_unchecked_mut() {
+                Either::Left(stream) => Pin::new_unchecked(stream).poll_next(cx),
+                Either::Right(stream) => Pin::new_unchecked(stream).poll_next(cx),
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self {
+            Either::Left(stream) => stream.size_hint(),
+            Either::Right(stream) => stream.size_hint(),
+        }
+    }
+}
+
+impl<A, B> Future for Either<A, B>
+where
+    A: Future,
+    B: Future<Output = A::Output>,
+{
+    type Output = A::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        unsafe {
+            match self.get_unchecked_mut() {
+                Either::Left(future) => Pin::new_unchecked(future).poll(cx),
+                Either::Right(future) => Pin::new_unchecked(future).poll(cx),
+            }
+        }
+    }
+}
+
+impl<A, B> Read for Either<A, B>
+where
+    A: Read,
+    B: Read,
+{
+    fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<io::Result<usize>> {
+        unsafe {
+            match self.get_unchecked_mut() {
+                Either::Left(reader) => Pin::new_unchecked(reader).poll_read(cx, buf),
+                Either::Right(reader) => Pin::new_unchecked(reader).poll_read(cx, buf),
+            }
+        }
+    }
+}
+
+impl<A, B> Write for Either<A, B>
+where
+    A: Write,
+    B: Write,
+{
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+        unsafe {
+            match self.get_unchecked_mut() {
+                Either::Left(writer) => Pin::new_unchecked(writer).poll_write(cx, buf),
+                Either::Right(writer) => Pin::new_unchecked(writer).poll_write(cx, buf),
+            }
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        unsafe {
+            match self.get_unchecked_mut() {
+                Either::Left(writer) => Pin::new_unchecked(writer).poll_flush(cx),
+                Either::Right(writer) => Pin::new_unchecked(writer).poll_flush(cx),
+            }
+        }
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        unsafe {
+            match self.get_unchecked_mut() {
+                Either::Left(writer) => Pin::new_unchecked(writer).poll_close(cx),
+                Either::Right(writer) => Pin::new_unchecked(writer).poll_close(cx),
+            }
+        }
+    }
+}
@@ -0,0 +1,36 @@
+use crate::sync::channel;
+use crate::sync::Receiver;
+use crate::task;
+
+/// Creates a stream that bridges a blocking, synchronous [`Iterator`] onto
+/// the async world.
+///
+/// `iter` is driven to completion on the blocking thread pool (see
+/// [`task::spawn_blocking`]), sending each item over a bounded channel as it
+/// is produced; the returned stream reads from the other end. This is the
+/// stream counterpart to [`task::spawn_blocking`] for iterators that can't
+/// be rewritten as an async generator -- a blocking database cursor or a
+/// synchronous parser over a large file, for example.
+///
+/// Backpressure is applied through the channel: the blocking thread stalls
+/// on `send` once the buffer (of capacity `buffer`) fills up, so a slow
+/// consumer throttles iteration rather than buffering unboundedly in
+/// memory.
+///
+/// [`Iterator`]: https://doc.rust-lang.org/std/iter/trait.Iterator.html
+/// [`task::spawn_blocking`]: ../task/fn.spawn_blocking.html
+pub fn from_blocking_iter<I>(iter: I, buffer: usize) -> Receiver<I::Item>
+where
+    I: Iterator + Send + 'static,
+    I::Item: Send + 'static,
+{
+    let (sender, receiver) = channel(buffer);
+
+    task::spawn_blocking(move || {
+        for item in iter {
+            task::block_on(sender.send(item));
+        }
+    });
+
+    receiver
+}
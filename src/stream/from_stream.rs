@@ -0,0 +1,69 @@
+use core::future::Future;
+use core::pin::Pin;
+
+use alloc::boxed::Box;
+
+use crate::stream::Stream;
+
+/// Trait for constructing a container from a stream, mirroring
+/// `std::iter::FromIterator`.
+///
+/// This trait is used to implement [`StreamExt::collect`]. Types which
+/// implement it can be produced by calling `.collect()` (or
+/// `T::from_stream(stream)` directly, though that should rarely be
+/// necessary) on a stream whose items match the `T` parameter.
+///
+/// [`FromStreamFuture`](FromStream::FromStreamFuture) is a generic
+/// associated type rather than a fixed `Pin<Box<dyn Future<...>>>`: an impl
+/// whose collection logic is a single accumulate-as-you-go pass -- building
+/// a `HashMap` or a `BinaryHeap` from a stream, say -- can express itself as
+/// one [`fold`](crate::stream::stream::StreamExt::fold) and return
+/// [`FoldFuture`](crate::stream::stream::FoldFuture) directly, with no
+/// allocation per call. An impl that has to compose another type's
+/// `from_stream` future (`Box<[T]>` building on `Vec<T>`'s, below) still
+/// has nothing concrete to name there and boxes instead.
+///
+/// [`StreamExt::collect`]: crate::stream::stream::StreamExt::collect
+pub trait FromStream<T>: Sized {
+    /// The future returned by [`from_stream`](FromStream::from_stream).
+    type FromStreamFuture<'a, S>: Future<Output = Self> + 'a
+    where
+        S: Stream<Item = T> + 'a;
+
+    /// Creates `Self` from a stream, consuming it in the process.
+    fn from_stream<'a, S>(stream: S) -> Self::FromStreamFuture<'a, S>
+    where
+        S: Stream<Item = T> + 'a;
+}
+
+/// Bridges implementors of the pre-GAT shape of [`FromStream`] -- the
+/// `Pin<Box<dyn Future<...>>>`-returning `from_stream` this trait used to
+/// require -- the same way [`BoxedSum`](crate::stream::sum::BoxedSum)
+/// bridges [`Sum`](crate::stream::sum::Sum). Only covers streams with no
+/// borrow of their own shorter than `'static`; sealed for the same reason
+/// `BoxedSum` is.
+pub trait BoxedFromStream<T>: private::Sealed<T> + Sized {
+    /// Creates `Self` from a stream, boxing the resulting future.
+    fn from_stream<'a, S>(stream: S) -> Pin<Box<dyn Future<Output = Self> + 'a>>
+    where
+        S: Stream<Item = T> + 'a;
+}
+
+mod private {
+    pub trait Sealed<T> {}
+    impl<U, T> Sealed<T> for U where U: super::BoxedFromStream<T> {}
+}
+
+impl<U, T> FromStream<T> for U
+where
+    U: BoxedFromStream<T>,
+{
+    type FromStreamFuture<'a, S> = Pin<Box<dyn Future<Output = Self> + 'a>> where S: Stream<Item = T> + 'a;
+
+    fn from_stream<'a, S>(stream: S) -> Self::FromStreamFuture<'a, S>
+    where
+        S: Stream<Item = T> + 'a,
+    {
+        <U as BoxedFromStream<T>>::from_stream(stream)
+    }
+}
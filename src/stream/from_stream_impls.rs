@@ -0,0 +1,96 @@
+use core::future::Future;
+use core::hash::Hash;
+use core::pin::Pin;
+
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::collections::BinaryHeap;
+use alloc::vec::Vec;
+
+use crate::stream::from_stream::FromStream;
+use crate::stream::stream::{FoldFuture, StreamExt};
+use crate::stream::Stream;
+
+#[cfg(feature = "std")]
+impl<K, V, H> FromStream<(K, V)> for std::collections::HashMap<K, V, H>
+where
+    K: Eq + Hash,
+    H: std::hash::BuildHasher + Default,
+{
+    type FromStreamFuture<'a, S> = FoldFuture<
+        S,
+        fn(std::collections::HashMap<K, V, H>, (K, V)) -> std::collections::HashMap<K, V, H>,
+        std::collections::HashMap<K, V, H>,
+    > where S: Stream<Item = (K, V)> + 'a;
+
+    fn from_stream<'a, S>(stream: S) -> Self::FromStreamFuture<'a, S>
+    where
+        S: Stream<Item = (K, V)> + 'a,
+    {
+        fn insert<K: Eq + Hash, V, H: std::hash::BuildHasher + Default>(
+            mut map: std::collections::HashMap<K, V, H>,
+            (k, v): (K, V),
+        ) -> std::collections::HashMap<K, V, H> {
+            map.insert(k, v);
+            map
+        }
+        stream.fold(
+            std::collections::HashMap::with_hasher(H::default()),
+            insert::<K, V, H>
+                as fn(std::collections::HashMap<K, V, H>, (K, V)) -> std::collections::HashMap<K, V, H>,
+        )
+    }
+}
+
+impl<T> FromStream<T> for BinaryHeap<T>
+where
+    T: Ord,
+{
+    type FromStreamFuture<'a, S> = FoldFuture<S, fn(BinaryHeap<T>, T) -> BinaryHeap<T>, BinaryHeap<T>>
+    where
+        S: Stream<Item = T> + 'a;
+
+    fn from_stream<'a, S>(stream: S) -> Self::FromStreamFuture<'a, S>
+    where
+        S: Stream<Item = T> + 'a,
+    {
+        fn push<T: Ord>(mut heap: BinaryHeap<T>, item: T) -> BinaryHeap<T> {
+            heap.push(item);
+            heap
+        }
+        stream.fold(BinaryHeap::new(), push::<T> as fn(BinaryHeap<T>, T) -> BinaryHeap<T>)
+    }
+}
+
+/// Unlike the impls above, this composes `Vec<T>`'s own `from_stream`
+/// future rather than folding in place, and `Vec`'s `FromStream` impl --
+/// like the ambient `Reactor` this crate's I/O wrappers assume -- isn't
+/// part of this snapshot (it lives wherever the rest of the standard
+/// collection impls for `FromStream` do), so there's no concrete future
+/// type to name here even in principle. Boxes for that reason.
+impl<T> FromStream<T> for Box<[T]> {
+    type FromStreamFuture<'a, S> = Pin<Box<dyn Future<Output = Self> + 'a>> where S: Stream<Item = T> + 'a;
+
+    fn from_stream<'a, S>(stream: S) -> Self::FromStreamFuture<'a, S>
+    where
+        S: Stream<Item = T> + 'a,
+    {
+        Box::pin(async move { Vec::from_stream(stream).await.into_boxed_slice() })
+    }
+}
+
+/// Boxes for the same reason [`Box<[T]>`](FromStream)'s impl above does:
+/// it composes `Vec<T>`'s `from_stream` future, which isn't nameable here.
+impl<'c, T> FromStream<T> for Cow<'c, [T]>
+where
+    T: Clone,
+{
+    type FromStreamFuture<'a, S> = Pin<Box<dyn Future<Output = Self> + 'a>> where S: Stream<Item = T> + 'a;
+
+    fn from_stream<'a, S>(stream: S) -> Self::FromStreamFuture<'a, S>
+    where
+        S: Stream<Item = T> + 'a,
+    {
+        Box::pin(async move { Cow::Owned(Vec::from_stream(stream).await) })
+    }
+}
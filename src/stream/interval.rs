@@ -0,0 +1,128 @@
+//! A stream that yields at a fixed period.
+//!
+//! Plain period-based scheduling has to decide what happens when a
+//! tick handler runs long enough that one or more ticks are missed
+//! entirely -- [`MissedTickBehavior`] makes that choice explicit
+//! instead of silently picking one.
+
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use core::time::Duration;
+
+use alloc::boxed::Box;
+
+use std::time::Instant;
+
+use crate::stream::Stream;
+use crate::task::sleep;
+
+/// What [`Interval`] does when a tick handler takes so long that one
+/// or more ticks were missed while it ran.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MissedTickBehavior {
+    /// Fire every missed tick back-to-back with no delay, until the
+    /// schedule has caught back up to the original period grid. Keeps
+    /// the total tick *count* accurate at the cost of a burst.
+    Burst,
+    /// Drop every missed tick and resume ticking every `period`
+    /// starting from whenever the handler actually returned. Shifts
+    /// the phase of the schedule but never bursts.
+    Delay,
+    /// Drop every missed tick, but resume on the original period
+    /// grid (the next tick that's still in the future), rather than
+    /// rephasing around the late handler the way `Delay` does.
+    Skip,
+}
+
+impl Default for MissedTickBehavior {
+    fn default() -> Self {
+        MissedTickBehavior::Burst
+    }
+}
+
+/// Stream returned by [`interval`] and [`interval_at`].
+pub struct Interval {
+    period: Duration,
+    next_deadline: Instant,
+    missed_tick_behavior: MissedTickBehavior,
+    timer: Pin<Box<dyn Future<Output = ()> + Send>>,
+}
+
+/// Yields `Instant`s every `period`, starting `period` from now.
+pub fn interval(period: Duration) -> Interval {
+    interval_at(crate::time::now() + period, period)
+}
+
+/// Yields `Instant`s every `period`, with the first tick at `first`.
+pub fn interval_at(first: Instant, period: Duration) -> Interval {
+    Interval {
+        period,
+        next_deadline: first,
+        missed_tick_behavior: MissedTickBehavior::default(),
+        timer: Box::pin(sleep(first.saturating_duration_since(crate::time::now()))),
+    }
+}
+
+impl Interval {
+    /// Changes how this interval catches up after a missed tick. Only
+    /// affects ticks scheduled after this call.
+    pub fn set_missed_tick_behavior(&mut self, behavior: MissedTickBehavior) {
+        self.missed_tick_behavior = behavior;
+    }
+
+    /// Reschedules the next tick to fire `period` from now, discarding
+    /// whatever was previously scheduled.
+    pub fn reset(&mut self) {
+        let period = self.period;
+        self.reset_at(crate::time::now() + period);
+    }
+
+    /// Reschedules the next tick to fire at `deadline`, discarding
+    /// whatever was previously scheduled.
+    pub fn reset_at(&mut self, deadline: Instant) {
+        self.next_deadline = deadline;
+        self.timer = Box::pin(sleep(deadline.saturating_duration_since(crate::time::now())));
+    }
+
+    /// Waits for the next tick, usable outside of a `Stream` context.
+    pub async fn tick(&mut self) -> Instant {
+        core::future::poll_fn(|cx| self.poll_tick(cx)).await
+    }
+
+    fn poll_tick(&mut self, cx: &mut Context<'_>) -> Poll<Instant> {
+        match self.timer.as_mut().poll(cx) {
+            Poll::Pending => Poll::Pending,
+            Poll::Ready(()) => {
+                let fired_at = self.next_deadline;
+                let now = crate::time::now();
+                self.next_deadline = match self.missed_tick_behavior {
+                    MissedTickBehavior::Burst => self.next_deadline + self.period,
+                    MissedTickBehavior::Delay => now + self.period,
+                    MissedTickBehavior::Skip => {
+                        let mut next = self.next_deadline + self.period;
+                        while next <= now {
+                            next += self.period;
+                        }
+                        next
+                    }
+                };
+                self.timer = Box::pin(sleep(self.next_deadline.saturating_duration_since(now)));
+                Poll::Ready(fired_at)
+            }
+        }
+    }
+}
+
+impl Stream for Interval {
+    type Item = Instant;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Instant>> {
+        // `Interval` holds no self-referential or externally-pinned
+        // state -- `timer` is a `Box`, which is always `Unpin` no
+        // matter what it points to -- so projecting through the pin
+        // is safe without the `unsafe` pin-casting this crate's other
+        // stream combinators need for their generic fields.
+        self.get_mut().poll_tick(cx).map(Some)
+    }
+}
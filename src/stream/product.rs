@@ -0,0 +1,289 @@
+use core::future::Future;
+use core::pin::Pin;
+
+use alloc::boxed::Box;
+
+use crate::stream::stream::FoldFuture;
+use crate::stream::Stream;
+
+/// Trait to represent types that can be created by multiplying the elements
+/// of a stream.
+///
+/// This trait is used to implement the [`product`] method on streams. Types
+/// which implement the trait can be generated by the [`product`] method.
+/// Like [`FromStream`] this trait should rarely be called directly and
+/// instead interacted with through [`StreamExt::product`].
+///
+/// [`ProductFuture`](Product::ProductFuture) is a generic associated type
+/// rather than a fixed `Pin<Box<dyn Future<...>>>`, for the same reason
+/// [`Sum::SumFuture`](crate::stream::sum::Sum::SumFuture) is: every impl
+/// below can express itself as a single
+/// [`fold`](crate::stream::stream::StreamExt::fold), so it returns
+/// [`FoldFuture`] directly rather than paying for a `Box::pin`.
+///
+/// [`product`]: trait.Product.html#tymethod.product
+/// [`FromStream`]: trait.FromStream.html
+/// [`StreamExt::product`]: trait.StreamExt.html#method.product
+pub trait Product<A = Self>: Sized {
+    /// The future returned by [`product`](Product::product).
+    type ProductFuture<'a, S>: Future<Output = Self> + 'a
+    where
+        S: Stream<Item = A> + 'a;
+
+    /// Method which takes a stream and generates `Self` from the elements by
+    /// multiplying the items.
+    fn product<'a, S>(stream: S) -> Self::ProductFuture<'a, S>
+    where
+        S: Stream<Item = A> + 'a;
+}
+
+/// Bridges implementors of the pre-GAT shape of [`Product`] -- the
+/// `Pin<Box<dyn Future<...>>>`-returning `product` this trait used to
+/// require -- the same way [`BoxedSum`](crate::stream::sum::BoxedSum)
+/// bridges [`Sum`]. Only covers streams with no borrow of their own shorter
+/// than `'static`; sealed for the same reason `BoxedSum` is.
+pub trait BoxedProduct<A = Self>: private::Sealed<A> + Sized {
+    /// Method which takes a stream and generates `Self` from the elements by
+    /// multiplying the items, boxing the resulting future.
+    fn product<'a, S>(stream: S) -> Pin<Box<dyn Future<Output = Self> + 'a>>
+    where
+        S: Stream<Item = A> + 'a;
+}
+
+mod private {
+    pub trait Sealed<A> {}
+    impl<T, A> Sealed<A> for T where T: super::BoxedProduct<A> {}
+}
+
+impl<T, A> Product<A> for T
+where
+    T: BoxedProduct<A>,
+{
+    type ProductFuture<'a, S> = Pin<Box<dyn Future<Output = Self> + 'a>> where S: Stream<Item = A> + 'a;
+
+    fn product<'a, S>(stream: S) -> Self::ProductFuture<'a, S>
+    where
+        S: Stream<Item = A> + 'a,
+    {
+        <T as BoxedProduct<A>>::product(stream)
+    }
+}
+
+use crate::stream::stream::StreamExt;
+use core::num::Wrapping;
+use core::ops::Mul;
+
+macro_rules! integer_product {
+    (@impls $one: expr, $($a:ty)*) => ($(
+        #[cfg(not(feature = "num-traits"))]
+        impl Product for $a {
+            type ProductFuture<'a, S> = FoldFuture<S, fn($a, $a) -> $a, $a> where S: Stream<Item = $a> + 'a;
+
+            fn product<'a, S>(stream: S) -> Self::ProductFuture<'a, S>
+            where
+                S: Stream<Item = $a> + 'a,
+            {
+                stream.fold($one, Mul::mul as fn($a, $a) -> $a)
+            }
+        }
+        impl<'a> Product<&'a $a> for $a {
+            type ProductFuture<'b, S> = FoldFuture<S, fn($a, &'a $a) -> $a, $a> where S: Stream<Item = &'a $a> + 'b;
+
+            fn product<'b, S>(stream: S) -> Self::ProductFuture<'b, S>
+            where
+                S: Stream<Item = &'a $a> + 'b,
+            {
+                stream.fold($one, Mul::mul as fn($a, &'a $a) -> $a)
+            }
+        }
+    )*);
+    ($($a:ty)*) => (
+        integer_product!(@impls 1, $($a)*);
+        integer_product!(@impls Wrapping(1), $(Wrapping<$a>)*);
+    );
+}
+
+macro_rules! float_product {
+    ($($a:ty)*) => ($(
+        #[cfg(not(feature = "num-traits"))]
+        impl Product for $a {
+            type ProductFuture<'a, S> = FoldFuture<S, fn($a, $a) -> $a, $a> where S: Stream<Item = $a> + 'a;
+
+            fn product<'a, S>(stream: S) -> Self::ProductFuture<'a, S>
+                where S: Stream<Item = $a> + 'a,
+            {
+                stream.fold(1.0, (|a: $a, b: $a| a * b) as fn($a, $a) -> $a)
+            }
+        }
+        impl<'a> Product<&'a $a> for $a {
+            type ProductFuture<'b, S> = FoldFuture<S, fn($a, &'a $a) -> $a, $a> where S: Stream<Item = &'a $a> + 'b;
+
+            fn product<'b, S>(stream: S) -> Self::ProductFuture<'b, S>
+                where S: Stream<Item = &'a $a> + 'b,
+            {
+                stream.fold(1.0, (|a: $a, b: &'a $a| a * b) as fn($a, &'a $a) -> $a)
+            }
+        }
+    )*);
+    ($($a:ty)*) => (
+        float_product!(@impls 1.0, $($a)*);
+        float_product!(@impls Wrapping(1.0), $(Wrapping<$a>)*);
+    );
+}
+
+integer_product! { i8 i16 i32 i64 i128 isize u8 u16 u32 u64 u128 usize }
+float_product! { f32 f64 }
+
+/// Blanket impl of [`Product`] for any type with a multiplicative identity,
+/// gated behind the `num-traits` feature.
+///
+/// This lets [`Stream::product`] work for arbitrary-precision integers,
+/// decimals, and other domain-specific numeric types, not just the
+/// primitives above.
+///
+/// [`Product`]: trait.Product.html
+/// [`Stream::product`]: trait.Stream.html#method.product
+#[cfg(feature = "num-traits")]
+impl<T> Product for T
+where
+    T: Mul<Output = T> + num_traits::One,
+{
+    type ProductFuture<'a, S> = FoldFuture<S, fn(T, T) -> T, T> where S: Stream<Item = T> + 'a;
+
+    fn product<'a, S>(stream: S) -> Self::ProductFuture<'a, S>
+    where
+        S: Stream<Item = T> + 'a,
+    {
+        stream.fold(T::one(), Mul::mul as fn(T, T) -> T)
+    }
+}
+
+/// Trait to represent types that can be created by multiplying the elements
+/// of a stream, short-circuiting to `None` as soon as a multiplication
+/// overflows.
+///
+/// This trait is used to implement the [`checked_product`] method on
+/// streams. Unlike [`TryProduct`], which reports overflow as an `Err`, this
+/// mirrors the `checked_*` naming used by the primitive integer types
+/// themselves. This trait should rarely be called directly and instead
+/// interacted with through [`StreamExt::checked_product`].
+///
+/// Unlike [`Product`], this one still always boxes: short-circuiting
+/// partway through the stream on overflow isn't expressible as a single
+/// `fold`.
+///
+/// [`checked_product`]: trait.CheckedProduct.html#tymethod.checked_product
+/// [`TryProduct`]: trait.TryProduct.html
+/// [`StreamExt::checked_product`]: trait.StreamExt.html#method.checked_product
+pub trait CheckedProduct<A = Self>: Sized {
+    /// Method which takes a stream and generates `Self` from the elements by
+    /// multiplying the items, resolving to `None` the first time a
+    /// multiplication would overflow.
+    fn checked_product<'a, S>(stream: S) -> Pin<Box<dyn Future<Output = Option<Self>> + 'a>>
+    where
+        S: Stream<Item = A> + 'a;
+}
+
+macro_rules! integer_checked_product {
+    ($($a:ty)*) => ($(
+        impl CheckedProduct for $a {
+            fn checked_product<'a, S>(
+                stream: S,
+            ) -> Pin<Box<dyn Future<Output = Option<Self>> + 'a>>
+            where
+                S: Stream<Item = $a> + 'a,
+            {
+                Box::pin(async move {
+                    pin_utils::pin_mut!(stream);
+                    let mut acc: $a = 1;
+                    while let Some(x) = stream.next().await {
+                        acc = acc.checked_mul(x)?;
+                    }
+                    Some(acc)
+                })
+            }
+        }
+        impl<'a> CheckedProduct<&'a $a> for $a {
+            fn checked_product<'b, S>(
+                stream: S,
+            ) -> Pin<Box<dyn Future<Output = Option<Self>> + 'b>>
+            where
+                S: Stream<Item = &'a $a> + 'b,
+            {
+                Box::pin(async move {
+                    pin_utils::pin_mut!(stream);
+                    let mut acc: $a = 1;
+                    while let Some(x) = stream.next().await {
+                        acc = acc.checked_mul(*x)?;
+                    }
+                    Some(acc)
+                })
+            }
+        }
+    )*);
+}
+
+integer_checked_product! { i8 i16 i32 i64 i128 isize u8 u16 u32 u64 u128 usize }
+
+use alloc::vec::Vec;
+
+/// Takes each element in the stream: if it is `None`, no further elements
+/// are pulled and the overall result is `None`; otherwise the inner values
+/// are collected into `Some` of a [`Product`], mirroring
+/// `core::iter::Product<Option<A>>`.
+///
+/// This recurses into `T::product` of a freshly built stream rather than
+/// folding in place, so -- unlike the impls above -- there's no single
+/// concrete, unboxed future to name here.
+///
+/// [`Product`]: trait.Product.html
+impl<T, U> Product<Option<U>> for Option<T>
+where
+    T: Product<U>,
+{
+    type ProductFuture<'a, S> = Pin<Box<dyn Future<Output = Self> + 'a>> where S: Stream<Item = Option<U>> + 'a;
+
+    fn product<'a, S>(stream: S) -> Self::ProductFuture<'a, S>
+    where
+        S: Stream<Item = Option<U>> + 'a,
+    {
+        Box::pin(async move {
+            pin_utils::pin_mut!(stream);
+            let mut items = Vec::new();
+            while let Some(item) = stream.next().await {
+                items.push(item?);
+            }
+            Some(T::product(crate::stream::from_iter(items)).await)
+        })
+    }
+}
+
+/// Takes each element in the stream: if it is `Err`, no further elements are
+/// pulled and the overall result is that `Err`; otherwise the `Ok` values
+/// are collected into `Ok` of a [`Product`], mirroring
+/// `core::iter::Product<Result<A, E>>`.
+///
+/// Like the `Option` impl above, this recurses into `T::product` rather
+/// than folding in place, so it stays boxed.
+///
+/// [`Product`]: trait.Product.html
+impl<T, U, E> Product<Result<U, E>> for Result<T, E>
+where
+    T: Product<U>,
+{
+    type ProductFuture<'a, S> = Pin<Box<dyn Future<Output = Self> + 'a>> where S: Stream<Item = Result<U, E>> + 'a;
+
+    fn product<'a, S>(stream: S) -> Self::ProductFuture<'a, S>
+    where
+        S: Stream<Item = Result<U, E>> + 'a,
+    {
+        Box::pin(async move {
+            pin_utils::pin_mut!(stream);
+            let mut items = Vec::new();
+            while let Some(item) = stream.next().await {
+                items.push(item?);
+            }
+            Ok(T::product(crate::stream::from_iter(items)).await)
+        })
+    }
+}
@@ -0,0 +1,77 @@
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use alloc::vec::Vec;
+
+use crate::stream::Stream;
+
+/// Stream returned by [`select_all`].
+#[derive(Debug)]
+pub struct SelectAll<S> {
+    streams: Vec<S>,
+    // Round-robin cursor into `streams`, so no single source stream can
+    // starve the others by always being polled first.
+    next: usize,
+}
+
+/// Merges a collection of streams of the same type into a single stream
+/// that polls all of them and yields items as soon as any one of them
+/// produces one.
+///
+/// The merged stream ends once every input stream has ended. Polling starts
+/// from a different stream each time (round-robin), so no single source can
+/// starve the others.
+pub fn select_all<I>(streams: I) -> SelectAll<I::Item>
+where
+    I: IntoIterator,
+    I::Item: Stream + Unpin,
+{
+    SelectAll {
+        streams: streams.into_iter().collect(),
+        next: 0,
+    }
+}
+
+impl<S> Stream for SelectAll<S>
+where
+    S: Stream + Unpin,
+{
+    type Item = S::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = unsafe { self.get_unchecked_mut() };
+
+        if this.streams.is_empty() {
+            return Poll::Ready(None);
+        }
+
+        let len = this.streams.len();
+        let start = this.next % len;
+        let mut result = None;
+        let mut exhausted = Vec::new();
+
+        for offset in 0..len {
+            let i = (start + offset) % len;
+            match Pin::new(&mut this.streams[i]).poll_next(cx) {
+                Poll::Ready(Some(item)) => {
+                    this.next = i + 1;
+                    result = Some(item);
+                    break;
+                }
+                Poll::Ready(None) => exhausted.push(i),
+                Poll::Pending => {}
+            }
+        }
+
+        exhausted.sort_unstable_by(|a, b| b.cmp(a));
+        for i in exhausted {
+            this.streams.swap_remove(i);
+        }
+
+        match result {
+            Some(item) => Poll::Ready(Some(item)),
+            None if this.streams.is_empty() => Poll::Ready(None),
+            None => Poll::Pending,
+        }
+    }
+}
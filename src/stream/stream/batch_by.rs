@@ -0,0 +1,85 @@
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::stream::Stream;
+
+/// Stream for the [`batch_by`](super::StreamExt::batch_by) method.
+pub struct BatchBy<S, F>
+where
+    S: Stream,
+{
+    stream: S,
+    pred: F,
+    current: Option<Vec<S::Item>>,
+    pending: Option<S::Item>,
+    stream_ended: bool,
+}
+
+impl<S, F> BatchBy<S, F>
+where
+    S: Stream,
+{
+    pub(super) fn new(stream: S, pred: F) -> Self {
+        Self {
+            stream,
+            pred,
+            current: None,
+            pending: None,
+            stream_ended: false,
+        }
+    }
+}
+
+impl<S, F> Stream for BatchBy<S, F>
+where
+    S: Stream,
+    F: FnMut(&S::Item, &S::Item) -> bool,
+{
+    type Item = Vec<S::Item>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = unsafe { self.get_unchecked_mut() };
+        let mut stream = unsafe { Pin::new_unchecked(&mut this.stream) };
+
+        if this.current.is_none() {
+            if this.stream_ended {
+                return Poll::Ready(None);
+            }
+            let item = match this.pending.take() {
+                Some(item) => item,
+                None => match stream.as_mut().poll_next(cx) {
+                    Poll::Ready(Some(item)) => item,
+                    Poll::Ready(None) => {
+                        this.stream_ended = true;
+                        return Poll::Ready(None);
+                    }
+                    Poll::Pending => return Poll::Pending,
+                },
+            };
+            this.current = Some(vec![item]);
+        }
+
+        loop {
+            match stream.as_mut().poll_next(cx) {
+                Poll::Ready(Some(item)) => {
+                    let batch = this.current.as_mut().unwrap();
+                    let last = batch.last().unwrap();
+                    if (this.pred)(last, &item) {
+                        batch.push(item);
+                    } else {
+                        this.pending = Some(item);
+                        return Poll::Ready(this.current.take());
+                    }
+                }
+                Poll::Ready(None) => {
+                    this.stream_ended = true;
+                    return Poll::Ready(this.current.take());
+                }
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
@@ -0,0 +1,77 @@
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
+use crate::stream::Stream;
+
+/// Stream for the [`buffer_unordered`](super::StreamExt::buffer_unordered)
+/// method.
+pub struct BufferUnordered<S>
+where
+    S: Stream,
+    S::Item: Future,
+{
+    stream: S,
+    in_flight: Vec<Pin<Box<S::Item>>>,
+    cap: usize,
+    done: bool,
+}
+
+impl<S> BufferUnordered<S>
+where
+    S: Stream,
+    S::Item: Future,
+{
+    pub(super) fn new(stream: S, cap: usize) -> Self {
+        assert!(cap > 0, "buffer size must be greater than zero");
+        Self {
+            stream,
+            in_flight: Vec::with_capacity(cap),
+            cap,
+            done: false,
+        }
+    }
+}
+
+impl<S> Stream for BufferUnordered<S>
+where
+    S: Stream,
+    S::Item: Future,
+{
+    type Item = <S::Item as Future>::Output;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = unsafe { self.get_unchecked_mut() };
+        let mut stream = unsafe { Pin::new_unchecked(&mut this.stream) };
+
+        while !this.done && this.in_flight.len() < this.cap {
+            match stream.as_mut().poll_next(cx) {
+                Poll::Ready(Some(fut)) => this.in_flight.push(Box::pin(fut)),
+                Poll::Ready(None) => {
+                    this.done = true;
+                    break;
+                }
+                Poll::Pending => break,
+            }
+        }
+
+        // Order doesn't matter here, so the first future to complete (in
+        // whatever order polling finds it) is emitted immediately instead
+        // of waiting for earlier ones, unlike `buffered`.
+        for i in 0..this.in_flight.len() {
+            if let Poll::Ready(output) = this.in_flight[i].as_mut().poll(cx) {
+                this.in_flight.swap_remove(i);
+                return Poll::Ready(Some(output));
+            }
+        }
+
+        if this.in_flight.is_empty() && this.done {
+            Poll::Ready(None)
+        } else {
+            Poll::Pending
+        }
+    }
+}
@@ -0,0 +1,86 @@
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use alloc::boxed::Box;
+use alloc::collections::VecDeque;
+
+use crate::stream::Stream;
+
+enum Slot<Fut: Future> {
+    Pending(Pin<Box<Fut>>),
+    Ready(Fut::Output),
+}
+
+/// Stream for the [`buffered`](super::StreamExt::buffered) method.
+pub struct Buffered<S>
+where
+    S: Stream,
+    S::Item: Future,
+{
+    stream: S,
+    in_flight: VecDeque<Slot<S::Item>>,
+    cap: usize,
+    done: bool,
+}
+
+impl<S> Buffered<S>
+where
+    S: Stream,
+    S::Item: Future,
+{
+    pub(super) fn new(stream: S, cap: usize) -> Self {
+        assert!(cap > 0, "buffer size must be greater than zero");
+        Self {
+            stream,
+            in_flight: VecDeque::with_capacity(cap),
+            cap,
+            done: false,
+        }
+    }
+}
+
+impl<S> Stream for Buffered<S>
+where
+    S: Stream,
+    S::Item: Future,
+{
+    type Item = <S::Item as Future>::Output;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = unsafe { self.get_unchecked_mut() };
+        let mut stream = unsafe { Pin::new_unchecked(&mut this.stream) };
+
+        while !this.done && this.in_flight.len() < this.cap {
+            match stream.as_mut().poll_next(cx) {
+                Poll::Ready(Some(fut)) => this.in_flight.push_back(Slot::Pending(Box::pin(fut))),
+                Poll::Ready(None) => {
+                    this.done = true;
+                    break;
+                }
+                Poll::Pending => break,
+            }
+        }
+
+        // Drive every in-flight future forward, regardless of position --
+        // only the front slot's readiness is what we're allowed to emit, but
+        // everything behind it still needs to make progress while it waits.
+        for slot in this.in_flight.iter_mut() {
+            if let Slot::Pending(fut) = slot {
+                if let Poll::Ready(output) = fut.as_mut().poll(cx) {
+                    *slot = Slot::Ready(output);
+                }
+            }
+        }
+
+        match this.in_flight.front() {
+            Some(Slot::Ready(_)) => match this.in_flight.pop_front() {
+                Some(Slot::Ready(output)) => Poll::Ready(Some(output)),
+                _ => unreachable!(),
+            },
+            Some(Slot::Pending(_)) => Poll::Pending,
+            None if this.done => Poll::Ready(None),
+            None => Poll::Pending,
+        }
+    }
+}
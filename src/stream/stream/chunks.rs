@@ -0,0 +1,64 @@
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use alloc::vec::Vec;
+
+use crate::stream::Stream;
+
+/// Stream for the [`chunks`](super::StreamExt::chunks) method.
+#[derive(Debug)]
+pub struct Chunks<S>
+where
+    S: Stream,
+{
+    stream: S,
+    cap: usize,
+    buf: Vec<S::Item>,
+}
+
+impl<S> Chunks<S>
+where
+    S: Stream,
+{
+    pub(super) fn new(stream: S, cap: usize) -> Self {
+        assert!(cap > 0, "chunk size must be greater than zero");
+        Self {
+            stream,
+            cap,
+            buf: Vec::with_capacity(cap),
+        }
+    }
+}
+
+impl<S> Stream for Chunks<S>
+where
+    S: Stream,
+{
+    type Item = Vec<S::Item>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = unsafe { self.get_unchecked_mut() };
+        let mut stream = unsafe { Pin::new_unchecked(&mut this.stream) };
+
+        loop {
+            match stream.as_mut().poll_next(cx) {
+                Poll::Ready(Some(item)) => {
+                    this.buf.push(item);
+                    if this.buf.len() >= this.cap {
+                        return Poll::Ready(Some(core::mem::replace(
+                            &mut this.buf,
+                            Vec::with_capacity(this.cap),
+                        )));
+                    }
+                }
+                Poll::Ready(None) => {
+                    if this.buf.is_empty() {
+                        return Poll::Ready(None);
+                    }
+                    return Poll::Ready(Some(core::mem::take(&mut this.buf)));
+                }
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
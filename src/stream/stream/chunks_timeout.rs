@@ -0,0 +1,91 @@
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use core::time::Duration;
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
+use crate::stream::Stream;
+use crate::task::sleep;
+
+/// Stream for the [`chunks_timeout`](super::StreamExt::chunks_timeout)
+/// method.
+pub struct ChunksTimeout<S>
+where
+    S: Stream,
+{
+    stream: S,
+    cap: usize,
+    duration: Duration,
+    buf: Vec<S::Item>,
+    deadline: Option<Pin<Box<dyn Future<Output = ()> + Send>>>,
+}
+
+impl<S> ChunksTimeout<S>
+where
+    S: Stream,
+{
+    pub(super) fn new(stream: S, cap: usize, duration: Duration) -> Self {
+        assert!(cap > 0, "chunk size must be greater than zero");
+        Self {
+            stream,
+            cap,
+            duration,
+            buf: Vec::with_capacity(cap),
+            deadline: None,
+        }
+    }
+}
+
+impl<S> Stream for ChunksTimeout<S>
+where
+    S: Stream,
+{
+    type Item = Vec<S::Item>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = unsafe { self.get_unchecked_mut() };
+        let mut stream = unsafe { Pin::new_unchecked(&mut this.stream) };
+
+        loop {
+            match stream.as_mut().poll_next(cx) {
+                Poll::Ready(Some(item)) => {
+                    if this.buf.is_empty() {
+                        this.deadline = Some(Box::pin(sleep(this.duration)));
+                    }
+                    this.buf.push(item);
+                    if this.buf.len() >= this.cap {
+                        this.deadline = None;
+                        return Poll::Ready(Some(core::mem::replace(
+                            &mut this.buf,
+                            Vec::with_capacity(this.cap),
+                        )));
+                    }
+                }
+                Poll::Ready(None) => {
+                    this.deadline = None;
+                    if this.buf.is_empty() {
+                        return Poll::Ready(None);
+                    }
+                    return Poll::Ready(Some(core::mem::take(&mut this.buf)));
+                }
+                Poll::Pending => break,
+            }
+        }
+
+        if let Some(deadline) = this.deadline.as_mut() {
+            if deadline.as_mut().poll(cx).is_ready() {
+                this.deadline = None;
+                if !this.buf.is_empty() {
+                    return Poll::Ready(Some(core::mem::replace(
+                        &mut this.buf,
+                        Vec::with_capacity(this.cap),
+                    )));
+                }
+            }
+        }
+
+        Poll::Pending
+    }
+}
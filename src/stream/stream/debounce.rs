@@ -0,0 +1,81 @@
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use core::time::Duration;
+
+use alloc::boxed::Box;
+
+use crate::stream::Stream;
+use crate::task::sleep;
+
+/// Stream for the [`debounce`](super::StreamExt::debounce) method.
+pub struct Debounce<S>
+where
+    S: Stream,
+{
+    stream: S,
+    duration: Duration,
+    pending: Option<S::Item>,
+    timer: Option<Pin<Box<dyn Future<Output = ()> + Send>>>,
+    stream_ended: bool,
+}
+
+impl<S> Debounce<S>
+where
+    S: Stream,
+{
+    pub(super) fn new(stream: S, duration: Duration) -> Self {
+        Self {
+            stream,
+            duration,
+            pending: None,
+            timer: None,
+            stream_ended: false,
+        }
+    }
+}
+
+impl<S> Stream for Debounce<S>
+where
+    S: Stream,
+{
+    type Item = S::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = unsafe { self.get_unchecked_mut() };
+        let mut stream = unsafe { Pin::new_unchecked(&mut this.stream) };
+
+        while !this.stream_ended {
+            match stream.as_mut().poll_next(cx) {
+                Poll::Ready(Some(item)) => {
+                    this.pending = Some(item);
+                    this.timer = Some(Box::pin(sleep(this.duration)));
+                }
+                Poll::Ready(None) => {
+                    this.stream_ended = true;
+                    this.timer = None;
+                    break;
+                }
+                Poll::Pending => break,
+            }
+        }
+
+        if this.stream_ended {
+            return match this.pending.take() {
+                Some(item) => Poll::Ready(Some(item)),
+                None => Poll::Ready(None),
+            };
+        }
+
+        if let Some(timer) = this.timer.as_mut() {
+            if timer.as_mut().poll(cx).is_ready() {
+                this.timer = None;
+                if let Some(item) = this.pending.take() {
+                    return Poll::Ready(Some(item));
+                }
+            }
+        }
+
+        Poll::Pending
+    }
+}
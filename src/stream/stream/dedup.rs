@@ -0,0 +1,103 @@
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use crate::stream::Stream;
+
+/// Stream for the [`dedup`](super::StreamExt::dedup) method.
+#[derive(Debug)]
+pub struct Dedup<S>
+where
+    S: Stream,
+{
+    stream: S,
+    last: Option<S::Item>,
+}
+
+impl<S> Dedup<S>
+where
+    S: Stream,
+{
+    pub(super) fn new(stream: S) -> Self {
+        Self { stream, last: None }
+    }
+}
+
+impl<S> Stream for Dedup<S>
+where
+    S: Stream,
+    S::Item: PartialEq + Clone,
+{
+    type Item = S::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = unsafe { self.get_unchecked_mut() };
+        let mut stream = unsafe { Pin::new_unchecked(&mut this.stream) };
+
+        loop {
+            match stream.as_mut().poll_next(cx) {
+                Poll::Ready(Some(item)) => {
+                    if this.last.as_ref() == Some(&item) {
+                        continue;
+                    }
+                    this.last = Some(item.clone());
+                    return Poll::Ready(Some(item));
+                }
+                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+/// Stream for the [`dedup_by_key`](super::StreamExt::dedup_by_key) method.
+#[derive(Debug)]
+pub struct DedupByKey<S, F, K>
+where
+    S: Stream,
+{
+    stream: S,
+    f: F,
+    last_key: Option<K>,
+}
+
+impl<S, F, K> DedupByKey<S, F, K>
+where
+    S: Stream,
+{
+    pub(super) fn new(stream: S, f: F) -> Self {
+        Self {
+            stream,
+            f,
+            last_key: None,
+        }
+    }
+}
+
+impl<S, F, K> Stream for DedupByKey<S, F, K>
+where
+    S: Stream,
+    F: FnMut(&S::Item) -> K,
+    K: PartialEq,
+{
+    type Item = S::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = unsafe { self.get_unchecked_mut() };
+        let mut stream = unsafe { Pin::new_unchecked(&mut this.stream) };
+
+        loop {
+            match stream.as_mut().poll_next(cx) {
+                Poll::Ready(Some(item)) => {
+                    let key = (this.f)(&item);
+                    if this.last_key.as_ref() == Some(&key) {
+                        continue;
+                    }
+                    this.last_key = Some(key);
+                    return Poll::Ready(Some(item));
+                }
+                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use alloc::boxed::Box;
+
+use crate::stream::Stream;
+
+/// Stream for the
+/// [`filter_map_async`](super::StreamExt::filter_map_async) method.
+pub struct FilterMapAsync<S, F, Fut> {
+    stream: S,
+    f: F,
+    in_flight: Option<Pin<Box<Fut>>>,
+}
+
+impl<S, F, Fut> FilterMapAsync<S, F, Fut> {
+    pub(super) fn new(stream: S, f: F) -> Self {
+        Self {
+            stream,
+            f,
+            in_flight: None,
+        }
+    }
+}
+
+impl<S, F, Fut, B> Stream for FilterMapAsync<S, F, Fut>
+where
+    S: Stream,
+    F: FnMut(S::Item) -> Fut,
+    Fut: Future<Output = Option<B>>,
+{
+    type Item = B;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = unsafe { self.get_unchecked_mut() };
+        let mut stream = unsafe { Pin::new_unchecked(&mut this.stream) };
+
+        loop {
+            if let Some(fut) = this.in_flight.as_mut() {
+                match fut.as_mut().poll(cx) {
+                    Poll::Ready(Some(item)) => {
+                        this.in_flight = None;
+                        return Poll::Ready(Some(item));
+                    }
+                    Poll::Ready(None) => this.in_flight = None,
+                    Poll::Pending => return Poll::Pending,
+                }
+                continue;
+            }
+
+            match stream.as_mut().poll_next(cx) {
+                Poll::Ready(Some(item)) => this.in_flight = Some(Box::pin((this.f)(item))),
+                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
@@ -0,0 +1,80 @@
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
+use crate::stream::Stream;
+
+/// Stream for the
+/// [`flat_map_unordered`](super::StreamExt::flat_map_unordered) method.
+pub struct FlatMapUnordered<S, U, F>
+where
+    S: Stream,
+    U: Stream,
+{
+    stream: S,
+    f: F,
+    limit: usize,
+    active: Vec<Pin<Box<U>>>,
+    done: bool,
+}
+
+impl<S, U, F> FlatMapUnordered<S, U, F>
+where
+    S: Stream,
+    U: Stream,
+{
+    pub(super) fn new(stream: S, limit: usize, f: F) -> Self {
+        assert!(limit > 0, "concurrency limit must be greater than zero");
+        Self {
+            stream,
+            f,
+            limit,
+            active: Vec::with_capacity(limit),
+            done: false,
+        }
+    }
+}
+
+impl<S, U, F> Stream for FlatMapUnordered<S, U, F>
+where
+    S: Stream,
+    U: Stream,
+    F: FnMut(S::Item) -> U,
+{
+    type Item = U::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = unsafe { self.get_unchecked_mut() };
+        let mut stream = unsafe { Pin::new_unchecked(&mut this.stream) };
+
+        while !this.done && this.active.len() < this.limit {
+            match stream.as_mut().poll_next(cx) {
+                Poll::Ready(Some(item)) => this.active.push(Box::pin((this.f)(item))),
+                Poll::Ready(None) => {
+                    this.done = true;
+                    break;
+                }
+                Poll::Pending => break,
+            }
+        }
+
+        let mut i = 0;
+        while i < this.active.len() {
+            match this.active[i].as_mut().poll_next(cx) {
+                Poll::Ready(Some(item)) => return Poll::Ready(Some(item)),
+                Poll::Ready(None) => {
+                    this.active.swap_remove(i);
+                }
+                Poll::Pending => i += 1,
+            }
+        }
+
+        if this.done && this.active.is_empty() {
+            Poll::Ready(None)
+        } else {
+            Poll::Pending
+        }
+    }
+}
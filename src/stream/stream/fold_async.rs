@@ -0,0 +1,62 @@
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use alloc::boxed::Box;
+
+use crate::stream::Stream;
+
+/// Future for the [`fold_async`](super::StreamExt::fold_async) method.
+pub struct FoldAsync<S, F, Fut, B> {
+    stream: S,
+    f: F,
+    acc: Option<B>,
+    in_flight: Option<Pin<Box<Fut>>>,
+}
+
+impl<S, F, Fut, B> FoldAsync<S, F, Fut, B> {
+    pub(super) fn new(stream: S, f: F, init: B) -> Self {
+        Self {
+            stream,
+            f,
+            acc: Some(init),
+            in_flight: None,
+        }
+    }
+}
+
+impl<S, F, Fut, B> Future for FoldAsync<S, F, Fut, B>
+where
+    S: Stream,
+    F: FnMut(B, S::Item) -> Fut,
+    Fut: Future<Output = B>,
+{
+    type Output = B;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = unsafe { self.get_unchecked_mut() };
+        let mut stream = unsafe { Pin::new_unchecked(&mut this.stream) };
+
+        loop {
+            if let Some(fut) = this.in_flight.as_mut() {
+                match fut.as_mut().poll(cx) {
+                    Poll::Ready(acc) => {
+                        this.acc = Some(acc);
+                        this.in_flight = None;
+                    }
+                    Poll::Pending => return Poll::Pending,
+                }
+                continue;
+            }
+
+            match stream.as_mut().poll_next(cx) {
+                Poll::Ready(Some(item)) => {
+                    let acc = this.acc.take().unwrap();
+                    this.in_flight = Some(Box::pin((this.f)(acc, item)));
+                }
+                Poll::Ready(None) => return Poll::Ready(this.acc.take().unwrap()),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
@@ -0,0 +1,95 @@
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use crate::sink::Sink;
+use crate::stream::Stream;
+
+#[derive(Debug)]
+enum State<Item> {
+    Feeding,
+    Flushing,
+    Closing,
+    Done(core::marker::PhantomData<Item>),
+}
+
+/// Future for the [`forward`](super::StreamExt::forward) method.
+#[derive(Debug)]
+pub struct Forward<S, K, Item> {
+    stream: S,
+    sink: Option<K>,
+    buffered_item: Option<Item>,
+    state: State<Item>,
+}
+
+impl<S, K, Item> Forward<S, K, Item> {
+    pub(super) fn new(stream: S, sink: K) -> Self {
+        Self {
+            stream,
+            sink: Some(sink),
+            buffered_item: None,
+            state: State::Feeding,
+        }
+    }
+}
+
+impl<S, K> Future for Forward<S, K, S::Item>
+where
+    S: Stream,
+    K: Sink<S::Item>,
+{
+    type Output = Result<(), K::Error>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = unsafe { self.get_unchecked_mut() };
+
+        loop {
+            match &this.state {
+                State::Feeding => {
+                    let mut sink = unsafe { Pin::new_unchecked(this.sink.as_mut().unwrap()) };
+
+                    if let Some(item) = this.buffered_item.take() {
+                        match sink.as_mut().poll_ready(cx) {
+                            Poll::Ready(Ok(())) => {
+                                if let Err(e) = sink.as_mut().start_send(item) {
+                                    return Poll::Ready(Err(e));
+                                }
+                            }
+                            Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                            Poll::Pending => {
+                                this.buffered_item = Some(item);
+                                return Poll::Pending;
+                            }
+                        }
+                    }
+
+                    let mut stream = unsafe { Pin::new_unchecked(&mut this.stream) };
+                    match stream.as_mut().poll_next(cx) {
+                        Poll::Ready(Some(item)) => this.buffered_item = Some(item),
+                        Poll::Ready(None) => this.state = State::Flushing,
+                        Poll::Pending => return Poll::Pending,
+                    }
+                }
+                State::Flushing => {
+                    let sink = unsafe { Pin::new_unchecked(this.sink.as_mut().unwrap()) };
+                    match sink.poll_flush(cx) {
+                        Poll::Ready(Ok(())) => this.state = State::Closing,
+                        Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                        Poll::Pending => return Poll::Pending,
+                    }
+                }
+                State::Closing => {
+                    let sink = unsafe { Pin::new_unchecked(this.sink.as_mut().unwrap()) };
+                    match sink.poll_close(cx) {
+                        Poll::Ready(Ok(())) => {
+                            this.state = State::Done(core::marker::PhantomData);
+                        }
+                        Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                        Poll::Pending => return Poll::Pending,
+                    }
+                }
+                State::Done(_) => return Poll::Ready(Ok(())),
+            }
+        }
+    }
+}
@@ -0,0 +1,91 @@
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::stream::Stream;
+
+/// Stream for the [`group_by`](super::StreamExt::group_by) method.
+pub struct GroupBy<S, F, K>
+where
+    S: Stream,
+{
+    stream: S,
+    f: F,
+    // The group currently being accumulated, kept across `Pending` polls so
+    // a slow underlying stream doesn't fracture one logical group into
+    // several.
+    current: Option<(K, Vec<S::Item>)>,
+    // An item that didn't belong to `current` and starts the next group.
+    pending: Option<S::Item>,
+    stream_ended: bool,
+}
+
+impl<S, F, K> GroupBy<S, F, K>
+where
+    S: Stream,
+{
+    pub(super) fn new(stream: S, f: F) -> Self {
+        Self {
+            stream,
+            f,
+            current: None,
+            pending: None,
+            stream_ended: false,
+        }
+    }
+}
+
+impl<S, F, K> Stream for GroupBy<S, F, K>
+where
+    S: Stream,
+    F: FnMut(&S::Item) -> K,
+    K: PartialEq,
+{
+    type Item = (K, Vec<S::Item>);
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = unsafe { self.get_unchecked_mut() };
+        let mut stream = unsafe { Pin::new_unchecked(&mut this.stream) };
+
+        if this.current.is_none() {
+            if this.stream_ended {
+                return Poll::Ready(None);
+            }
+            let item = match this.pending.take() {
+                Some(item) => item,
+                None => match stream.as_mut().poll_next(cx) {
+                    Poll::Ready(Some(item)) => item,
+                    Poll::Ready(None) => {
+                        this.stream_ended = true;
+                        return Poll::Ready(None);
+                    }
+                    Poll::Pending => return Poll::Pending,
+                },
+            };
+            let key = (this.f)(&item);
+            this.current = Some((key, vec![item]));
+        }
+
+        loop {
+            match stream.as_mut().poll_next(cx) {
+                Poll::Ready(Some(item)) => {
+                    let key = (this.f)(&item);
+                    let (current_key, group) = this.current.as_mut().unwrap();
+                    if key == *current_key {
+                        group.push(item);
+                    } else {
+                        this.pending = Some(item);
+                        return Poll::Ready(this.current.take());
+                    }
+                }
+                Poll::Ready(None) => {
+                    this.stream_ended = true;
+                    return Poll::Ready(this.current.take());
+                }
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
@@ -0,0 +1,139 @@
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use crate::stream::Stream;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Turn {
+    A,
+    B,
+}
+
+/// Stream for the [`interleave`](super::StreamExt::interleave) and
+/// [`interleave_shortest`](super::StreamExt::interleave_shortest) methods.
+#[derive(Debug)]
+pub struct Interleave<A, B> {
+    a: A,
+    b: B,
+    turn: Turn,
+    a_done: bool,
+    b_done: bool,
+    shortest: bool,
+}
+
+impl<A, B> Interleave<A, B> {
+    pub(super) fn new(a: A, b: B, shortest: bool) -> Self {
+        Self {
+            a,
+            b,
+            turn: Turn::A,
+            a_done: false,
+            b_done: false,
+            shortest,
+        }
+    }
+}
+
+impl<A, B> Stream for Interleave<A, B>
+where
+    A: Stream,
+    B: Stream<Item = A::Item>,
+{
+    type Item = A::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = unsafe { self.get_unchecked_mut() };
+
+        loop {
+            if this.a_done && this.b_done {
+                return Poll::Ready(None);
+            }
+
+            let turn = if this.turn == Turn::A && this.a_done {
+                Turn::B
+            } else if this.turn == Turn::B && this.b_done {
+                Turn::A
+            } else {
+                this.turn
+            };
+
+            let poll = match turn {
+                Turn::A => unsafe { Pin::new_unchecked(&mut this.a) }.poll_next(cx),
+                Turn::B => unsafe { Pin::new_unchecked(&mut this.b) }.poll_next(cx),
+            };
+
+            match poll {
+                Poll::Ready(Some(item)) => {
+                    this.turn = match turn {
+                        Turn::A => Turn::B,
+                        Turn::B => Turn::A,
+                    };
+                    return Poll::Ready(Some(item));
+                }
+                Poll::Ready(None) => {
+                    match turn {
+                        Turn::A => this.a_done = true,
+                        Turn::B => this.b_done = true,
+                    }
+                    if this.shortest {
+                        return Poll::Ready(None);
+                    }
+                    // Hand the turn to the other stream and keep going.
+                    this.turn = match turn {
+                        Turn::A => Turn::B,
+                        Turn::B => Turn::A,
+                    };
+                    continue;
+                }
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use crate::stream::StreamExt;
+
+    async fn closed_stream<I: IntoIterator<Item = u32>>(items: I) -> crate::channel::Receiver<u32> {
+        let (sender, receiver) = crate::channel::unbounded();
+        for item in items {
+            sender.send(item).await.unwrap();
+        }
+        receiver
+    }
+
+    #[test]
+    fn interleave_alternates_and_drains_the_longer_stream() {
+        crate::task::block_on(async {
+            let a = closed_stream([1, 2]).await;
+            let b = closed_stream([10, 20, 30]).await;
+
+            let mut interleaved = a.interleave(b);
+            let mut items = Vec::new();
+            while let Some(item) = interleaved.next().await {
+                items.push(item);
+            }
+
+            assert_eq!(items, alloc::vec![1, 10, 2, 20, 30]);
+        });
+    }
+
+    #[test]
+    fn interleave_shortest_stops_as_soon_as_either_side_ends() {
+        crate::task::block_on(async {
+            let a = closed_stream([1, 2]).await;
+            let b = closed_stream([10, 20, 30]).await;
+
+            let mut interleaved = a.interleave_shortest(b);
+            let mut items = Vec::new();
+            while let Some(item) = interleaved.next().await {
+                items.push(item);
+            }
+
+            assert_eq!(items, alloc::vec![1, 10, 2, 20]);
+        });
+    }
+}
@@ -0,0 +1,54 @@
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use crate::stream::Stream;
+
+/// Stream for the [`map_while`](super::StreamExt::map_while) method.
+#[derive(Debug)]
+pub struct MapWhile<S, F> {
+    stream: S,
+    f: F,
+    done: bool,
+}
+
+impl<S, F> MapWhile<S, F> {
+    pub(super) fn new(stream: S, f: F) -> Self {
+        Self {
+            stream,
+            f,
+            done: false,
+        }
+    }
+}
+
+impl<S, F, B> Stream for MapWhile<S, F>
+where
+    S: Stream,
+    F: FnMut(S::Item) -> Option<B>,
+{
+    type Item = B;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = unsafe { self.get_unchecked_mut() };
+
+        if this.done {
+            return Poll::Ready(None);
+        }
+
+        let mut stream = unsafe { Pin::new_unchecked(&mut this.stream) };
+        match stream.as_mut().poll_next(cx) {
+            Poll::Ready(Some(item)) => match (this.f)(item) {
+                Some(item) => Poll::Ready(Some(item)),
+                None => {
+                    this.done = true;
+                    Poll::Ready(None)
+                }
+            },
+            Poll::Ready(None) => {
+                this.done = true;
+                Poll::Ready(None)
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
@@ -0,0 +1,730 @@
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use crate::stream::either::Either;
+use crate::stream::product::{CheckedProduct, Product};
+use crate::stream::sum::{CheckedSum, Sum};
+use crate::stream::try_product::TryProduct;
+use crate::stream::try_sum::{SumError, TrySum};
+use crate::stream::Stream;
+
+mod batch_by;
+mod buffer_unordered;
+mod buffered;
+mod chunks;
+mod chunks_timeout;
+mod debounce;
+mod dedup;
+mod flat_map_unordered;
+mod fold_async;
+mod filter_map_async;
+mod forward;
+mod group_by;
+mod interleave;
+mod map_while;
+mod partition;
+mod peekable;
+mod rate_limit;
+mod ready_chunks;
+mod sample;
+mod skip_while_async;
+mod sorted;
+mod try_collect;
+mod try_filter;
+mod try_filter_map;
+mod try_fold;
+mod try_for_each;
+mod try_for_each_concurrent;
+mod unzip;
+mod windows;
+mod zip_longest;
+
+use self::batch_by::BatchBy;
+use self::buffer_unordered::BufferUnordered;
+use self::buffered::Buffered;
+use self::chunks::Chunks;
+use self::chunks_timeout::ChunksTimeout;
+use self::debounce::Debounce;
+use self::dedup::{Dedup, DedupByKey};
+use self::flat_map_unordered::FlatMapUnordered;
+use self::fold_async::FoldAsync;
+use self::filter_map_async::FilterMapAsync;
+use self::forward::Forward;
+use crate::sink::Sink;
+use self::group_by::GroupBy;
+use self::interleave::Interleave;
+use self::map_while::MapWhile;
+use self::partition::PartitionFuture;
+use self::peekable::Peekable;
+use self::rate_limit::RateLimit;
+use self::ready_chunks::ReadyChunks;
+use self::sample::Sample;
+use self::skip_while_async::SkipWhileAsync;
+use self::sorted::Sorted;
+use self::try_collect::TryCollectFuture;
+use self::try_filter::TryFilter;
+use self::try_filter_map::TryFilterMap;
+use self::try_fold::TryFoldFuture;
+use self::try_for_each::TryForEachFuture;
+use self::try_for_each_concurrent::TryForEachConcurrent;
+use self::unzip::UnzipFuture;
+use self::windows::Windows;
+use self::zip_longest::{EitherOrBoth, ZipLongest};
+
+/// An extension trait for the [`Stream`] trait, providing a collection of
+/// useful combinators.
+///
+/// [`Stream`]: trait.Stream.html
+pub trait StreamExt: Stream {
+    /// Advances the stream and returns the next value.
+    ///
+    /// Returns `None` when iteration is finished.
+    fn next(&mut self) -> NextFuture<'_, Self>
+    where
+        Self: Unpin,
+    {
+        NextFuture { stream: self }
+    }
+
+    /// Creates a stream which can use [`peek`](Peekable::peek) and
+    /// [`peek_mut`](Peekable::peek_mut) to look at the next element
+    /// without consuming it.
+    fn peekable(self) -> Peekable<Self>
+    where
+        Self: Sized,
+    {
+        Peekable::new(self)
+    }
+
+    /// Folds every element into an accumulator by applying an operation,
+    /// returning the final result.
+    fn fold<B, F>(self, init: B, f: F) -> FoldFuture<Self, F, B>
+    where
+        Self: Sized,
+        F: FnMut(B, Self::Item) -> B,
+    {
+        FoldFuture {
+            stream: self,
+            f,
+            acc: Some(init),
+        }
+    }
+
+    /// Folds every element into an accumulator by applying an
+    /// asynchronous operation, returning the final result.
+    fn fold_async<B, F, Fut>(self, init: B, f: F) -> FoldAsync<Self, F, Fut, B>
+    where
+        Self: Sized,
+        F: FnMut(B, Self::Item) -> Fut,
+        Fut: Future<Output = B>,
+    {
+        FoldAsync::new(self, f, init)
+    }
+
+    /// Creates a stream that both filters and maps by applying an
+    /// asynchronous closure to each element.
+    ///
+    /// `f` returns a future that resolves to `Option<B>`; `None` is
+    /// treated as "filtered out" and `Some(item)` is yielded.
+    fn filter_map_async<F, Fut, B>(self, f: F) -> FilterMapAsync<Self, F, Fut>
+    where
+        Self: Sized,
+        F: FnMut(Self::Item) -> Fut,
+        Fut: Future<Output = Option<B>>,
+    {
+        FilterMapAsync::new(self, f)
+    }
+
+    /// Skips elements while an asynchronous closure returns `true`.
+    ///
+    /// Once `f` resolves to `false` for some element, that element and
+    /// every element after it is yielded, without calling `f` again.
+    fn skip_while_async<F, Fut>(self, f: F) -> SkipWhileAsync<Self, F, Fut>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> Fut,
+        Fut: Future<Output = bool>,
+    {
+        SkipWhileAsync::new(self, f)
+    }
+
+    /// Sums the elements of this stream.
+    ///
+    /// See [`Sum::sum`] for details.
+    ///
+    /// [`Sum::sum`]: trait.Sum.html#tymethod.sum
+    fn sum<'a, S>(self) -> S::SumFuture<'a, Self>
+    where
+        Self: Sized + Stream + 'a,
+        S: Sum<Self::Item> + 'a,
+    {
+        S::sum(self)
+    }
+
+    /// Multiplies the elements of this stream together.
+    ///
+    /// See [`Product::product`] for details.
+    ///
+    /// [`Product::product`]: trait.Product.html#tymethod.product
+    fn product<'a, P>(self) -> P::ProductFuture<'a, Self>
+    where
+        Self: Sized + Stream + 'a,
+        P: Product<Self::Item> + 'a,
+    {
+        P::product(self)
+    }
+
+    /// Sums the elements of this stream, short-circuiting on overflow.
+    ///
+    /// See [`TrySum::try_sum`] for details.
+    ///
+    /// [`TrySum::try_sum`]: trait.TrySum.html#tymethod.try_sum
+    fn try_sum<'a, S>(self) -> Pin<Box<dyn Future<Output = Result<S, SumError>> + 'a>>
+    where
+        Self: Sized + Stream + 'a,
+        S: TrySum<Self::Item> + 'a,
+    {
+        S::try_sum(self)
+    }
+
+    /// Multiplies the elements of this stream together, short-circuiting on
+    /// overflow.
+    ///
+    /// See [`TryProduct::try_product`] for details.
+    ///
+    /// [`TryProduct::try_product`]: trait.TryProduct.html#tymethod.try_product
+    fn try_product<'a, P>(self) -> Pin<Box<dyn Future<Output = Result<P, SumError>> + 'a>>
+    where
+        Self: Sized + Stream + 'a,
+        P: TryProduct<Self::Item> + 'a,
+    {
+        P::try_product(self)
+    }
+
+    /// Zips this stream with another, running until *both* are exhausted
+    /// rather than stopping at the shorter one.
+    ///
+    /// Each step yields [`EitherOrBoth`], so the caller can tell which side
+    /// (if either) ran dry.
+    fn zip_longest<B>(self, other: B) -> ZipLongest<Self, B>
+    where
+        Self: Sized,
+        B: Stream,
+    {
+        ZipLongest::new(self, other)
+    }
+
+    /// Strictly alternates between this stream and `other`, item for item.
+    ///
+    /// Unlike `merge`, which yields whichever side is ready first,
+    /// `interleave` always takes turns, so neither side can starve the
+    /// other. Once one side is exhausted, the rest of the other side is
+    /// yielded on its own.
+    fn interleave<B>(self, other: B) -> Interleave<Self, B>
+    where
+        Self: Sized,
+        B: Stream<Item = Self::Item>,
+    {
+        Interleave::new(self, other, false)
+    }
+
+    /// Like [`interleave`](Self::interleave), but stops as soon as either
+    /// side runs out instead of draining the remainder of the other.
+    fn interleave_shortest<B>(self, other: B) -> Interleave<Self, B>
+    where
+        Self: Sized,
+        B: Stream<Item = Self::Item>,
+    {
+        Interleave::new(self, other, true)
+    }
+
+    /// Yields overlapping `Vec`s of `size` consecutive elements, sliding
+    /// forward by one element each time, the same way `slice::windows`
+    /// treats a slice.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is zero.
+    fn windows(self, size: usize) -> Windows<Self>
+    where
+        Self: Sized,
+        Self::Item: Clone,
+    {
+        Windows::new(self, size)
+    }
+
+    /// Feeds every item of this stream into `sink`, flushing and closing it
+    /// once the stream ends.
+    ///
+    /// See [`Sink`](../sink/trait.Sink.html).
+    fn forward<K>(self, sink: K) -> Forward<Self, K, Self::Item>
+    where
+        Self: Sized,
+        K: Sink<Self::Item>,
+    {
+        Forward::new(self, sink)
+    }
+
+    /// Maps each element to a new stream with `f`, then interleaves up to
+    /// `limit` of those streams concurrently, yielding items from whichever
+    /// one produces one next.
+    ///
+    /// Unlike a hypothetical `flat_map` that concatenates sub-streams in
+    /// order, results here arrive in whatever order their source streams
+    /// produce them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `limit` is zero.
+    fn flat_map_unordered<U, F>(self, limit: usize, f: F) -> FlatMapUnordered<Self, U, F>
+    where
+        Self: Sized,
+        U: Stream,
+        F: FnMut(Self::Item) -> U,
+    {
+        FlatMapUnordered::new(self, limit, f)
+    }
+
+    /// Suppresses items that are immediately followed by another item
+    /// within `duration`, only yielding the most recent item once the
+    /// stream has been quiet for `duration`.
+    ///
+    /// This is the common "debounce" behavior from UI event handling:
+    /// bursts of rapid items collapse down to their last member.
+    fn debounce(self, duration: core::time::Duration) -> Debounce<Self>
+    where
+        Self: Sized,
+    {
+        Debounce::new(self, duration)
+    }
+
+    /// Skips consecutive repeated elements, yielding only the first of each
+    /// run, the same way `slice::dedup` treats consecutive runs.
+    ///
+    /// Equal elements that aren't adjacent are not deduplicated.
+    fn dedup(self) -> Dedup<Self>
+    where
+        Self: Sized,
+        Self::Item: PartialEq + Clone,
+    {
+        Dedup::new(self)
+    }
+
+    /// Like [`dedup`], but compares elements by a derived key instead of the
+    /// elements themselves.
+    ///
+    /// [`dedup`]: #method.dedup
+    fn dedup_by_key<F, K>(self, f: F) -> DedupByKey<Self, F, K>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> K,
+        K: PartialEq,
+    {
+        DedupByKey::new(self, f)
+    }
+
+    /// Throttles this stream with a token-bucket limiter: up to `capacity`
+    /// items may pass through immediately, and thereafter one more token is
+    /// minted every `refill_every`, capping the long-run rate while still
+    /// tolerating small bursts.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is zero.
+    fn rate_limit(self, capacity: u32, refill_every: core::time::Duration) -> RateLimit<Self>
+    where
+        Self: Sized,
+    {
+        RateLimit::new(self, capacity, refill_every)
+    }
+
+    /// Maps elements with `f` until it returns `None`, ending the stream at
+    /// that point (and not resuming even if a later element would have
+    /// mapped to `Some`), mirroring `Iterator::map_while`.
+    fn map_while<B, F>(self, f: F) -> MapWhile<Self, F>
+    where
+        Self: Sized,
+        F: FnMut(Self::Item) -> Option<B>,
+    {
+        MapWhile::new(self, f)
+    }
+
+    /// Batches whatever items are immediately available into a `Vec`, up to
+    /// `size`, without waiting to fill it the way [`chunks`] does.
+    ///
+    /// Waits for at least one item, then greedily drains anything already
+    /// buffered before yielding; a burst that arrives all at once still
+    /// comes back as separate chunks if it's larger than `size`.
+    ///
+    /// [`chunks`]: #method.chunks
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is zero.
+    fn ready_chunks(self, size: usize) -> ReadyChunks<Self>
+    where
+        Self: Sized,
+    {
+        ReadyChunks::new(self, size)
+    }
+
+    /// Consumes a stream of pairs, splitting it into two collections,
+    /// mirroring `Iterator::unzip`.
+    fn unzip<T, U, A, B>(self) -> UnzipFuture<Self, A, B>
+    where
+        Self: Sized + Stream<Item = (T, U)>,
+        A: Default + Extend<T>,
+        B: Default + Extend<U>,
+    {
+        UnzipFuture::new(self)
+    }
+
+    /// Consumes the stream, splitting its items into two collections based
+    /// on a predicate, mirroring `Iterator::partition`.
+    ///
+    /// Items for which `f` returns `true` go into the first collection,
+    /// everything else into the second.
+    fn partition<B, F>(self, f: F) -> PartitionFuture<Self, F, B>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> bool,
+        B: Default + Extend<Self::Item>,
+    {
+        PartitionFuture::new(self, f)
+    }
+
+    /// Buffers the whole stream, sorts it by `Ord::cmp`, and yields the
+    /// items back out in order.
+    ///
+    /// Necessarily eager: nothing can be yielded until the final item has
+    /// been seen.
+    fn sorted<T>(self) -> Sorted<Self, fn(&T, &T) -> core::cmp::Ordering>
+    where
+        Self: Sized + Stream<Item = T>,
+        T: Ord,
+    {
+        Sorted::new(self, T::cmp)
+    }
+
+    /// Like [`sorted`](#method.sorted), but orders items by a derived key
+    /// instead of the items themselves.
+    fn sorted_by_key<K, F>(self, mut f: F) -> Sorted<Self, impl FnMut(&Self::Item, &Self::Item) -> core::cmp::Ordering>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> K,
+        K: Ord,
+    {
+        Sorted::new(self, move |a, b| f(a).cmp(&f(b)))
+    }
+
+    /// Samples this stream on a fixed `period`, yielding only the most
+    /// recently seen item at each tick and dropping everything else --
+    /// "throttle, keep latest" rather than [`debounce`]'s "wait for quiet".
+    ///
+    /// If no item arrived since the last tick, that tick produces nothing
+    /// and the next poll waits for the one after.
+    ///
+    /// [`debounce`]: #method.debounce
+    fn sample(self, period: core::time::Duration) -> Sample<Self>
+    where
+        Self: Sized,
+    {
+        Sample::new(self, period)
+    }
+
+    /// Alias for [`sample`](#method.sample); the name `futures`/`rxjs` users
+    /// tend to reach for first when looking for "keep only the latest item
+    /// per interval" behavior.
+    fn throttle_latest(self, period: core::time::Duration) -> Sample<Self>
+    where
+        Self: Sized,
+    {
+        Sample::new(self, period)
+    }
+
+    /// Groups consecutive elements that map to the same key under `f` into
+    /// `(key, Vec<items>)` pairs, the same way `itertools::group_by` treats
+    /// consecutive runs of an iterator.
+    ///
+    /// Equal keys that aren't adjacent start a new group -- this does not
+    /// sort or otherwise reorder the stream first.
+    fn group_by<F, K>(self, f: F) -> GroupBy<Self, F, K>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> K,
+        K: PartialEq,
+    {
+        GroupBy::new(self, f)
+    }
+
+    /// Groups consecutive elements into `Vec` batches, starting a new batch
+    /// whenever `pred(previous, next)` returns `false`.
+    ///
+    /// Unlike [`group_by`], which groups by equal keys, `pred` compares
+    /// each item only to the one immediately before it, so the boundary
+    /// condition can be relative (e.g. "still increasing") rather than an
+    /// equality check.
+    ///
+    /// [`group_by`]: #method.group_by
+    fn batch_by<F>(self, pred: F) -> BatchBy<Self, F>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item, &Self::Item) -> bool,
+    {
+        BatchBy::new(self, pred)
+    }
+
+    /// Runs up to `limit` of this stream's futures concurrently, yielding
+    /// their outputs in the same order the futures were produced in.
+    ///
+    /// Unlike [`buffer_unordered`], a slow future at the front of the
+    /// window holds up outputs from faster futures behind it, even though
+    /// those futures are still being driven to completion in the meantime.
+    ///
+    /// [`buffer_unordered`]: #method.buffer_unordered
+    ///
+    /// # Panics
+    ///
+    /// Panics if `limit` is zero.
+    fn buffered(self, limit: usize) -> Buffered<Self>
+    where
+        Self: Sized,
+        Self::Item: Future,
+    {
+        Buffered::new(self, limit)
+    }
+
+    /// Runs up to `limit` of this stream's futures concurrently, yielding
+    /// their outputs as soon as each one completes, in whatever order that
+    /// happens to be.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `limit` is zero.
+    fn buffer_unordered(self, limit: usize) -> BufferUnordered<Self>
+    where
+        Self: Sized,
+        Self::Item: Future,
+    {
+        BufferUnordered::new(self, limit)
+    }
+
+    /// Batches elements of this stream into non-overlapping, ordered
+    /// `Vec`s of (at most) `size` items each.
+    ///
+    /// The final batch may be shorter than `size` if the stream ends before
+    /// it fills up; it is still yielded as long as it's non-empty.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is zero.
+    fn chunks(self, size: usize) -> Chunks<Self>
+    where
+        Self: Sized,
+    {
+        Chunks::new(self, size)
+    }
+
+    /// Batches elements of this stream the same way [`chunks`] does, but
+    /// also flushes a non-empty, under-sized batch once `duration` has
+    /// elapsed since its first item arrived, so a slow trickle of items
+    /// doesn't wait forever for a full chunk.
+    ///
+    /// The timer is (re)armed only while the current batch is non-empty;
+    /// an idle stream does not wake this adapter.
+    ///
+    /// [`chunks`]: #method.chunks
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is zero.
+    fn chunks_timeout(self, size: usize, duration: core::time::Duration) -> ChunksTimeout<Self>
+    where
+        Self: Sized,
+    {
+        ChunksTimeout::new(self, size, duration)
+    }
+
+    /// Collects the `Ok` values of a `Result`-producing stream into `C`,
+    /// short-circuiting on the first `Err`.
+    ///
+    /// Mirrors `futures::TryStreamExt::try_collect`.
+    fn try_collect<T, E, C>(self) -> TryCollectFuture<Self, C>
+    where
+        Self: Sized + Stream<Item = Result<T, E>>,
+        C: Default + Extend<T>,
+    {
+        TryCollectFuture::new(self)
+    }
+
+    /// Applies a fallible accumulator function over the elements of a
+    /// `Result`-producing stream, short-circuiting on the first `Err`.
+    ///
+    /// This mirrors `futures::TryStreamExt::try_fold`, except `f` is a plain
+    /// `FnMut` rather than one returning a future -- add an async variant
+    /// alongside it if one is ever needed.
+    fn try_fold<T, E, B, F>(self, init: B, f: F) -> TryFoldFuture<Self, F, B>
+    where
+        Self: Sized + Stream<Item = Result<T, E>>,
+        F: FnMut(B, T) -> Result<B, E>,
+    {
+        TryFoldFuture::new(self, f, init)
+    }
+
+    /// Calls a fallible closure on each element of a `Result`-producing
+    /// stream, short-circuiting on the first `Err`.
+    ///
+    /// Mirrors `futures::TryStreamExt::try_for_each`.
+    fn try_for_each<T, E, F>(self, f: F) -> TryForEachFuture<Self, F>
+    where
+        Self: Sized + Stream<Item = Result<T, E>>,
+        F: FnMut(T) -> Result<(), E>,
+    {
+        TryForEachFuture::new(self, f)
+    }
+
+    /// Like [`try_for_each`], but runs up to `limit` invocations of `f`
+    /// concurrently, short-circuiting as soon as any of them returns `Err`.
+    ///
+    /// [`try_for_each`]: #method.try_for_each
+    ///
+    /// # Panics
+    ///
+    /// Panics if `limit` is zero.
+    fn try_for_each_concurrent<F, Fut, E>(
+        self,
+        limit: usize,
+        f: F,
+    ) -> TryForEachConcurrent<Self, F, Fut>
+    where
+        Self: Sized,
+        F: FnMut(Self::Item) -> Fut,
+        Fut: Future<Output = Result<(), E>>,
+    {
+        TryForEachConcurrent::new(self, limit, f)
+    }
+
+    /// Skips elements of a `Result`-producing stream that don't satisfy
+    /// `predicate`, passing every `Err` through unconditionally.
+    ///
+    /// Mirrors `futures::TryStreamExt::try_filter`.
+    fn try_filter<T, E, P>(self, predicate: P) -> TryFilter<Self, P>
+    where
+        Self: Sized + Stream<Item = Result<T, E>>,
+        P: FnMut(&T) -> bool,
+    {
+        TryFilter::new(self, predicate)
+    }
+
+    /// Filters and maps the `Ok` values of a `Result`-producing stream with
+    /// a fallible closure, passing every `Err` through unconditionally.
+    ///
+    /// Mirrors `futures::TryStreamExt::try_filter_map`.
+    fn try_filter_map<T, U, E, F>(self, f: F) -> TryFilterMap<Self, F>
+    where
+        Self: Sized + Stream<Item = Result<T, E>>,
+        F: FnMut(T) -> Result<Option<U>, E>,
+    {
+        TryFilterMap::new(self, f)
+    }
+
+    /// Sums the elements of this stream, short-circuiting to `None` on
+    /// overflow.
+    ///
+    /// See [`CheckedSum::checked_sum`] for details.
+    ///
+    /// [`CheckedSum::checked_sum`]: trait.CheckedSum.html#tymethod.checked_sum
+    fn checked_sum<'a, S>(self) -> Pin<Box<dyn Future<Output = Option<S>> + 'a>>
+    where
+        Self: Sized + Stream + 'a,
+        S: CheckedSum<Self::Item> + 'a,
+    {
+        S::checked_sum(self)
+    }
+
+    /// Multiplies the elements of this stream together, short-circuiting to
+    /// `None` on overflow.
+    ///
+    /// See [`CheckedProduct::checked_product`] for details.
+    ///
+    /// [`CheckedProduct::checked_product`]: trait.CheckedProduct.html#tymethod.checked_product
+    fn checked_product<'a, P>(self) -> Pin<Box<dyn Future<Output = Option<P>> + 'a>>
+    where
+        Self: Sized + Stream + 'a,
+        P: CheckedProduct<Self::Item> + 'a,
+    {
+        P::checked_product(self)
+    }
+
+    /// Combines this stream with another, structurally different stream
+    /// into a single concrete [`Either`] stream, picking this stream (the
+    /// `Left` arm) when `cond` is `true`, and `other` (the `Right` arm)
+    /// otherwise.
+    ///
+    /// The choice is made once, up front, before either arm is polled, so
+    /// `cond` is a plain `bool` rather than a predicate over the (opaque,
+    /// not-yet-polled) stream -- there is nothing meaningful to inspect on
+    /// `self` at this point.
+    ///
+    /// [`Either`]: enum.Either.html
+    fn either<B>(self, cond: bool, other: B) -> Either<Self, B>
+    where
+        Self: Sized,
+        B: Stream<Item = Self::Item>,
+    {
+        if cond {
+            Either::Left(self)
+        } else {
+            Either::Right(other)
+        }
+    }
+}
+
+impl<S: Stream + ?Sized> StreamExt for S {}
+
+/// Future for the [`next`](StreamExt::next) method.
+#[derive(Debug)]
+pub struct NextFuture<'a, S: ?Sized> {
+    stream: &'a mut S,
+}
+
+impl<'a, S: Stream + Unpin + ?Sized> Future for NextFuture<'a, S> {
+    type Output = Option<S::Item>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut *self.stream).poll_next(cx)
+    }
+}
+
+/// Future for the [`fold`](StreamExt::fold) method.
+#[derive(Debug)]
+pub struct FoldFuture<S, F, B> {
+    stream: S,
+    f: F,
+    acc: Option<B>,
+}
+
+impl<S, F, B> Future for FoldFuture<S, F, B>
+where
+    S: Stream,
+    F: FnMut(B, S::Item) -> B,
+{
+    type Output = B;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = unsafe { self.get_unchecked_mut() };
+        let mut stream = unsafe { Pin::new_unchecked(&mut this.stream) };
+
+        loop {
+            match stream.as_mut().poll_next(cx) {
+                Poll::Ready(Some(item)) => {
+                    let acc = this.acc.take().unwrap();
+                    this.acc = Some((this.f)(acc, item));
+                }
+                Poll::Ready(None) => return Poll::Ready(this.acc.take().unwrap()),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
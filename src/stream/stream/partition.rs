@@ -0,0 +1,52 @@
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use crate::stream::Stream;
+
+/// Future for the [`partition`](super::StreamExt::partition) method.
+#[derive(Debug)]
+pub struct PartitionFuture<S, F, B> {
+    stream: S,
+    f: F,
+    res: Option<(B, B)>,
+}
+
+impl<S, F, B: Default> PartitionFuture<S, F, B> {
+    pub(super) fn new(stream: S, f: F) -> Self {
+        Self {
+            stream,
+            f,
+            res: Some((B::default(), B::default())),
+        }
+    }
+}
+
+impl<S, F, B> Future for PartitionFuture<S, F, B>
+where
+    S: Stream,
+    F: FnMut(&S::Item) -> bool,
+    B: Default + Extend<S::Item>,
+{
+    type Output = (B, B);
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = unsafe { self.get_unchecked_mut() };
+        let mut stream = unsafe { Pin::new_unchecked(&mut this.stream) };
+
+        loop {
+            match stream.as_mut().poll_next(cx) {
+                Poll::Ready(Some(item)) => {
+                    let (left, right) = this.res.as_mut().unwrap();
+                    if (this.f)(&item) {
+                        left.extend(Some(item));
+                    } else {
+                        right.extend(Some(item));
+                    }
+                }
+                Poll::Ready(None) => return Poll::Ready(this.res.take().unwrap()),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
@@ -0,0 +1,188 @@
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use alloc::boxed::Box;
+
+use crate::stream::Stream;
+
+/// Stream for the [`peekable`](super::StreamExt::peekable) method.
+#[derive(Debug)]
+pub struct Peekable<S>
+where
+    S: Stream,
+{
+    stream: S,
+    peeked: Option<S::Item>,
+}
+
+impl<S> Peekable<S>
+where
+    S: Stream,
+{
+    pub(super) fn new(stream: S) -> Self {
+        Self {
+            stream,
+            peeked: None,
+        }
+    }
+
+    /// Returns a reference to the next value without advancing the
+    /// stream.
+    pub fn peek(&mut self) -> PeekFuture<'_, S>
+    where
+        S: Unpin,
+    {
+        PeekFuture {
+            peekable: Some(self),
+        }
+    }
+
+    /// Returns a mutable reference to the next value without advancing
+    /// the stream.
+    pub fn peek_mut(&mut self) -> PeekMutFuture<'_, S>
+    where
+        S: Unpin,
+    {
+        PeekMutFuture {
+            peekable: Some(self),
+        }
+    }
+
+    /// Consumes and returns the next value if `func` returns `true` when
+    /// applied to it; otherwise leaves the stream untouched and returns
+    /// `None`.
+    pub fn next_if<'a, F>(&'a mut self, func: F) -> NextIfFuture<'a, S>
+    where
+        S: Unpin,
+        F: FnOnce(&S::Item) -> bool + 'a,
+    {
+        NextIfFuture {
+            peekable: Some(self),
+            predicate: Some(Box::new(func)),
+        }
+    }
+
+    /// Consumes and returns the next value if it is equal to `expected`;
+    /// otherwise leaves the stream untouched and returns `None`.
+    pub fn next_if_eq<'a, T>(&'a mut self, expected: &'a T) -> NextIfFuture<'a, S>
+    where
+        S: Unpin,
+        S::Item: PartialEq<T>,
+    {
+        self.next_if(move |item| item == expected)
+    }
+}
+
+impl<S> Stream for Peekable<S>
+where
+    S: Stream + Unpin,
+{
+    type Item = S::Item;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if let Some(item) = self.peeked.take() {
+            return Poll::Ready(Some(item));
+        }
+        Pin::new(&mut self.stream).poll_next(cx)
+    }
+}
+
+/// Future for the [`Peekable::peek`] method.
+#[derive(Debug)]
+pub struct PeekFuture<'a, S>
+where
+    S: Stream,
+{
+    peekable: Option<&'a mut Peekable<S>>,
+}
+
+impl<'a, S> Future for PeekFuture<'a, S>
+where
+    S: Stream + Unpin,
+{
+    type Output = Option<&'a S::Item>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = Pin::get_mut(self);
+        let peekable = this.peekable.take().expect("PeekFuture polled after completion");
+        if peekable.peeked.is_none() {
+            match Pin::new(&mut peekable.stream).poll_next(cx) {
+                Poll::Ready(item) => peekable.peeked = item,
+                Poll::Pending => {
+                    this.peekable = Some(peekable);
+                    return Poll::Pending;
+                }
+            }
+        }
+        Poll::Ready(peekable.peeked.as_ref())
+    }
+}
+
+/// Future for the [`Peekable::peek_mut`] method.
+#[derive(Debug)]
+pub struct PeekMutFuture<'a, S>
+where
+    S: Stream,
+{
+    peekable: Option<&'a mut Peekable<S>>,
+}
+
+impl<'a, S> Future for PeekMutFuture<'a, S>
+where
+    S: Stream + Unpin,
+{
+    type Output = Option<&'a mut S::Item>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = Pin::get_mut(self);
+        let peekable = this.peekable.take().expect("PeekMutFuture polled after completion");
+        if peekable.peeked.is_none() {
+            match Pin::new(&mut peekable.stream).poll_next(cx) {
+                Poll::Ready(item) => peekable.peeked = item,
+                Poll::Pending => {
+                    this.peekable = Some(peekable);
+                    return Poll::Pending;
+                }
+            }
+        }
+        Poll::Ready(peekable.peeked.as_mut())
+    }
+}
+
+/// Future for the [`Peekable::next_if`] and [`Peekable::next_if_eq`]
+/// methods.
+pub struct NextIfFuture<'a, S>
+where
+    S: Stream,
+{
+    peekable: Option<&'a mut Peekable<S>>,
+    predicate: Option<Box<dyn FnOnce(&S::Item) -> bool + 'a>>,
+}
+
+impl<'a, S> Future for NextIfFuture<'a, S>
+where
+    S: Stream + Unpin,
+{
+    type Output = Option<S::Item>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = Pin::get_mut(self);
+        let peekable = this.peekable.take().expect("NextIfFuture polled after completion");
+        if peekable.peeked.is_none() {
+            match Pin::new(&mut peekable.stream).poll_next(cx) {
+                Poll::Ready(item) => peekable.peeked = item,
+                Poll::Pending => {
+                    this.peekable = Some(peekable);
+                    return Poll::Pending;
+                }
+            }
+        }
+
+        let predicate = this.predicate.take().expect("NextIfFuture polled after completion");
+        match &peekable.peeked {
+            Some(item) if predicate(item) => Poll::Ready(peekable.peeked.take()),
+            _ => Poll::Ready(None),
+        }
+    }
+}
@@ -0,0 +1,64 @@
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use core::time::Duration;
+
+use alloc::boxed::Box;
+
+use crate::stream::Stream;
+use crate::task::sleep;
+
+/// Stream for the [`rate_limit`](super::StreamExt::rate_limit) method.
+pub struct RateLimit<S> {
+    stream: S,
+    capacity: u32,
+    tokens: u32,
+    refill_every: Duration,
+    timer: Pin<Box<dyn Future<Output = ()> + Send>>,
+}
+
+impl<S> RateLimit<S> {
+    pub(super) fn new(stream: S, capacity: u32, refill_every: Duration) -> Self {
+        assert!(capacity > 0, "token bucket capacity must be greater than zero");
+        Self {
+            stream,
+            capacity,
+            tokens: capacity,
+            refill_every,
+            timer: Box::pin(sleep(refill_every)),
+        }
+    }
+}
+
+impl<S> Stream for RateLimit<S>
+where
+    S: Stream,
+{
+    type Item = S::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = unsafe { self.get_unchecked_mut() };
+
+        // A fresh token is minted every `refill_every`, up to `capacity` in
+        // the bucket; items are only let through while a token is
+        // available, so bursts beyond `capacity` get spread out over time
+        // instead of passing straight through.
+        while this.timer.as_mut().poll(cx).is_ready() {
+            this.tokens = (this.tokens + 1).min(this.capacity);
+            this.timer = Box::pin(sleep(this.refill_every));
+        }
+
+        if this.tokens == 0 {
+            return Poll::Pending;
+        }
+
+        let mut stream = unsafe { Pin::new_unchecked(&mut this.stream) };
+        match stream.as_mut().poll_next(cx) {
+            Poll::Ready(Some(item)) => {
+                this.tokens -= 1;
+                Poll::Ready(Some(item))
+            }
+            other => other,
+        }
+    }
+}
@@ -0,0 +1,66 @@
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use alloc::vec::Vec;
+
+use crate::stream::Stream;
+
+/// Stream for the [`ready_chunks`](super::StreamExt::ready_chunks) method.
+#[derive(Debug)]
+pub struct ReadyChunks<S> {
+    stream: S,
+    cap: usize,
+    done: bool,
+}
+
+impl<S> ReadyChunks<S> {
+    pub(super) fn new(stream: S, cap: usize) -> Self {
+        assert!(cap > 0, "chunk size must be greater than zero");
+        Self {
+            stream,
+            cap,
+            done: false,
+        }
+    }
+}
+
+impl<S> Stream for ReadyChunks<S>
+where
+    S: Stream,
+{
+    type Item = Vec<S::Item>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = unsafe { self.get_unchecked_mut() };
+        let mut stream = unsafe { Pin::new_unchecked(&mut this.stream) };
+
+        if this.done {
+            return Poll::Ready(None);
+        }
+
+        // The first item is waited for; everything after that is grabbed
+        // opportunistically -- as soon as the stream isn't immediately
+        // ready, the partial batch is flushed instead of waiting for `cap`.
+        let mut buf = match stream.as_mut().poll_next(cx) {
+            Poll::Ready(Some(item)) => alloc::vec![item],
+            Poll::Ready(None) => {
+                this.done = true;
+                return Poll::Ready(None);
+            }
+            Poll::Pending => return Poll::Pending,
+        };
+
+        while buf.len() < this.cap {
+            match stream.as_mut().poll_next(cx) {
+                Poll::Ready(Some(item)) => buf.push(item),
+                Poll::Ready(None) => {
+                    this.done = true;
+                    break;
+                }
+                Poll::Pending => break,
+            }
+        }
+
+        Poll::Ready(Some(buf))
+    }
+}
@@ -0,0 +1,100 @@
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use core::time::Duration;
+
+use alloc::boxed::Box;
+
+use crate::stream::Stream;
+use crate::task::sleep;
+
+/// Stream for the [`sample`](super::StreamExt::sample) method.
+pub struct Sample<S>
+where
+    S: Stream,
+{
+    stream: S,
+    period: Duration,
+    latest: Option<S::Item>,
+    timer: Pin<Box<dyn Future<Output = ()> + Send>>,
+    stream_ended: bool,
+}
+
+impl<S> Sample<S>
+where
+    S: Stream,
+{
+    pub(super) fn new(stream: S, period: Duration) -> Self {
+        Self {
+            stream,
+            timer: Box::pin(sleep(period)),
+            period,
+            latest: None,
+            stream_ended: false,
+        }
+    }
+}
+
+impl<S> Stream for Sample<S>
+where
+    S: Stream,
+{
+    type Item = S::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = unsafe { self.get_unchecked_mut() };
+        let mut stream = unsafe { Pin::new_unchecked(&mut this.stream) };
+
+        while !this.stream_ended {
+            match stream.as_mut().poll_next(cx) {
+                Poll::Ready(Some(item)) => this.latest = Some(item),
+                Poll::Ready(None) => {
+                    this.stream_ended = true;
+                    break;
+                }
+                Poll::Pending => break,
+            }
+        }
+
+        if this.stream_ended {
+            return match this.latest.take() {
+                Some(item) => Poll::Ready(Some(item)),
+                None => Poll::Ready(None),
+            };
+        }
+
+        if this.timer.as_mut().poll(cx).is_ready() {
+            this.timer = Box::pin(sleep(this.period));
+            if let Some(item) = this.latest.take() {
+                return Poll::Ready(Some(item));
+            }
+        }
+
+        Poll::Pending
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stream::StreamExt;
+
+    #[test]
+    fn sample_emits_only_the_most_recent_item_per_period() {
+        crate::task::block_on(async {
+            let (sender, receiver) = crate::channel::unbounded();
+            sender.send(1).await.unwrap();
+            sender.send(2).await.unwrap();
+            sender.send(3).await.unwrap();
+            drop(sender);
+
+            // All three sends land well within one sampling period, so
+            // only the last one queued should ever be observed.
+            let mut sampled = receiver.sample(Duration::from_millis(50));
+            assert_eq!(sampled.next().await, Some(3));
+            // The source is disconnected and drained, so the stream
+            // ends instead of waiting out another period.
+            assert_eq!(sampled.next().await, None);
+        });
+    }
+}
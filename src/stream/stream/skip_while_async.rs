@@ -0,0 +1,85 @@
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use alloc::boxed::Box;
+
+use crate::stream::Stream;
+
+/// Stream for the [`skip_while_async`](super::StreamExt::skip_while_async)
+/// method.
+pub struct SkipWhileAsync<S, F, Fut>
+where
+    S: Stream,
+{
+    stream: S,
+    f: F,
+    // `item` is heap-allocated and kept alive for as long as `in_flight`
+    // borrows it -- moving the `Box` itself never moves the pointee, so the
+    // reference `in_flight` was built from stays valid.
+    item: Option<Box<S::Item>>,
+    in_flight: Option<Pin<Box<Fut>>>,
+    skipping: bool,
+}
+
+impl<S, F, Fut> SkipWhileAsync<S, F, Fut>
+where
+    S: Stream,
+{
+    pub(super) fn new(stream: S, f: F) -> Self {
+        Self {
+            stream,
+            f,
+            item: None,
+            in_flight: None,
+            skipping: true,
+        }
+    }
+}
+
+impl<S, F, Fut> Stream for SkipWhileAsync<S, F, Fut>
+where
+    S: Stream,
+    F: FnMut(&S::Item) -> Fut,
+    Fut: Future<Output = bool>,
+{
+    type Item = S::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = unsafe { self.get_unchecked_mut() };
+        let mut stream = unsafe { Pin::new_unchecked(&mut this.stream) };
+
+        if !this.skipping {
+            return stream.as_mut().poll_next(cx);
+        }
+
+        loop {
+            if let Some(fut) = this.in_flight.as_mut() {
+                match fut.as_mut().poll(cx) {
+                    Poll::Ready(still_skip) => {
+                        this.in_flight = None;
+                        let item = *this.item.take().unwrap();
+                        if !still_skip {
+                            this.skipping = false;
+                            return Poll::Ready(Some(item));
+                        }
+                        // Keep skipping; fall through to pull the next item.
+                    }
+                    Poll::Pending => return Poll::Pending,
+                }
+                continue;
+            }
+
+            match stream.as_mut().poll_next(cx) {
+                Poll::Ready(Some(item)) => {
+                    let boxed = Box::new(item);
+                    let fut = (this.f)(&boxed);
+                    this.item = Some(boxed);
+                    this.in_flight = Some(Box::pin(fut));
+                }
+                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
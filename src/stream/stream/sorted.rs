@@ -0,0 +1,77 @@
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use alloc::collections::VecDeque;
+use alloc::vec::Vec;
+
+use crate::stream::Stream;
+
+enum State<S: Stream> {
+    Collecting(S, Vec<S::Item>),
+    Draining(VecDeque<S::Item>),
+}
+
+/// Stream for the [`sorted`](super::StreamExt::sorted) and
+/// [`sorted_by_key`](super::StreamExt::sorted_by_key) methods.
+///
+/// Both are driven by a comparator so the buffer-then-sort machinery lives
+/// in one place: `sorted` passes `Ord::cmp` directly, `sorted_by_key`
+/// compares derived keys.
+pub struct Sorted<S, F>
+where
+    S: Stream,
+{
+    state: Option<State<S>>,
+    cmp: F,
+}
+
+impl<S, F> Sorted<S, F>
+where
+    S: Stream,
+{
+    pub(super) fn new(stream: S, cmp: F) -> Self {
+        Self {
+            state: Some(State::Collecting(stream, Vec::new())),
+            cmp,
+        }
+    }
+}
+
+impl<S, F> Stream for Sorted<S, F>
+where
+    S: Stream,
+    F: FnMut(&S::Item, &S::Item) -> core::cmp::Ordering,
+{
+    type Item = S::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = unsafe { self.get_unchecked_mut() };
+
+        loop {
+            match this.state.take().unwrap() {
+                State::Collecting(mut stream, mut items) => {
+                    let mut pinned_stream = unsafe { Pin::new_unchecked(&mut stream) };
+                    match pinned_stream.as_mut().poll_next(cx) {
+                        Poll::Ready(Some(item)) => {
+                            items.push(item);
+                            this.state = Some(State::Collecting(stream, items));
+                        }
+                        Poll::Ready(None) => {
+                            items.sort_by(&mut this.cmp);
+                            this.state = Some(State::Draining(items.into()));
+                        }
+                        Poll::Pending => {
+                            this.state = Some(State::Collecting(stream, items));
+                            return Poll::Pending;
+                        }
+                    }
+                }
+                State::Draining(mut items) => {
+                    let next = items.pop_front();
+                    this.state = Some(State::Draining(items));
+                    return Poll::Ready(next);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,45 @@
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use crate::stream::Stream;
+
+/// Future for the [`try_collect`](super::StreamExt::try_collect) method.
+#[derive(Debug)]
+pub struct TryCollectFuture<S, C> {
+    stream: S,
+    items: Option<C>,
+}
+
+impl<S, C: Default> TryCollectFuture<S, C> {
+    pub(super) fn new(stream: S) -> Self {
+        Self {
+            stream,
+            items: Some(C::default()),
+        }
+    }
+}
+
+impl<S, T, E, C> Future for TryCollectFuture<S, C>
+where
+    S: Stream<Item = Result<T, E>>,
+    C: Default + Extend<T>,
+{
+    type Output = Result<C, E>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = unsafe { self.get_unchecked_mut() };
+        let mut stream = unsafe { Pin::new_unchecked(&mut this.stream) };
+
+        loop {
+            match stream.as_mut().poll_next(cx) {
+                Poll::Ready(Some(Ok(item))) => {
+                    this.items.as_mut().unwrap().extend(Some(item));
+                }
+                Poll::Ready(Some(Err(e))) => return Poll::Ready(Err(e)),
+                Poll::Ready(None) => return Poll::Ready(Ok(this.items.take().unwrap())),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
@@ -0,0 +1,43 @@
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use crate::stream::Stream;
+
+/// Stream for the [`try_filter`](super::StreamExt::try_filter) method.
+#[derive(Debug)]
+pub struct TryFilter<S, P> {
+    stream: S,
+    predicate: P,
+}
+
+impl<S, P> TryFilter<S, P> {
+    pub(super) fn new(stream: S, predicate: P) -> Self {
+        Self { stream, predicate }
+    }
+}
+
+impl<S, T, E, P> Stream for TryFilter<S, P>
+where
+    S: Stream<Item = Result<T, E>>,
+    P: FnMut(&T) -> bool,
+{
+    type Item = Result<T, E>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = unsafe { self.get_unchecked_mut() };
+        let mut stream = unsafe { Pin::new_unchecked(&mut this.stream) };
+
+        loop {
+            match stream.as_mut().poll_next(cx) {
+                Poll::Ready(Some(Ok(item))) => {
+                    if (this.predicate)(&item) {
+                        return Poll::Ready(Some(Ok(item)));
+                    }
+                }
+                Poll::Ready(Some(Err(e))) => return Poll::Ready(Some(Err(e))),
+                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
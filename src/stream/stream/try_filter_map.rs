@@ -0,0 +1,44 @@
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use crate::stream::Stream;
+
+/// Stream for the [`try_filter_map`](super::StreamExt::try_filter_map)
+/// method.
+#[derive(Debug)]
+pub struct TryFilterMap<S, F> {
+    stream: S,
+    f: F,
+}
+
+impl<S, F> TryFilterMap<S, F> {
+    pub(super) fn new(stream: S, f: F) -> Self {
+        Self { stream, f }
+    }
+}
+
+impl<S, T, U, E, F> Stream for TryFilterMap<S, F>
+where
+    S: Stream<Item = Result<T, E>>,
+    F: FnMut(T) -> Result<Option<U>, E>,
+{
+    type Item = Result<U, E>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = unsafe { self.get_unchecked_mut() };
+        let mut stream = unsafe { Pin::new_unchecked(&mut this.stream) };
+
+        loop {
+            match stream.as_mut().poll_next(cx) {
+                Poll::Ready(Some(Ok(item))) => match (this.f)(item) {
+                    Ok(Some(item)) => return Poll::Ready(Some(Ok(item))),
+                    Ok(None) => {}
+                    Err(e) => return Poll::Ready(Some(Err(e))),
+                },
+                Poll::Ready(Some(Err(e))) => return Poll::Ready(Some(Err(e))),
+                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
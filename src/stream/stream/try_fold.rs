@@ -0,0 +1,51 @@
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use crate::stream::Stream;
+
+/// Future for the [`try_fold`](super::StreamExt::try_fold) method.
+#[derive(Debug)]
+pub struct TryFoldFuture<S, F, B> {
+    stream: S,
+    f: F,
+    acc: Option<B>,
+}
+
+impl<S, F, B> TryFoldFuture<S, F, B> {
+    pub(super) fn new(stream: S, f: F, init: B) -> Self {
+        Self {
+            stream,
+            f,
+            acc: Some(init),
+        }
+    }
+}
+
+impl<S, T, E, F, B> Future for TryFoldFuture<S, F, B>
+where
+    S: Stream<Item = Result<T, E>>,
+    F: FnMut(B, T) -> Result<B, E>,
+{
+    type Output = Result<B, E>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = unsafe { self.get_unchecked_mut() };
+        let mut stream = unsafe { Pin::new_unchecked(&mut this.stream) };
+
+        loop {
+            match stream.as_mut().poll_next(cx) {
+                Poll::Ready(Some(Ok(item))) => {
+                    let acc = this.acc.take().unwrap();
+                    match (this.f)(acc, item) {
+                        Ok(acc) => this.acc = Some(acc),
+                        Err(e) => return Poll::Ready(Err(e)),
+                    }
+                }
+                Poll::Ready(Some(Err(e))) => return Poll::Ready(Err(e)),
+                Poll::Ready(None) => return Poll::Ready(Ok(this.acc.take().unwrap())),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
@@ -0,0 +1,44 @@
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use crate::stream::Stream;
+
+/// Future for the [`try_for_each`](super::StreamExt::try_for_each) method.
+#[derive(Debug)]
+pub struct TryForEachFuture<S, F> {
+    stream: S,
+    f: F,
+}
+
+impl<S, F> TryForEachFuture<S, F> {
+    pub(super) fn new(stream: S, f: F) -> Self {
+        Self { stream, f }
+    }
+}
+
+impl<S, T, E, F> Future for TryForEachFuture<S, F>
+where
+    S: Stream<Item = Result<T, E>>,
+    F: FnMut(T) -> Result<(), E>,
+{
+    type Output = Result<(), E>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = unsafe { self.get_unchecked_mut() };
+        let mut stream = unsafe { Pin::new_unchecked(&mut this.stream) };
+
+        loop {
+            match stream.as_mut().poll_next(cx) {
+                Poll::Ready(Some(Ok(item))) => {
+                    if let Err(e) = (this.f)(item) {
+                        return Poll::Ready(Err(e));
+                    }
+                }
+                Poll::Ready(Some(Err(e))) => return Poll::Ready(Err(e)),
+                Poll::Ready(None) => return Poll::Ready(Ok(())),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
+use crate::stream::Stream;
+
+/// Future for the
+/// [`try_for_each_concurrent`](super::StreamExt::try_for_each_concurrent)
+/// method.
+pub struct TryForEachConcurrent<S, F, Fut> {
+    stream: S,
+    f: F,
+    limit: usize,
+    in_flight: Vec<Pin<Box<Fut>>>,
+    done: bool,
+}
+
+impl<S, F, Fut> TryForEachConcurrent<S, F, Fut> {
+    pub(super) fn new(stream: S, limit: usize, f: F) -> Self {
+        assert!(limit > 0, "concurrency limit must be greater than zero");
+        Self {
+            stream,
+            f,
+            limit,
+            in_flight: Vec::with_capacity(limit),
+            done: false,
+        }
+    }
+}
+
+impl<S, F, Fut, E> Future for TryForEachConcurrent<S, F, Fut>
+where
+    S: Stream,
+    F: FnMut(S::Item) -> Fut,
+    Fut: Future<Output = Result<(), E>>,
+{
+    type Output = Result<(), E>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = unsafe { self.get_unchecked_mut() };
+        let mut stream = unsafe { Pin::new_unchecked(&mut this.stream) };
+
+        while !this.done && this.in_flight.len() < this.limit {
+            match stream.as_mut().poll_next(cx) {
+                Poll::Ready(Some(item)) => this.in_flight.push(Box::pin((this.f)(item))),
+                Poll::Ready(None) => {
+                    this.done = true;
+                    break;
+                }
+                Poll::Pending => break,
+            }
+        }
+
+        let mut i = 0;
+        while i < this.in_flight.len() {
+            match this.in_flight[i].as_mut().poll(cx) {
+                Poll::Ready(Ok(())) => {
+                    this.in_flight.swap_remove(i);
+                }
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                Poll::Pending => i += 1,
+            }
+        }
+
+        if this.done && this.in_flight.is_empty() {
+            Poll::Ready(Ok(()))
+        } else {
+            Poll::Pending
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use crate::stream::Stream;
+
+/// Future for the [`unzip`](super::StreamExt::unzip) method.
+#[derive(Debug)]
+pub struct UnzipFuture<S, A, B> {
+    stream: S,
+    res: Option<(A, B)>,
+}
+
+impl<S, A: Default, B: Default> UnzipFuture<S, A, B> {
+    pub(super) fn new(stream: S) -> Self {
+        Self {
+            stream,
+            res: Some((A::default(), B::default())),
+        }
+    }
+}
+
+impl<S, T, U, A, B> Future for UnzipFuture<S, A, B>
+where
+    S: Stream<Item = (T, U)>,
+    A: Default + Extend<T>,
+    B: Default + Extend<U>,
+{
+    type Output = (A, B);
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = unsafe { self.get_unchecked_mut() };
+        let mut stream = unsafe { Pin::new_unchecked(&mut this.stream) };
+
+        loop {
+            match stream.as_mut().poll_next(cx) {
+                Poll::Ready(Some((a, b))) => {
+                    let (left, right) = this.res.as_mut().unwrap();
+                    left.extend(Some(a));
+                    right.extend(Some(b));
+                }
+                Poll::Ready(None) => return Poll::Ready(this.res.take().unwrap()),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use alloc::collections::VecDeque;
+use alloc::vec::Vec;
+
+use crate::stream::Stream;
+
+/// Stream for the [`windows`](super::StreamExt::windows) method.
+#[derive(Debug)]
+pub struct Windows<S>
+where
+    S: Stream,
+{
+    stream: S,
+    size: usize,
+    buf: VecDeque<S::Item>,
+}
+
+impl<S> Windows<S>
+where
+    S: Stream,
+{
+    pub(super) fn new(stream: S, size: usize) -> Self {
+        assert!(size > 0, "window size must be greater than zero");
+        Self {
+            stream,
+            size,
+            buf: VecDeque::with_capacity(size),
+        }
+    }
+}
+
+impl<S> Stream for Windows<S>
+where
+    S: Stream,
+    S::Item: Clone,
+{
+    type Item = Vec<S::Item>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = unsafe { self.get_unchecked_mut() };
+        let mut stream = unsafe { Pin::new_unchecked(&mut this.stream) };
+
+        while this.buf.len() < this.size {
+            match stream.as_mut().poll_next(cx) {
+                Poll::Ready(Some(item)) => this.buf.push_back(item),
+                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+
+        let window: Vec<S::Item> = this.buf.iter().cloned().collect();
+        this.buf.pop_front();
+        Poll::Ready(Some(window))
+    }
+}
@@ -0,0 +1,77 @@
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use crate::stream::Stream;
+
+/// Value yielded by [`zip_longest`](super::StreamExt::zip_longest) for each
+/// step, covering the case where one side has already run out.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EitherOrBoth<A, B> {
+    /// Both streams still had an item.
+    Both(A, B),
+    /// Only the left stream had an item left.
+    Left(A),
+    /// Only the right stream had an item left.
+    Right(B),
+}
+
+/// Stream for the [`zip_longest`](super::StreamExt::zip_longest) method.
+#[derive(Debug)]
+pub struct ZipLongest<A, B> {
+    a: A,
+    b: B,
+    a_done: bool,
+    b_done: bool,
+}
+
+impl<A, B> ZipLongest<A, B> {
+    pub(super) fn new(a: A, b: B) -> Self {
+        Self {
+            a,
+            b,
+            a_done: false,
+            b_done: false,
+        }
+    }
+}
+
+impl<A, B> Stream for ZipLongest<A, B>
+where
+    A: Stream,
+    B: Stream,
+{
+    type Item = EitherOrBoth<A::Item, B::Item>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = unsafe { self.get_unchecked_mut() };
+
+        let a_poll = if this.a_done {
+            Poll::Ready(None)
+        } else {
+            unsafe { Pin::new_unchecked(&mut this.a) }.poll_next(cx)
+        };
+        let b_poll = if this.b_done {
+            Poll::Ready(None)
+        } else {
+            unsafe { Pin::new_unchecked(&mut this.b) }.poll_next(cx)
+        };
+
+        match (a_poll, b_poll) {
+            (Poll::Pending, _) | (_, Poll::Pending) => Poll::Pending,
+            (Poll::Ready(None), Poll::Ready(None)) => {
+                this.a_done = true;
+                this.b_done = true;
+                Poll::Ready(None)
+            }
+            (Poll::Ready(Some(a)), Poll::Ready(Some(b))) => Poll::Ready(Some(EitherOrBoth::Both(a, b))),
+            (Poll::Ready(Some(a)), Poll::Ready(None)) => {
+                this.b_done = true;
+                Poll::Ready(Some(EitherOrBoth::Left(a)))
+            }
+            (Poll::Ready(None), Poll::Ready(Some(b))) => {
+                this.a_done = true;
+                Poll::Ready(Some(EitherOrBoth::Right(b)))
+            }
+        }
+    }
+}
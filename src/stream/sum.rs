@@ -1,6 +1,9 @@
 use core::future::Future;
 use core::pin::Pin;
 
+use alloc::boxed::Box;
+
+use crate::stream::stream::FoldFuture;
 use crate::stream::Stream;
 
 /// Trait to represent types that can be created by summing up a stream.
@@ -8,39 +11,95 @@ use crate::stream::Stream;
 /// This trait is used to implement the [`sum`] method on streams. Types which
 /// implement the trait can be generated by the [`sum`] method. Like
 /// [`FromStream`] this trait should rarely be called directly and instead
-/// interacted with through [`Stream::sum`].
+/// interacted with through [`StreamExt::sum`].
+///
+/// [`SumFuture`](Sum::SumFuture) is a generic associated type rather than a
+/// fixed `Pin<Box<dyn Future<...>>>` so that an impl which can express its
+/// summation as a single [`fold`](crate::stream::stream::StreamExt::fold) --
+/// every primitive below does -- returns [`FoldFuture`] directly and pays no
+/// allocation per call. An impl with no such shortcut is still free to set
+/// `SumFuture` to a boxed future; the associated type just stops that from
+/// being the only option.
 ///
 /// [`sum`]: trait.Sum.html#tymethod.sum
 /// [`FromStream`]: trait.FromStream.html
-/// [`Stream::sum`]: trait.Stream.html#method.sum
+/// [`StreamExt::sum`]: trait.StreamExt.html#method.sum
 pub trait Sum<A = Self>: Sized {
+    /// The future returned by [`sum`](Sum::sum).
+    type SumFuture<'a, S>: Future<Output = Self> + 'a
+    where
+        S: Stream<Item = A> + 'a;
+
     /// Method which takes a stream and generates `Self` from the elements by
     /// "summing up" the items.
+    fn sum<'a, S>(stream: S) -> Self::SumFuture<'a, S>
+    where
+        S: Stream<Item = A> + 'a;
+}
+
+/// Bridges implementors of the pre-GAT shape of [`Sum`] -- the
+/// `Pin<Box<dyn Future<...>>>`-returning `sum` this trait used to require --
+/// so a crate that already implemented it doesn't have to pick a
+/// [`Sum::SumFuture`] of its own, just rename the trait it implements from
+/// `Sum` to this one.
+///
+/// Only covers streams with no borrow of their own shorter than `'static`;
+/// a caller summing a stream that borrows short-lived data needs the target
+/// type to implement [`Sum`] directly. Sealed so nothing can implement it
+/// for a `(Self, A)` pair that isn't already covered by the blanket
+/// [`Sum`] impl below.
+pub trait BoxedSum<A = Self>: private::Sealed<A> + Sized {
+    /// Method which takes a stream and generates `Self` from the elements by
+    /// "summing up" the items, boxing the resulting future.
     fn sum<'a, S>(stream: S) -> Pin<Box<dyn Future<Output = Self> + 'a>>
     where
         S: Stream<Item = A> + 'a;
 }
 
+mod private {
+    pub trait Sealed<A> {}
+    impl<T, A> Sealed<A> for T where T: super::BoxedSum<A> {}
+}
+
+impl<T, A> Sum<A> for T
+where
+    T: BoxedSum<A>,
+{
+    type SumFuture<'a, S> = Pin<Box<dyn Future<Output = Self> + 'a>> where S: Stream<Item = A> + 'a;
+
+    fn sum<'a, S>(stream: S) -> Self::SumFuture<'a, S>
+    where
+        S: Stream<Item = A> + 'a,
+    {
+        <T as BoxedSum<A>>::sum(stream)
+    }
+}
+
 use crate::stream::stream::StreamExt;
 use core::num::Wrapping;
 use core::ops::Add;
 
 macro_rules! integer_sum {
     (@impls $zero: expr, $($a:ty)*) => ($(
+        #[cfg(not(feature = "num-traits"))]
         impl Sum for $a {
-            fn sum<'a, S>(stream: S) -> Pin<Box<dyn Future<Output = Self>+ 'a>>
+            type SumFuture<'a, S> = FoldFuture<S, fn($a, $a) -> $a, $a> where S: Stream<Item = $a> + 'a;
+
+            fn sum<'a, S>(stream: S) -> Self::SumFuture<'a, S>
             where
                 S: Stream<Item = $a> + 'a,
             {
-                Box::pin(async move { stream.fold($zero, Add::add).await } )
+                stream.fold($zero, Add::add as fn($a, $a) -> $a)
             }
         }
         impl<'a> Sum<&'a $a> for $a {
-            fn sum<'b, S>(stream: S) -> Pin<Box<dyn Future<Output = Self> + 'b>>
+            type SumFuture<'b, S> = FoldFuture<S, fn($a, &'a $a) -> $a, $a> where S: Stream<Item = &'a $a> + 'b;
+
+            fn sum<'b, S>(stream: S) -> Self::SumFuture<'b, S>
             where
                 S: Stream<Item = &'a $a> + 'b,
             {
-                Box::pin(async move { stream.fold($zero, Add::add).await } )
+                stream.fold($zero, Add::add as fn($a, &'a $a) -> $a)
             }
         }
     )*);
@@ -52,18 +111,23 @@ macro_rules! integer_sum {
 
 macro_rules! float_sum {
     ($($a:ty)*) => ($(
+        #[cfg(not(feature = "num-traits"))]
         impl Sum for $a {
-            fn sum<'a, S>(stream: S) -> Pin<Box<dyn Future<Output = Self> + 'a>>
+            type SumFuture<'a, S> = FoldFuture<S, fn($a, $a) -> $a, $a> where S: Stream<Item = $a> + 'a;
+
+            fn sum<'a, S>(stream: S) -> Self::SumFuture<'a, S>
                 where S: Stream<Item = $a> + 'a,
             {
-                Box::pin(async move { stream.fold(0.0, |a, b| a + b).await } )
+                stream.fold(0.0, (|a: $a, b: $a| a + b) as fn($a, $a) -> $a)
             }
         }
         impl<'a> Sum<&'a $a> for $a {
-            fn sum<'b, S>(stream: S) -> Pin<Box<dyn Future<Output = Self> + 'b>>
+            type SumFuture<'b, S> = FoldFuture<S, fn($a, &'a $a) -> $a, $a> where S: Stream<Item = &'a $a> + 'b;
+
+            fn sum<'b, S>(stream: S) -> Self::SumFuture<'b, S>
                 where S: Stream<Item = &'a $a> + 'b,
             {
-                Box::pin(async move { stream.fold(0.0, |a, b| a + b).await } )
+                stream.fold(0.0, (|a: $a, b: &'a $a| a + b) as fn($a, &'a $a) -> $a)
             }
         }
     )*);
@@ -75,3 +139,178 @@ macro_rules! float_sum {
 
 integer_sum! { i8 i16 i32 i64 i128 isize u8 u16 u32 u64 u128 usize }
 float_sum! { f32 f64 }
+
+/// Blanket impl of [`Sum`] for any type with an additive identity, gated
+/// behind the `num-traits` feature.
+///
+/// This lets [`Stream::sum`] work for arbitrary-precision integers, decimals,
+/// and other domain-specific numeric types, not just the primitives above.
+///
+/// [`Sum`]: trait.Sum.html
+/// [`Stream::sum`]: trait.Stream.html#method.sum
+#[cfg(feature = "num-traits")]
+impl<T> Sum for T
+where
+    T: Add<Output = T> + num_traits::Zero,
+{
+    type SumFuture<'a, S> = FoldFuture<S, fn(T, T) -> T, T> where S: Stream<Item = T> + 'a;
+
+    fn sum<'a, S>(stream: S) -> Self::SumFuture<'a, S>
+    where
+        S: Stream<Item = T> + 'a,
+    {
+        stream.fold(T::zero(), Add::add as fn(T, T) -> T)
+    }
+}
+
+/// Trait to represent types that can be created by summing up a stream,
+/// short-circuiting to `None` as soon as an addition overflows.
+///
+/// This trait is used to implement the [`checked_sum`] method on streams.
+/// Unlike [`TrySum`], which reports overflow as an `Err`, this mirrors the
+/// `checked_*` naming used by the primitive integer types themselves. This
+/// trait should rarely be called directly and instead interacted with
+/// through [`StreamExt::checked_sum`].
+///
+/// Unlike [`Sum`], this one still always boxes: short-circuiting partway
+/// through the stream on overflow isn't expressible as a single `fold`, so
+/// there's no `FoldFuture`-shaped win to be had here the way there is above.
+///
+/// [`checked_sum`]: trait.CheckedSum.html#tymethod.checked_sum
+/// [`TrySum`]: trait.TrySum.html
+/// [`StreamExt::checked_sum`]: trait.StreamExt.html#method.checked_sum
+pub trait CheckedSum<A = Self>: Sized {
+    /// Method which takes a stream and generates `Self` from the elements by
+    /// "summing up" the items, resolving to `None` the first time an
+    /// addition would overflow.
+    fn checked_sum<'a, S>(stream: S) -> Pin<Box<dyn Future<Output = Option<Self>> + 'a>>
+    where
+        S: Stream<Item = A> + 'a;
+}
+
+macro_rules! integer_checked_sum {
+    ($($a:ty)*) => ($(
+        impl CheckedSum for $a {
+            fn checked_sum<'a, S>(
+                stream: S,
+            ) -> Pin<Box<dyn Future<Output = Option<Self>> + 'a>>
+            where
+                S: Stream<Item = $a> + 'a,
+            {
+                Box::pin(async move {
+                    pin_utils::pin_mut!(stream);
+                    let mut acc: $a = 0;
+                    while let Some(x) = stream.next().await {
+                        acc = acc.checked_add(x)?;
+                    }
+                    Some(acc)
+                })
+            }
+        }
+        impl<'a> CheckedSum<&'a $a> for $a {
+            fn checked_sum<'b, S>(
+                stream: S,
+            ) -> Pin<Box<dyn Future<Output = Option<Self>> + 'b>>
+            where
+                S: Stream<Item = &'a $a> + 'b,
+            {
+                Box::pin(async move {
+                    pin_utils::pin_mut!(stream);
+                    let mut acc: $a = 0;
+                    while let Some(x) = stream.next().await {
+                        acc = acc.checked_add(*x)?;
+                    }
+                    Some(acc)
+                })
+            }
+        }
+    )*);
+}
+
+integer_checked_sum! { i8 i16 i32 i64 i128 isize u8 u16 u32 u64 u128 usize }
+
+use alloc::vec::Vec;
+use core::time::Duration;
+
+impl Sum for Duration {
+    type SumFuture<'a, S> = FoldFuture<S, fn(Duration, Duration) -> Duration, Duration> where S: Stream<Item = Duration> + 'a;
+
+    fn sum<'a, S>(stream: S) -> Self::SumFuture<'a, S>
+    where
+        S: Stream<Item = Duration> + 'a,
+    {
+        stream.fold(Duration::new(0, 0), Add::add as fn(Duration, Duration) -> Duration)
+    }
+}
+
+impl<'a> Sum<&'a Duration> for Duration {
+    type SumFuture<'b, S> = FoldFuture<S, fn(Duration, &'a Duration) -> Duration, Duration> where S: Stream<Item = &'a Duration> + 'b;
+
+    fn sum<'b, S>(stream: S) -> Self::SumFuture<'b, S>
+    where
+        S: Stream<Item = &'a Duration> + 'b,
+    {
+        stream.fold(Duration::new(0, 0), (|a: Duration, b: &'a Duration| a + *b) as fn(Duration, &'a Duration) -> Duration)
+    }
+}
+
+/// Takes each element in the stream: if it is `None`, no further elements
+/// are pulled and the overall result is `None`; otherwise the inner values
+/// are collected into `Some` of a [`Sum`], mirroring
+/// `core::iter::Sum<Option<A>>`.
+///
+/// This recurses into `T::sum` of a freshly built stream rather than
+/// folding in place, so -- unlike the impls above -- there's no single
+/// concrete, unboxed future to name here.
+///
+/// [`Sum`]: trait.Sum.html
+impl<T, U> Sum<Option<U>> for Option<T>
+where
+    T: Sum<U>,
+{
+    type SumFuture<'a, S> = Pin<Box<dyn Future<Output = Self> + 'a>> where S: Stream<Item = Option<U>> + 'a;
+
+    fn sum<'a, S>(stream: S) -> Self::SumFuture<'a, S>
+    where
+        S: Stream<Item = Option<U>> + 'a,
+    {
+        Box::pin(async move {
+            pin_utils::pin_mut!(stream);
+            let mut items = Vec::new();
+            while let Some(item) = stream.next().await {
+                items.push(item?);
+            }
+            Some(T::sum(crate::stream::from_iter(items)).await)
+        })
+    }
+}
+
+/// Takes each element in the stream: if it is `Err`, no further elements are
+/// pulled and the overall result is that `Err`; otherwise the `Ok` values
+/// are collected into `Ok` of a [`Sum`], mirroring
+/// `core::iter::Sum<Result<A, E>>`.
+///
+/// Like the `Option` impl above, this recurses into `T::sum` rather than
+/// folding in place, so it stays boxed.
+///
+/// [`Sum`]: trait.Sum.html
+impl<T, U, E> Sum<Result<U, E>> for Result<T, E>
+where
+    T: Sum<U>,
+{
+    type SumFuture<'a, S> = Pin<Box<dyn Future<Output = Self> + 'a>> where S: Stream<Item = Result<U, E>> + 'a;
+
+    fn sum<'a, S>(stream: S) -> Self::SumFuture<'a, S>
+    where
+        S: Stream<Item = Result<U, E>> + 'a,
+    {
+        Box::pin(async move {
+            pin_utils::pin_mut!(stream);
+            let mut items = Vec::new();
+            while let Some(item) = stream.next().await {
+                items.push(item?);
+            }
+            Ok(T::sum(crate::stream::from_iter(items)).await)
+        })
+    }
+}
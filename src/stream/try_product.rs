@@ -0,0 +1,68 @@
+use core::future::Future;
+use core::pin::Pin;
+
+use crate::stream::stream::StreamExt;
+use crate::stream::try_sum::SumError;
+use crate::stream::Stream;
+
+/// Trait to represent types that can be fallibly created by multiplying the
+/// elements of a stream, short-circuiting on overflow.
+///
+/// This trait is used to implement the [`try_product`] method on streams.
+/// Types which implement the trait can be generated by the [`try_product`]
+/// method. This trait should rarely be called directly and instead
+/// interacted with through [`StreamExt::try_product`].
+///
+/// [`try_product`]: trait.TryProduct.html#tymethod.try_product
+/// [`StreamExt::try_product`]: trait.StreamExt.html#method.try_product
+pub trait TryProduct<A = Self>: Sized {
+    /// Method which takes a stream and generates `Self` from the elements by
+    /// multiplying the items, resolving to `Err(SumError::Overflow)` the
+    /// first time a multiplication would overflow.
+    fn try_product<'a, S>(
+        stream: S,
+    ) -> Pin<Box<dyn Future<Output = Result<Self, SumError>> + 'a>>
+    where
+        S: Stream<Item = A> + 'a;
+}
+
+macro_rules! integer_try_product {
+    ($($a:ty)*) => ($(
+        impl TryProduct for $a {
+            fn try_product<'a, S>(
+                stream: S,
+            ) -> Pin<Box<dyn Future<Output = Result<Self, SumError>> + 'a>>
+            where
+                S: Stream<Item = $a> + 'a,
+            {
+                Box::pin(async move {
+                    pin_utils::pin_mut!(stream);
+                    let mut acc: $a = 1;
+                    while let Some(x) = stream.next().await {
+                        acc = acc.checked_mul(x).ok_or(SumError::Overflow)?;
+                    }
+                    Ok(acc)
+                })
+            }
+        }
+        impl<'a> TryProduct<&'a $a> for $a {
+            fn try_product<'b, S>(
+                stream: S,
+            ) -> Pin<Box<dyn Future<Output = Result<Self, SumError>> + 'b>>
+            where
+                S: Stream<Item = &'a $a> + 'b,
+            {
+                Box::pin(async move {
+                    pin_utils::pin_mut!(stream);
+                    let mut acc: $a = 1;
+                    while let Some(x) = stream.next().await {
+                        acc = acc.checked_mul(*x).ok_or(SumError::Overflow)?;
+                    }
+                    Ok(acc)
+                })
+            }
+        }
+    )*);
+}
+
+integer_try_product! { i8 i16 i32 i64 i128 isize u8 u16 u32 u64 u128 usize }
@@ -0,0 +1,88 @@
+use core::fmt;
+use core::future::Future;
+use core::pin::Pin;
+
+use crate::stream::stream::StreamExt;
+use crate::stream::Stream;
+
+/// The error returned by [`try_sum`]/[`try_product`] when an arithmetic
+/// operation overflows.
+///
+/// [`try_sum`]: trait.StreamExt.html#method.try_sum
+/// [`try_product`]: trait.StreamExt.html#method.try_product
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SumError {
+    /// An addition or multiplication overflowed while folding the stream.
+    Overflow,
+}
+
+impl fmt::Display for SumError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SumError::Overflow => write!(f, "arithmetic overflow while folding a stream"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SumError {}
+
+/// Trait to represent types that can be fallibly created by summing up a
+/// stream, short-circuiting on overflow.
+///
+/// This trait is used to implement the [`try_sum`] method on streams. Types
+/// which implement the trait can be generated by the [`try_sum`] method. This
+/// trait should rarely be called directly and instead interacted with
+/// through [`StreamExt::try_sum`].
+///
+/// [`try_sum`]: trait.TrySum.html#tymethod.try_sum
+/// [`StreamExt::try_sum`]: trait.StreamExt.html#method.try_sum
+pub trait TrySum<A = Self>: Sized {
+    /// Method which takes a stream and generates `Self` from the elements by
+    /// "summing up" the items, resolving to `Err(SumError::Overflow)` the
+    /// first time an addition would overflow.
+    fn try_sum<'a, S>(stream: S) -> Pin<Box<dyn Future<Output = Result<Self, SumError>> + 'a>>
+    where
+        S: Stream<Item = A> + 'a;
+}
+
+macro_rules! integer_try_sum {
+    ($($a:ty)*) => ($(
+        impl TrySum for $a {
+            fn try_sum<'a, S>(
+                stream: S,
+            ) -> Pin<Box<dyn Future<Output = Result<Self, SumError>> + 'a>>
+            where
+                S: Stream<Item = $a> + 'a,
+            {
+                Box::pin(async move {
+                    pin_utils::pin_mut!(stream);
+                    let mut acc: $a = 0;
+                    while let Some(x) = stream.next().await {
+                        acc = acc.checked_add(x).ok_or(SumError::Overflow)?;
+                    }
+                    Ok(acc)
+                })
+            }
+        }
+        impl<'a> TrySum<&'a $a> for $a {
+            fn try_sum<'b, S>(
+                stream: S,
+            ) -> Pin<Box<dyn Future<Output = Result<Self, SumError>> + 'b>>
+            where
+                S: Stream<Item = &'a $a> + 'b,
+            {
+                Box::pin(async move {
+                    pin_utils::pin_mut!(stream);
+                    let mut acc: $a = 0;
+                    while let Some(x) = stream.next().await {
+                        acc = acc.checked_add(*x).ok_or(SumError::Overflow)?;
+                    }
+                    Ok(acc)
+                })
+            }
+        }
+    )*);
+}
+
+integer_try_sum! { i8 i16 i32 i64 i128 isize u8 u16 u32 u64 u128 usize }
@@ -0,0 +1,89 @@
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use alloc::boxed::Box;
+
+use crate::stream::Stream;
+
+/// Creates a stream from a fallible, asynchronous generator function.
+///
+/// `f` is called with the current state, returning a future that resolves
+/// to `Ok(Some((item, next_state)))` to yield `item` and continue with
+/// `next_state`, `Ok(None)` to end the stream, or `Err(e)` to yield `e` as
+/// the stream's last item and end it there -- no further calls to `f` are
+/// made once either happens. This is the fallible counterpart to
+/// `stream::unfold`, for generators (a paginated API fetcher walking a
+/// `next_page` token, say) whose step itself can fail, so the caller isn't
+/// stuck threading a `Result` through the state just to report that.
+///
+/// ```ignore
+/// let pages = stream::try_unfold(Some(first_token), |token| async move {
+///     let token = match token {
+///         Some(token) => token,
+///         None => return Ok(None),
+///     };
+///     let page = fetch_page(&token).await?;
+///     Ok(Some((page.items, page.next_token)))
+/// });
+/// ```
+pub fn try_unfold<T, F, Fut, Item, E>(init: T, f: F) -> TryUnfold<T, F, Fut>
+where
+    F: FnMut(T) -> Fut,
+    Fut: Future<Output = Result<Option<(Item, T)>, E>>,
+{
+    TryUnfold {
+        f,
+        state: Some(init),
+        in_flight: None,
+    }
+}
+
+/// Stream for the [`try_unfold`] function.
+pub struct TryUnfold<T, F, Fut> {
+    f: F,
+    state: Option<T>,
+    in_flight: Option<Pin<Box<Fut>>>,
+}
+
+impl<T, F, Fut, Item, E> Stream for TryUnfold<T, F, Fut>
+where
+    F: FnMut(T) -> Fut,
+    Fut: Future<Output = Result<Option<(Item, T)>, E>>,
+{
+    type Item = Result<Item, E>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = unsafe { self.get_unchecked_mut() };
+
+        loop {
+            if let Some(fut) = this.in_flight.as_mut() {
+                return match fut.as_mut().poll(cx) {
+                    Poll::Ready(Ok(Some((item, next_state)))) => {
+                        this.in_flight = None;
+                        this.state = Some(next_state);
+                        Poll::Ready(Some(Ok(item)))
+                    }
+                    Poll::Ready(Ok(None)) => {
+                        this.in_flight = None;
+                        Poll::Ready(None)
+                    }
+                    Poll::Ready(Err(e)) => {
+                        this.in_flight = None;
+                        Poll::Ready(Some(Err(e)))
+                    }
+                    Poll::Pending => Poll::Pending,
+                };
+            }
+
+            let state = match this.state.take() {
+                Some(state) => state,
+                // Either the generator already ended (`Ok(None)`) or
+                // failed (`Err`) on a previous call; stay ended rather
+                // than calling `f` again.
+                None => return Poll::Ready(None),
+            };
+            this.in_flight = Some(Box::pin((this.f)(state)));
+        }
+    }
+}
@@ -0,0 +1,216 @@
+//! A rendezvous point for a fixed number of tasks: every task calls
+//! [`Barrier::wait`] and none of them proceed until all of them have.
+
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll, Waker};
+use core::time::Duration;
+
+use alloc::vec::Vec;
+use std::sync::Mutex;
+
+struct State {
+    n: usize,
+    /// How many parties have arrived for the current generation.
+    arrived: usize,
+    /// Bumped every time `arrived` reaches `n` and the barrier
+    /// releases everyone, so a party that gave up via
+    /// [`Barrier::wait_timeout`] can tell its wakeup apart from a
+    /// stale one belonging to a generation it already left.
+    generation: u64,
+    wakers: Vec<Waker>,
+}
+
+/// A barrier that `n` tasks must all reach before any of them
+/// continues.
+pub struct Barrier {
+    state: Mutex<State>,
+    n: usize,
+}
+
+/// The result of [`Barrier::wait`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BarrierWaitResult {
+    is_leader: bool,
+    generation: u64,
+}
+
+impl BarrierWaitResult {
+    /// Whether this task was the one whose arrival tripped the
+    /// barrier, released all the others, and so can, for example,
+    /// alone run barrier-local cleanup.
+    pub fn is_leader(&self) -> bool {
+        self.is_leader
+    }
+
+    /// The generation this wait completed, i.e. how many times the
+    /// barrier has tripped, including this one.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+}
+
+impl Barrier {
+    /// Creates a barrier for `n` parties.
+    pub fn new(n: usize) -> Self {
+        assert!(n > 0, "a barrier needs at least one party");
+        Self {
+            state: Mutex::new(State {
+                n,
+                arrived: 0,
+                generation: 0,
+                wakers: Vec::new(),
+            }),
+            n,
+        }
+    }
+
+    /// Waits for every party to arrive, then releases all of them
+    /// together.
+    pub fn wait(&self) -> Wait<'_> {
+        Wait { barrier: self, generation: None }
+    }
+
+    /// Waits for every party to arrive, giving up and returning `None`
+    /// if `duration` passes first.
+    ///
+    /// A party that times out leaves the barrier rather than being
+    /// stuck counted as "arrived" forever, so the remaining parties
+    /// can still trip it on a future round instead of hanging on a
+    /// straggler indefinitely.
+    pub async fn wait_timeout(&self, duration: Duration) -> Option<BarrierWaitResult> {
+        let mut wait = Wait { barrier: self, generation: None };
+        match crate::future::timeout(duration, &mut wait).await {
+            Ok(result) => Some(result),
+            Err(_) => {
+                wait.abandon();
+                None
+            }
+        }
+    }
+
+    /// The number of parties this barrier was created for.
+    pub fn parties(&self) -> usize {
+        self.n
+    }
+
+    /// The current generation: how many times the barrier has tripped
+    /// so far.
+    pub fn generation(&self) -> u64 {
+        self.state.lock().unwrap().generation
+    }
+}
+
+/// Future returned by [`Barrier::wait`].
+pub struct Wait<'a> {
+    barrier: &'a Barrier,
+    /// The generation this task registered as "arrived" for, once it
+    /// has -- `None` until the first poll.
+    generation: Option<u64>,
+}
+
+impl Wait<'_> {
+    /// Leaves the barrier without completing the wait, so the parties
+    /// still waiting aren't stuck waiting on one that gave up.
+    fn abandon(&mut self) {
+        if let Some(joined_generation) = self.generation.take() {
+            let mut state = self.barrier.state.lock().unwrap();
+            if state.generation == joined_generation {
+                state.arrived -= 1;
+            }
+        }
+    }
+}
+
+impl Drop for Wait<'_> {
+    fn drop(&mut self) {
+        self.abandon();
+    }
+}
+
+impl<'a> Future for Wait<'a> {
+    type Output = BarrierWaitResult;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let mut state = this.barrier.state.lock().unwrap();
+
+        if this.generation.is_none() {
+            state.arrived += 1;
+            this.generation = Some(state.generation);
+        }
+        let joined_generation = this.generation.unwrap();
+
+        if state.generation != joined_generation {
+            // Someone else's arrival tripped the barrier while we were
+            // waiting; we've already been counted and released.
+            return Poll::Ready(BarrierWaitResult { is_leader: false, generation: state.generation });
+        }
+
+        if state.arrived == state.n {
+            state.generation += 1;
+            state.arrived = 0;
+            for waker in state.wakers.drain(..) {
+                waker.wake();
+            }
+            return Poll::Ready(BarrierWaitResult { is_leader: true, generation: state.generation });
+        }
+
+        state.wakers.push(cx.waker().clone());
+        Poll::Pending
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::sync::Arc;
+
+    #[test]
+    fn all_parties_trip_together_with_one_leader() {
+        crate::task::block_on(async {
+            let barrier = Arc::new(Barrier::new(3));
+            let mut handles = Vec::new();
+            for _ in 0..3 {
+                let barrier = barrier.clone();
+                handles.push(crate::task::spawn(async move { barrier.wait().await }));
+            }
+
+            let mut leaders = 0;
+            for handle in handles {
+                let result = handle.await;
+                assert_eq!(result.generation(), 1);
+                if result.is_leader() {
+                    leaders += 1;
+                }
+            }
+            assert_eq!(leaders, 1, "exactly one party should observe itself as the leader");
+            assert_eq!(barrier.generation(), 1);
+        });
+    }
+
+    #[test]
+    fn wait_timeout_reports_a_straggler_instead_of_hanging() {
+        crate::task::block_on(async {
+            let barrier = Barrier::new(2);
+            // Only one of the two required parties ever arrives, so the
+            // timeout -- not the barrier itself -- is what has to end
+            // the wait.
+            let result = barrier.wait_timeout(Duration::from_millis(50)).await;
+            assert!(result.is_none());
+            // The straggler's abandoned wait shouldn't leave it
+            // permanently counted as arrived.
+            assert_eq!(barrier.generation(), 0);
+        });
+    }
+
+    #[test]
+    fn generation_increments_on_each_trip() {
+        crate::task::block_on(async {
+            let barrier = Arc::new(Barrier::new(1));
+            assert_eq!(barrier.wait().await.generation(), 1);
+            assert_eq!(barrier.wait().await.generation(), 2);
+            assert_eq!(barrier.generation(), 2);
+        });
+    }
+}
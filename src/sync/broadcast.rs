@@ -0,0 +1,188 @@
+//! A multi-producer, multi-consumer broadcast channel: every value sent
+//! is delivered to every receiver that hasn't fallen behind, unlike an
+//! mpsc channel where each value goes to exactly one receiver.
+//!
+//! This is a plain value channel; for fanning an existing [`Stream`]
+//! out to subscribers, see [`crate::stream::broadcast::Broadcast`]
+//! instead.
+
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll, Waker};
+
+use alloc::collections::VecDeque;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use std::sync::Mutex;
+
+struct Shared<T> {
+    buffer: VecDeque<T>,
+    /// Sequence number of the oldest slot still in `buffer`.
+    base_seq: u64,
+    /// Sequence number the next `send` will use.
+    next_seq: u64,
+    capacity: usize,
+    sender_count: usize,
+    receiver_count: usize,
+    receiver_wakers: Vec<Waker>,
+}
+
+/// Creates a broadcast channel that retains up to `capacity` unread
+/// values; a receiver that falls more than `capacity` values behind
+/// skips the ones it missed and finds out via
+/// [`RecvError::Lagged`](RecvError::Lagged).
+pub fn channel<T: Clone>(capacity: usize) -> (Sender<T>, Receiver<T>) {
+    assert!(capacity > 0, "broadcast channel capacity must be greater than zero");
+    let shared = Arc::new(Mutex::new(Shared {
+        buffer: VecDeque::with_capacity(capacity),
+        base_seq: 0,
+        next_seq: 0,
+        capacity,
+        sender_count: 1,
+        receiver_count: 1,
+        receiver_wakers: Vec::new(),
+    }));
+    let receiver = Receiver {
+        shared: shared.clone(),
+        next: 0,
+    };
+    (Sender { shared }, receiver)
+}
+
+/// The sending half of a broadcast channel.
+pub struct Sender<T> {
+    shared: Arc<Mutex<Shared<T>>>,
+}
+
+impl<T: Clone> Sender<T> {
+    /// Sends `value` to every subscribed receiver, returning the number
+    /// of receivers it was sent to. Never blocks: a full buffer simply
+    /// evicts its oldest value, causing slow receivers to lag.
+    pub fn send(&self, value: T) -> usize {
+        let mut shared = self.shared.lock().unwrap();
+        if shared.buffer.len() == shared.capacity {
+            shared.buffer.pop_front();
+            shared.base_seq += 1;
+        }
+        shared.buffer.push_back(value);
+        shared.next_seq += 1;
+
+        let receivers = shared.receiver_count;
+        for waker in shared.receiver_wakers.drain(..) {
+            waker.wake();
+        }
+        receivers
+    }
+
+    /// Creates a new receiver that will see every value sent from this
+    /// point on.
+    pub fn subscribe(&self) -> Receiver<T> {
+        let mut shared = self.shared.lock().unwrap();
+        shared.receiver_count += 1;
+        Receiver {
+            shared: self.shared.clone(),
+            next: shared.next_seq,
+        }
+    }
+
+    /// The number of receivers currently subscribed.
+    pub fn receiver_count(&self) -> usize {
+        self.shared.lock().unwrap().receiver_count
+    }
+}
+
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Self {
+        self.shared.lock().unwrap().sender_count += 1;
+        Self { shared: self.shared.clone() }
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        let mut shared = self.shared.lock().unwrap();
+        shared.sender_count -= 1;
+        if shared.sender_count == 0 {
+            for waker in shared.receiver_wakers.drain(..) {
+                waker.wake();
+            }
+        }
+    }
+}
+
+/// The receiving half of a broadcast channel.
+pub struct Receiver<T> {
+    shared: Arc<Mutex<Shared<T>>>,
+    /// Sequence number of the next value this receiver hasn't seen.
+    next: u64,
+}
+
+/// The error returned by [`Receiver::recv`].
+#[derive(Debug, PartialEq, Eq)]
+pub enum RecvError {
+    /// Every sender was dropped and there are no more buffered values.
+    Closed,
+    /// The receiver fell behind and skipped `n` values; it now resumes
+    /// from the oldest value still buffered.
+    Lagged(u64),
+}
+
+impl<T: Clone> Receiver<T> {
+    /// Waits for the next value, or for the channel to close.
+    pub fn recv(&mut self) -> Recv<'_, T> {
+        Recv { receiver: self }
+    }
+
+    fn poll_recv(&mut self, cx: &mut Context<'_>) -> Poll<Result<T, RecvError>> {
+        let mut shared = self.shared.lock().unwrap();
+
+        if self.next < shared.base_seq {
+            let lagged = shared.base_seq - self.next;
+            self.next = shared.base_seq;
+            return Poll::Ready(Err(RecvError::Lagged(lagged)));
+        }
+
+        if self.next < shared.next_seq {
+            let index = (self.next - shared.base_seq) as usize;
+            let value = shared.buffer[index].clone();
+            self.next += 1;
+            return Poll::Ready(Ok(value));
+        }
+
+        if shared.sender_count == 0 {
+            return Poll::Ready(Err(RecvError::Closed));
+        }
+
+        shared.receiver_wakers.push(cx.waker().clone());
+        Poll::Pending
+    }
+}
+
+/// Future returned by [`Receiver::recv`].
+pub struct Recv<'a, T> {
+    receiver: &'a mut Receiver<T>,
+}
+
+impl<'a, T: Clone> Future for Recv<'a, T> {
+    type Output = Result<T, RecvError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.get_mut().receiver.poll_recv(cx)
+    }
+}
+
+impl<T> Clone for Receiver<T> {
+    fn clone(&self) -> Self {
+        self.shared.lock().unwrap().receiver_count += 1;
+        Receiver {
+            shared: self.shared.clone(),
+            next: self.next,
+        }
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        self.shared.lock().unwrap().receiver_count -= 1;
+    }
+}
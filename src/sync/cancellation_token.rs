@@ -0,0 +1,165 @@
+//! A cooperative cancellation signal that propagates down a tree of
+//! tokens, for graceful shutdown across a hierarchy of tasks without
+//! ad-hoc channel plumbing in every application.
+
+use core::future::Future;
+
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+
+use crate::sync::Notify;
+
+struct Inner {
+    cancelled: std::sync::atomic::AtomicBool,
+    notify: Notify,
+    children: std::sync::Mutex<Vec<Arc<Inner>>>,
+}
+
+/// A handle to a cancellation signal. Cloning a token shares the same
+/// signal; [`child_token`](CancellationToken::child_token) instead
+/// creates an independent token that's cancelled whenever its parent
+/// is, but can also be cancelled on its own without affecting the
+/// parent or siblings.
+#[derive(Clone)]
+pub struct CancellationToken {
+    inner: Arc<Inner>,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                cancelled: std::sync::atomic::AtomicBool::new(false),
+                notify: Notify::new(),
+                children: std::sync::Mutex::new(Vec::new()),
+            }),
+        }
+    }
+
+    /// Cancels this token and every descendant created via
+    /// [`child_token`](Self::child_token).
+    pub fn cancel(&self) {
+        if self.inner.cancelled.swap(true, std::sync::atomic::Ordering::AcqRel) {
+            return;
+        }
+        self.inner.notify.notify_waiters();
+        for child in self.inner.children.lock().unwrap().drain(..) {
+            Self { inner: child }.cancel();
+        }
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.cancelled.load(std::sync::atomic::Ordering::Acquire)
+    }
+
+    /// Resolves once this token is cancelled (directly, or via an
+    /// ancestor).
+    pub async fn cancelled(&self) {
+        loop {
+            if self.is_cancelled() {
+                return;
+            }
+            self.inner.notify.notified().await;
+        }
+    }
+
+    /// Creates a child token: cancelled automatically when `self` is,
+    /// but can also be cancelled independently without affecting
+    /// `self` or any sibling.
+    pub fn child_token(&self) -> CancellationToken {
+        let child = CancellationToken::new();
+        if self.is_cancelled() {
+            child.cancel();
+        } else {
+            self.inner.children.lock().unwrap().push(child.inner.clone());
+        }
+        child
+    }
+
+    /// Runs `future` to completion, or stops polling it and returns
+    /// `None` as soon as this token is cancelled.
+    pub async fn run_until_cancelled<F: Future>(&self, future: F) -> Option<F::Output> {
+        pin_utils::pin_mut!(future);
+        crate::select! {
+            value = future => Some(value),
+            () = self.cancelled() => None,
+        }
+    }
+}
+
+impl Default for CancellationToken {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cancel_is_idempotent_and_observed_by_is_cancelled() {
+        let token = CancellationToken::new();
+        assert!(!token.is_cancelled());
+        token.cancel();
+        token.cancel();
+        assert!(token.is_cancelled());
+    }
+
+    #[test]
+    fn cancelled_future_resolves_once_cancelled() {
+        crate::task::block_on(async {
+            let token = CancellationToken::new();
+            let waiter_token = token.clone();
+            let waiter = crate::task::spawn(async move { waiter_token.cancelled().await });
+
+            token.cancel();
+            waiter.await;
+        });
+    }
+
+    #[test]
+    fn cancelling_a_parent_cancels_every_descendant() {
+        let parent = CancellationToken::new();
+        let child = parent.child_token();
+        let grandchild = child.child_token();
+
+        parent.cancel();
+
+        assert!(child.is_cancelled());
+        assert!(grandchild.is_cancelled());
+    }
+
+    #[test]
+    fn cancelling_a_child_does_not_affect_its_parent_or_siblings() {
+        let parent = CancellationToken::new();
+        let child = parent.child_token();
+        let sibling = parent.child_token();
+
+        child.cancel();
+
+        assert!(child.is_cancelled());
+        assert!(!parent.is_cancelled());
+        assert!(!sibling.is_cancelled());
+    }
+
+    #[test]
+    fn child_token_created_after_cancellation_starts_cancelled() {
+        let parent = CancellationToken::new();
+        parent.cancel();
+        let child = parent.child_token();
+        assert!(child.is_cancelled());
+    }
+
+    #[test]
+    fn run_until_cancelled_stops_polling_once_cancelled() {
+        crate::task::block_on(async {
+            let token = CancellationToken::new();
+            let canceller = token.clone();
+            crate::task::spawn(async move { canceller.cancel() }).await;
+
+            let result = token.run_until_cancelled(core::future::pending::<()>()).await;
+            assert!(result.is_none());
+        });
+    }
+}
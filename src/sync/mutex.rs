@@ -0,0 +1,231 @@
+//! An async mutual-exclusion lock, with mapped guards for borrowing a
+//! single field out of the protected value, and `Arc`-owned guards for
+//! holding a lock across a spawned task without fighting the borrow
+//! checker over its lifetime.
+
+use core::cell::UnsafeCell;
+use core::future::Future;
+use core::marker::PhantomData;
+use core::ops::{Deref, DerefMut};
+use core::pin::Pin;
+use core::task::{Context, Poll, Waker};
+use core::time::Duration;
+
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use std::sync::Mutex as StdMutex;
+
+struct State {
+    locked: bool,
+    wakers: Vec<Waker>,
+}
+
+/// An async mutex.
+pub struct Mutex<T: ?Sized> {
+    state: StdMutex<State>,
+    value: UnsafeCell<T>,
+}
+
+unsafe impl<T: ?Sized + Send> Send for Mutex<T> {}
+unsafe impl<T: ?Sized + Send> Sync for Mutex<T> {}
+
+impl<T> Mutex<T> {
+    pub fn new(value: T) -> Self {
+        Self {
+            state: StdMutex::new(State { locked: false, wakers: Vec::new() }),
+            value: UnsafeCell::new(value),
+        }
+    }
+
+    pub fn into_inner(self) -> T {
+        self.value.into_inner()
+    }
+}
+
+impl<T: ?Sized> Mutex<T> {
+    /// Acquires the lock, waiting if it's currently held.
+    pub fn lock(&self) -> Lock<'_, T> {
+        Lock { mutex: self }
+    }
+
+    /// Acquires the lock without waiting, failing if it's currently
+    /// held.
+    pub fn try_lock(&self) -> Option<MutexGuard<'_, T>> {
+        let mut state = self.state.lock().unwrap();
+        if state.locked {
+            None
+        } else {
+            state.locked = true;
+            Some(MutexGuard { mutex: self })
+        }
+    }
+
+    /// Acquires the lock, giving up and returning `None` if it's still
+    /// held after `duration` -- useful for deadlock detection or
+    /// graceful degradation instead of blocking a caller indefinitely.
+    pub async fn lock_timeout(&self, duration: Duration) -> Option<MutexGuard<'_, T>> {
+        crate::future::timeout(duration, self.lock()).await.ok()
+    }
+
+    pub fn get_mut(&mut self) -> &mut T {
+        self.value.get_mut()
+    }
+
+    fn unlock(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.locked = false;
+        for waker in state.wakers.drain(..) {
+            waker.wake();
+        }
+    }
+}
+
+impl<T> Mutex<T> {
+    /// Like [`lock`](Self::lock), but the returned guard owns an `Arc`
+    /// clone of the mutex instead of borrowing it, so it can be moved
+    /// into a spawned task independently of `self`'s lifetime.
+    pub fn lock_owned(self: &Arc<Self>) -> LockOwned<T> {
+        LockOwned { mutex: self.clone() }
+    }
+
+    /// Like [`try_lock`](Self::try_lock), returning an owned guard.
+    pub fn try_lock_owned(self: &Arc<Self>) -> Option<OwnedMutexGuard<T>> {
+        let mut state = self.state.lock().unwrap();
+        if state.locked {
+            None
+        } else {
+            state.locked = true;
+            Some(OwnedMutexGuard { mutex: self.clone() })
+        }
+    }
+}
+
+/// Future returned by [`Mutex::lock`].
+pub struct Lock<'a, T: ?Sized> {
+    mutex: &'a Mutex<T>,
+}
+
+impl<'a, T: ?Sized> Future for Lock<'a, T> {
+    type Output = MutexGuard<'a, T>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.mutex.state.lock().unwrap();
+        if !state.locked {
+            state.locked = true;
+            return Poll::Ready(MutexGuard { mutex: self.mutex });
+        }
+        state.wakers.push(cx.waker().clone());
+        Poll::Pending
+    }
+}
+
+/// A held lock.
+pub struct MutexGuard<'a, T: ?Sized> {
+    mutex: &'a Mutex<T>,
+}
+
+impl<T: ?Sized> Deref for MutexGuard<'_, T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        unsafe { &*self.mutex.value.get() }
+    }
+}
+
+impl<T: ?Sized> DerefMut for MutexGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        unsafe { &mut *self.mutex.value.get() }
+    }
+}
+
+impl<T: ?Sized> Drop for MutexGuard<'_, T> {
+    fn drop(&mut self) {
+        self.mutex.unlock();
+    }
+}
+
+impl<'a, T: ?Sized> MutexGuard<'a, T> {
+    /// Borrows a field (or any derived `&mut U`) out of the guarded
+    /// value, returning a guard that still holds the original lock but
+    /// derefs to `U` instead of `T` -- handing a lock guard to a field
+    /// back out of an async fn without `unsafe`.
+    pub fn map<U: ?Sized>(this: Self, f: impl FnOnce(&mut T) -> &mut U) -> MappedMutexGuard<'a, T, U> {
+        let mutex = this.mutex;
+        let value: *mut U = f(unsafe { &mut *mutex.value.get() });
+        core::mem::forget(this);
+        MappedMutexGuard { mutex, value, _marker: PhantomData }
+    }
+}
+
+/// A guard produced by [`MutexGuard::map`], derefing to `U` instead of
+/// the mutex's full value `T` while still holding the same lock.
+pub struct MappedMutexGuard<'a, T: ?Sized, U: ?Sized> {
+    mutex: &'a Mutex<T>,
+    value: *mut U,
+    _marker: PhantomData<&'a mut U>,
+}
+
+unsafe impl<T: ?Sized, U: ?Sized + Send> Send for MappedMutexGuard<'_, T, U> {}
+unsafe impl<T: ?Sized, U: ?Sized + Sync> Sync for MappedMutexGuard<'_, T, U> {}
+
+impl<T: ?Sized, U: ?Sized> Deref for MappedMutexGuard<'_, T, U> {
+    type Target = U;
+    fn deref(&self) -> &U {
+        unsafe { &*self.value }
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> DerefMut for MappedMutexGuard<'_, T, U> {
+    fn deref_mut(&mut self) -> &mut U {
+        unsafe { &mut *self.value }
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Drop for MappedMutexGuard<'_, T, U> {
+    fn drop(&mut self) {
+        self.mutex.unlock();
+    }
+}
+
+/// Future returned by [`Mutex::lock_owned`].
+pub struct LockOwned<T: ?Sized> {
+    mutex: Arc<Mutex<T>>,
+}
+
+impl<T: ?Sized> Future for LockOwned<T> {
+    type Output = OwnedMutexGuard<T>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.mutex.state.lock().unwrap();
+        if !state.locked {
+            state.locked = true;
+            return Poll::Ready(OwnedMutexGuard { mutex: self.mutex.clone() });
+        }
+        state.wakers.push(cx.waker().clone());
+        Poll::Pending
+    }
+}
+
+/// Like [`MutexGuard`], but owns an `Arc` clone of the mutex instead of
+/// borrowing it.
+pub struct OwnedMutexGuard<T: ?Sized> {
+    mutex: Arc<Mutex<T>>,
+}
+
+impl<T: ?Sized> Deref for OwnedMutexGuard<T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        unsafe { &*self.mutex.value.get() }
+    }
+}
+
+impl<T: ?Sized> DerefMut for OwnedMutexGuard<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        unsafe { &mut *self.mutex.value.get() }
+    }
+}
+
+impl<T: ?Sized> Drop for OwnedMutexGuard<T> {
+    fn drop(&mut self) {
+        self.mutex.unlock();
+    }
+}
@@ -0,0 +1,157 @@
+//! A signaling primitive for waking tasks on an edge (queue went from
+//! empty to non-empty, a flag flipped) rather than on a value, which is
+//! what `Notify` gives you over a channel: no item to allocate or
+//! drop, and a `notify_one`/`notify_all` before anyone is waiting isn't
+//! lost.
+
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll, Waker};
+
+use alloc::collections::VecDeque;
+use std::sync::Mutex;
+
+struct State {
+    /// Set by `notify_one`/`notify_all` when there are no waiters yet,
+    /// so the next call to `notified()` resolves immediately instead of
+    /// missing the wakeup.
+    permits: usize,
+    waiters: VecDeque<Waker>,
+}
+
+/// A notification primitive: tasks call [`notified`](Notify::notified)
+/// to wait for a signal, and any task can send one with
+/// [`notify_one`](Notify::notify_one) or
+/// [`notify_all`](Notify::notify_all)/[`notify_waiters`](Notify::notify_waiters).
+pub struct Notify {
+    state: Mutex<State>,
+}
+
+impl Notify {
+    pub fn new() -> Self {
+        Self {
+            state: Mutex::new(State {
+                permits: 0,
+                waiters: VecDeque::new(),
+            }),
+        }
+    }
+
+    /// Wakes one waiting task, or, if none are currently waiting,
+    /// stores a permit so the next call to [`notified`](Notify::notified)
+    /// returns immediately.
+    pub fn notify_one(&self) {
+        let mut state = self.state.lock().unwrap();
+        if let Some(waker) = state.waiters.pop_front() {
+            waker.wake();
+        } else {
+            state.permits += 1;
+        }
+    }
+
+    /// Wakes every task currently waiting. Unlike `notify_one`, this
+    /// carries no permit: a task that calls `notified()` after this
+    /// returns still has to wait for the next notification.
+    pub fn notify_waiters(&self) {
+        let mut state = self.state.lock().unwrap();
+        for waker in state.waiters.drain(..) {
+            waker.wake();
+        }
+    }
+
+    /// Alias for [`notify_waiters`](Notify::notify_waiters).
+    pub fn notify_all(&self) {
+        self.notify_waiters();
+    }
+
+    /// Waits for a notification.
+    pub fn notified(&self) -> Notified<'_> {
+        Notified { notify: self }
+    }
+}
+
+impl Default for Notify {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Future returned by [`Notify::notified`].
+pub struct Notified<'a> {
+    notify: &'a Notify,
+}
+
+impl<'a> Future for Notified<'a> {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let mut state = self.notify.state.lock().unwrap();
+        if state.permits > 0 {
+            state.permits -= 1;
+            return Poll::Ready(());
+        }
+        if !state.waiters.iter().any(|w| w.will_wake(cx.waker())) {
+            state.waiters.push_back(cx.waker().clone());
+        }
+        Poll::Pending
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn notify_one_before_anyone_waits_is_not_lost() {
+        crate::task::block_on(async {
+            let notify = Notify::new();
+            notify.notify_one();
+            notify.notified().await;
+        });
+    }
+
+    #[test]
+    fn notify_one_wakes_exactly_one_waiter_at_a_time() {
+        crate::task::block_on(async {
+            let notify = std::sync::Arc::new(Notify::new());
+            let woken = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+
+            let spawn_waiter = || {
+                let notify = notify.clone();
+                let woken = woken.clone();
+                crate::task::spawn(async move {
+                    notify.notified().await;
+                    woken.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                })
+            };
+            let a = spawn_waiter();
+            let b = spawn_waiter();
+
+            // Give both tasks a chance to register as waiters before
+            // sending a single notification.
+            crate::task::sleep(core::time::Duration::from_millis(20)).await;
+            notify.notify_one();
+            crate::task::sleep(core::time::Duration::from_millis(20)).await;
+            assert_eq!(woken.load(std::sync::atomic::Ordering::SeqCst), 1, "notify_one should wake only one of the two waiters");
+
+            notify.notify_one();
+            a.await;
+            b.await;
+            assert_eq!(woken.load(std::sync::atomic::Ordering::SeqCst), 2);
+        });
+    }
+
+    #[test]
+    fn notify_waiters_wakes_everyone_currently_waiting_but_carries_no_permit() {
+        crate::task::block_on(async {
+            let notify = Notify::new();
+
+            // No one is waiting yet, so this notification is simply
+            // dropped -- `notify_waiters` never stores a permit.
+            notify.notify_waiters();
+
+            let timed_out = crate::future::timeout(core::time::Duration::from_millis(20), notify.notified()).await;
+            assert!(timed_out.is_err(), "notify_waiters must not leave a permit for a later notified() call");
+        });
+    }
+}
@@ -0,0 +1,149 @@
+//! A cell that's initialized at most once, asynchronously -- for
+//! lazily building a value (a connection pool, a parsed config) that
+//! multiple tasks might race to create, without every racer doing the
+//! work or blocking a thread.
+
+use core::cell::UnsafeCell;
+use core::future::Future;
+use core::mem::MaybeUninit;
+use core::sync::atomic::{AtomicU8, Ordering};
+
+use crate::sync::Notify;
+
+const UNINIT: u8 = 0;
+const INITIALIZING: u8 = 1;
+const INIT: u8 = 2;
+
+/// An async-friendly cell that can be written to at most once.
+pub struct OnceCell<T> {
+    state: AtomicU8,
+    notify: Notify,
+    value: UnsafeCell<MaybeUninit<T>>,
+}
+
+// SAFETY: access to `value` is gated by `state`, which only ever
+// transitions UNINIT -> INITIALIZING -> INIT, with exactly one task
+// ever winning the UNINIT -> INITIALIZING transition and writing the
+// value before publishing INIT.
+unsafe impl<T: Send> Send for OnceCell<T> {}
+unsafe impl<T: Send + Sync> Sync for OnceCell<T> {}
+
+impl<T> OnceCell<T> {
+    pub const fn new() -> Self {
+        Self {
+            state: AtomicU8::new(UNINIT),
+            notify: Notify::new(),
+            value: UnsafeCell::new(MaybeUninit::uninit()),
+        }
+    }
+
+    /// Returns the value if it's already initialized, without blocking.
+    pub fn get(&self) -> Option<&T> {
+        if self.state.load(Ordering::Acquire) == INIT {
+            Some(unsafe { (*self.value.get()).assume_init_ref() })
+        } else {
+            None
+        }
+    }
+
+    /// Returns the existing value, or initializes it by running `init`.
+    /// If multiple tasks call this concurrently, exactly one runs
+    /// `init`; the rest wait for it to finish and then see its result.
+    pub async fn get_or_init<F, Fut>(&self, init: F) -> &T
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = T>,
+    {
+        match self
+            .get_or_try_init(|| async move { Ok::<T, core::convert::Infallible>(init().await) })
+            .await
+        {
+            Ok(value) => value,
+            Err(never) => match never {},
+        }
+    }
+
+    /// Like [`get_or_init`](Self::get_or_init), but allows `init` to
+    /// fail; on failure the cell stays uninitialized so a later caller
+    /// can retry.
+    pub async fn get_or_try_init<F, Fut, E>(&self, init: F) -> Result<&T, E>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        loop {
+            if let Some(value) = self.get() {
+                return Ok(value);
+            }
+
+            match self.state.compare_exchange(UNINIT, INITIALIZING, Ordering::AcqRel, Ordering::Acquire) {
+                Ok(_) => {
+                    match init().await {
+                        Ok(value) => {
+                            unsafe { (*self.value.get()).write(value) };
+                            self.state.store(INIT, Ordering::Release);
+                            self.notify.notify_waiters();
+                            return Ok(self.get().expect("just initialized"));
+                        }
+                        Err(e) => {
+                            self.state.store(UNINIT, Ordering::Release);
+                            self.notify.notify_waiters();
+                            return Err(e);
+                        }
+                    }
+                }
+                Err(INITIALIZING) => {
+                    self.notify.notified().await;
+                }
+                Err(_) => continue,
+            }
+        }
+    }
+}
+
+impl<T> Default for OnceCell<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Drop for OnceCell<T> {
+    fn drop(&mut self) {
+        if self.state.load(Ordering::Acquire) == INIT {
+            unsafe { (*self.value.get()).assume_init_drop() };
+        }
+    }
+}
+
+/// A value that's computed on first access and cached for every access
+/// after that, similar to `OnceCell` but owning its initializer instead
+/// of taking one per call.
+pub struct Lazy<T, F = fn() -> T> {
+    cell: OnceCell<T>,
+    init: crate::sync::Mutex<Option<F>>,
+}
+
+impl<T, F> Lazy<T, F> {
+    pub const fn new(init: F) -> Self {
+        Self {
+            cell: OnceCell::new(),
+            init: crate::sync::Mutex::new(Some(init)),
+        }
+    }
+}
+
+impl<T, F, Fut> Lazy<T, F>
+where
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = T>,
+{
+    /// Returns the value, computing it on the first call.
+    pub async fn get(&self) -> &T {
+        self.cell
+            .get_or_init(|| async {
+                let init = self.init.lock().await.take().expect("Lazy initializer polled after completion");
+                init().await
+            })
+            .await
+    }
+}
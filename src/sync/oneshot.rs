@@ -0,0 +1,141 @@
+//! A channel for sending exactly one value between two tasks -- the
+//! common case of "spawn a task, get its result back" without the
+//! overhead of a full MPMC channel for a single item.
+
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll, Waker};
+
+use alloc::sync::Arc;
+use std::sync::Mutex;
+
+struct Shared<T> {
+    value: Option<T>,
+    sender_dropped: bool,
+    receiver_dropped: bool,
+    waker: Option<Waker>,
+}
+
+/// Creates a oneshot channel, returning the sending and receiving
+/// halves.
+pub fn channel<T>() -> (Sender<T>, Receiver<T>) {
+    let shared = Arc::new(Mutex::new(Shared {
+        value: None,
+        sender_dropped: false,
+        receiver_dropped: false,
+        waker: None,
+    }));
+    (Sender { shared: shared.clone() }, Receiver { shared })
+}
+
+/// The sending half of a oneshot channel.
+pub struct Sender<T> {
+    shared: Arc<Mutex<Shared<T>>>,
+}
+
+/// The error returned by [`Sender::send`] when the [`Receiver`] has
+/// already been dropped; it hands the value back.
+#[derive(Debug)]
+pub struct SendError<T>(pub T);
+
+impl<T> Sender<T> {
+    /// Sends `value` to the receiver. Fails, returning `value`, if the
+    /// receiver was already dropped.
+    pub fn send(self, value: T) -> Result<(), SendError<T>> {
+        let mut shared = self.shared.lock().unwrap();
+        if shared.receiver_dropped {
+            return Err(SendError(value));
+        }
+        shared.value = Some(value);
+        if let Some(waker) = shared.waker.take() {
+            waker.wake();
+        }
+        Ok(())
+    }
+
+    /// Reports whether the receiver has already been dropped, without
+    /// waiting.
+    pub fn is_closed(&self) -> bool {
+        self.shared.lock().unwrap().receiver_dropped
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        let mut shared = self.shared.lock().unwrap();
+        shared.sender_dropped = true;
+        if let Some(waker) = shared.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+/// The receiving half of a oneshot channel.
+pub struct Receiver<T> {
+    shared: Arc<Mutex<Shared<T>>>,
+}
+
+/// The error returned when the [`Sender`] was dropped without sending
+/// a value.
+#[derive(Debug, PartialEq, Eq)]
+pub struct RecvError;
+
+impl<T> Receiver<T> {
+    /// Waits for the value, or for the sender to be dropped.
+    pub fn recv(&mut self) -> Recv<'_, T> {
+        Recv { shared: &self.shared }
+    }
+
+    /// Takes the value if the sender has already sent one, without
+    /// waiting.
+    pub fn try_recv(&mut self) -> Result<T, TryRecvError> {
+        let mut shared = self.shared.lock().unwrap();
+        if let Some(value) = shared.value.take() {
+            Ok(value)
+        } else if shared.sender_dropped {
+            Err(TryRecvError::Closed)
+        } else {
+            Err(TryRecvError::Empty)
+        }
+    }
+}
+
+/// The error returned by [`Receiver::try_recv`].
+#[derive(Debug, PartialEq, Eq)]
+pub enum TryRecvError {
+    /// No value has been sent yet.
+    Empty,
+    /// The sender was dropped without sending a value.
+    Closed,
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        let mut shared = self.shared.lock().unwrap();
+        shared.receiver_dropped = true;
+        if let Some(waker) = shared.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+/// Future returned by [`Receiver::recv`].
+pub struct Recv<'a, T> {
+    shared: &'a Arc<Mutex<Shared<T>>>,
+}
+
+impl<'a, T> Future for Recv<'a, T> {
+    type Output = Result<T, RecvError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut shared = self.shared.lock().unwrap();
+        if let Some(value) = shared.value.take() {
+            Poll::Ready(Ok(value))
+        } else if shared.sender_dropped {
+            Poll::Ready(Err(RecvError))
+        } else {
+            shared.waker = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+}
@@ -0,0 +1,133 @@
+//! A token-bucket rate limiter, the thing every project re-implements
+//! on top of a `Mutex` and an `Instant` the moment it needs to throttle
+//! outgoing requests.
+
+use core::time::Duration;
+use std::sync::Mutex;
+use std::time::Instant;
+
+struct State {
+    /// Tokens available right now, as of `last_refill`.
+    tokens: f64,
+    last_refill: Instant,
+}
+
+/// A token-bucket rate limiter: up to `burst` calls can go through
+/// immediately, and afterward it admits calls at a steady rate of one
+/// token every `per(duration) / rate` -- configured via
+/// [`RateLimiter::new`]'s `rate` and `per`.
+pub struct RateLimiter {
+    burst: f64,
+    tokens_per_sec: f64,
+    state: Mutex<State>,
+}
+
+impl RateLimiter {
+    /// Allows `rate` calls per `per`, with a burst capacity equal to
+    /// `rate` (i.e. up to `rate` calls can go through back-to-back
+    /// before the steady rate kicks in).
+    pub fn new(rate: u32, per: Duration) -> Self {
+        Self::with_burst(rate, per, rate)
+    }
+
+    /// Like [`new`](Self::new), with an explicit burst capacity
+    /// instead of one equal to `rate`.
+    pub fn with_burst(rate: u32, per: Duration, burst: u32) -> Self {
+        assert!(rate > 0, "rate must be greater than zero");
+        assert!(!per.is_zero(), "per must be greater than zero");
+        let tokens_per_sec = rate as f64 / per.as_secs_f64();
+        Self {
+            burst: burst as f64,
+            tokens_per_sec,
+            state: Mutex::new(State {
+                tokens: burst as f64,
+                last_refill: Instant::now(),
+            }),
+        }
+    }
+
+    fn refill(&self, state: &mut State) {
+        let now = Instant::now();
+        let elapsed = now.saturating_duration_since(state.last_refill).as_secs_f64();
+        state.tokens = (state.tokens + elapsed * self.tokens_per_sec).min(self.burst);
+        state.last_refill = now;
+    }
+
+    /// Consumes a token if one's available, without waiting.
+    pub fn check(&self) -> bool {
+        self.check_n(1)
+    }
+
+    /// Consumes `n` tokens if that many are available, without
+    /// waiting.
+    pub fn check_n(&self, n: u32) -> bool {
+        let mut state = self.state.lock().unwrap();
+        self.refill(&mut state);
+        if state.tokens >= n as f64 {
+            state.tokens -= n as f64;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Waits until a token is available, then consumes it.
+    pub async fn until_ready(&self) {
+        self.until_ready_n(1).await
+    }
+
+    /// Waits until `n` tokens are available, then consumes them.
+    pub async fn until_ready_n(&self, n: u32) {
+        loop {
+            let wait = {
+                let mut state = self.state.lock().unwrap();
+                self.refill(&mut state);
+                if state.tokens >= n as f64 {
+                    state.tokens -= n as f64;
+                    return;
+                }
+                let deficit = n as f64 - state.tokens;
+                Duration::from_secs_f64(deficit / self.tokens_per_sec)
+            };
+            crate::task::sleep(wait).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_admits_up_to_the_burst_then_refuses() {
+        let limiter = RateLimiter::with_burst(10, Duration::from_secs(1), 3);
+        assert!(limiter.check());
+        assert!(limiter.check());
+        assert!(limiter.check());
+        assert!(!limiter.check(), "the fourth call within the same instant should exceed burst capacity");
+    }
+
+    #[test]
+    fn check_n_consumes_multiple_tokens_atomically() {
+        let limiter = RateLimiter::with_burst(10, Duration::from_secs(1), 5);
+        assert!(!limiter.check_n(6), "must not partially consume tokens it doesn't have enough of");
+        assert!(limiter.check_n(5));
+        assert!(!limiter.check());
+    }
+
+    #[test]
+    fn until_ready_waits_for_the_bucket_to_refill() {
+        crate::task::block_on(async {
+            // One token every 20ms, no burst beyond the first call.
+            let limiter = RateLimiter::with_burst(50, Duration::from_secs(1), 1);
+            assert!(limiter.check(), "the single burst token should be available immediately");
+
+            let start = Instant::now();
+            limiter.until_ready().await;
+            assert!(
+                start.elapsed() >= Duration::from_millis(10),
+                "until_ready should have waited for a refill instead of returning immediately"
+            );
+        });
+    }
+}
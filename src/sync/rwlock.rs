@@ -0,0 +1,562 @@
+//! An async reader-writer lock, with an upgradable read guard for the
+//! common check-then-insert pattern: hold a read lock to check whether
+//! work is needed, then atomically upgrade to a write lock to do it,
+//! without a gap where another writer could slip in.
+
+use core::cell::UnsafeCell;
+use core::future::Future;
+use core::ops::{Deref, DerefMut};
+use core::pin::Pin;
+use core::task::{Context, Poll, Waker};
+use core::time::Duration;
+
+use alloc::collections::VecDeque;
+use std::sync::Mutex;
+
+struct State {
+    readers: usize,
+    writer: bool,
+    /// At most one upgradable read guard can be outstanding at a time,
+    /// same as a plain write lock with respect to other upgradable
+    /// readers (but it still allows ordinary readers in alongside it).
+    upgradable: bool,
+    /// Writers currently waiting for the lock. Under
+    /// [`RwLockPolicy::WriterPreferring`] a new reader checks this
+    /// before joining, so a steady stream of readers can't starve a
+    /// writer that's already in line.
+    pending_writers: usize,
+    read_wakers: VecDeque<Waker>,
+    write_wakers: VecDeque<Waker>,
+    upgrade_wakers: VecDeque<Waker>,
+}
+
+/// Governs whether a waiting writer can be starved by a continuous
+/// stream of readers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RwLockPolicy {
+    /// New readers are always let in immediately, even if a writer is
+    /// waiting. Maximizes read throughput, at the cost of writers
+    /// potentially waiting indefinitely under sustained read load.
+    ReaderPreferring,
+    /// A new reader waits if a writer is already queued, so a writer
+    /// is guaranteed to run within one "generation" of the readers
+    /// active when it arrived, rather than being starved.
+    WriterPreferring,
+}
+
+/// An async reader-writer lock.
+pub struct RwLock<T: ?Sized> {
+    policy: RwLockPolicy,
+    state: Mutex<State>,
+    value: UnsafeCell<T>,
+}
+
+unsafe impl<T: ?Sized + Send> Send for RwLock<T> {}
+unsafe impl<T: ?Sized + Send + Sync> Sync for RwLock<T> {}
+
+impl<T> RwLock<T> {
+    /// Creates a lock with the default [`RwLockPolicy::WriterPreferring`]
+    /// policy.
+    pub fn new(value: T) -> Self {
+        Self::with_policy(value, RwLockPolicy::WriterPreferring)
+    }
+
+    /// Creates a lock with an explicit fairness policy.
+    pub fn with_policy(value: T, policy: RwLockPolicy) -> Self {
+        Self {
+            policy,
+            state: Mutex::new(State {
+                readers: 0,
+                writer: false,
+                upgradable: false,
+                pending_writers: 0,
+                read_wakers: VecDeque::new(),
+                write_wakers: VecDeque::new(),
+                upgrade_wakers: VecDeque::new(),
+            }),
+            value: UnsafeCell::new(value),
+        }
+    }
+
+    pub fn into_inner(self) -> T {
+        self.value.into_inner()
+    }
+}
+
+impl<T: ?Sized> RwLock<T> {
+    /// Acquires a shared read lock, waiting if a writer currently holds
+    /// it (or, under [`RwLockPolicy::WriterPreferring`], if one is
+    /// already waiting for it).
+    pub fn read(&self) -> Read<'_, T> {
+        Read { lock: self }
+    }
+
+    /// Acquires the exclusive write lock, waiting for every reader
+    /// (including an upgradable one) to release first.
+    pub fn write(&self) -> Write<'_, T> {
+        Write { lock: self, counted: false }
+    }
+
+    /// Acquires an upgradable read lock: it behaves like an ordinary
+    /// read lock (other readers may still acquire theirs) except that
+    /// at most one upgradable read lock can be outstanding, and it can
+    /// later be turned into a write lock with
+    /// [`UpgradableReadGuard::upgrade`] without ever releasing the
+    /// lock in between.
+    pub fn upgradable_read(&self) -> UpgradableRead<'_, T> {
+        UpgradableRead { lock: self }
+    }
+
+    /// Acquires a read lock, giving up and returning `None` if it's
+    /// still unavailable after `duration`.
+    pub async fn read_timeout(&self, duration: Duration) -> Option<RwLockReadGuard<'_, T>> {
+        crate::future::timeout(duration, self.read()).await.ok()
+    }
+
+    /// Acquires the write lock, giving up and returning `None` if it's
+    /// still unavailable after `duration`.
+    pub async fn write_timeout(&self, duration: Duration) -> Option<RwLockWriteGuard<'_, T>> {
+        crate::future::timeout(duration, self.write()).await.ok()
+    }
+
+    pub fn try_read(&self) -> Option<RwLockReadGuard<'_, T>> {
+        let mut state = self.state.lock().unwrap();
+        if state.writer {
+            return None;
+        }
+        state.readers += 1;
+        Some(RwLockReadGuard { lock: self })
+    }
+
+    pub fn try_write(&self) -> Option<RwLockWriteGuard<'_, T>> {
+        let mut state = self.state.lock().unwrap();
+        if state.writer || state.readers > 0 || state.upgradable {
+            return None;
+        }
+        state.writer = true;
+        Some(RwLockWriteGuard { lock: self })
+    }
+
+    pub fn get_mut(&mut self) -> &mut T {
+        self.value.get_mut()
+    }
+
+    fn release_read(lock: &RwLock<T>) {
+        let mut state = lock.state.lock().unwrap();
+        state.readers -= 1;
+        if state.readers == 0 {
+            if let Some(waker) = state.write_wakers.pop_front() {
+                waker.wake();
+            } else if let Some(waker) = state.upgrade_wakers.pop_front() {
+                waker.wake();
+            }
+        }
+    }
+
+    fn wake_after_write_release(state: &mut State) {
+        // A writer releasing can let either one future writer in, or
+        // every waiting reader (plus at most one upgrader) in -- never
+        // both, so prefer writers to avoid starving them.
+        if let Some(waker) = state.write_wakers.pop_front() {
+            waker.wake();
+            return;
+        }
+        if let Some(waker) = state.upgrade_wakers.pop_front() {
+            waker.wake();
+        }
+        for waker in state.read_wakers.drain(..) {
+            waker.wake();
+        }
+    }
+}
+
+/// Future returned by [`RwLock::read`].
+pub struct Read<'a, T: ?Sized> {
+    lock: &'a RwLock<T>,
+}
+
+impl<'a, T: ?Sized> Future for Read<'a, T> {
+    type Output = RwLockReadGuard<'a, T>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.lock.state.lock().unwrap();
+        let blocked_by_pending_writer = self.lock.policy == RwLockPolicy::WriterPreferring && state.pending_writers > 0;
+        if !state.writer && !blocked_by_pending_writer {
+            state.readers += 1;
+            return Poll::Ready(RwLockReadGuard { lock: self.lock });
+        }
+        state.read_wakers.push_back(cx.waker().clone());
+        Poll::Pending
+    }
+}
+
+/// Future returned by [`RwLock::write`].
+pub struct Write<'a, T: ?Sized> {
+    lock: &'a RwLock<T>,
+    counted: bool,
+}
+
+impl<'a, T: ?Sized> Future for Write<'a, T> {
+    type Output = RwLockWriteGuard<'a, T>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.lock.state.lock().unwrap();
+        if !state.writer && state.readers == 0 && !state.upgradable {
+            if self.counted {
+                state.pending_writers -= 1;
+                self.counted = false;
+                if state.pending_writers == 0 {
+                    for waker in state.read_wakers.drain(..) {
+                        waker.wake();
+                    }
+                }
+            }
+            state.writer = true;
+            return Poll::Ready(RwLockWriteGuard { lock: self.lock });
+        }
+        if !self.counted {
+            state.pending_writers += 1;
+            self.counted = true;
+        }
+        state.write_wakers.push_back(cx.waker().clone());
+        Poll::Pending
+    }
+}
+
+impl<T: ?Sized> Drop for Write<'_, T> {
+    fn drop(&mut self) {
+        if self.counted {
+            let mut state = self.lock.state.lock().unwrap();
+            state.pending_writers -= 1;
+            if state.pending_writers == 0 {
+                for waker in state.read_wakers.drain(..) {
+                    waker.wake();
+                }
+            }
+        }
+    }
+}
+
+/// Future returned by [`RwLock::upgradable_read`].
+pub struct UpgradableRead<'a, T: ?Sized> {
+    lock: &'a RwLock<T>,
+}
+
+impl<'a, T: ?Sized> Future for UpgradableRead<'a, T> {
+    type Output = UpgradableReadGuard<'a, T>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.lock.state.lock().unwrap();
+        if !state.writer && !state.upgradable {
+            state.upgradable = true;
+            return Poll::Ready(UpgradableReadGuard { lock: self.lock });
+        }
+        state.read_wakers.push_back(cx.waker().clone());
+        Poll::Pending
+    }
+}
+
+/// A held shared read lock.
+pub struct RwLockReadGuard<'a, T: ?Sized> {
+    lock: &'a RwLock<T>,
+}
+
+impl<T: ?Sized> Deref for RwLockReadGuard<'_, T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        unsafe { &*self.lock.value.get() }
+    }
+}
+
+impl<T: ?Sized> Drop for RwLockReadGuard<'_, T> {
+    fn drop(&mut self) {
+        RwLock::release_read(self.lock);
+    }
+}
+
+impl<'a, T: ?Sized> RwLockReadGuard<'a, T> {
+    /// Borrows a field (or any derived `&U`) out of the guarded value,
+    /// returning a guard that still holds the original read lock but
+    /// derefs to `U` instead of `T`.
+    pub fn map<U: ?Sized>(this: Self, f: impl FnOnce(&T) -> &U) -> MappedRwLockReadGuard<'a, T, U> {
+        let lock = this.lock;
+        let value: *const U = f(unsafe { &*lock.value.get() });
+        core::mem::forget(this);
+        MappedRwLockReadGuard { lock, value, _marker: core::marker::PhantomData }
+    }
+}
+
+/// A guard produced by [`RwLockReadGuard::map`], derefing to `U`
+/// instead of the lock's full value `T` while still holding the same
+/// read lock.
+pub struct MappedRwLockReadGuard<'a, T: ?Sized, U: ?Sized> {
+    lock: &'a RwLock<T>,
+    value: *const U,
+    _marker: core::marker::PhantomData<&'a U>,
+}
+
+unsafe impl<T: ?Sized, U: ?Sized + Sync> Sync for MappedRwLockReadGuard<'_, T, U> {}
+
+impl<T: ?Sized, U: ?Sized> Deref for MappedRwLockReadGuard<'_, T, U> {
+    type Target = U;
+    fn deref(&self) -> &U {
+        unsafe { &*self.value }
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> Drop for MappedRwLockReadGuard<'_, T, U> {
+    fn drop(&mut self) {
+        RwLock::release_read(self.lock);
+    }
+}
+
+/// A held exclusive write lock.
+pub struct RwLockWriteGuard<'a, T: ?Sized> {
+    lock: &'a RwLock<T>,
+}
+
+impl<T: ?Sized> Deref for RwLockWriteGuard<'_, T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        unsafe { &*self.lock.value.get() }
+    }
+}
+
+impl<T: ?Sized> DerefMut for RwLockWriteGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        unsafe { &mut *self.lock.value.get() }
+    }
+}
+
+impl<'a, T: ?Sized> RwLockWriteGuard<'a, T> {
+    /// Atomically converts the write lock into a read lock, letting
+    /// other readers in without ever leaving a gap where the lock was
+    /// held by nobody.
+    pub fn downgrade(self) -> RwLockReadGuard<'a, T> {
+        let lock = self.lock;
+        core::mem::forget(self);
+        let mut state = lock.state.lock().unwrap();
+        state.writer = false;
+        state.readers += 1;
+        for waker in state.read_wakers.drain(..) {
+            waker.wake();
+        }
+        RwLockReadGuard { lock }
+    }
+}
+
+impl<T: ?Sized> Drop for RwLockWriteGuard<'_, T> {
+    fn drop(&mut self) {
+        let mut state = self.lock.state.lock().unwrap();
+        state.writer = false;
+        RwLock::wake_after_write_release(&mut state);
+    }
+}
+
+/// A held upgradable read lock: behaves like [`RwLockReadGuard`], but
+/// can be turned into a [`RwLockWriteGuard`] with
+/// [`upgrade`](Self::upgrade) without re-acquiring from scratch.
+pub struct UpgradableReadGuard<'a, T: ?Sized> {
+    lock: &'a RwLock<T>,
+}
+
+impl<T: ?Sized> Deref for UpgradableReadGuard<'_, T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        unsafe { &*self.lock.value.get() }
+    }
+}
+
+impl<'a, T: ?Sized> UpgradableReadGuard<'a, T> {
+    /// Waits for every ordinary reader to release, then converts this
+    /// guard into a write lock.
+    pub fn upgrade(self) -> Upgrade<'a, T> {
+        let lock = self.lock;
+        core::mem::forget(self);
+        Upgrade { lock, done: false }
+    }
+
+    /// Releases the upgradable lock back to a plain read lock.
+    pub fn downgrade(self) -> RwLockReadGuard<'a, T> {
+        let lock = self.lock;
+        core::mem::forget(self);
+        let mut state = lock.state.lock().unwrap();
+        state.upgradable = false;
+        state.readers += 1;
+        for waker in state.read_wakers.drain(..) {
+            waker.wake();
+        }
+        RwLockReadGuard { lock }
+    }
+}
+
+impl<T: ?Sized> Drop for UpgradableReadGuard<'_, T> {
+    fn drop(&mut self) {
+        let mut state = self.lock.state.lock().unwrap();
+        state.upgradable = false;
+        if let Some(waker) = state.write_wakers.pop_front() {
+            waker.wake();
+        }
+        for waker in state.read_wakers.drain(..) {
+            waker.wake();
+        }
+    }
+}
+
+/// Future returned by [`UpgradableReadGuard::upgrade`].
+pub struct Upgrade<'a, T: ?Sized> {
+    lock: &'a RwLock<T>,
+    done: bool,
+}
+
+impl<'a, T: ?Sized> Future for Upgrade<'a, T> {
+    type Output = RwLockWriteGuard<'a, T>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.lock.state.lock().unwrap();
+        if state.readers == 0 {
+            state.upgradable = false;
+            state.writer = true;
+            self.done = true;
+            return Poll::Ready(RwLockWriteGuard { lock: self.lock });
+        }
+        state.upgrade_wakers.push_back(cx.waker().clone());
+        Poll::Pending
+    }
+}
+
+impl<T: ?Sized> Drop for Upgrade<'_, T> {
+    fn drop(&mut self) {
+        // Dropping an in-flight upgrade before it completes has
+        // nowhere to hand the upgradable guard back to, so it releases
+        // the lock entirely rather than leaving `upgradable` stuck set
+        // forever.
+        if !self.done {
+            let mut state = self.lock.state.lock().unwrap();
+            state.upgradable = false;
+            if let Some(waker) = state.write_wakers.pop_front() {
+                waker.wake();
+            }
+            for waker in state.read_wakers.drain(..) {
+                waker.wake();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+
+    /// Under [`RwLockPolicy::WriterPreferring`] (the default), a writer
+    /// that's already queued must be let in within a bounded number of
+    /// read acquisitions, not starved indefinitely by a continuous
+    /// stream of new readers -- the exact scenario this policy exists
+    /// to rule out.
+    #[test]
+    fn writer_bounded_under_continuous_read_pressure() {
+        let lock = Arc::new(RwLock::new(0));
+        crate::task::block_on(async {
+            let stop = Arc::new(AtomicBool::new(false));
+
+            let reader_lock = lock.clone();
+            let reader_stop = stop.clone();
+            let readers = crate::task::spawn(async move {
+                while !reader_stop.load(Ordering::Relaxed) {
+                    let _guard = reader_lock.read().await;
+                    crate::task::consume_budget().await;
+                }
+            });
+
+            let wrote = crate::future::timeout(Duration::from_secs(5), async {
+                let mut guard = lock.write().await;
+                *guard += 1;
+            })
+            .await;
+
+            stop.store(true, Ordering::Relaxed);
+            readers.await;
+
+            assert!(wrote.is_ok(), "writer starved under continuous read pressure");
+            assert_eq!(*lock.read().await, 1);
+        });
+    }
+
+    #[test]
+    fn try_read_and_try_write_reflect_lock_state() {
+        let lock = RwLock::new(1);
+        let read_guard = lock.try_read().unwrap();
+        assert!(lock.try_write().is_none());
+        drop(read_guard);
+        let write_guard = lock.try_write().unwrap();
+        assert!(lock.try_read().is_none());
+        drop(write_guard);
+        assert!(lock.try_read().is_some());
+    }
+
+    #[test]
+    fn upgradable_read_coexists_with_ordinary_readers_but_blocks_a_writer() {
+        crate::task::block_on(async {
+            let lock = RwLock::new(1);
+            let upgradable = lock.upgradable_read().await;
+            assert_eq!(*upgradable, 1);
+
+            // An upgradable reader is still just a reader to everyone else.
+            let read_guard = lock.read().await;
+            assert_eq!(*read_guard, 1);
+            drop(read_guard);
+
+            assert!(lock.try_write().is_none(), "a write must not be admitted while an upgradable read is held");
+        });
+    }
+
+    #[test]
+    fn only_one_upgradable_read_is_admitted_at_a_time() {
+        crate::task::block_on(async {
+            let lock = RwLock::new(1);
+            let _upgradable = lock.upgradable_read().await;
+
+            let second = crate::future::timeout(Duration::from_millis(50), lock.upgradable_read()).await;
+            assert!(second.is_err(), "a second upgradable read must wait behind the first");
+        });
+    }
+
+    #[test]
+    fn upgrade_waits_for_existing_readers_then_grants_exclusive_access() {
+        let lock = Arc::new(RwLock::new(0));
+        crate::task::block_on(async {
+            let upgradable = lock.upgradable_read().await;
+            let read_guard = lock.read().await;
+
+            let upgrade_lock = lock.clone();
+            let upgrader = crate::task::spawn(async move {
+                let mut guard = upgradable.upgrade().await;
+                *guard += 1;
+                let _ = upgrade_lock;
+            });
+
+            // The upgrade can't complete while `read_guard` is still held.
+            crate::task::sleep(Duration::from_millis(20)).await;
+            assert_eq!(*lock.read().await, 0);
+
+            drop(read_guard);
+            upgrader.await;
+            assert_eq!(*lock.read().await, 1);
+        });
+    }
+
+    #[test]
+    fn downgrading_an_upgradable_read_admits_other_readers_again() {
+        crate::task::block_on(async {
+            let lock = RwLock::new(1);
+            let upgradable = lock.upgradable_read().await;
+            let read_guard = upgradable.downgrade();
+            assert_eq!(*read_guard, 1);
+
+            // With no upgradable reader left, another one is admitted.
+            assert!(crate::future::poll_immediate(lock.upgradable_read()).await.is_ready());
+        });
+    }
+}
@@ -0,0 +1,241 @@
+//! A counting semaphore for bounding concurrency -- connection limits,
+//! parallel file handles, anything where a bounded channel would be
+//! used only for its capacity and never for the values sent through
+//! it.
+
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll, Waker};
+
+use alloc::collections::VecDeque;
+use alloc::sync::Arc;
+use std::sync::Mutex;
+
+struct State {
+    permits: usize,
+    waiters: VecDeque<Waker>,
+}
+
+/// An async counting semaphore.
+///
+/// Permits are acquired with [`acquire`](Semaphore::acquire) (or one of
+/// its variants) and released automatically when the returned guard is
+/// dropped.
+pub struct Semaphore {
+    state: Mutex<State>,
+}
+
+impl Semaphore {
+    /// Creates a semaphore with `permits` available permits.
+    pub fn new(permits: usize) -> Self {
+        Self {
+            state: Mutex::new(State {
+                permits,
+                waiters: VecDeque::new(),
+            }),
+        }
+    }
+
+    /// The number of permits currently available.
+    pub fn available_permits(&self) -> usize {
+        self.state.lock().unwrap().permits
+    }
+
+    /// Adds `n` permits to the semaphore, waking waiters as capacity
+    /// allows.
+    pub fn add_permits(&self, n: usize) {
+        let mut state = self.state.lock().unwrap();
+        state.permits += n;
+        for _ in 0..n {
+            if let Some(waker) = state.waiters.pop_front() {
+                waker.wake();
+            } else {
+                break;
+            }
+        }
+    }
+
+    /// Waits for a single permit to become available.
+    pub fn acquire(&self) -> Acquire<'_> {
+        Acquire { sem: self, needed: 1 }
+    }
+
+    /// Waits for `n` permits to become available, returning a single
+    /// guard that releases all of them together.
+    pub fn acquire_many(&self, n: usize) -> Acquire<'_> {
+        Acquire { sem: self, needed: n }
+    }
+
+    /// Attempts to acquire a single permit without waiting.
+    pub fn try_acquire(&self) -> Option<SemaphorePermit<'_>> {
+        let mut state = self.state.lock().unwrap();
+        if state.permits >= 1 {
+            state.permits -= 1;
+            Some(SemaphorePermit { sem: self, count: 1 })
+        } else {
+            None
+        }
+    }
+
+    /// Waits for a single permit, returning a guard that keeps `self`
+    /// alive via `Arc` so it can outlive the borrow that produced it --
+    /// useful for passing a permit into a spawned task.
+    pub fn acquire_owned(self: &Arc<Self>) -> AcquireOwned {
+        AcquireOwned {
+            sem: self.clone(),
+            needed: 1,
+        }
+    }
+
+    fn try_take(&self, needed: usize) -> bool {
+        let mut state = self.state.lock().unwrap();
+        if state.permits >= needed {
+            state.permits -= needed;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn register(&self, waker: &Waker) {
+        let mut state = self.state.lock().unwrap();
+        if !state.waiters.iter().any(|w| w.will_wake(waker)) {
+            state.waiters.push_back(waker.clone());
+        }
+    }
+
+    fn release(&self, n: usize) {
+        self.add_permits(n);
+    }
+}
+
+/// A held permit (or group of permits) from a [`Semaphore`]; releases
+/// them when dropped.
+pub struct SemaphorePermit<'a> {
+    sem: &'a Semaphore,
+    count: usize,
+}
+
+impl Drop for SemaphorePermit<'_> {
+    fn drop(&mut self) {
+        self.sem.release(self.count);
+    }
+}
+
+/// Like [`SemaphorePermit`], but owns a reference to the semaphore via
+/// `Arc` instead of borrowing it, so it can move across task
+/// boundaries independently of the `Semaphore` itself.
+pub struct OwnedSemaphorePermit {
+    sem: Arc<Semaphore>,
+    count: usize,
+}
+
+impl Drop for OwnedSemaphorePermit {
+    fn drop(&mut self) {
+        self.sem.release(self.count);
+    }
+}
+
+/// Future returned by [`Semaphore::acquire`] and
+/// [`Semaphore::acquire_many`].
+pub struct Acquire<'a> {
+    sem: &'a Semaphore,
+    needed: usize,
+}
+
+impl<'a> Future for Acquire<'a> {
+    type Output = SemaphorePermit<'a>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if self.sem.try_take(self.needed) {
+            return Poll::Ready(SemaphorePermit {
+                sem: self.sem,
+                count: self.needed,
+            });
+        }
+        self.sem.register(cx.waker());
+        // Re-check after registering to close the race where permits
+        // were released between the failed `try_take` above and the
+        // waker being stored.
+        if self.sem.try_take(self.needed) {
+            return Poll::Ready(SemaphorePermit {
+                sem: self.sem,
+                count: self.needed,
+            });
+        }
+        Poll::Pending
+    }
+}
+
+/// Future returned by [`Semaphore::acquire_owned`].
+pub struct AcquireOwned {
+    sem: Arc<Semaphore>,
+    needed: usize,
+}
+
+impl Future for AcquireOwned {
+    type Output = OwnedSemaphorePermit;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if self.sem.try_take(self.needed) {
+            return Poll::Ready(OwnedSemaphorePermit {
+                sem: self.sem.clone(),
+                count: self.needed,
+            });
+        }
+        self.sem.register(cx.waker());
+        if self.sem.try_take(self.needed) {
+            return Poll::Ready(OwnedSemaphorePermit {
+                sem: self.sem.clone(),
+                count: self.needed,
+            });
+        }
+        Poll::Pending
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::time::Duration;
+
+    #[test]
+    fn try_acquire_respects_available_permits() {
+        let sem = Semaphore::new(1);
+        let permit = sem.try_acquire().unwrap();
+        assert_eq!(sem.available_permits(), 0);
+        assert!(sem.try_acquire().is_none());
+        drop(permit);
+        assert_eq!(sem.available_permits(), 1);
+    }
+
+    #[test]
+    fn acquire_many_waits_for_enough_permits_to_accumulate() {
+        crate::task::block_on(async {
+            let sem = Arc::new(Semaphore::new(1));
+
+            let waiter_sem = sem.clone();
+            let waiter = crate::task::spawn(async move {
+                let _permit = waiter_sem.acquire_many(3).await;
+            });
+
+            crate::task::sleep(Duration::from_millis(20)).await;
+            assert!(sem.try_acquire().is_none(), "acquire_many should already hold the one available permit while it waits for more");
+
+            sem.add_permits(2);
+            waiter.await;
+            assert_eq!(sem.available_permits(), 0);
+        });
+    }
+
+    #[test]
+    fn acquire_owned_outlives_the_semaphore_borrow() {
+        crate::task::block_on(async {
+            let sem = Arc::new(Semaphore::new(1));
+            let permit = sem.acquire_owned().await;
+            assert_eq!(sem.available_permits(), 0);
+            drop(permit);
+            assert_eq!(sem.available_permits(), 1);
+        });
+    }
+}
@@ -0,0 +1,319 @@
+//! A reader-writer lock that spreads readers across independent shards,
+//! so concurrent reads on different cores don't all fight over the same
+//! cache line the way a single reader count does under [`RwLock`].
+//!
+//! [`RwLock`]: crate::sync::RwLock
+
+use core::cell::UnsafeCell;
+use core::future::Future;
+use core::hash::{Hash, Hasher};
+use core::ops::{Deref, DerefMut};
+use core::pin::Pin;
+use core::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use core::task::{Context, Poll, Waker};
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use std::collections::hash_map::DefaultHasher;
+use std::sync::Mutex;
+use std::thread;
+
+struct Shard {
+    readers: AtomicUsize,
+}
+
+struct State {
+    /// At most one writer admitted (waiting or holding) at a time; later
+    /// writers queue behind it.
+    writer_active: bool,
+    write_wakers: Vec<Waker>,
+    /// Readers that arrived while a writer was active or queued.
+    read_wakers: Vec<Waker>,
+}
+
+/// A reader-writer lock whose readers are spread across a fixed number
+/// of shards instead of sharing one counter.
+///
+/// Each reader picks a shard by hashing its thread's [`ThreadId`], so
+/// reads from different threads usually land on different shards and
+/// never touch each other's cache line. A writer still needs exclusive
+/// access to the whole value, so it sets a flag that turns away new
+/// readers and then waits for every shard's count to drain to zero --
+/// the cost of a write is paying for the read-side scalability, same
+/// trade-off as `std`'s `ShardedLock` (removed before 1.0, but the same
+/// idea is still in the `sharded-slab`/`left-right` family of crates).
+///
+/// [`ThreadId`]: std::thread::ThreadId
+pub struct ShardedLock<T: ?Sized> {
+    shards: Box<[Shard]>,
+    writer: AtomicBool,
+    state: Mutex<State>,
+    value: UnsafeCell<T>,
+}
+
+unsafe impl<T: ?Sized + Send> Send for ShardedLock<T> {}
+unsafe impl<T: ?Sized + Send + Sync> Sync for ShardedLock<T> {}
+
+impl<T> ShardedLock<T> {
+    /// Creates a lock sharded across one shard per available core (via
+    /// [`std::thread::available_parallelism`], falling back to a single
+    /// shard if that can't be determined).
+    pub fn new(value: T) -> Self {
+        let shard_count = thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+        Self::with_shards(value, shard_count)
+    }
+
+    /// Creates a lock with an explicit number of shards, for tuning
+    /// against a known reader count instead of the core count.
+    pub fn with_shards(value: T, shard_count: usize) -> Self {
+        let shard_count = shard_count.max(1);
+        Self {
+            shards: (0..shard_count).map(|_| Shard { readers: AtomicUsize::new(0) }).collect(),
+            writer: AtomicBool::new(false),
+            state: Mutex::new(State { writer_active: false, write_wakers: Vec::new(), read_wakers: Vec::new() }),
+            value: UnsafeCell::new(value),
+        }
+    }
+
+    pub fn into_inner(self) -> T {
+        self.value.into_inner()
+    }
+}
+
+impl<T: ?Sized> ShardedLock<T> {
+    fn shard_index(&self) -> usize {
+        let mut hasher = DefaultHasher::new();
+        thread::current().id().hash(&mut hasher);
+        (hasher.finish() as usize) % self.shards.len()
+    }
+
+    fn sum_readers(&self) -> usize {
+        self.shards.iter().map(|shard| shard.readers.load(Ordering::Acquire)).sum()
+    }
+
+    /// Acquires a shared read lock on this thread's shard, waiting out
+    /// any writer that currently holds or is waiting for the lock.
+    pub fn read(&self) -> Read<'_, T> {
+        Read { lock: self, shard: self.shard_index() }
+    }
+
+    /// Acquires the exclusive write lock, waiting for every shard's
+    /// readers to drain.
+    pub fn write(&self) -> Write<'_, T> {
+        Write { lock: self, admitted: false }
+    }
+
+    pub fn try_read(&self) -> Option<ShardedLockReadGuard<'_, T>> {
+        if self.writer.load(Ordering::Acquire) {
+            return None;
+        }
+        let shard = self.shard_index();
+        self.shards[shard].readers.fetch_add(1, Ordering::AcqRel);
+        if self.writer.load(Ordering::Acquire) {
+            self.release_read(shard);
+            return None;
+        }
+        Some(ShardedLockReadGuard { lock: self, shard })
+    }
+
+    pub fn try_write(&self) -> Option<ShardedLockWriteGuard<'_, T>> {
+        let mut state = self.state.lock().unwrap();
+        if state.writer_active {
+            return None;
+        }
+        if self.sum_readers() > 0 {
+            return None;
+        }
+        state.writer_active = true;
+        self.writer.store(true, Ordering::Release);
+        Some(ShardedLockWriteGuard { lock: self })
+    }
+
+    pub fn get_mut(&mut self) -> &mut T {
+        self.value.get_mut()
+    }
+
+    fn release_read(&self, shard: usize) {
+        self.shards[shard].readers.fetch_sub(1, Ordering::AcqRel);
+        // A writer waiting on the drain only needs a nudge to recheck
+        // the sum across all shards; it doesn't matter which shard's
+        // release triggers that recheck.
+        if self.writer.load(Ordering::Acquire) {
+            let state = self.state.lock().unwrap();
+            for waker in &state.write_wakers {
+                waker.wake_by_ref();
+            }
+        }
+    }
+}
+
+/// Future returned by [`ShardedLock::read`].
+pub struct Read<'a, T: ?Sized> {
+    lock: &'a ShardedLock<T>,
+    shard: usize,
+}
+
+impl<'a, T: ?Sized> Future for Read<'a, T> {
+    type Output = ShardedLockReadGuard<'a, T>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.lock.state.lock().unwrap();
+        if !state.writer_active {
+            self.lock.shards[self.shard].readers.fetch_add(1, Ordering::AcqRel);
+            return Poll::Ready(ShardedLockReadGuard { lock: self.lock, shard: self.shard });
+        }
+        state.read_wakers.push(cx.waker().clone());
+        Poll::Pending
+    }
+}
+
+/// Future returned by [`ShardedLock::write`].
+pub struct Write<'a, T: ?Sized> {
+    lock: &'a ShardedLock<T>,
+    admitted: bool,
+}
+
+impl<'a, T: ?Sized> Future for Write<'a, T> {
+    type Output = ShardedLockWriteGuard<'a, T>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.lock.state.lock().unwrap();
+        if !self.admitted {
+            if state.writer_active {
+                state.write_wakers.push(cx.waker().clone());
+                return Poll::Pending;
+            }
+            state.writer_active = true;
+            self.admitted = true;
+            self.lock.writer.store(true, Ordering::Release);
+        }
+        if self.lock.sum_readers() > 0 {
+            state.write_wakers.push(cx.waker().clone());
+            return Poll::Pending;
+        }
+        Poll::Ready(ShardedLockWriteGuard { lock: self.lock })
+    }
+}
+
+impl<T: ?Sized> Drop for Write<'_, T> {
+    fn drop(&mut self) {
+        // Dropped before reaching `Ready`: release the admission slot
+        // (if taken) so the next queued writer, or readers if none are
+        // queued, aren't left waiting on a writer that never shows up.
+        if self.admitted {
+            let mut state = self.lock.state.lock().unwrap();
+            state.writer_active = false;
+            self.lock.writer.store(false, Ordering::Release);
+            if let Some(waker) = state.write_wakers.pop() {
+                waker.wake();
+            } else {
+                for waker in state.read_wakers.drain(..) {
+                    waker.wake();
+                }
+            }
+        }
+    }
+}
+
+/// A held shard-local read lock.
+pub struct ShardedLockReadGuard<'a, T: ?Sized> {
+    lock: &'a ShardedLock<T>,
+    shard: usize,
+}
+
+impl<T: ?Sized> Deref for ShardedLockReadGuard<'_, T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        unsafe { &*self.lock.value.get() }
+    }
+}
+
+impl<T: ?Sized> Drop for ShardedLockReadGuard<'_, T> {
+    fn drop(&mut self) {
+        self.lock.release_read(self.shard);
+    }
+}
+
+/// A held exclusive write lock.
+pub struct ShardedLockWriteGuard<'a, T: ?Sized> {
+    lock: &'a ShardedLock<T>,
+}
+
+impl<T: ?Sized> Deref for ShardedLockWriteGuard<'_, T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        unsafe { &*self.lock.value.get() }
+    }
+}
+
+impl<T: ?Sized> DerefMut for ShardedLockWriteGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        unsafe { &mut *self.lock.value.get() }
+    }
+}
+
+impl<T: ?Sized> Drop for ShardedLockWriteGuard<'_, T> {
+    fn drop(&mut self) {
+        let mut state = self.lock.state.lock().unwrap();
+        state.writer_active = false;
+        self.lock.writer.store(false, Ordering::Release);
+        if let Some(waker) = state.write_wakers.pop() {
+            waker.wake();
+        } else {
+            for waker in state.read_wakers.drain(..) {
+                waker.wake();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::time::Duration;
+
+    use alloc::sync::Arc;
+
+    #[test]
+    fn try_read_and_try_write_reflect_lock_state() {
+        let lock = ShardedLock::with_shards(1, 4);
+        let read_guard = lock.try_read().unwrap();
+        assert!(lock.try_write().is_none(), "a write must not be admitted while a read is held");
+        drop(read_guard);
+
+        let write_guard = lock.try_write().unwrap();
+        assert!(lock.try_read().is_none(), "a read must not be admitted while a write is held");
+        drop(write_guard);
+
+        assert!(lock.try_read().is_some());
+    }
+
+    #[test]
+    fn multiple_readers_are_admitted_concurrently() {
+        let lock = ShardedLock::with_shards(1, 4);
+        let a = lock.try_read().unwrap();
+        let b = lock.try_read().unwrap();
+        assert_eq!(*a, 1);
+        assert_eq!(*b, 1);
+    }
+
+    #[test]
+    fn write_blocks_until_every_reader_releases() {
+        crate::task::block_on(async {
+            let lock = Arc::new(ShardedLock::with_shards(0, 4));
+            let guard = lock.read().await;
+
+            let writer_lock = lock.clone();
+            let writer = crate::task::spawn(async move {
+                let mut guard = writer_lock.write().await;
+                *guard += 1;
+            });
+
+            crate::task::sleep(Duration::from_millis(20)).await;
+            drop(guard);
+
+            writer.await;
+            assert_eq!(*lock.read().await, 1);
+        });
+    }
+}
@@ -0,0 +1,220 @@
+//! A channel that holds only the most recently sent value, for
+//! propagating state (a config reload, a "shutting down" flag) to any
+//! number of observers that only ever care about the latest value, not
+//! every intermediate one.
+
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll, Waker};
+
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use std::sync::{RwLock, RwLockReadGuard};
+
+struct Shared<T> {
+    value: RwLock<T>,
+    /// Bumped on every `send`/`send_modify`, so receivers can tell
+    /// whether the value changed since they last looked without
+    /// comparing it themselves.
+    version: core::sync::atomic::AtomicU64,
+    sender_dropped: core::sync::atomic::AtomicBool,
+    wakers: std::sync::Mutex<Vec<Waker>>,
+}
+
+/// Creates a watch channel seeded with `initial`.
+pub fn channel<T>(initial: T) -> (Sender<T>, Receiver<T>) {
+    let shared = Arc::new(Shared {
+        value: RwLock::new(initial),
+        version: core::sync::atomic::AtomicU64::new(0),
+        sender_dropped: core::sync::atomic::AtomicBool::new(false),
+        wakers: std::sync::Mutex::new(Vec::new()),
+    });
+    let receiver = Receiver { shared: shared.clone(), seen_version: 0 };
+    (Sender { shared }, receiver)
+}
+
+/// The sending half of a watch channel.
+pub struct Sender<T> {
+    shared: Arc<Shared<T>>,
+}
+
+/// The error returned when sending or modifying on a [`Sender`] whose
+/// every [`Receiver`] has been dropped.
+#[derive(Debug)]
+pub struct SendError<T>(pub T);
+
+impl<T> Sender<T> {
+    /// Replaces the current value and wakes every waiting receiver.
+    pub fn send(&self, value: T) -> Result<(), SendError<T>> {
+        if Arc::strong_count(&self.shared) == 1 {
+            return Err(SendError(value));
+        }
+        *self.shared.value.write().unwrap() = value;
+        self.bump_and_wake();
+        Ok(())
+    }
+
+    /// Modifies the current value in place via `modify`, and wakes
+    /// every waiting receiver.
+    pub fn send_modify(&self, modify: impl FnOnce(&mut T)) {
+        modify(&mut self.shared.value.write().unwrap());
+        self.bump_and_wake();
+    }
+
+    /// Borrows the current value without marking it as seen by any
+    /// receiver.
+    pub fn borrow(&self) -> RwLockReadGuard<'_, T> {
+        self.shared.value.read().unwrap()
+    }
+
+    fn bump_and_wake(&self) {
+        self.shared.version.fetch_add(1, core::sync::atomic::Ordering::AcqRel);
+        for waker in self.shared.wakers.lock().unwrap().drain(..) {
+            waker.wake();
+        }
+    }
+
+    /// Creates a new receiver that sees the current value as already
+    /// seen, and will be notified of every change from here on.
+    pub fn subscribe(&self) -> Receiver<T> {
+        Receiver {
+            shared: self.shared.clone(),
+            seen_version: self.shared.version.load(core::sync::atomic::Ordering::Acquire),
+        }
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        self.shared.sender_dropped.store(true, core::sync::atomic::Ordering::Release);
+        for waker in self.shared.wakers.lock().unwrap().drain(..) {
+            waker.wake();
+        }
+    }
+}
+
+/// The receiving half of a watch channel.
+pub struct Receiver<T> {
+    shared: Arc<Shared<T>>,
+    seen_version: u64,
+}
+
+/// The error returned by [`Receiver::changed`] once every [`Sender`]
+/// has been dropped.
+#[derive(Debug, PartialEq, Eq)]
+pub struct RecvError;
+
+impl<T> Receiver<T> {
+    /// Borrows the current value, whether or not it's been seen yet.
+    pub fn borrow(&self) -> RwLockReadGuard<'_, T> {
+        self.shared.value.read().unwrap()
+    }
+
+    /// Borrows the current value and marks it as seen, so a subsequent
+    /// [`changed`](Self::changed) only resolves on the next change
+    /// after this one.
+    pub fn borrow_and_update(&mut self) -> RwLockReadGuard<'_, T> {
+        self.seen_version = self.shared.version.load(core::sync::atomic::Ordering::Acquire);
+        self.shared.value.read().unwrap()
+    }
+
+    /// Waits until the value changes since it was last seen (by this
+    /// call or by [`borrow_and_update`](Self::borrow_and_update)).
+    pub fn changed(&mut self) -> Changed<'_, T> {
+        Changed { receiver: self }
+    }
+
+    fn poll_changed(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), RecvError>> {
+        let version = self.shared.version.load(core::sync::atomic::Ordering::Acquire);
+        if version != self.seen_version {
+            self.seen_version = version;
+            return Poll::Ready(Ok(()));
+        }
+        if self.shared.sender_dropped.load(core::sync::atomic::Ordering::Acquire) {
+            return Poll::Ready(Err(RecvError));
+        }
+        self.shared.wakers.lock().unwrap().push(cx.waker().clone());
+        Poll::Pending
+    }
+}
+
+impl<T> Clone for Receiver<T> {
+    fn clone(&self) -> Self {
+        Receiver {
+            shared: self.shared.clone(),
+            seen_version: self.seen_version,
+        }
+    }
+}
+
+/// Future returned by [`Receiver::changed`].
+pub struct Changed<'a, T> {
+    receiver: &'a mut Receiver<T>,
+}
+
+impl<'a, T> Future for Changed<'a, T> {
+    type Output = Result<(), RecvError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.get_mut().receiver.poll_changed(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn receiver_sees_initial_value_and_wakes_on_send() {
+        crate::task::block_on(async {
+            let (sender, mut receiver) = channel(1);
+            assert_eq!(*receiver.borrow(), 1);
+
+            sender.send(2).unwrap();
+            receiver.changed().await.unwrap();
+            assert_eq!(*receiver.borrow_and_update(), 2);
+        });
+    }
+
+    #[test]
+    fn changed_only_resolves_once_per_update() {
+        crate::task::block_on(async {
+            let (sender, mut receiver) = channel(1);
+            sender.send(2).unwrap();
+            receiver.changed().await.unwrap();
+
+            let immediately_pending = crate::future::poll_immediate(receiver.changed()).await;
+            assert_eq!(immediately_pending, Poll::Pending);
+        });
+    }
+
+    #[test]
+    fn subscribe_starts_caught_up_and_clone_shares_state() {
+        crate::task::block_on(async {
+            let (sender, receiver) = channel(1);
+            sender.send(2).unwrap();
+
+            let mut subscriber = sender.subscribe();
+            assert_eq!(*subscriber.borrow(), 2);
+            assert_eq!(
+                crate::future::poll_immediate(subscriber.changed()).await,
+                Poll::Pending,
+                "a fresh subscriber should already be caught up to the current value"
+            );
+
+            let mut cloned = receiver.clone();
+            sender.send(3).unwrap();
+            cloned.changed().await.unwrap();
+            assert_eq!(*cloned.borrow(), 3);
+        });
+    }
+
+    #[test]
+    fn changed_errors_once_every_sender_is_dropped() {
+        crate::task::block_on(async {
+            let (sender, mut receiver) = channel(1);
+            drop(sender);
+            assert_eq!(receiver.changed().await, Err(RecvError));
+        });
+    }
+}
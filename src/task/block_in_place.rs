@@ -0,0 +1,23 @@
+//! Running a blocking section in place on the current worker thread,
+//! for the case `spawn_blocking` can't cover: a closure that borrows
+//! stack data instead of owning everything it touches.
+
+/// Runs `f` to completion on the current thread.
+///
+/// Because `f` stays on the thread that called it instead of moving to
+/// a blocking-pool thread, it's free to borrow non-`'static` data --
+/// unlike [`crate::task::spawn_blocking`], which requires its closure
+/// to be `'static` so it can be sent to a worker thread.
+///
+/// Handing this thread's queue of other runnable tasks off to another
+/// worker for the duration of `f` (so they keep making progress while
+/// this thread is blocked) is the executor's job, and the executor
+/// isn't part of this snapshot -- so until it is, a long-running `f`
+/// here blocks this worker thread the same way it would without this
+/// function, it just does so while legally holding borrowed data.
+pub fn block_in_place<F, R>(f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    f()
+}
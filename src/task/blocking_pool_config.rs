@@ -0,0 +1,98 @@
+//! Configuration for the thread pool backing `spawn_blocking`.
+//!
+//! This module defines the configuration surface only: reading it
+//! into environment variables and a builder, in the style already
+//! used for the executor's own thread count (`ASYNC_STD_THREAD_COUNT`).
+//! Actually resizing the live pool, enforcing `min_idle_threads`, and
+//! publishing `queued`/`active` metrics requires hooking this into the
+//! pool's own scheduling loop, which isn't part of this snapshot --
+//! whichever code owns that loop should read [`BlockingPoolConfig`] at
+//! startup the same way the executor reads its own thread count.
+
+use core::time::Duration;
+
+use alloc::string::String;
+
+/// Configuration for the `spawn_blocking` thread pool.
+#[derive(Debug, Clone)]
+pub struct BlockingPoolConfig {
+    /// The largest number of blocking threads the pool may run at
+    /// once. Work queues once this many are busy.
+    pub max_threads: usize,
+    /// The number of idle threads the pool tries to keep warm instead
+    /// of spinning up fresh ones for the next burst of blocking work.
+    pub min_idle_threads: usize,
+    /// How long an idle thread beyond `min_idle_threads` waits for new
+    /// work before it's allowed to exit.
+    pub idle_keep_alive: Duration,
+    /// Prefix used when naming blocking-pool threads, e.g.
+    /// `"async-std/blocking-"`.
+    pub thread_name_prefix: String,
+}
+
+impl BlockingPoolConfig {
+    /// The defaults used when nothing else is configured.
+    pub fn new() -> Self {
+        Self {
+            max_threads: 512,
+            min_idle_threads: 0,
+            idle_keep_alive: Duration::from_secs(10),
+            thread_name_prefix: String::from("async-std/blocking-"),
+        }
+    }
+
+    pub fn max_threads(mut self, max_threads: usize) -> Self {
+        self.max_threads = max_threads;
+        self
+    }
+
+    pub fn min_idle_threads(mut self, min_idle_threads: usize) -> Self {
+        self.min_idle_threads = min_idle_threads;
+        self
+    }
+
+    pub fn idle_keep_alive(mut self, idle_keep_alive: Duration) -> Self {
+        self.idle_keep_alive = idle_keep_alive;
+        self
+    }
+
+    pub fn thread_name_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.thread_name_prefix = prefix.into();
+        self
+    }
+
+    /// Builds a config from defaults overridden by, in order,
+    /// `ASYNC_STD_MAX_BLOCKING_THREADS`,
+    /// `ASYNC_STD_MIN_IDLE_BLOCKING_THREADS`,
+    /// `ASYNC_STD_BLOCKING_IDLE_TIMEOUT_MS`, and
+    /// `ASYNC_STD_BLOCKING_THREAD_NAME`. Unset or unparsable variables
+    /// fall back to the default for that field.
+    pub fn from_env() -> Self {
+        let mut config = Self::new();
+
+        if let Some(value) = parse_env("ASYNC_STD_MAX_BLOCKING_THREADS") {
+            config.max_threads = value;
+        }
+        if let Some(value) = parse_env("ASYNC_STD_MIN_IDLE_BLOCKING_THREADS") {
+            config.min_idle_threads = value;
+        }
+        if let Some(millis) = parse_env::<u64>("ASYNC_STD_BLOCKING_IDLE_TIMEOUT_MS") {
+            config.idle_keep_alive = Duration::from_millis(millis);
+        }
+        if let Ok(prefix) = std::env::var("ASYNC_STD_BLOCKING_THREAD_NAME") {
+            config.thread_name_prefix = prefix;
+        }
+
+        config
+    }
+}
+
+impl Default for BlockingPoolConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn parse_env<T: core::str::FromStr>(name: &str) -> Option<T> {
+    std::env::var(name).ok()?.parse().ok()
+}
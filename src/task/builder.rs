@@ -0,0 +1,192 @@
+//! Named, identifiable tasks, and a best-effort snapshot of which ones
+//! are currently alive -- for debugging a stuck service when `spawn`
+//! alone leaves every task anonymous.
+
+use core::fmt;
+use core::future::Future;
+use core::panic::Location;
+use core::pin::Pin;
+use core::sync::atomic::{AtomicU64, Ordering};
+use core::task::{Context, Poll};
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use std::collections::HashMap;
+use std::io;
+use std::sync::{Mutex, OnceLock};
+use std::time::Instant;
+
+use crate::task::hooks::current_hook;
+use crate::task::JoinHandle;
+
+/// A stable identifier for a spawned task, unique for the life of the
+/// process.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct TaskId(u64);
+
+impl fmt::Display for TaskId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "TaskId({})", self.0)
+    }
+}
+
+impl TaskId {
+    fn next() -> Self {
+        static NEXT: AtomicU64 = AtomicU64::new(1);
+        TaskId(NEXT.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+/// A relative scheduling priority for a task.
+///
+/// This is recorded on the task (and shown in [`dump`]) so an
+/// operator can see it, but nothing in this snapshot actually reorders
+/// run queues by it -- that requires the executor's own scheduler,
+/// which isn't part of this snapshot. Whichever code owns that
+/// scheduler should read a task's `Priority` the same way it already
+/// needs to read the task's future to poll it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum Priority {
+    Background,
+    #[default]
+    Normal,
+    High,
+}
+
+struct TaskMeta {
+    id: TaskId,
+    name: Option<String>,
+    priority: Priority,
+    spawned_at: &'static Location<'static>,
+}
+
+fn registry() -> &'static Mutex<HashMap<TaskId, TaskMeta>> {
+    static REGISTRY: OnceLock<Mutex<HashMap<TaskId, TaskMeta>>> = OnceLock::new();
+    REGISTRY.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+static SPAWNED_TOTAL: AtomicU64 = AtomicU64::new(0);
+
+/// The number of tasks ever spawned via [`Builder::spawn`], for the
+/// life of the process. Monotonically increasing, unlike [`dump`]'s
+/// length.
+pub fn spawned_total() -> u64 {
+    SPAWNED_TOTAL.load(Ordering::Relaxed)
+}
+
+/// A snapshot of one task registered at the time [`dump`] was called.
+///
+/// This only reflects identity and liveness, not fine-grained
+/// scheduling state (running / idle / blocked) -- that would require
+/// the executor itself to report what it's doing with each task on
+/// every poll, which is beyond what a registry kept from the outside
+/// can observe.
+#[derive(Debug, Clone)]
+pub struct TaskSnapshot {
+    pub id: TaskId,
+    pub name: Option<String>,
+    pub priority: Priority,
+    pub spawned_at: String,
+}
+
+/// Returns a snapshot of every task currently registered (spawned via
+/// [`Builder::spawn`] and not yet finished).
+pub fn dump() -> Vec<TaskSnapshot> {
+    registry()
+        .lock()
+        .unwrap()
+        .values()
+        .map(|meta| TaskSnapshot {
+            id: meta.id,
+            name: meta.name.clone(),
+            priority: meta.priority,
+            spawned_at: meta.spawned_at.to_string(),
+        })
+        .collect()
+}
+
+/// Configures a task before spawning it, for attaching a name (and,
+/// transitively, an id and spawn location visible in [`dump`]) instead
+/// of spawning anonymously via the bare `task::spawn`.
+#[derive(Debug, Default, Clone)]
+pub struct Builder {
+    name: Option<String>,
+    priority: Priority,
+}
+
+impl Builder {
+    pub fn new() -> Self {
+        Self { name: None, priority: Priority::default() }
+    }
+
+    /// Sets the task's name, shown in [`dump`] output.
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Sets the task's priority, shown in [`dump`] output. See
+    /// [`Priority`] for what this does and doesn't affect.
+    pub fn priority(mut self, priority: Priority) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Spawns `future`, registering it under a fresh [`TaskId`] for
+    /// the duration of its run.
+    #[track_caller]
+    pub fn spawn<F>(self, future: F) -> io::Result<JoinHandle<F::Output>>
+    where
+        F: Future + Send + 'static,
+        F::Output: Send + 'static,
+    {
+        let id = TaskId::next();
+        SPAWNED_TOTAL.fetch_add(1, Ordering::Relaxed);
+        if let Some(hook) = current_hook() {
+            hook.on_spawn(id, self.name.as_deref());
+        }
+        registry().lock().unwrap().insert(
+            id,
+            TaskMeta { id, name: self.name, priority: self.priority, spawned_at: Location::caller() },
+        );
+
+        Ok(crate::task::spawn(async move {
+            let output = Instrumented { id, inner: future }.await;
+            registry().lock().unwrap().remove(&id);
+            if let Some(hook) = current_hook() {
+                hook.on_complete(id);
+            }
+            output
+        }))
+    }
+}
+
+/// Times each individual poll of `inner` and reports it through
+/// whatever [`RuntimeHook`](crate::task::RuntimeHook) is installed.
+struct Instrumented<F> {
+    id: TaskId,
+    inner: F,
+}
+
+impl<F: Future> Future for Instrumented<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<F::Output> {
+        // SAFETY: `inner` is never moved out of; this mirrors the
+        // structural-pinning projection used by this crate's other
+        // hand-written combinator futures.
+        let this = unsafe { self.get_unchecked_mut() };
+        let inner = unsafe { Pin::new_unchecked(&mut this.inner) };
+
+        let hook = current_hook();
+        if let Some(hook) = &hook {
+            hook.on_poll_start(this.id);
+        }
+        let started = Instant::now();
+        let result = inner.poll(cx);
+        if let Some(hook) = &hook {
+            hook.on_poll_end(this.id, started.elapsed());
+        }
+        result
+    }
+}
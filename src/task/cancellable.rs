@@ -0,0 +1,71 @@
+//! A `spawn` variant whose handle can request that the task stop at
+//! its next await point, instead of requiring every task to thread an
+//! application-level flag through its own body.
+
+use core::future::Future;
+use core::pin::Pin;
+use core::sync::atomic::{AtomicBool, Ordering};
+use core::task::{Context, Poll};
+
+use alloc::sync::Arc;
+
+use crate::sync::CancellationToken;
+use crate::task::JoinHandle;
+
+/// Spawns `future` as a task that can be cancelled from the returned
+/// handle. The task stops at its next `.await` point after
+/// [`cancel`](CancellableHandle::cancel) is called and the handle
+/// resolves to `None`; a task that runs to completion first resolves
+/// to `Some(value)`.
+pub fn spawn_cancellable<F>(future: F) -> CancellableHandle<F::Output>
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    let token = CancellationToken::new();
+    let task_token = token.clone();
+    let finished = Arc::new(AtomicBool::new(false));
+    let task_finished = finished.clone();
+
+    let handle = crate::task::spawn(async move {
+        let result = task_token.run_until_cancelled(future).await;
+        task_finished.store(true, Ordering::Release);
+        result
+    });
+
+    CancellableHandle { handle, token, finished }
+}
+
+/// A handle to a task spawned with [`spawn_cancellable`].
+pub struct CancellableHandle<T> {
+    handle: JoinHandle<Option<T>>,
+    token: CancellationToken,
+    finished: Arc<AtomicBool>,
+}
+
+impl<T> CancellableHandle<T> {
+    /// Requests that the task stop at its next await point.
+    pub fn cancel(&self) {
+        self.token.cancel();
+    }
+
+    /// Alias for [`cancel`](Self::cancel).
+    pub fn abort(&self) {
+        self.cancel();
+    }
+
+    /// Reports whether the task has completed (whether by finishing or
+    /// by being cancelled), without waiting or consuming the handle.
+    pub fn is_finished(&self) -> bool {
+        self.finished.load(Ordering::Acquire)
+    }
+}
+
+impl<T> Future for CancellableHandle<T> {
+    type Output = Option<T>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        Pin::new(&mut this.handle).poll(cx)
+    }
+}
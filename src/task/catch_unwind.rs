@@ -0,0 +1,87 @@
+//! A `spawn` variant whose handle reports a panic instead of silently
+//! taking down whatever was relying on the task's result.
+
+use core::any::Any;
+use core::fmt;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use alloc::boxed::Box;
+
+use crate::task::JoinHandle;
+
+/// The outcome of a task spawned with [`spawn_catching`]: either its
+/// output, or the payload of the panic that unwound out of it.
+pub enum JoinError {
+    /// The task panicked; this is the value passed to `panic!` (or
+    /// whatever the panic hook attached), exactly as caught by
+    /// [`std::panic::catch_unwind`].
+    Panic(Box<dyn Any + Send + 'static>),
+}
+
+impl JoinError {
+    /// The panic message, if the payload was a `&str` or `String` --
+    /// which covers everything produced by `panic!`, `unwrap`, and
+    /// `expect`.
+    pub fn message(&self) -> Option<&str> {
+        let JoinError::Panic(payload) = self;
+        if let Some(s) = payload.downcast_ref::<&str>() {
+            Some(s)
+        } else if let Some(s) = payload.downcast_ref::<alloc::string::String>() {
+            Some(s.as_str())
+        } else {
+            None
+        }
+    }
+}
+
+impl fmt::Debug for JoinError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("JoinError").field("message", &self.message()).finish()
+    }
+}
+
+impl fmt::Display for JoinError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.message() {
+            Some(message) => write!(f, "task panicked: {message}"),
+            None => write!(f, "task panicked"),
+        }
+    }
+}
+
+impl std::error::Error for JoinError {}
+
+/// Spawns `future` as a task, catching any panic it unwinds with so
+/// the spawning task can observe and act on it instead of the panic
+/// propagating past `block_on` or being silently swallowed by the
+/// executor.
+pub fn spawn_catching<F>(future: F) -> JoinHandle<Result<F::Output, JoinError>>
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    crate::task::spawn(CatchUnwind { inner: future })
+}
+
+struct CatchUnwind<F> {
+    inner: F,
+}
+
+impl<F: Future> Future for CatchUnwind<F> {
+    type Output = Result<F::Output, JoinError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // SAFETY: `inner` is never moved out of; we only ever produce a
+        // pinned reference to it, matching the structural-pinning
+        // projection used throughout this crate's hand-written futures.
+        let inner = unsafe { self.map_unchecked_mut(|this| &mut this.inner) };
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| inner.poll(cx)));
+        match result {
+            Ok(Poll::Ready(value)) => Poll::Ready(Ok(value)),
+            Ok(Poll::Pending) => Poll::Pending,
+            Err(payload) => Poll::Ready(Err(JoinError::Panic(payload))),
+        }
+    }
+}
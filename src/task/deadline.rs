@@ -0,0 +1,87 @@
+//! Task-scoped deadlines: an ambient "give up by this instant" that
+//! follows a task across every future it `.await`s and, via
+//! [`spawn`], every task it spawns -- so a deeply nested call doesn't
+//! need a timeout threaded in by hand just to respect one set far
+//! above it.
+//!
+//! This only records the deadline; nothing here polls a timer on its
+//! own, since this crate has no executor hook that could cancel a
+//! future between polls without it choosing to check. [`checked`] (and
+//! [`io::timeout`](crate::io::timeout)-style code written the same way)
+//! is what actually turns an expired deadline into an error.
+
+use core::future::Future;
+use core::time::Duration;
+
+use std::time::Instant;
+
+use crate::future::timeout::{self, Elapsed};
+use crate::task::JoinHandle;
+use crate::task_local;
+
+task_local! {
+    static DEADLINE: Instant;
+}
+
+/// Runs `future` with `deadline` in effect for the current task for as
+/// long as `future` is being polled, readable with
+/// [`remaining_time`]/[`current_deadline`]. Nesting narrows the
+/// deadline for the duration of the inner call and restores the outer
+/// one once it returns, the same way [`LocalKey::scope`] always does.
+///
+/// [`LocalKey::scope`]: crate::task::LocalKey::scope
+pub fn with_deadline<F: Future>(deadline: Instant, future: F) -> impl Future<Output = F::Output> {
+    DEADLINE.scope(deadline, future)
+}
+
+/// Like [`with_deadline`], `timeout` from now rather than an absolute
+/// instant.
+pub fn with_timeout<F: Future>(timeout: Duration, future: F) -> impl Future<Output = F::Output> {
+    with_deadline(Instant::now() + timeout, future)
+}
+
+/// The current task's deadline, or `None` if no [`with_deadline`] is in
+/// effect.
+pub fn current_deadline() -> Option<Instant> {
+    DEADLINE.try_with(|deadline| *deadline).ok()
+}
+
+/// The time remaining until the current task's deadline, or `None` if
+/// no [`with_deadline`] is in effect. An already-passed deadline reports
+/// `Some(Duration::ZERO)` rather than an error, matching
+/// [`Instant::saturating_duration_since`].
+pub fn remaining_time() -> Option<Duration> {
+    current_deadline().map(|deadline| deadline.saturating_duration_since(Instant::now()))
+}
+
+/// Spawns `future` as a new task, inheriting the spawning task's
+/// current deadline (if any).
+///
+/// Plain [`task::spawn`](crate::task::spawn) starts the new task with
+/// no deadline in effect: [`DEADLINE`] lives in `with_deadline`'s
+/// future, not anywhere a freshly spawned, independently-polled task
+/// would see it, so carrying it over takes re-wrapping `future` in
+/// [`with_deadline`] here before handing it to the real `spawn` --
+/// the same "wrap before handing to `task::spawn`" shape
+/// [`spawn_cancellable`](crate::task::spawn_cancellable) uses to carry
+/// over its own per-task state.
+pub fn spawn<F>(future: F) -> JoinHandle<F::Output>
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    match current_deadline() {
+        Some(deadline) => crate::task::spawn(with_deadline(deadline, future)),
+        None => crate::task::spawn(future),
+    }
+}
+
+/// Awaits `future`, failing with [`Elapsed`] if the current task's
+/// deadline (if any) passes first. With no deadline in effect, this is
+/// just `future.await` wrapped in `Ok`.
+pub async fn checked<F: Future>(future: F) -> Result<F::Output, Elapsed> {
+    match current_deadline() {
+        Some(deadline) => timeout::timeout_at(deadline, future).await,
+        None => Ok(future.await),
+    }
+}
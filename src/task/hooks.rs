@@ -0,0 +1,57 @@
+//! Pluggable instrumentation for task lifecycle events, for pinpointing
+//! which task has long poll times instead of only coarse logging.
+//!
+//! There's no `tracing` dependency in this snapshot to gate a feature
+//! on, so this exposes the hook itself as a trait instead: implement
+//! [`RuntimeHook`] (backed by `tracing`, a metrics client, or anything
+//! else) and install it with [`set_hook`].
+
+use core::time::Duration;
+
+use alloc::sync::Arc;
+use std::sync::{OnceLock, RwLock};
+
+use crate::task::builder::TaskId;
+
+/// Receives task lifecycle events from tasks spawned via
+/// [`crate::task::Builder`].
+///
+/// Every method has a default no-op body, so an implementation only
+/// needs to override the events it cares about.
+pub trait RuntimeHook: Send + Sync {
+    fn on_spawn(&self, id: TaskId, name: Option<&str>) {
+        let _ = (id, name);
+    }
+
+    fn on_poll_start(&self, id: TaskId) {
+        let _ = id;
+    }
+
+    fn on_poll_end(&self, id: TaskId, duration: Duration) {
+        let _ = (id, duration);
+    }
+
+    fn on_complete(&self, id: TaskId) {
+        let _ = id;
+    }
+}
+
+fn hook_slot() -> &'static RwLock<Option<Arc<dyn RuntimeHook>>> {
+    static HOOK: OnceLock<RwLock<Option<Arc<dyn RuntimeHook>>>> = OnceLock::new();
+    HOOK.get_or_init(|| RwLock::new(None))
+}
+
+/// Installs `hook` to receive every task lifecycle event from this
+/// point on, replacing whatever was previously installed.
+pub fn set_hook(hook: Arc<dyn RuntimeHook>) {
+    *hook_slot().write().unwrap() = Some(hook);
+}
+
+/// Removes whatever hook is currently installed.
+pub fn clear_hook() {
+    *hook_slot().write().unwrap() = None;
+}
+
+pub(crate) fn current_hook() -> Option<Arc<dyn RuntimeHook>> {
+    hook_slot().read().unwrap().clone()
+}
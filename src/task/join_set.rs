@@ -0,0 +1,97 @@
+//! A dynamically-sized group of spawned tasks, for supervising a
+//! varying number of them (one per accepted connection, one per item
+//! in a batch) without hand-rolling completion tracking over a
+//! `Vec<JoinHandle<_>>`.
+
+use core::future::poll_fn;
+use core::pin::Pin;
+use core::task::Poll;
+
+use alloc::vec::Vec;
+use core::future::Future;
+
+use crate::task::cancellable::{spawn_cancellable, CancellableHandle};
+
+/// A set of spawned tasks, all producing the same output type `T`.
+///
+/// Dropping a `JoinSet` aborts every task still in it, so a supervisor
+/// that bails out early doesn't leak its children.
+pub struct JoinSet<T> {
+    tasks: Vec<CancellableHandle<T>>,
+}
+
+impl<T: Send + 'static> JoinSet<T> {
+    pub fn new() -> Self {
+        Self { tasks: Vec::new() }
+    }
+
+    /// Spawns `future` and adds it to this set.
+    pub fn spawn<F>(&mut self, future: F)
+    where
+        F: Future<Output = T> + Send + 'static,
+    {
+        self.tasks.push(spawn_cancellable(future));
+    }
+
+    /// The number of tasks currently in this set.
+    pub fn len(&self) -> usize {
+        self.tasks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tasks.is_empty()
+    }
+
+    /// Requests that every task in this set stop at its next await
+    /// point, without waiting for them to do so.
+    pub fn abort_all(&self) {
+        for task in &self.tasks {
+            task.cancel();
+        }
+    }
+
+    /// Waits for the next task in this set to finish and returns its
+    /// output, or `None` once the set is empty.
+    ///
+    /// Completion order, not spawn order: whichever task finishes
+    /// first is returned first.
+    pub async fn join_next(&mut self) -> Option<T> {
+        loop {
+            if self.tasks.is_empty() {
+                return None;
+            }
+
+            let tasks = &mut self.tasks;
+            let (index, output) = poll_fn(|cx| {
+                for (index, task) in tasks.iter_mut().enumerate() {
+                    if let Poll::Ready(output) = Pin::new(task).poll(cx) {
+                        return Poll::Ready((index, output));
+                    }
+                }
+                Poll::Pending
+            })
+            .await;
+
+            self.tasks.swap_remove(index);
+            if let Some(output) = output {
+                return Some(output);
+            }
+            // That task was aborted rather than completing normally;
+            // keep waiting for one that actually produces a value.
+        }
+    }
+}
+
+impl<T: Send + 'static> Default for JoinSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Drop for JoinSet<T> {
+    fn drop(&mut self) {
+        for task in &self.tasks {
+            task.cancel();
+        }
+    }
+}
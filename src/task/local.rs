@@ -0,0 +1,170 @@
+//! Running `!Send` futures (`Rc`-based state, FFI handles tied to a
+//! particular thread) as tasks, which the ordinary `spawn` can't do
+//! since it requires `Send`.
+
+use core::cell::RefCell;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll, Waker};
+
+use alloc::boxed::Box;
+use alloc::rc::Rc;
+use alloc::vec::Vec;
+
+std::thread_local! {
+    static CURRENT: RefCell<Vec<*const LocalSet>> = RefCell::new(Vec::new());
+}
+
+struct LocalTaskState<T> {
+    output: Option<T>,
+    waker: Option<Waker>,
+}
+
+/// A handle to a task spawned with [`spawn_local`] or
+/// [`LocalSet::spawn_local`].
+pub struct LocalJoinHandle<T> {
+    state: Rc<RefCell<LocalTaskState<T>>>,
+}
+
+impl<T> Future for LocalJoinHandle<T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+        let mut state = self.state.borrow_mut();
+        match state.output.take() {
+            Some(output) => Poll::Ready(output),
+            None => {
+                state.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/// A single-threaded task set: runs `!Send` futures spawned onto it
+/// cooperatively alongside a driving future, via [`LocalSet::run_until`].
+pub struct LocalSet {
+    tasks: RefCell<Vec<Pin<Box<dyn Future<Output = ()>>>>>,
+}
+
+impl LocalSet {
+    pub fn new() -> Self {
+        Self { tasks: RefCell::new(Vec::new()) }
+    }
+
+    /// Spawns a `!Send` future onto this set. Unlike [`spawn_local`],
+    /// this can be called before the set is ever driven by
+    /// [`run_until`](Self::run_until).
+    pub fn spawn_local<F>(&self, future: F) -> LocalJoinHandle<F::Output>
+    where
+        F: Future + 'static,
+        F::Output: 'static,
+    {
+        let state: Rc<RefCell<LocalTaskState<F::Output>>> =
+            Rc::new(RefCell::new(LocalTaskState { output: None, waker: None }));
+        let task_state = state.clone();
+        let task: Pin<Box<dyn Future<Output = ()>>> = Box::pin(async move {
+            let output = future.await;
+            let mut state = task_state.borrow_mut();
+            state.output = Some(output);
+            if let Some(waker) = state.waker.take() {
+                waker.wake();
+            }
+        });
+        self.tasks.borrow_mut().push(task);
+        LocalJoinHandle { state }
+    }
+
+    /// Drives `future` to completion, polling every task spawned onto
+    /// this set (via [`spawn_local`] or [`LocalSet::spawn_local`])
+    /// alongside it on the current thread.
+    pub async fn run_until<F: Future>(&self, future: F) -> F::Output {
+        RunUntil { set: self, future }.await
+    }
+
+    fn poll_tasks(&self, cx: &mut Context<'_>) {
+        let mut tasks = self.tasks.borrow_mut();
+        let mut i = 0;
+        while i < tasks.len() {
+            if tasks[i].as_mut().poll(cx).is_ready() {
+                tasks.swap_remove(i);
+            } else {
+                i += 1;
+            }
+        }
+    }
+}
+
+impl Default for LocalSet {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+struct RunUntil<'a, F> {
+    set: &'a LocalSet,
+    future: F,
+}
+
+impl<'a, F: Future> Future for RunUntil<'a, F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<F::Output> {
+        // SAFETY: `future` is never moved out of; projecting it to a
+        // pinned reference follows the same structural-pinning
+        // convention used by this crate's other hand-written futures.
+        let this = unsafe { self.get_unchecked_mut() };
+        let set_ptr = this.set as *const LocalSet;
+
+        CURRENT.with(|current| current.borrow_mut().push(set_ptr));
+        let result = {
+            let future = unsafe { Pin::new_unchecked(&mut this.future) };
+            let poll = future.poll(cx);
+            this.set.poll_tasks(cx);
+            poll
+        };
+        CURRENT.with(|current| {
+            current.borrow_mut().pop();
+        });
+        result
+    }
+}
+
+/// Runs `future` to completion on the current thread alone, inside a
+/// fresh [`LocalSet`] -- the single-threaded counterpart to
+/// [`task::block_on`](crate::task::block_on) for callers that don't
+/// want (or, in a container with a tight thread limit or a plugin
+/// hosted inside someone else's process, can't have) a worker-thread
+/// pool spun up at all. `future` and anything it spawns via
+/// [`spawn_local`] run interleaved on this one thread; nothing here
+/// touches the executor's worker threads, so this works the same
+/// whether or not one exists.
+pub fn block_on_local<F: Future>(future: F) -> F::Output {
+    let set = LocalSet::new();
+    crate::task::block_on(set.run_until(future))
+}
+
+/// Spawns a `!Send` future onto the [`LocalSet`] currently driving this
+/// task via [`LocalSet::run_until`].
+///
+/// # Panics
+///
+/// Panics if called outside of `LocalSet::run_until`.
+pub fn spawn_local<F>(future: F) -> LocalJoinHandle<F::Output>
+where
+    F: Future + 'static,
+    F::Output: 'static,
+{
+    CURRENT.with(|current| {
+        let set_ptr = *current
+            .borrow()
+            .last()
+            .expect("spawn_local called outside of a LocalSet");
+        // SAFETY: the pointer was pushed by a `RunUntil` that is still
+        // on the stack below us (it's popped only after this future
+        // returns control), so the `LocalSet` it points to is alive
+        // and not concurrently mutated from another thread.
+        let set = unsafe { &*set_ptr };
+        set.spawn_local(future)
+    })
+}
@@ -0,0 +1,34 @@
+//! A runtime metrics snapshot, for shipping executor health to a
+//! metrics backend instead of waiting for users to notice latency.
+//!
+//! This only reports what's genuinely tracked elsewhere in the crate:
+//! active and total spawned tasks, via the registry [`crate::task`]'s
+//! [`Builder`](crate::task::Builder) already keeps for [`dump`]'s
+//! sake. Blocking-pool queue depth/thread count, reactor registration
+//! counts, timer entry counts, and scheduling-delay histograms all
+//! need a real pool/reactor/timer implementation to produce, and none
+//! of those exist in this snapshot -- so rather than publish made-up
+//! zeroes for them, this only exposes the counters above, and whoever
+//! adds those subsystems should extend [`RuntimeMetrics`] alongside
+//! them.
+
+use crate::task::builder;
+
+/// A point-in-time snapshot of task-related runtime metrics.
+#[derive(Debug, Clone, Copy)]
+pub struct RuntimeMetrics {
+    /// The number of tasks spawned via [`crate::task::Builder::spawn`]
+    /// that haven't finished yet.
+    pub active_tasks: usize,
+    /// The number of tasks ever spawned via
+    /// [`crate::task::Builder::spawn`], for the life of the process.
+    pub spawned_total: u64,
+}
+
+/// Takes a snapshot of the current runtime metrics.
+pub fn metrics() -> RuntimeMetrics {
+    RuntimeMetrics {
+        active_tasks: builder::dump().len(),
+        spawned_total: builder::spawned_total(),
+    }
+}
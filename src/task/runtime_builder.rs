@@ -0,0 +1,175 @@
+//! Configuring the executor's own worker threads: how many there are,
+//! what they're named, where they're pinned, and what runs on each one
+//! before (and after) it starts picking up tasks.
+//!
+//! Like [`BlockingPoolConfig`](crate::task::BlockingPoolConfig), this
+//! is the configuration surface only -- actually spinning up worker
+//! threads with these settings is the executor's job, and the
+//! executor itself isn't part of this snapshot. Whichever code owns
+//! the worker-thread startup sequence should read a [`RuntimeBuilder`]
+//! there the same way it already needs to read `ASYNC_STD_THREAD_COUNT`.
+
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+
+/// A callback run on a worker thread, once on startup
+/// ([`RuntimeBuilder::on_thread_start`]) or once on shutdown
+/// ([`RuntimeBuilder::on_thread_stop`]) -- for initializing or tearing
+/// down thread-local FFI/GPU state that a task shouldn't have to set
+/// up itself on every call.
+pub type ThreadLifecycleHook = Arc<dyn Fn() + Send + Sync>;
+
+/// Whether the executor should run tasks across a worker-thread pool
+/// or entirely on the thread that drives it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RuntimeFlavor {
+    /// A pool of worker threads picks up spawned tasks (the default).
+    #[default]
+    MultiThread,
+    /// No worker threads are spawned; everything runs on the thread
+    /// that calls into the executor, the same way
+    /// [`task::block_on_local`](crate::task::block_on_local) already
+    /// does for `!Send` futures via [`LocalSet`](crate::task::LocalSet).
+    CurrentThread,
+}
+
+/// Configuration for the executor's worker threads.
+#[derive(Clone, Default)]
+pub struct RuntimeBuilder {
+    flavor: RuntimeFlavor,
+    worker_threads: Option<usize>,
+    thread_name_prefix: Option<String>,
+    core_affinity: Option<Vec<usize>>,
+    on_thread_start: Option<ThreadLifecycleHook>,
+    on_thread_stop: Option<ThreadLifecycleHook>,
+    lifo_slot: Option<bool>,
+    steal_batch_size: Option<usize>,
+    global_queue_interval: Option<u32>,
+}
+
+impl RuntimeBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Runs everything on the current thread instead of spinning up a
+    /// worker-thread pool. Equivalent to `flavor(RuntimeFlavor::CurrentThread)`.
+    pub fn current_thread(self) -> Self {
+        self.flavor(RuntimeFlavor::CurrentThread)
+    }
+
+    /// Sets which threading model the executor should use.
+    pub fn flavor(mut self, flavor: RuntimeFlavor) -> Self {
+        self.flavor = flavor;
+        self
+    }
+
+    pub fn get_flavor(&self) -> RuntimeFlavor {
+        self.flavor
+    }
+
+    /// The number of worker threads to run, overriding
+    /// `ASYNC_STD_THREAD_COUNT` and the number-of-cores default.
+    pub fn worker_threads(mut self, count: usize) -> Self {
+        self.worker_threads = Some(count);
+        self
+    }
+
+    /// Prefix used when naming worker threads, e.g. `"async-std/"`.
+    pub fn thread_name_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.thread_name_prefix = Some(prefix.into());
+        self
+    }
+
+    /// Pins worker thread `i` to the core at `cores[i]`, cycling
+    /// through `cores` if there are more worker threads than entries.
+    pub fn core_affinity(mut self, cores: Vec<usize>) -> Self {
+        self.core_affinity = Some(cores);
+        self
+    }
+
+    /// Runs `hook` on a worker thread just after it starts, before it
+    /// polls any task.
+    pub fn on_thread_start<F>(mut self, hook: F) -> Self
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        self.on_thread_start = Some(Arc::new(hook));
+        self
+    }
+
+    /// Runs `hook` on a worker thread just before it exits.
+    pub fn on_thread_stop<F>(mut self, hook: F) -> Self
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        self.on_thread_stop = Some(Arc::new(hook));
+        self
+    }
+
+    pub fn get_worker_threads(&self) -> Option<usize> {
+        self.worker_threads
+    }
+
+    pub fn get_thread_name_prefix(&self) -> Option<&str> {
+        self.thread_name_prefix.as_deref()
+    }
+
+    pub fn get_core_affinity(&self) -> Option<&[usize]> {
+        self.core_affinity.as_deref()
+    }
+
+    pub fn get_on_thread_start(&self) -> Option<&ThreadLifecycleHook> {
+        self.on_thread_start.as_ref()
+    }
+
+    pub fn get_on_thread_stop(&self) -> Option<&ThreadLifecycleHook> {
+        self.on_thread_stop.as_ref()
+    }
+
+    /// Whether each worker keeps a single-slot LIFO handoff alongside
+    /// its own deque: a task that wakes another (a ping-pong reply, a
+    /// oneshot completion) is run next by the same worker instead of
+    /// being pushed to the back of the queue or stolen, trading
+    /// fairness for locality on message-passing-heavy workloads.
+    ///
+    /// `None` (the default) leaves the choice to the executor.
+    pub fn lifo_slot(mut self, enabled: bool) -> Self {
+        self.lifo_slot = Some(enabled);
+        self
+    }
+
+    /// How many tasks a worker takes at once when stealing from
+    /// another worker's queue (or the global queue), rather than
+    /// stealing one task per empty-queue check.
+    ///
+    /// `None` (the default) leaves the choice to the executor.
+    pub fn steal_batch_size(mut self, count: usize) -> Self {
+        self.steal_batch_size = Some(count);
+        self
+    }
+
+    /// How many tasks a worker polls from its own queue before
+    /// checking the global/injector queue for fairness, so a worker
+    /// that keeps scheduling its own tasks doesn't starve work handed
+    /// in from outside (e.g. `block_on` or another thread's `spawn`).
+    ///
+    /// `None` (the default) leaves the choice to the executor.
+    pub fn global_queue_interval(mut self, interval: u32) -> Self {
+        self.global_queue_interval = Some(interval);
+        self
+    }
+
+    pub fn get_lifo_slot(&self) -> Option<bool> {
+        self.lifo_slot
+    }
+
+    pub fn get_steal_batch_size(&self) -> Option<usize> {
+        self.steal_batch_size
+    }
+
+    pub fn get_global_queue_interval(&self) -> Option<u32> {
+        self.global_queue_interval
+    }
+}
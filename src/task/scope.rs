@@ -0,0 +1,146 @@
+//! Structured concurrency: spawning child tasks that are guaranteed to
+//! finish (or be joined) before the scope that spawned them returns,
+//! so those children can safely borrow from the parent's stack instead
+//! of needing a `'static` bound.
+
+use core::future::Future;
+use core::marker::PhantomData;
+use core::pin::Pin;
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use std::cell::RefCell;
+
+use crate::task::JoinHandle;
+
+/// A handle for spawning tasks that borrow from the enclosing
+/// [`scope`] call, passed to the closure given to it.
+pub struct Scope<'scope, T> {
+    handles: RefCell<Vec<JoinHandle<T>>>,
+    _marker: PhantomData<&'scope ()>,
+}
+
+impl<'scope, T: Send + 'static> Scope<'scope, T> {
+    /// Spawns `future` as a child task of this scope. `future` may
+    /// borrow data from the scope's stack frame with lifetime
+    /// `'scope`, since every handle collected here is joined -- either
+    /// by [`scope`] running to completion, or by `Drop for Scope`
+    /// blocking for it -- before `'scope` ends.
+    pub fn spawn<F>(&self, future: F)
+    where
+        F: Future<Output = T> + Send + 'scope,
+    {
+        let future: Pin<Box<dyn Future<Output = T> + Send + 'scope>> = Box::pin(future);
+        // SAFETY: this erases the `'scope` bound to `'static` so the
+        // future can be handed to `task::spawn`, which requires it.
+        // This is sound only because every handle pushed to
+        // `self.handles` is joined before `'scope` ends: `scope` joins
+        // them on its ordinary return path, and `Drop for Scope` joins
+        // whatever's left otherwise -- including when `scope`'s own
+        // future is dropped mid-poll (a racing `future::timeout`, a
+        // `select!` arm, an abandoned task) instead of running to
+        // completion. Without that `Drop` impl, dropping a
+        // `JoinHandle` would merely detach its task rather than join
+        // or cancel it, leaving a still-running child polling this
+        // `'static`-cast reference after the real `'scope` data it
+        // points at has already unwound.
+        let future: Pin<Box<dyn Future<Output = T> + Send + 'static>> =
+            unsafe { core::mem::transmute(future) };
+        self.handles.borrow_mut().push(crate::task::spawn(future));
+    }
+}
+
+impl<'scope, T> Drop for Scope<'scope, T> {
+    fn drop(&mut self) {
+        // Normally `scope` has already drained `handles` via
+        // `into_inner` by the time this runs, leaving nothing here.
+        // But if `scope`'s future was itself dropped before reaching
+        // that point, these handles are the only thing standing
+        // between `'scope` ending and a child task that's still
+        // polling a reference into it -- block for them rather than
+        // letting them detach.
+        for handle in self.handles.get_mut().drain(..) {
+            crate::task::block_on(handle);
+        }
+    }
+}
+
+/// Runs `f` with a [`Scope`] that child tasks can be spawned onto, and
+/// returns the output of every spawned child (in spawn order) once
+/// they've all completed.
+///
+/// All children are joined before `scope` returns, even if `f`'s
+/// future is dropped before driving the scope to its own completion --
+/// eliminating the class of bugs where a detached task outlives the
+/// data it borrowed.
+pub async fn scope<'scope, T, F, Fut>(f: F) -> Vec<T>
+where
+    T: Send + 'static,
+    F: FnOnce(&Scope<'scope, T>) -> Fut,
+    Fut: Future<Output = ()>,
+{
+    let scope = Scope { handles: RefCell::new(Vec::new()), _marker: PhantomData };
+    f(&scope).await;
+
+    let handles = scope.handles.into_inner();
+    let mut results = Vec::with_capacity(handles.len());
+    for handle in handles {
+        results.push(handle.await);
+    }
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::sync::atomic::{AtomicBool, Ordering};
+    use core::time::Duration;
+
+    use alloc::sync::Arc;
+
+    #[test]
+    fn joins_every_child_on_the_ordinary_return_path() {
+        crate::task::block_on(async {
+            let total = 0u32;
+            let results = scope(|s| async {
+                for i in 0..3u32 {
+                    let total = &total;
+                    s.spawn(async move { *total as u32 + i });
+                }
+            })
+            .await;
+            assert_eq!(results, alloc::vec![0, 1, 2]);
+        });
+    }
+
+    #[test]
+    fn drop_mid_poll_still_joins_every_spawned_child() {
+        crate::task::block_on(async {
+            let done = Arc::new(AtomicBool::new(false));
+            let done_in_child = done.clone();
+
+            let scope_fut = scope(move |s: &Scope<'_, ()>| {
+                let done = done_in_child.clone();
+                async move {
+                    s.spawn(async move {
+                        crate::task::sleep(Duration::from_millis(50)).await;
+                        done.store(true, Ordering::SeqCst);
+                    });
+                    // Never let `f`'s own future resolve on its own, so the
+                    // only way out of the `timeout` below is for `scope_fut`
+                    // (and the `Scope` it owns) to be dropped mid-poll.
+                    core::future::pending::<()>().await;
+                }
+            });
+
+            let timed_out = crate::future::timeout(Duration::from_millis(10), scope_fut).await;
+            assert!(timed_out.is_err(), "the scope body never resolves, so the timeout should fire first");
+
+            // `scope_fut` was a temporary consumed by `timeout`, already
+            // dropped by the time `await` above produced its `Err` -- if
+            // `Drop for Scope` hadn't blocked for the spawned child, it
+            // could still be mid-sleep here instead of having finished.
+            assert!(done.load(Ordering::SeqCst), "dropping scope mid-poll must still join its children");
+        });
+    }
+}
@@ -0,0 +1,85 @@
+//! An explicit, coordinated shutdown sequence, instead of relying on
+//! process exit to implicitly drop everything -- which makes clean
+//! teardown in tests and embedding scenarios unreliable.
+
+use core::time::Duration;
+
+use std::sync::OnceLock;
+use std::time::Instant;
+
+use crate::sync::CancellationToken;
+
+fn root_token() -> &'static CancellationToken {
+    static ROOT: OnceLock<CancellationToken> = OnceLock::new();
+    ROOT.get_or_init(CancellationToken::new)
+}
+
+/// The process-wide shutdown signal. Long-running tasks should race
+/// their own work against this (e.g. via
+/// [`CancellationToken::run_until_cancelled`]) so [`shutdown`] can
+/// actually get them to stop instead of only being able to wait on
+/// them.
+pub fn shutdown_signal() -> CancellationToken {
+    root_token().clone()
+}
+
+/// Begins a graceful shutdown: signals [`shutdown_signal`] as
+/// cancelled, then waits up to `timeout` for every task spawned via
+/// [`crate::task::Builder`] to finish. Returns `true` if they all
+/// finished in time, `false` if `timeout` elapsed first.
+///
+/// This only stops tasks that cooperate by observing
+/// [`shutdown_signal`]; a task that never checks it, or that was
+/// spawned with the bare `task::spawn` instead of through
+/// [`crate::task::Builder`] and so isn't tracked, can cause this to
+/// time out. Dropping the reactor and blocking pool deterministically
+/// once every task is confirmed stopped would be the next step, but
+/// neither is a distinct, ownable object in this snapshot -- that part
+/// is left to whatever code does own them.
+pub async fn shutdown(timeout: Duration) -> bool {
+    root_token().cancel();
+
+    let deadline = Instant::now() + timeout;
+    loop {
+        if crate::task::dump().is_empty() {
+            return true;
+        }
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return false;
+        }
+        crate::task::sleep(remaining.min(Duration::from_millis(10))).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // `shutdown_signal`'s root token is a process-wide singleton that
+    // only ever goes from uncancelled to cancelled, never back --
+    // calling `shutdown` here permanently cancels it for the rest of
+    // this test binary, so there can only be one test exercising it
+    // per process.
+    #[test]
+    fn shutdown_waits_for_cooperative_tasks_then_succeeds() {
+        crate::task::block_on(async {
+            let signal = shutdown_signal();
+            let handle = crate::task::Builder::new()
+                .name("cooperative-worker")
+                .spawn(async move {
+                    signal.cancelled().await;
+                })
+                .unwrap();
+
+            assert!(crate::task::dump().iter().any(|task| task.name.as_deref() == Some("cooperative-worker")));
+
+            let finished = shutdown(Duration::from_secs(5)).await;
+            assert!(finished, "shutdown should succeed once the cooperative task observes the signal");
+
+            handle.await;
+            assert!(crate::task::dump().is_empty());
+            assert!(shutdown_signal().is_cancelled());
+        });
+    }
+}
@@ -0,0 +1,74 @@
+//! A resettable analog of [`sleep`](crate::task::sleep), for timers
+//! that get pushed back over and over (e.g. "reset the idle timeout
+//! every time a byte arrives") instead of being recreated per event.
+
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use core::time::Duration;
+
+use alloc::boxed::Box;
+
+use std::time::Instant;
+
+/// A future that resolves at a deadline which can be pushed back (or
+/// pulled in) while the future is already pinned in place, without
+/// the caller having to drop and recreate it.
+pub struct Sleep {
+    deadline: Instant,
+    inner: Pin<Box<dyn Future<Output = ()> + Send>>,
+}
+
+/// Creates a [`Sleep`] that resolves `duration` from now.
+pub fn sleep_timer(duration: Duration) -> Sleep {
+    Sleep::new(crate::time::now() + duration)
+}
+
+/// Creates a [`Sleep`] that resolves at `deadline`.
+pub fn sleep_timer_until(deadline: Instant) -> Sleep {
+    Sleep::new(deadline)
+}
+
+impl Sleep {
+    fn new(deadline: Instant) -> Self {
+        Sleep {
+            deadline,
+            inner: Box::pin(crate::task::sleep(deadline.saturating_duration_since(crate::time::now()))),
+        }
+    }
+
+    /// The deadline this timer currently resolves at.
+    pub fn deadline(&self) -> Instant {
+        self.deadline
+    }
+
+    /// Changes the deadline this timer resolves at.
+    ///
+    /// This snapshot has no direct access to the reactor's timer
+    /// registration, so `reset` is built on top of
+    /// [`task::sleep`](crate::task::sleep) rather than mutating an
+    /// already-registered timer entry in place: it still constructs a
+    /// fresh inner future each call. What it *does* give callers over
+    /// calling `task::sleep` fresh each time is the ergonomics this
+    /// was asked for -- one long-lived, pinned value with a stable
+    /// identity that can be reset in a hot loop without
+    /// `Option`-rewrapping or rebuilding a `Stream`/`DelayQueue`
+    /// around it.
+    pub fn reset(self: Pin<&mut Self>, deadline: Instant) {
+        // SAFETY: `inner` is a `Box`, which is always `Unpin`
+        // regardless of what it points to, so there's nothing
+        // self-referential or externally-pinned to preserve here.
+        let this = unsafe { self.get_unchecked_mut() };
+        this.deadline = deadline;
+        this.inner = Box::pin(crate::task::sleep(deadline.saturating_duration_since(crate::time::now())));
+    }
+}
+
+impl Future for Sleep {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let this = unsafe { self.get_unchecked_mut() };
+        this.inner.as_mut().poll(cx)
+    }
+}
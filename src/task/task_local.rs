@@ -0,0 +1,141 @@
+//! Per-task values: state that should follow one task across every
+//! future it `.await`s, without being readable by any other
+//! concurrently-running task, even on a multi-threaded executor.
+//!
+//! Unlike a thread-local, the value is scoped to the lifetime of a
+//! future via [`LocalKey::scope`] rather than a whole OS thread, which
+//! is what makes it possible to thread per-request context (a trace
+//! id, a tenant id) through combinator-built futures cleanly.
+
+use core::cell::RefCell;
+use core::fmt;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+/// The error returned by [`LocalKey::try_with`] when called outside of
+/// an active [`LocalKey::scope`] for this key on the current task.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AccessError;
+
+impl fmt::Display for AccessError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("task-local value not set for this task")
+    }
+}
+
+impl std::error::Error for AccessError {}
+
+/// A key for a per-task value, created by [`task_local!`].
+pub struct LocalKey<T: 'static> {
+    #[doc(hidden)]
+    pub __cell: fn() -> &'static std::thread::LocalKey<RefCell<Option<T>>>,
+}
+
+impl<T: 'static> LocalKey<T> {
+    #[doc(hidden)]
+    pub const fn __new(cell: fn() -> &'static std::thread::LocalKey<RefCell<Option<T>>>) -> Self {
+        Self { __cell: cell }
+    }
+
+    /// Accesses the current value, panicking if this task isn't
+    /// currently inside a [`scope`](Self::scope) for this key.
+    pub fn with<F, R>(&'static self, f: F) -> R
+    where
+        F: FnOnce(&T) -> R,
+    {
+        self.try_with(f).expect("task-local value not set for this task")
+    }
+
+    /// Accesses the current value, without panicking if there isn't
+    /// one.
+    pub fn try_with<F, R>(&'static self, f: F) -> Result<R, AccessError>
+    where
+        F: FnOnce(&T) -> R,
+    {
+        (self.__cell)().with(|cell| cell.borrow().as_ref().map(f).ok_or(AccessError))
+    }
+
+    /// Runs `future` with this key set to `value` for the duration of
+    /// every poll of `future` (including ones on a different worker
+    /// thread than the one that called `scope`), restoring whatever
+    /// value (if any) was set before `scope` was called once `future`
+    /// completes or is dropped.
+    pub fn scope<F: Future>(&'static self, value: T, future: F) -> Scope<T, F> {
+        Scope { key: self, value: Some(value), future }
+    }
+
+    /// Takes the current value out, leaving nothing in its place. A
+    /// subsequent [`with`](Self::with) panics (and
+    /// [`try_with`](Self::try_with) returns `Err`) until the enclosing
+    /// [`scope`] puts a value back or a new one is installed with
+    /// [`replace`](Self::replace).
+    pub fn take(&'static self) -> Option<T> {
+        (self.__cell)().with(|cell| cell.borrow_mut().take())
+    }
+
+    /// Installs `value` in place of whatever is currently set,
+    /// returning the previous value (if any).
+    pub fn replace(&'static self, value: T) -> Option<T> {
+        (self.__cell)().with(|cell| cell.borrow_mut().replace(value))
+    }
+}
+
+/// Future returned by [`LocalKey::scope`].
+pub struct Scope<T: 'static, F> {
+    key: &'static LocalKey<T>,
+    /// The value owned by this scope between polls; `None` while it's
+    /// on loan to the thread-local cell during an active poll, or
+    /// permanently once [`LocalKey::take`] has been called and not
+    /// replaced.
+    value: Option<T>,
+    future: F,
+}
+
+impl<T: 'static, F: Future> Future for Scope<T, F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<F::Output> {
+        // SAFETY: `future` is never moved out of; this mirrors the
+        // structural-pinning projection used by this crate's other
+        // hand-written combinator futures.
+        let this = unsafe { self.get_unchecked_mut() };
+        let future = unsafe { Pin::new_unchecked(&mut this.future) };
+
+        let cell = (this.key.__cell)();
+        let mine = this.value.take();
+        let previous = cell.with(|c| c.replace(mine));
+        let result = future.poll(cx);
+        // Pick up whatever's in the cell now -- ordinarily still ours,
+        // but `take`/`replace` may have changed it mid-poll, and that
+        // change should be what we carry into the next poll.
+        this.value = cell.with(|c| c.replace(previous));
+        result
+    }
+}
+
+/// Declares one or more per-task values, each accessed through the
+/// [`LocalKey`] returned in place of the `static` item itself:
+///
+/// ```ignore
+/// task_local! {
+///     static REQUEST_ID: u64;
+/// }
+/// ```
+#[macro_export]
+macro_rules! task_local {
+    () => {};
+    ($(#[$attr:meta])* $vis:vis static $name:ident: $t:ty; $($rest:tt)*) => {
+        $(#[$attr])*
+        $vis static $name: $crate::task::LocalKey<$t> = {
+            std::thread_local! {
+                static __TASK_LOCAL: core::cell::RefCell<Option<$t>> = core::cell::RefCell::new(None);
+            }
+            fn __access() -> &'static std::thread::LocalKey<core::cell::RefCell<Option<$t>>> {
+                &__TASK_LOCAL
+            }
+            $crate::task::LocalKey::__new(__access)
+        };
+        $crate::task_local! { $($rest)* }
+    };
+}
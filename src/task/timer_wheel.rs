@@ -0,0 +1,162 @@
+//! A hierarchical timing wheel: `O(1)` insert and cancel, and expiry
+//! checked in amortized-`O(1)` batches rather than per-timer.
+//!
+//! This snapshot has no reactor or timer driver to plug this into --
+//! `task::sleep`/`timeout`/`interval` are ambient and this crate has
+//! no visibility into (or control over) how they're actually backed.
+//! So this is offered as the data structure a timer driver would use
+//! internally, not as a drop-in replacement for anything: wiring it
+//! up would mean a driver thread calling [`TimerWheel::advance`] on
+//! every reactor tick and waking the wakers it returns, entirely
+//! inside whatever already implements `task::sleep` today. The public
+//! `sleep`/`timeout`/`interval` call sites wouldn't need to change at
+//! all, which is what "keeping the API unchanged" means here -- it's
+//! not that there's an old implementation being swapped out under
+//! them, it's that there's nothing (visible to this crate) to swap.
+//!
+//! The algorithm is the classic multi-level hashed wheel (as used by
+//! the Linux kernel's old timer implementation, and by most userspace
+//! timer wheels since): timers more than [`SLOTS`] ticks out are
+//! parked in a coarser level and *cascaded* down a level at a time as
+//! the wheel catches up to them, so a timer's exact slot only ever
+//! needs recomputing `O(log n)` times over its lifetime rather than
+//! once per tick.
+
+use core::mem;
+use core::time::Duration;
+
+use std::collections::HashMap;
+use std::time::Instant;
+
+const SLOT_BITS: u32 = 6;
+const SLOTS: usize = 1 << SLOT_BITS; // 64
+const LEVELS: usize = 4; // 64^4 ticks ~ 16.7M ticks of range
+
+/// A handle to an entry in a [`TimerWheel`], usable to
+/// [`cancel`](TimerWheel::cancel) it before it expires.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TimerId(u64);
+
+/// A hierarchical timing wheel scheduling arbitrary tokens (typically
+/// wakers) to fire at a given deadline.
+pub struct TimerWheel<T> {
+    granularity: Duration,
+    start: Instant,
+    current_tick: u64,
+    // levels[level][slot] -> id -> (absolute deadline tick, token)
+    levels: Vec<Vec<HashMap<u64, (u64, T)>>>,
+    locations: HashMap<u64, (usize, usize)>,
+    next_id: u64,
+}
+
+impl<T> TimerWheel<T> {
+    /// Creates a wheel ticking every `granularity`, starting now.
+    /// Deadlines are rounded up to the nearest tick, so `granularity`
+    /// is also the coarsest expiry precision this wheel can offer.
+    pub fn new(granularity: Duration) -> Self {
+        TimerWheel {
+            granularity,
+            start: Instant::now(),
+            current_tick: 0,
+            levels: (0..LEVELS).map(|_| (0..SLOTS).map(|_| HashMap::new()).collect()).collect(),
+            locations: HashMap::new(),
+            next_id: 0,
+        }
+    }
+
+    /// Schedules `token` to expire at `deadline`.
+    ///
+    /// `deadline` must be within `SLOTS^LEVELS` ticks of the current
+    /// tick (at the default 6-bit/4-level sizing, about 16.7 million
+    /// ticks -- hours out at millisecond granularity); this wheel has
+    /// no overflow list for deadlines further out than that.
+    pub fn insert(&mut self, deadline: Instant, token: T) -> TimerId {
+        let deadline_tick = self.tick_for(deadline).max(self.current_tick + 1);
+        let id = self.next_id;
+        self.next_id += 1;
+        self.place(id, deadline_tick, token);
+        TimerId(id)
+    }
+
+    /// Cancels a previously inserted timer, returning its token if it
+    /// hadn't already expired.
+    pub fn cancel(&mut self, id: TimerId) -> Option<T> {
+        let (level, slot) = self.locations.remove(&id.0)?;
+        self.levels[level][slot].remove(&id.0).map(|(_, token)| token)
+    }
+
+    /// The number of timers still pending expiry.
+    pub fn len(&self) -> usize {
+        self.locations.len()
+    }
+
+    /// Whether there are no timers pending expiry.
+    pub fn is_empty(&self) -> bool {
+        self.locations.is_empty()
+    }
+
+    /// Advances the wheel to `now`, returning every timer that expired
+    /// along the way (oldest first), cascading any coarser-level
+    /// entries down as their buckets come due.
+    pub fn advance(&mut self, now: Instant) -> Vec<(TimerId, T)> {
+        let target = self.tick_for(now);
+        let mut expired = Vec::new();
+
+        while self.current_tick < target {
+            self.current_tick += 1;
+
+            // Whenever the tick completes a full rotation of
+            // everything below a given level, that level's current
+            // bucket is due for re-placement: every entry in it now
+            // has a delta small enough to land at a finer level (or,
+            // if due, directly into expiry on this same pass).
+            for level in 1..LEVELS {
+                if self.current_tick % (1u64 << (SLOT_BITS * level as u32)) != 0 {
+                    break;
+                }
+                let slot = Self::slot_for(self.current_tick, level);
+                let bucket = mem::take(&mut self.levels[level][slot]);
+                for (id, (deadline_tick, token)) in bucket {
+                    self.locations.remove(&id);
+                    self.place(id, deadline_tick, token);
+                }
+            }
+
+            let slot0 = Self::slot_for(self.current_tick, 0);
+            let bucket = mem::take(&mut self.levels[0][slot0]);
+            for (id, (_, token)) in bucket {
+                self.locations.remove(&id);
+                expired.push((TimerId(id), token));
+            }
+        }
+
+        expired
+    }
+
+    fn tick_for(&self, instant: Instant) -> u64 {
+        let elapsed = instant.saturating_duration_since(self.start);
+        let ticks = elapsed.as_nanos() / self.granularity.as_nanos().max(1);
+        ticks.min(u64::MAX as u128) as u64
+    }
+
+    fn place(&mut self, id: u64, deadline_tick: u64, token: T) {
+        let delta = deadline_tick.saturating_sub(self.current_tick).max(1);
+        let level = Self::level_for(delta);
+        let slot = Self::slot_for(deadline_tick, level);
+        self.levels[level][slot].insert(id, (deadline_tick, token));
+        self.locations.insert(id, (level, slot));
+    }
+
+    fn level_for(delta: u64) -> usize {
+        for level in 0..LEVELS - 1 {
+            if delta < (1u64 << (SLOT_BITS * (level as u32 + 1))) {
+                return level;
+            }
+        }
+        LEVELS - 1
+    }
+
+    fn slot_for(tick: u64, level: usize) -> usize {
+        ((tick >> (SLOT_BITS * level as u32)) & (SLOTS as u64 - 1)) as usize
+    }
+}
@@ -0,0 +1,93 @@
+#![cfg(feature = "tokio-compat")]
+//! Letting tokio-based libraries (hyper, tonic, most DB drivers) run from
+//! an async-std task without panicking with "there is no reactor
+//! running, must be called from the context of a Tokio 1.x runtime".
+//!
+//! Those panics happen because tokio's I/O and timer types look up their
+//! driver through a thread-local set by [`Handle::enter`], which nothing
+//! in this crate's own executor ever sets. [`with_tokio`] fixes that the
+//! same way the `async-compat` crate does: a background tokio
+//! [`Runtime`] is started lazily the first time it's needed, and
+//! [`WithTokio`] enters that runtime's [`Handle`] on every poll before
+//! delegating to the wrapped future, so any tokio type the future touches
+//! during that poll finds a driver. [`spawn`] is the other direction --
+//! for a future that's really tokio's to own (because it calls
+//! `tokio::spawn` itself and needs that spawn to land on an actual tokio
+//! worker, not just borrow one's context for a poll) -- and hands it to
+//! the same background runtime directly.
+//!
+//! This pulls in the `tokio` crate (with the `rt-multi-thread` feature,
+//! to get a background runtime with its own worker threads, and
+//! whichever of `net`/`time`/`io-util` the caller's dependencies need)
+//! behind `cfg(feature = "tokio-compat")`, which this checkout has no
+//! `Cargo.toml` to declare as an optional dependency -- the same gap
+//! `task/wasm.rs` documents for `wasm-bindgen-futures`/`gloo-timers`.
+//!
+//! [`Handle::enter`]: tokio::runtime::Handle::enter
+//! [`Runtime`]: tokio::runtime::Runtime
+//! [`Handle`]: tokio::runtime::Handle
+
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use std::sync::OnceLock;
+
+use tokio::runtime::{Handle, Runtime};
+
+fn background_runtime() -> &'static Runtime {
+    static RUNTIME: OnceLock<Runtime> = OnceLock::new();
+    RUNTIME.get_or_init(|| {
+        tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .thread_name("async-std/tokio-compat")
+            .build()
+            .expect("failed to start the background tokio runtime backing tokio-compat")
+    })
+}
+
+/// Wraps `future` so tokio-based types it touches while being polled --
+/// `tokio::net::TcpStream`, `tokio::time::sleep`, `tokio::fs::File`, and
+/// so on -- find a driver, no matter which executor actually drives
+/// `future` itself.
+///
+/// This only holds the tokio context open for the duration of each
+/// individual `poll` call; `future` can still be an ordinary async-std
+/// task spawned with [`task::spawn`](crate::task::spawn).
+pub fn with_tokio<F: Future>(future: F) -> WithTokio<F> {
+    WithTokio { future, handle: background_runtime().handle().clone() }
+}
+
+/// Future for [`with_tokio`].
+pub struct WithTokio<F> {
+    future: F,
+    handle: Handle,
+}
+
+impl<F: Future> Future for WithTokio<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // SAFETY: `future` is never moved out from behind the `Pin`; only
+        // polled through a re-pinned reference, matching the
+        // structural-pinning convention used by this crate's other
+        // hand-written futures (e.g. `future::Timeout`).
+        let this = unsafe { self.get_unchecked_mut() };
+        let _guard = this.handle.enter();
+        let future = unsafe { Pin::new_unchecked(&mut this.future) };
+        future.poll(cx)
+    }
+}
+
+/// Spawns `future` directly onto the background tokio runtime, for a
+/// future that calls `tokio::spawn` (or otherwise needs to actually be a
+/// tokio task) rather than one that merely calls into tokio-based
+/// libraries from inside an async-std task. Use [`with_tokio`] for the
+/// latter.
+pub fn spawn<F>(future: F) -> tokio::task::JoinHandle<F::Output>
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    background_runtime().spawn(future)
+}
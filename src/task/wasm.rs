@@ -0,0 +1,55 @@
+#![cfg(target_arch = "wasm32")]
+//! `task::spawn_local`, `task::sleep`, and `task::block_on`'s
+//! replacement for `wasm32-unknown-unknown` -- a browser tab has no
+//! threads to run a worker pool on and no way to block the one thread
+//! it does have without freezing the page, so this is a deliberately
+//! different shape from the native executor in `task/`, not the same
+//! API cfg'd out to a stub.
+//!
+//! This pulls in `wasm-bindgen-futures` (for [`spawn_local`]) and
+//! `gloo-timers` (for [`sleep`]'s `setTimeout` binding), neither of
+//! which this crate depends on yet; both would need adding behind
+//! `cfg(target_arch = "wasm32")` so they never reach a native build's
+//! dependency graph. `fs`/`net` have no browser-sandbox-compatible
+//! implementation at all (no filesystem, and sockets only exist as
+//! `fetch`/`WebSocket`, neither of which is a drop-in for
+//! `TcpStream`), so the crate root gating them off entirely for this
+//! target is a change to make where the crate root itself is --
+//! there's no `lib.rs` in this checkout to make it in.
+
+use core::future::Future;
+use core::time::Duration;
+
+/// Spawns `future` onto the browser's microtask queue.
+///
+/// There's no worker-thread pool to hand this to, so -- unlike native
+/// [`task::spawn`](crate::task::spawn) -- `future` doesn't need to be
+/// `Send`: everything on wasm32 already runs on the one thread the
+/// page has.
+pub fn spawn_local<F>(future: F)
+where
+    F: Future<Output = ()> + 'static,
+{
+    wasm_bindgen_futures::spawn_local(future);
+}
+
+/// Resolves after `duration`, via the browser's `setTimeout` rather
+/// than this crate's native timer machinery (`task::Sleep`,
+/// `stream::Interval`, ...), none of which has anything to register
+/// against here.
+pub async fn sleep(duration: Duration) {
+    let millis = duration.as_millis().min(u32::MAX as u128) as u32;
+    gloo_timers::future::TimeoutFuture::new(millis).await;
+}
+
+/// Unlike native [`task::block_on`](crate::task::block_on), this
+/// always panics: blocking the only thread a page has would freeze
+/// its event loop, including the very `setTimeout`/microtask
+/// machinery anything spawned onto it would need to ever make
+/// progress -- there's no such thing as a "blocking wait" on wasm32,
+/// only handing control back to the browser and spawning a
+/// continuation. Call [`spawn_local`] instead of structuring code
+/// around `block_on`.
+pub fn block_on<F: Future>(_future: F) -> F::Output {
+    panic!("task::block_on is not supported on wasm32 -- use task::wasm::spawn_local and let the browser's event loop drive the future instead")
+}
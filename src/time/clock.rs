@@ -0,0 +1,114 @@
+#![cfg(feature = "test-util")]
+//! A virtual clock, for tests that exercise timeout/retry/debounce
+//! logic without actually waiting in real time.
+//!
+//! [`pause`] freezes [`now`] at the instant it's called; [`advance`]
+//! then moves it forward by a chosen amount instantly, and
+//! [`advance_if_idle`] does the same but only when there's no work
+//! left to do, for tests that want time to "just keep going" without
+//! hand-picking every jump.
+//!
+//! This clock is consulted by this crate's own `now()`-based timing
+//! code ([`stream::Interval`](crate::stream::Interval),
+//! [`stream::DelayQueue`](crate::stream::DelayQueue),
+//! [`task::Sleep`](crate::task::Sleep),
+//! [`future::timeout`](crate::future::timeout)) once `test-util` is
+//! enabled and [`pause`] has been called. It is *not* consulted by
+//! [`task::sleep`](crate::task::sleep) itself: that's an ambient
+//! primitive this crate doesn't implement in this snapshot, so
+//! whatever really backs it keeps waiting on the real clock
+//! regardless of this module's state. Pausing still serves every
+//! piece of timing logic above that only cares about elapsed
+//! wall-clock time, not about a `sleep` future itself resolving.
+
+use core::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use core::time::Duration;
+
+use std::sync::OnceLock;
+use std::time::Instant;
+
+static PAUSED: AtomicBool = AtomicBool::new(false);
+static BASE: OnceLock<Instant> = OnceLock::new();
+static OFFSET_NANOS: AtomicU64 = AtomicU64::new(0);
+
+/// Freezes [`now`] at the real current time. Idempotent: calling this
+/// again while already paused has no effect on the frozen instant.
+pub fn pause() {
+    let _ = BASE.set(Instant::now());
+    PAUSED.store(true, Ordering::SeqCst);
+}
+
+/// Unfreezes [`now`], returning to the real clock.
+pub fn resume() {
+    PAUSED.store(false, Ordering::SeqCst);
+}
+
+/// Whether the clock is currently paused.
+pub fn is_paused() -> bool {
+    PAUSED.load(Ordering::SeqCst)
+}
+
+/// Moves the paused clock forward by `duration`.
+///
+/// # Panics
+///
+/// Panics if the clock isn't currently [`pause`]d.
+pub fn advance(duration: Duration) {
+    assert!(is_paused(), "time::advance called without time::pause");
+    OFFSET_NANOS.fetch_add(duration.as_nanos() as u64, Ordering::SeqCst);
+}
+
+/// Like [`advance`], but only if there's currently no task tracked by
+/// [`task::dump`](crate::task::dump) -- a best-effort "nothing left to
+/// do, so let time move on" check.
+///
+/// This only sees tasks spawned through
+/// [`task::Builder`](crate::task::Builder); it can't see every task an
+/// executor might be running, since this snapshot has no reactor hook
+/// to ask "is anything waiting on a real event" the way a full
+/// auto-advancing test clock would. Returns whether it advanced.
+pub fn advance_if_idle(duration: Duration) -> bool {
+    if crate::task::dump().is_empty() {
+        advance(duration);
+        true
+    } else {
+        false
+    }
+}
+
+/// The current time, per this clock: the real clock while unpaused,
+/// or the frozen-plus-advanced instant while [`pause`]d.
+pub fn now() -> Instant {
+    if is_paused() {
+        let base = *BASE.get().expect("time::pause always sets BASE before PAUSED");
+        base + Duration::from_nanos(OFFSET_NANOS.load(Ordering::SeqCst))
+    } else {
+        Instant::now()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // `PAUSED`/`OFFSET_NANOS` are process-wide, so only one test in
+    // this binary can drive this clock; anything else exercising
+    // `time::now` concurrently would race with it.
+    #[test]
+    fn pause_freezes_and_advance_moves_it_forward_instantly() {
+        assert!(!is_paused());
+        pause();
+        assert!(is_paused());
+
+        let frozen = now();
+        std::thread::sleep(Duration::from_millis(20));
+        assert_eq!(now(), frozen, "paused clock must not move with real time");
+
+        advance(Duration::from_secs(60));
+        assert_eq!(now(), frozen + Duration::from_secs(60));
+
+        resume();
+        assert!(!is_paused());
+        assert!(now() >= frozen, "resumed clock should track real time again");
+    }
+}
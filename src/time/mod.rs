@@ -0,0 +1,18 @@
+//! The wall clock this crate's timing primitives consult.
+//!
+//! Normally this is just [`std::time::Instant::now`]. With the
+//! `test-util` feature enabled, [`clock::pause`] can swap it for a
+//! virtual clock instead, so tests covering timeout/retry/debounce
+//! logic don't have to actually wait in real time.
+
+pub mod clock;
+
+#[cfg(feature = "test-util")]
+pub use clock::now;
+
+/// The current time, per whichever clock is active -- see the
+/// [module docs](self).
+#[cfg(not(feature = "test-util"))]
+pub fn now() -> std::time::Instant {
+    std::time::Instant::now()
+}